@@ -0,0 +1,229 @@
+//! Generates the packet id table of a version in `protocol/src/protocol/versions/`
+//! from minecraft-data.
+//!
+//! minecraft-data names packets differently than `packet.rs`, and one of its
+//! packets may map to any of several variants there depending on the
+//! version. `names.rs` holds that mapping, so the generated tables only
+//! depend on minecraft-data and the map. Every packet which can't be mapped
+//! is left as a comment in the table and listed in the report, so it can be
+//! added to `packet.rs` and `names.rs`.
+//!
+//! Packets whose layout changed keep the variant of the last version with an
+//! entry, so the report should be checked against the protocol changes of a
+//! new version either way.
+
+mod names;
+
+use minecraft_data_rs::api::versions_by_minecraft_version;
+use minecraft_data_rs::models::protocol::PacketGrouping;
+use minecraft_data_rs::Api;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// The states in the order of the existing tables, with the name of their
+/// `State` variant
+const STATES: [(&str, &str); 4] = [
+    ("handshake", "Handshaking"),
+    ("play", "Play"),
+    ("login", "Login"),
+    ("status", "Status"),
+];
+const DIRECTIONS: [(&str, &str); 2] = [
+    ("serverbound", "Serverbound"),
+    ("clientbound", "Clientbound"),
+];
+
+/// Packet names by id, per state and direction
+type Table = BTreeMap<(String, String), BTreeMap<u32, String>>;
+
+/// The `packet.rs` name of a minecraft-data packet at a protocol version
+fn packet_name(
+    state: &str,
+    dir: &str,
+    data_name: &str,
+    protocol_version: i32,
+) -> Option<&'static str> {
+    names::NAMES
+        .iter()
+        .filter(|(s, d, name, first, _)| {
+            *s == state && *d == dir && *name == data_name && *first <= protocol_version
+        })
+        .max_by_key(|(_, _, _, first, _)| *first)
+        .map(|(_, _, _, _, name)| *name)
+}
+
+/// Packet names by id as minecraft-data knows them
+fn data_table(api: &Api) -> Table {
+    let protocol = api.protocols.get_protocol().unwrap();
+    let groupings: [(&str, &PacketGrouping); 4] = [
+        ("handshake", &protocol.handshaking),
+        ("play", &protocol.play),
+        ("login", &protocol.login),
+        ("status", &protocol.status),
+    ];
+    let mut table = Table::new();
+    for (state, grouping) in groupings {
+        for (dir, packets) in [
+            ("serverbound", &grouping.to_server),
+            ("clientbound", &grouping.to_client),
+        ] {
+            let ids = table.entry((state.to_owned(), dir.to_owned())).or_default();
+            for (id, name) in &packets.packet_mapper.mapper.mappings {
+                ids.insert(*id, name.clone());
+            }
+        }
+    }
+    table
+}
+
+/// `v1_16_4` for 1.16.4
+fn module_name(minecraft_version: &str) -> String {
+    format!("v{}", minecraft_version.replace('.', "_"))
+}
+
+/// The names of every packet declared in `packet.rs`
+fn declared_packets(source: &str) -> BTreeSet<String> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("packet "))
+        .filter_map(|rest| rest.split_whitespace().next())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// A generated table
+struct Generated {
+    source: String,
+    /// `state direction name` of every packet mapped
+    mapped: Vec<(&'static str, &'static str, &'static str)>,
+    /// `state direction id name` of the packets `names.rs` doesn't map
+    missing: Vec<String>,
+}
+
+/// The table of the packets minecraft-data lists for a protocol version
+fn generate(data: &Table, protocol_version: i32) -> Generated {
+    let mut generated = Generated {
+        source: String::new(),
+        mapped: vec![],
+        missing: vec![],
+    };
+    let out = &mut generated.source;
+    writeln!(out, "protocol_packet_ids!(").unwrap();
+    for (state, state_variant) in STATES {
+        writeln!(out, "    {} {} {{", state, state_variant).unwrap();
+        for (dir, dir_variant) in DIRECTIONS {
+            writeln!(out, "        {} {} {{", dir, dir_variant).unwrap();
+            let key = (state.to_owned(), dir.to_owned());
+            for (id, data_name) in data.get(&key).into_iter().flatten() {
+                match packet_name(state, dir, data_name, protocol_version) {
+                    Some(name) => {
+                        writeln!(out, "            0x{:02x} => {}", id, name).unwrap();
+                        generated.mapped.push((state, dir, name));
+                    }
+                    None => {
+                        writeln!(out, "            // 0x{:02x} => {}", id, data_name).unwrap();
+                        generated
+                            .missing
+                            .push(format!("{} {} 0x{:02x} {}", state, dir, id, data_name));
+                    }
+                }
+            }
+            writeln!(out, "        }}").unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, ");").unwrap();
+    generated
+}
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        let usage = format!("Usage: {} <minecraft-version> <protocol-dir>\n", args[0])
+            + &format!("Example: {} 1.19 protocol/src/protocol/", args[0]);
+        println!("{}", usage);
+        return Ok(());
+    }
+
+    let target = &args[1];
+    let protocol_dir = Path::new(&args[2]);
+    let versions = versions_by_minecraft_version().unwrap();
+    let target_version = match versions.get(target.as_str()) {
+        Some(version) => version.clone(),
+        None => {
+            println!("minecraft-data doesn't know version {}", target);
+            return Ok(());
+        }
+    };
+    if names::NAMES
+        .iter()
+        .all(|(_, _, _, first, _)| *first > target_version.version)
+    {
+        println!(
+            "names.rs has no names for {} yet, its table is still written by hand",
+            target
+        );
+        return Ok(());
+    }
+    let generated = generate(
+        &data_table(&Api::new(target_version.clone())),
+        target_version.version,
+    );
+
+    let output = protocol_dir
+        .join("versions")
+        .join(format!("{}.rs", module_name(target)));
+    println!("Writing {}", output.display());
+    fs::write(&output, &generated.source)?;
+
+    let declared = declared_packets(&fs::read_to_string(protocol_dir.join("packet.rs"))?);
+    let undeclared: Vec<_> = generated
+        .mapped
+        .iter()
+        .filter(|(_, _, name)| !declared.contains(*name))
+        .collect();
+    if generated.missing.is_empty() && undeclared.is_empty() {
+        println!("Every packet of {} is mapped", target);
+    }
+    if !generated.missing.is_empty() {
+        println!(
+            "{} packets of {} aren't in names.rs yet:",
+            generated.missing.len(),
+            target
+        );
+        for packet in &generated.missing {
+            println!("  {}", packet);
+        }
+    }
+    if !undeclared.is_empty() {
+        println!("Mapped to names packet.rs doesn't declare:");
+        for (state, dir, name) in undeclared {
+            println!("  {} {} {}", state, dir, name);
+        }
+    }
+    println!(
+        "New versions also need to be added to versions.rs and SUPPORTED_PROTOCOLS (protocol {})",
+        target_version.version
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The only check of the ids in `names.rs`, it needs the minecraft-data
+    /// checkout the generator is built with
+    #[test]
+    fn regenerates_v1_19() {
+        let version = versions_by_minecraft_version().unwrap()["1.19"].clone();
+        let generated = generate(&data_table(&Api::new(version.clone())), version.version);
+        assert!(generated.missing.is_empty(), "{:?}", generated.missing);
+        assert_eq!(
+            generated.source,
+            include_str!("../../protocol/src/protocol/versions/v1_19.rs")
+        );
+    }
+}
@@ -0,0 +1,852 @@
+//! The `packet.rs` variant for every minecraft-data packet name.
+//!
+//! An entry applies from its protocol version on, until an entry for the same
+//! packet with a newer protocol version replaces it. When a packet's layout
+//! changes, add an entry for the version which changed it instead of editing
+//! the old one, so older tables still regenerate unchanged.
+
+/// State, direction, minecraft-data name, first protocol version and
+/// `packet.rs` name
+pub const NAMES: &[(&str, &str, &str, i32, &str)] = &[
+    // handshake serverbound
+    ("handshake", "serverbound", "set_protocol", 759, "Handshake"),
+    // play serverbound
+    (
+        "play",
+        "serverbound",
+        "teleport_confirm",
+        759,
+        "TeleportConfirm",
+    ),
+    (
+        "play",
+        "serverbound",
+        "query_block_nbt",
+        759,
+        "QueryBlockNBT",
+    ),
+    (
+        "play",
+        "serverbound",
+        "set_difficulty",
+        759,
+        "SetDifficulty",
+    ),
+    ("play", "serverbound", "chat_command", 759, "ChatCommand"),
+    (
+        "play",
+        "serverbound",
+        "chat_message",
+        759,
+        "ChatMessage_Signed",
+    ),
+    (
+        "play",
+        "serverbound",
+        "chat_preview",
+        759,
+        "ChatPreviewServerbound",
+    ),
+    ("play", "serverbound", "client_command", 759, "ClientStatus"),
+    (
+        "play",
+        "serverbound",
+        "settings",
+        759,
+        "ClientSettings_Filtering",
+    ),
+    ("play", "serverbound", "tab_complete", 759, "TabComplete"),
+    (
+        "play",
+        "serverbound",
+        "enchant_item",
+        759,
+        "ClickWindowButton",
+    ),
+    (
+        "play",
+        "serverbound",
+        "window_click",
+        759,
+        "ClickWindow_State",
+    ),
+    ("play", "serverbound", "close_window", 759, "CloseWindow"),
+    (
+        "play",
+        "serverbound",
+        "custom_payload",
+        759,
+        "PluginMessageServerbound",
+    ),
+    ("play", "serverbound", "edit_book", 759, "EditBook_Pages"),
+    (
+        "play",
+        "serverbound",
+        "query_entity_nbt",
+        759,
+        "QueryEntityNBT",
+    ),
+    (
+        "play",
+        "serverbound",
+        "use_entity",
+        759,
+        "UseEntity_Sneakflag",
+    ),
+    (
+        "play",
+        "serverbound",
+        "generate_structure",
+        759,
+        "GenerateStructure",
+    ),
+    (
+        "play",
+        "serverbound",
+        "keep_alive",
+        759,
+        "KeepAliveServerbound_i64",
+    ),
+    (
+        "play",
+        "serverbound",
+        "lock_difficulty",
+        759,
+        "LockDifficulty",
+    ),
+    ("play", "serverbound", "position", 759, "PlayerPosition"),
+    (
+        "play",
+        "serverbound",
+        "position_look",
+        759,
+        "PlayerPositionLook",
+    ),
+    ("play", "serverbound", "look", 759, "PlayerLook"),
+    ("play", "serverbound", "flying", 759, "Player"),
+    ("play", "serverbound", "vehicle_move", 759, "VehicleMove"),
+    ("play", "serverbound", "steer_boat", 759, "SteerBoat"),
+    ("play", "serverbound", "pick_item", 759, "PickItem"),
+    (
+        "play",
+        "serverbound",
+        "craft_recipe_request",
+        759,
+        "CraftRecipeRequest",
+    ),
+    (
+        "play",
+        "serverbound",
+        "abilities",
+        759,
+        "ClientAbilities_u8",
+    ),
+    (
+        "play",
+        "serverbound",
+        "block_dig",
+        759,
+        "PlayerDigging_Sequence",
+    ),
+    ("play", "serverbound", "entity_action", 759, "PlayerAction"),
+    ("play", "serverbound", "steer_vehicle", 759, "SteerVehicle"),
+    ("play", "serverbound", "pong", 759, "Pong"),
+    (
+        "play",
+        "serverbound",
+        "recipe_book",
+        759,
+        "SetRecipeBookState",
+    ),
+    (
+        "play",
+        "serverbound",
+        "displayed_recipe",
+        759,
+        "SetDisplayedRecipe",
+    ),
+    ("play", "serverbound", "name_item", 759, "NameItem"),
+    (
+        "play",
+        "serverbound",
+        "resource_pack_receive",
+        759,
+        "ResourcePackStatus",
+    ),
+    (
+        "play",
+        "serverbound",
+        "advancement_tab",
+        759,
+        "AdvancementTab",
+    ),
+    ("play", "serverbound", "select_trade", 759, "SelectTrade"),
+    (
+        "play",
+        "serverbound",
+        "set_beacon_effect",
+        759,
+        "SetBeaconEffect",
+    ),
+    (
+        "play",
+        "serverbound",
+        "held_item_slot",
+        759,
+        "HeldItemChange",
+    ),
+    (
+        "play",
+        "serverbound",
+        "update_command_block",
+        759,
+        "UpdateCommandBlock",
+    ),
+    (
+        "play",
+        "serverbound",
+        "update_command_block_minecart",
+        759,
+        "UpdateCommandBlockMinecart",
+    ),
+    (
+        "play",
+        "serverbound",
+        "set_creative_slot",
+        759,
+        "CreativeInventoryAction",
+    ),
+    (
+        "play",
+        "serverbound",
+        "update_jigsaw_block",
+        759,
+        "UpdateJigsawBlock_Joint",
+    ),
+    (
+        "play",
+        "serverbound",
+        "update_structure_block",
+        759,
+        "UpdateStructureBlock",
+    ),
+    ("play", "serverbound", "update_sign", 759, "SetSign"),
+    ("play", "serverbound", "arm_animation", 759, "ArmSwing"),
+    ("play", "serverbound", "spectate", 759, "SpectateTeleport"),
+    (
+        "play",
+        "serverbound",
+        "block_place",
+        759,
+        "PlayerBlockPlacement_Sequence",
+    ),
+    ("play", "serverbound", "use_item", 759, "UseItem_Sequence"),
+    // play clientbound
+    (
+        "play",
+        "clientbound",
+        "spawn_entity",
+        759,
+        "SpawnObject_HeadYaw",
+    ),
+    (
+        "play",
+        "clientbound",
+        "spawn_entity_experience_orb",
+        759,
+        "SpawnExperienceOrb",
+    ),
+    (
+        "play",
+        "clientbound",
+        "named_entity_spawn",
+        759,
+        "SpawnPlayer_f64_NoMeta",
+    ),
+    ("play", "clientbound", "animation", 759, "Animation"),
+    ("play", "clientbound", "statistics", 759, "Statistics"),
+    (
+        "play",
+        "clientbound",
+        "acknowledge_player_digging",
+        759,
+        "AcknowledgeBlockChange",
+    ),
+    (
+        "play",
+        "clientbound",
+        "block_break_animation",
+        759,
+        "BlockBreakAnimation",
+    ),
+    (
+        "play",
+        "clientbound",
+        "tile_entity_data",
+        759,
+        "UpdateBlockEntity_VarInt",
+    ),
+    ("play", "clientbound", "block_action", 759, "BlockAction"),
+    (
+        "play",
+        "clientbound",
+        "block_change",
+        759,
+        "BlockChange_VarInt",
+    ),
+    ("play", "clientbound", "boss_bar", 759, "BossBar"),
+    (
+        "play",
+        "clientbound",
+        "difficulty",
+        759,
+        "ServerDifficulty_Locked",
+    ),
+    (
+        "play",
+        "clientbound",
+        "chat_preview",
+        759,
+        "ChatPreviewClientbound",
+    ),
+    ("play", "clientbound", "clear_titles", 759, "ClearTitles"),
+    (
+        "play",
+        "clientbound",
+        "tab_complete",
+        759,
+        "TabCompleteReply",
+    ),
+    (
+        "play",
+        "clientbound",
+        "declare_commands",
+        759,
+        "DeclareCommands",
+    ),
+    ("play", "clientbound", "close_window", 759, "WindowClose"),
+    (
+        "play",
+        "clientbound",
+        "window_items",
+        759,
+        "WindowItems_StateCarry",
+    ),
+    (
+        "play",
+        "clientbound",
+        "craft_progress_bar",
+        759,
+        "WindowProperty",
+    ),
+    (
+        "play",
+        "clientbound",
+        "set_slot",
+        759,
+        "WindowSetSlot_State",
+    ),
+    ("play", "clientbound", "set_cooldown", 759, "SetCooldown"),
+    (
+        "play",
+        "clientbound",
+        "custom_payload",
+        759,
+        "PluginMessageClientbound",
+    ),
+    (
+        "play",
+        "clientbound",
+        "named_sound_effect",
+        759,
+        "NamedSoundEffect_Seed",
+    ),
+    ("play", "clientbound", "kick_disconnect", 759, "Disconnect"),
+    ("play", "clientbound", "entity_status", 759, "EntityAction"),
+    ("play", "clientbound", "explosion", 759, "Explosion_VarInt"),
+    ("play", "clientbound", "unload_chunk", 759, "ChunkUnload"),
+    (
+        "play",
+        "clientbound",
+        "game_state_change",
+        759,
+        "ChangeGameState",
+    ),
+    (
+        "play",
+        "clientbound",
+        "open_horse_window",
+        759,
+        "WindowOpenHorse",
+    ),
+    (
+        "play",
+        "clientbound",
+        "initialize_world_border",
+        759,
+        "WorldBorderInit",
+    ),
+    (
+        "play",
+        "clientbound",
+        "keep_alive",
+        759,
+        "KeepAliveClientbound_i64",
+    ),
+    (
+        "play",
+        "clientbound",
+        "map_chunk",
+        759,
+        "ChunkData_AndLight",
+    ),
+    ("play", "clientbound", "world_event", 759, "Effect"),
+    (
+        "play",
+        "clientbound",
+        "world_particles",
+        759,
+        "Particle_VarInt_Vibration",
+    ),
+    (
+        "play",
+        "clientbound",
+        "update_light",
+        759,
+        "UpdateLight_BitSet",
+    ),
+    (
+        "play",
+        "clientbound",
+        "login",
+        759,
+        "JoinGame_DeathLocation",
+    ),
+    ("play", "clientbound", "map", 759, "Maps_OptionalIcons"),
+    (
+        "play",
+        "clientbound",
+        "trade_list",
+        759,
+        "TradeList_WithRestock",
+    ),
+    (
+        "play",
+        "clientbound",
+        "rel_entity_move",
+        759,
+        "EntityMove_i16",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_move_look",
+        759,
+        "EntityLookAndMove_i16",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_look",
+        759,
+        "EntityLook_VarInt",
+    ),
+    (
+        "play",
+        "clientbound",
+        "vehicle_move",
+        759,
+        "VehicleTeleport",
+    ),
+    ("play", "clientbound", "open_book", 759, "OpenBook"),
+    (
+        "play",
+        "clientbound",
+        "open_window",
+        759,
+        "WindowOpen_VarInt",
+    ),
+    (
+        "play",
+        "clientbound",
+        "open_sign_entity",
+        759,
+        "SignEditorOpen",
+    ),
+    ("play", "clientbound", "ping", 759, "Ping"),
+    (
+        "play",
+        "clientbound",
+        "craft_recipe_response",
+        759,
+        "CraftRecipeResponse",
+    ),
+    ("play", "clientbound", "abilities", 759, "PlayerAbilities"),
+    (
+        "play",
+        "clientbound",
+        "player_chat",
+        759,
+        "PlayerChatMessage",
+    ),
+    (
+        "play",
+        "clientbound",
+        "end_combat_event",
+        759,
+        "EndCombatEvent",
+    ),
+    (
+        "play",
+        "clientbound",
+        "enter_combat_event",
+        759,
+        "EnterCombatEvent",
+    ),
+    (
+        "play",
+        "clientbound",
+        "death_combat_event",
+        759,
+        "DeathCombatEvent",
+    ),
+    ("play", "clientbound", "player_info", 759, "PlayerInfo"),
+    ("play", "clientbound", "face_player", 759, "FacePlayer"),
+    (
+        "play",
+        "clientbound",
+        "position",
+        759,
+        "TeleportPlayer_WithDismount",
+    ),
+    (
+        "play",
+        "clientbound",
+        "unlock_recipes",
+        759,
+        "UnlockRecipes_WithBlastSmoker",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_destroy",
+        759,
+        "EntityDestroy",
+    ),
+    (
+        "play",
+        "clientbound",
+        "remove_entity_effect",
+        759,
+        "EntityRemoveEffect",
+    ),
+    (
+        "play",
+        "clientbound",
+        "resource_pack_send",
+        759,
+        "ResourcePackSend_Prompt",
+    ),
+    (
+        "play",
+        "clientbound",
+        "respawn",
+        759,
+        "Respawn_DeathLocation",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_head_rotation",
+        759,
+        "EntityHeadLook",
+    ),
+    (
+        "play",
+        "clientbound",
+        "multi_block_change",
+        759,
+        "MultiBlockChange_Packed",
+    ),
+    (
+        "play",
+        "clientbound",
+        "select_advancement_tab",
+        759,
+        "SelectAdvancementTab",
+    ),
+    ("play", "clientbound", "server_data", 759, "ServerData"),
+    ("play", "clientbound", "action_bar", 759, "ActionBar"),
+    (
+        "play",
+        "clientbound",
+        "world_border_center",
+        759,
+        "WorldBorderCenter",
+    ),
+    (
+        "play",
+        "clientbound",
+        "world_border_lerp_size",
+        759,
+        "WorldBorderLerpSize",
+    ),
+    (
+        "play",
+        "clientbound",
+        "world_border_size",
+        759,
+        "WorldBorderSize",
+    ),
+    (
+        "play",
+        "clientbound",
+        "world_border_warning_delay",
+        759,
+        "WorldBorderWarningDelay",
+    ),
+    (
+        "play",
+        "clientbound",
+        "world_border_warning_reach",
+        759,
+        "WorldBorderWarningReach",
+    ),
+    ("play", "clientbound", "camera", 759, "Camera"),
+    (
+        "play",
+        "clientbound",
+        "held_item_slot",
+        759,
+        "SetCurrentHotbarSlot",
+    ),
+    (
+        "play",
+        "clientbound",
+        "update_view_position",
+        759,
+        "UpdateViewPosition",
+    ),
+    (
+        "play",
+        "clientbound",
+        "update_view_distance",
+        759,
+        "UpdateViewDistance",
+    ),
+    (
+        "play",
+        "clientbound",
+        "spawn_position",
+        759,
+        "SpawnPosition_Angle",
+    ),
+    (
+        "play",
+        "clientbound",
+        "should_display_chat_preview",
+        759,
+        "SetDisplayChatPreview",
+    ),
+    (
+        "play",
+        "clientbound",
+        "scoreboard_display_objective",
+        759,
+        "ScoreboardDisplay",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_metadata",
+        759,
+        "EntityMetadata",
+    ),
+    ("play", "clientbound", "attach_entity", 759, "EntityAttach"),
+    (
+        "play",
+        "clientbound",
+        "entity_velocity",
+        759,
+        "EntityVelocity",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_equipment",
+        759,
+        "EntityEquipment_Array",
+    ),
+    ("play", "clientbound", "experience", 759, "SetExperience"),
+    ("play", "clientbound", "update_health", 759, "UpdateHealth"),
+    (
+        "play",
+        "clientbound",
+        "scoreboard_objective",
+        759,
+        "ScoreboardObjective",
+    ),
+    (
+        "play",
+        "clientbound",
+        "set_passengers",
+        759,
+        "SetPassengers",
+    ),
+    ("play", "clientbound", "teams", 759, "Teams_VarInt"),
+    (
+        "play",
+        "clientbound",
+        "scoreboard_score",
+        759,
+        "UpdateScore",
+    ),
+    (
+        "play",
+        "clientbound",
+        "simulation_distance",
+        759,
+        "UpdateSimulationDistance",
+    ),
+    (
+        "play",
+        "clientbound",
+        "set_title_subtitle",
+        759,
+        "SetTitleSubtitle",
+    ),
+    ("play", "clientbound", "update_time", 759, "TimeUpdate"),
+    ("play", "clientbound", "set_title_text", 759, "SetTitleText"),
+    (
+        "play",
+        "clientbound",
+        "set_title_time",
+        759,
+        "SetTitleTimes",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_sound_effect",
+        759,
+        "EntitySoundEffect_Seed",
+    ),
+    (
+        "play",
+        "clientbound",
+        "sound_effect",
+        759,
+        "SoundEffect_Seed",
+    ),
+    ("play", "clientbound", "stop_sound", 759, "StopSound"),
+    (
+        "play",
+        "clientbound",
+        "system_chat",
+        759,
+        "SystemChatMessage",
+    ),
+    (
+        "play",
+        "clientbound",
+        "playerlist_header",
+        759,
+        "PlayerListHeaderFooter",
+    ),
+    (
+        "play",
+        "clientbound",
+        "nbt_query_response",
+        759,
+        "NBTQueryResponse",
+    ),
+    ("play", "clientbound", "collect", 759, "CollectItem"),
+    (
+        "play",
+        "clientbound",
+        "entity_teleport",
+        759,
+        "EntityTeleport_f64",
+    ),
+    ("play", "clientbound", "advancements", 759, "Advancements"),
+    (
+        "play",
+        "clientbound",
+        "entity_update_attributes",
+        759,
+        "EntityProperties_VarIntLen",
+    ),
+    (
+        "play",
+        "clientbound",
+        "entity_effect",
+        759,
+        "EntityEffect_FactorData",
+    ),
+    (
+        "play",
+        "clientbound",
+        "declare_recipes",
+        759,
+        "DeclareRecipes",
+    ),
+    ("play", "clientbound", "tags", 759, "TagsWithTypes"),
+    // login serverbound
+    ("login", "serverbound", "login_start", 759, "LoginStart_Sig"),
+    (
+        "login",
+        "serverbound",
+        "encryption_begin",
+        759,
+        "EncryptionResponse_Sig",
+    ),
+    (
+        "login",
+        "serverbound",
+        "login_plugin_response",
+        759,
+        "LoginPluginResponse",
+    ),
+    // login clientbound
+    ("login", "clientbound", "disconnect", 759, "LoginDisconnect"),
+    (
+        "login",
+        "clientbound",
+        "encryption_begin",
+        759,
+        "EncryptionRequest",
+    ),
+    (
+        "login",
+        "clientbound",
+        "success",
+        759,
+        "LoginSuccess_Properties",
+    ),
+    (
+        "login",
+        "clientbound",
+        "compress",
+        759,
+        "SetInitialCompression",
+    ),
+    (
+        "login",
+        "clientbound",
+        "login_plugin_request",
+        759,
+        "LoginPluginRequest",
+    ),
+    // status serverbound
+    ("status", "serverbound", "ping_start", 759, "StatusRequest"),
+    ("status", "serverbound", "ping", 759, "StatusPing"),
+    // status clientbound
+    (
+        "status",
+        "clientbound",
+        "server_info",
+        759,
+        "StatusResponse",
+    ),
+    ("status", "clientbound", "ping", 759, "StatusPong"),
+];
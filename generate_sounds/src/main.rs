@@ -0,0 +1,52 @@
+//! Generates the sound event names by registry id of a version in
+//! `resources/assets/leafish/sounds/ids/` from the `sounds.json` of
+//! minecraft-data, which lists every event with its id.
+
+use serde_json::Value;
+use std::fs::{self, File};
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 4 {
+        let usage = format!(
+            "Usage: {} <minecraft-data sounds.json> <protocol-version> <resources-dir>\n",
+            args[0]
+        ) + &format!(
+            "Example: {} ../minecraft-data/data/pc/1.19/sounds.json 759 resources/",
+            args[0]
+        );
+        println!("{}", usage);
+        return Ok(());
+    }
+
+    let sounds: Value = serde_json::from_reader(File::open(&args[1])?)?;
+    let protocol_version: i32 = args[2].parse().expect("Invalid protocol version");
+    let mut names: Vec<String> = vec![];
+    for sound in sounds.as_array().expect("sounds.json isn't a list") {
+        let id = sound
+            .get("id")
+            .and_then(Value::as_u64)
+            .expect("Sound without id") as usize;
+        let name = sound
+            .get("name")
+            .and_then(Value::as_str)
+            .expect("Sound without name");
+        // Ids minecraft-data skips stay empty
+        if names.len() <= id {
+            names.resize(id + 1, String::new());
+        }
+        names[id] = name.to_owned();
+    }
+
+    let dir = Path::new(&args[3]).join("assets/leafish/sounds/ids");
+    fs::create_dir_all(&dir)?;
+    let output = dir.join(format!("{}.json", protocol_version));
+    println!(
+        "Writing {} sound events to {}",
+        names.len(),
+        output.display()
+    );
+    serde_json::to_writer(File::create(&output)?, &names)?;
+    Ok(())
+}
@@ -27,12 +27,16 @@ const LEGACY_CHAR: char = '§';
 #[derive(Debug, Clone)]
 pub struct Component {
     pub list: Vec<ComponentType>,
+    /// The tree the list was flattened from, if it was parsed from json.
+    /// Kept so the component serializes back the way it was sent.
+    pub source: Option<ChatSections>,
 }
 
 impl Component {
     pub fn new(component: ComponentType) -> Self {
         Self {
             list: vec![component],
+            source: None,
         }
     }
 
@@ -46,6 +50,7 @@ impl Component {
                     Component::from_legacy_str(comp.get_text(), comp.get_modifier()).list
                 })
                 .collect(),
+            source: self.source.clone(),
         }
     }
 
@@ -119,14 +124,17 @@ impl Component {
             });
         }
 
-        Self { list: components }
+        Self {
+            list: components,
+            source: None,
+        }
     }
 
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(str: &str) -> Self {
         log::trace!("Raw: {}", str);
         match serde_json::from_str::<ChatSections>(str) {
-            Ok(sections) => Component::from_chat_sections(sections, &Modifier::default()),
+            Ok(sections) => Component::from_chat_sections(sections),
             // Sometimes mojang sends a literal string, so we should interpret it literally
             Err(error) => {
                 log::trace!("Failed error: {}", error);
@@ -135,140 +143,78 @@ impl Component {
         }
     }
 
-    fn get_text(with: &ComponentData, modifier: &Modifier) -> Self {
-        match with {
-            ComponentData::Chat(chat) => {
-                Component::from_chat(chat, &modifier.over_write(&chat.get_modifier()))
-            }
-
-            ComponentData::Str(str) => Self {
-                list: vec![ComponentType::Text {
-                    text: str.to_string(),
-                    modifier: modifier.clone(),
-                }],
-            },
-        }
-    }
-
-    fn get_string_from_extra(extra: &[translate::ComponentData], modifier: &Modifier) -> Self {
-        Self {
-            list: extra
-                .iter()
-                .flat_map(|chat_or_string| match chat_or_string {
-                    ComponentData::Chat(chat) => {
-                        Component::from_chat(
-                            chat,
-                            &modifier.over_write(&chat_or_string.get_modifier()),
-                        )
-                        .list
-                    }
-                    ComponentData::Str(str) => {
-                        Component::from_legacy_str(
-                            str,
-                            &modifier.over_write(&chat_or_string.get_modifier()),
-                        )
-                        .list
-                    }
-                })
-                .collect::<_>(),
-        }
-    }
-
-    fn from_chat_sections(sections: ChatSections, modifier: &Modifier) -> Self {
+    fn from_chat_sections(sections: ChatSections) -> Self {
         Self {
             list: sections
                 .sections
-                .into_iter()
-                .flat_map(|c| Self::from_chat(&c, modifier).list)
+                .iter()
+                .flat_map(|chat| Component::from_chat(chat, &Modifier::default()))
                 .collect(),
+            source: Some(sections),
         }
     }
 
-    fn from_chat(chat: &Chat, modifier: &Modifier) -> Self {
-        let modifier = modifier.over_write(&chat.get_modifier());
-        let text_components: Vec<ComponentType> = match (&chat.translate, &chat.text, &chat.extra) {
-            (None, None, None) => chat
-                .with
-                .iter()
-                .flat_map(|with| Component::get_text(with, &modifier).list)
-                .collect(),
-
-            (Some(translate), None, None) => {
-                let mut list = chat
-                    .with
-                    .iter()
-                    .map(|inner_chat| Component::get_text(inner_chat, &modifier))
-                    .collect::<Vec<Component>>();
-
-                let mut iter_component = list.iter_mut();
-
-                // FIXME: this is just a band-aid solution, parse the translate string properly as defined in https://wiki.vg/Text_formatting#Text_components
-                if translate == "%s" {
-                    return list.into_iter().next().unwrap();
-                }
+    fn from_component_data(data: &ComponentData, modifier: &Modifier) -> Vec<ComponentType> {
+        match data {
+            ComponentData::Chat(chat) => Component::from_chat(chat, modifier),
+            ComponentData::Str(str) => Component::from_legacy_str(str, modifier).list,
+        }
+    }
 
-                let mut components = Vec::new();
-                let translated = translate::translate(translate).replace("%%", "%");
-                let mut index = 0;
-                for (i, char) in translated.char_indices() {
-                    match char {
-                        '{' => {
-                            components.push(ComponentType::Text {
-                                text: translated[index..i].to_string(),
-                                modifier: modifier.clone(),
-                            });
-                            if let Some(component) = iter_component.next() {
-                                components.append(&mut component.list);
-                            }
-                        }
-                        '}' => index = i + 1,
-                        _ => {}
-                    }
-                }
-                components.push(ComponentType::Text {
-                    text: translated[index..].to_string(),
-                    modifier: modifier.clone(),
-                });
-                components
-            }
-            (Some(translate), Some(text), None) => {
-                format!("ERR trans: {}, text: {}", translate, text);
-                todo!()
-            }
-            (Some(translate), Some(text), Some(extra)) => {
-                format!(
-                    "ERR trans: {}, text: {}, extra{:?}, ",
-                    translate, text, extra
-                );
-                todo!()
-            }
-            (Some(text), None, Some(extra)) => {
-                format!("ERR trans: {}, extra: {:?}", text, extra);
-                todo!()
-            }
-            (None, None, Some(extra)) => {
-                format!("ERR extra: {:?}", extra);
-                todo!()
-            }
-            (None, Some(text), Some(extra)) => {
-                let mut component = Component::from_legacy_str(text, &modifier).list;
-                component.append(&mut Component::get_string_from_extra(extra, &modifier).list);
-                component
-            }
-            (None, Some(text), None) => Component::from_legacy_str(text, &modifier).list,
+    fn from_chat(chat: &Chat, parent: &Modifier) -> Vec<ComponentType> {
+        let modifier = chat.style(parent);
+        let plain = |text: &str| {
+            vec![ComponentType::Text {
+                text: text.to_owned(),
+                modifier: modifier.clone(),
+            }]
         };
-        // chat.build_component_from_string(return_type);
-        Component {
-            list: text_components,
-        }
+        let mut list = if let Some(text) = &chat.text {
+            Component::from_legacy_str(text, &modifier).list
+        } else if let Some(key) = &chat.translate {
+            Component::from_translation(key, &chat.with, &modifier)
+        } else if let Some(score) = &chat.score {
+            plain(score.value.as_deref().unwrap_or_default())
+        } else if let Some(selector) = &chat.selector {
+            // Servers resolve selectors before sending them, the client
+            // can't, so the ones left are shown as written
+            plain(selector)
+        } else if let Some(keybind) = &chat.keybind {
+            plain(&translate::keybind(keybind))
+        } else {
+            vec![]
+        };
+        for extra in chat.extra.iter().flatten() {
+            list.append(&mut Component::from_component_data(extra, &modifier));
+        }
+        list
+    }
+
+    /// Fills the arguments into the format string of the key. Placeholders
+    /// without a matching argument are left out.
+    fn from_translation(
+        key: &str,
+        with: &[ComponentData],
+        modifier: &Modifier,
+    ) -> Vec<ComponentType> {
+        translate::split_format(&translate::translate(key))
+            .into_iter()
+            .flat_map(|part| match part {
+                FormatPart::Text(text) => vec![ComponentType::Text {
+                    text,
+                    modifier: modifier.clone(),
+                }],
+                FormatPart::Arg(index) => with
+                    .get(index)
+                    .map(|arg| Component::from_component_data(arg, modifier))
+                    .unwrap_or_default(),
+            })
+            .collect()
     }
 
     pub fn from_json(v: &serde_json::Value) -> Result<Self, Error> {
         match serde_json::from_value::<ChatSections>(v.clone()) {
-            Ok(sections) => Ok(Component::from_chat_sections(
-                sections,
-                &Modifier::default(),
-            )),
+            Ok(sections) => Ok(Component::from_chat_sections(sections)),
             // Sometimes mojang sends a literal string, so we should interpret it literally
             Err(error) => {
                 log::trace!("Failed error: {}", error);
@@ -283,8 +229,26 @@ impl Component {
         }
     }
 
+    /// Serializes the component back into its json form. Components which
+    /// weren't parsed from json are built from their parts, which become
+    /// extras of an unstyled root so they don't inherit each other's style.
     pub fn to_value(&self) -> serde_json::Value {
-        unimplemented!()
+        if let Some(source) = &self.source {
+            return serde_json::to_value(source).unwrap();
+        }
+        let parts = self
+            .list
+            .iter()
+            .filter(|comp| !comp.get_text().is_empty())
+            .collect::<Vec<_>>();
+        match parts.as_slice() {
+            [] => serde_json::json!({ "text": "" }),
+            [component] => component.to_value(),
+            parts => serde_json::json!({
+                "text": "",
+                "extra": parts.iter().map(|comp| comp.to_value()).collect::<Vec<_>>(),
+            }),
+        }
     }
 }
 
@@ -349,8 +313,28 @@ impl fmt::Debug for Modifier {
 // TODO: Missing events click/hover/insert
 
 impl Modifier {
+    /// Returns the json fields of the modifier, unset fields are left out.
     pub fn to_value(&self) -> serde_json::Value {
-        unimplemented!()
+        let mut map = serde_json::Map::new();
+        if self.bold {
+            map.insert("bold".to_owned(), true.into());
+        }
+        if self.italic {
+            map.insert("italic".to_owned(), true.into());
+        }
+        if self.underlined {
+            map.insert("underlined".to_owned(), true.into());
+        }
+        if self.strikethrough {
+            map.insert("strikethrough".to_owned(), true.into());
+        }
+        if self.obfuscated {
+            map.insert("obfuscated".to_owned(), true.into());
+        }
+        if self.color != Color::None {
+            map.insert("color".to_owned(), self.color.to_string().into());
+        }
+        serde_json::Value::Object(map)
     }
 
     pub fn over_write(&self, modifier: &Self) -> Self {
@@ -445,19 +429,30 @@ impl ComponentType {
     }
 
     pub fn to_value(&self) -> serde_json::Value {
-        unimplemented!()
+        let mut value = self.get_modifier().to_value();
+        value
+            .as_object_mut()
+            .unwrap()
+            .insert("text".to_owned(), self.get_text().into());
+        value
     }
 }
 
 impl Chat {
     fn get_modifier(&self) -> Modifier {
+        self.style(&Modifier::default())
+    }
+
+    /// The style of the section, taking whatever it doesn't set from its
+    /// parent. Unlike `Modifier::over_write` this lets it turn styles off.
+    fn style(&self, parent: &Modifier) -> Modifier {
         Modifier {
-            bold: self.bold.unwrap_or_default(),
-            italic: self.italic.unwrap_or_default(),
-            underlined: self.underlined.unwrap_or_default(),
-            strikethrough: self.strikethrough.unwrap_or_default(),
-            obfuscated: self.obfuscated.unwrap_or_default(),
-            color: self.color.unwrap_or_default(),
+            bold: self.bold.unwrap_or(parent.bold),
+            italic: self.italic.unwrap_or(parent.italic),
+            underlined: self.underlined.unwrap_or(parent.underlined),
+            strikethrough: self.strikethrough.unwrap_or(parent.strikethrough),
+            obfuscated: self.obfuscated.unwrap_or(parent.obfuscated),
+            color: self.color.unwrap_or(parent.color),
         }
     }
 
@@ -469,15 +464,6 @@ impl Chat {
     }
 }
 
-impl ComponentData {
-    fn get_modifier(&self) -> Modifier {
-        match self {
-            ComponentData::Chat(chat) => chat.get_modifier(),
-            ComponentData::Str(_) => Modifier::default(),
-        }
-    }
-}
-
 pub mod color {
     use crate::format::*;
 
@@ -724,6 +710,10 @@ mod test {
                 insertion: Some("baz".into()),
                 extra: Some(vec![]),
                 with: vec![],
+                keybind: None,
+                score: None,
+                selector: None,
+                separator: None,
             }
         );
     }
@@ -760,6 +750,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn component_round_trip() {
+        let component = Component::from_str(
+            r#"{"text":"","extra":[{"text":"foo","color":"red","bold":true},{"text":"bar"}]}"#,
+        );
+        let value = component.to_value();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "text": "",
+                "extra": [{"text": "foo", "color": "red", "bold": true}, {"text": "bar"}],
+            })
+        );
+        assert_eq!(Component::from_json(&value).unwrap().to_value(), value);
+    }
+
+    #[test]
+    fn tree_round_trip() {
+        let value = serde_json::json!({
+            "translate": "chat.type.text",
+            "with": [
+                {"text": "Steve", "clickEvent": {"action": "suggest_command", "value": "/tell Steve "}},
+                "hi",
+            ],
+            "extra": [
+                {"keybind": "key.jump", "italic": false},
+                {"score": {"name": "Steve", "objective": "kills", "value": "3"}},
+                {"selector": "@p", "separator": ", "},
+            ],
+            "color": "gray",
+        });
+        assert_eq!(Component::from_json(&value).unwrap().to_value(), value);
+    }
+
+    #[test]
+    fn translation_arguments() {
+        assert_eq!(
+            split_format("%2$s %s%% %1$s %x"),
+            vec![
+                FormatPart::Arg(1),
+                FormatPart::Text(" ".into()),
+                FormatPart::Arg(0),
+                FormatPart::Text("% ".into()),
+                FormatPart::Arg(0),
+                FormatPart::Text(" %x".into()),
+            ]
+        );
+
+        let component = Component::from_str(
+            r#"{"translate":"chat.type.text","color":"gray","with":[{"text":"Steve","color":"red"},"hi"]}"#,
+        );
+        assert_eq!(component.to_string(), "<Steve> hi");
+        let steve = component
+            .list
+            .iter()
+            .find(|comp| comp.get_text() == "Steve")
+            .unwrap();
+        assert_eq!(steve.get_modifier().color, Color::Red);
+        assert_eq!(component.list[0].get_modifier().color, Color::Gray);
+
+        let component =
+            Component::from_str(r#"{"text":"Press ","extra":[{"keybind":"key.jump"}]}"#);
+        assert_eq!(component.to_string(), "Press Space");
+    }
+
+    #[test]
+    fn bound_keys() {
+        translate::set_keybind_resolver(|name| match name {
+            "key.sneak" => Some("C".to_owned()),
+            _ => None,
+        });
+        let component = Component::from_str(
+            r#"{"text":"","extra":[{"keybind":"key.sneak"},{"keybind":"key.drop"},{"keybind":"key.unknown"}]}"#,
+        );
+        assert_eq!(component.to_string(), "CQkey.unknown");
+    }
+
     #[test]
     fn test_color_from() {
         match Color::from_str("FF0000").expect("could not parse FF0000") {
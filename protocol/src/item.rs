@@ -109,8 +109,12 @@ impl Serializable for Option<Stack> {
                     buf.write_i16::<BigEndian>(val.id as i16)?;
                     buf.write_u8(val.count as u8)?;
                     buf.write_i16::<BigEndian>(val.damage.unwrap_or(0) as i16)?;
-                    // TODO: compress zlib NBT if 1.7
-                    val.meta.0.write_to(buf)?;
+                    if protocol_version >= 47 {
+                        val.meta.0.write_to(buf)?;
+                    } else {
+                        // TODO: compress zlib NBT if 1.7, the tag is dropped when reading anyway
+                        buf.write_i16::<BigEndian>(-1)?;
+                    }
                 }
                 None => buf.write_i16::<BigEndian>(-1)?,
             }
@@ -12,13 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod snbt;
+
 use std::collections::HashMap;
+use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
 
 use super::protocol;
 use super::protocol::Serializable;
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
 
 #[derive(Debug, Clone)]
 pub enum Tag {
@@ -304,3 +310,112 @@ pub fn read_string<R: io::Read>(buf: &mut R) -> Result<String, protocol::Error>
     let ret = String::from_utf8(bytes).unwrap();
     Ok(ret)
 }
+
+/// How an NBT file is stored. `level.dat` and schematics are gzipped,
+/// `servers.dat` isn't compressed at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zlib,
+}
+
+impl NamedTag {
+    /// Reads a root tag as stored in files, its type followed by its name
+    /// and value.
+    pub fn read_root<R: io::Read>(buf: &mut R) -> Result<NamedTag, protocol::Error> {
+        let ty = buf.read_u8()?;
+        if ty == 0 {
+            return Err(protocol::Error::Err("missing root tag".to_owned()));
+        }
+        let name = read_string(buf)?;
+        Ok(NamedTag(name, Tag::read_type(ty, buf)?))
+    }
+
+    pub fn write_root<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
+        buf.write_u8(self.1.internal_id())?;
+        write_string(buf, &self.0)?;
+        self.1.write_to(buf)
+    }
+}
+
+/// Reads an NBT file, telling the compression apart by its first bytes so
+/// it can be written back the same way.
+pub fn read_compressed<R: io::Read>(
+    buf: &mut R,
+) -> Result<(NamedTag, Compression), protocol::Error> {
+    let mut data = Vec::new();
+    buf.read_to_end(&mut data)?;
+    match data[..] {
+        [0x1f, 0x8b, ..] => Ok((
+            NamedTag::read_root(&mut GzDecoder::new(&data[..]))?,
+            Compression::Gzip,
+        )),
+        // The header of a zlib stream using deflate is a multiple of 31
+        [0x78, flags, ..] if (0x7800 | u16::from(flags)) % 31 == 0 => Ok((
+            NamedTag::read_root(&mut ZlibDecoder::new(&data[..]))?,
+            Compression::Zlib,
+        )),
+        _ => Ok((NamedTag::read_root(&mut &data[..])?, Compression::None)),
+    }
+}
+
+pub fn write_compressed<W: io::Write>(
+    buf: &mut W,
+    tag: &NamedTag,
+    compression: Compression,
+) -> Result<(), protocol::Error> {
+    match compression {
+        Compression::None => tag.write_root(buf),
+        Compression::Gzip => {
+            let mut enc = GzEncoder::new(buf, flate2::Compression::default());
+            tag.write_root(&mut enc)?;
+            enc.finish()?;
+            Ok(())
+        }
+        Compression::Zlib => {
+            let mut enc = ZlibEncoder::new(buf, flate2::Compression::default());
+            tag.write_root(&mut enc)?;
+            enc.finish()?;
+            Ok(())
+        }
+    }
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<(NamedTag, Compression), protocol::Error> {
+    read_compressed(&mut BufReader::new(File::open(path)?))
+}
+
+pub fn write_file<P: AsRef<Path>>(
+    path: P,
+    tag: &NamedTag,
+    compression: Compression,
+) -> Result<(), protocol::Error> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write_compressed(&mut file, tag, compression)?;
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_round_trip() {
+        let tag = NamedTag(
+            "Data".to_owned(),
+            "{LevelName:\"New World\",Time:1200L,Pos:[0.5d,64.0d]}"
+                .parse()
+                .unwrap(),
+        );
+        for &compression in &[Compression::None, Compression::Gzip, Compression::Zlib] {
+            let mut data = Vec::new();
+            write_compressed(&mut data, &tag, compression).unwrap();
+            let (read, detected) = read_compressed(&mut &data[..]).unwrap();
+            assert_eq!(detected, compression);
+            assert_eq!(read.0, "Data");
+            assert_eq!(read.1.to_string(), tag.1.to_string());
+        }
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stringified NBT, the text form used by commands, e.g.
+//! `{display:{Name:'"Sword"'},Damage:3s}`.
+//!
+//! `Tag` parses it through `FromStr` and prints it through `Display`, the
+//! alternate form (`{:#}`) spreads compounds and nested lists over
+//! indented lines. Compound keys are printed in sorted order.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{NamedTag, Tag};
+use crate::protocol::Error;
+
+impl FromStr for Tag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Tag, Error> {
+        let mut parser = Parser { src: s, pos: 0 };
+        let tag = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != s.len() {
+            return Err(parser.error("trailing data"));
+        }
+        Ok(tag)
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, msg: &str) -> Error {
+        Error::Err(format!("Invalid SNBT at {}: {}", self.pos, msg))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    fn value(&mut self) -> Result<Tag, Error> {
+        match self.peek() {
+            Some('{') => self.compound(),
+            Some('[') => self.list(),
+            Some('"') | Some('\'') => Ok(Tag::String(self.quoted()?)),
+            Some(_) => {
+                let token = self.unquoted();
+                if token.is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+                Ok(unquoted_value(token))
+            }
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    fn compound(&mut self) -> Result<Tag, Error> {
+        self.expect('{')?;
+        let mut values = HashMap::new();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Tag::Compound(values));
+        }
+        loop {
+            let key = match self.peek() {
+                Some('"') | Some('\'') => self.quoted()?,
+                _ => self.unquoted().to_owned(),
+            };
+            if key.is_empty() {
+                return Err(self.error("expected a key"));
+            }
+            self.expect(':')?;
+            values.insert(key, self.value()?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Tag::Compound(values));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn list(&mut self) -> Result<Tag, Error> {
+        self.expect('[')?;
+        let rest = self.rest();
+        let array = ["B;", "I;", "L;"]
+            .iter()
+            .find(|prefix| rest.starts_with(*prefix));
+        if let Some(prefix) = array {
+            self.pos += 2;
+            let values = self.elements()?;
+            let mismatch = || self.error("wrong element type in array");
+            return Ok(match *prefix {
+                "B;" => Tag::ByteArray(
+                    values
+                        .iter()
+                        .map(|v| v.as_byte().map(|v| v as u8))
+                        .collect::<Option<_>>()
+                        .ok_or_else(mismatch)?,
+                ),
+                "I;" => Tag::IntArray(
+                    values
+                        .iter()
+                        .map(Tag::as_int)
+                        .collect::<Option<_>>()
+                        .ok_or_else(mismatch)?,
+                ),
+                _ => Tag::LongArray(
+                    values
+                        .iter()
+                        .map(Tag::as_long)
+                        .collect::<Option<_>>()
+                        .ok_or_else(mismatch)?,
+                ),
+            });
+        }
+        let values = self.elements()?;
+        if let Some(first) = values.first() {
+            if values
+                .iter()
+                .any(|v| v.internal_id() != first.internal_id())
+            {
+                return Err(self.error("mixed types in list"));
+            }
+        }
+        Ok(Tag::List(values))
+    }
+
+    /// The comma separated values of a list, up to and including the `]`
+    fn elements(&mut self) -> Result<Vec<Tag>, Error> {
+        let mut values = vec![];
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(values);
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, Error> {
+        let quote = self.peek().unwrap();
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) if c == '\\' || c == quote => out.push(c),
+                    _ => {
+                        self.pos += i;
+                        return Err(self.error("invalid escape"));
+                    }
+                },
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                c => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn unquoted(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !is_unquoted_char(c))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+/// Whether `s` is a decimal number, with `dot` telling whether it may
+/// contain a fraction
+fn is_number(s: &str, dot: bool) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) if dot => (&s[..i], Some(&s[i + 1..])),
+        _ => (s, None),
+    };
+    let digits = mantissa.chars().filter(char::is_ascii_digit).count();
+    let dots = mantissa.chars().filter(|&c| c == '.').count();
+    let exponent_ok = match exponent {
+        Some(e) => {
+            let e = e.strip_prefix(['-', '+']).unwrap_or(e);
+            !e.is_empty() && e.chars().all(|c| c.is_ascii_digit())
+        }
+        None => true,
+    };
+    digits > 0 && digits + dots == mantissa.len() && dots <= usize::from(dot) && exponent_ok
+}
+
+/// Numbers and booleans are written without quotes, everything else that
+/// isn't quoted is a string.
+fn unquoted_value(token: &str) -> Tag {
+    match token {
+        "true" => return Tag::Byte(1),
+        "false" => return Tag::Byte(0),
+        _ => {}
+    }
+    let (body, suffix) = token.split_at(token.len() - 1);
+    let tag = match suffix {
+        "b" | "B" if is_number(body, false) => body.parse().ok().map(Tag::Byte),
+        "s" | "S" if is_number(body, false) => body.parse().ok().map(Tag::Short),
+        "l" | "L" if is_number(body, false) => body.parse().ok().map(Tag::Long),
+        "f" | "F" if is_number(body, true) => body.parse().ok().map(Tag::Float),
+        "d" | "D" if is_number(body, true) => body.parse().ok().map(Tag::Double),
+        _ if is_number(token, false) => token.parse().ok().map(Tag::Int),
+        _ if token.contains('.') && is_number(token, true) => token.parse().ok().map(Tag::Double),
+        _ => None,
+    };
+    tag.unwrap_or_else(|| Tag::String(token.to_owned()))
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let quote = if s.contains('"') && !s.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+    write!(f, "{}", quote)?;
+    for c in s.chars() {
+        if c == quote || c == '\\' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    write!(f, "{}", quote)
+}
+
+fn write_key(f: &mut fmt::Formatter, key: &str) -> fmt::Result {
+    if !key.is_empty() && key.chars().all(is_unquoted_char) {
+        write!(f, "{}", key)
+    } else {
+        write_string(f, key)
+    }
+}
+
+fn write_indent(f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    write!(f, "{:1$}", "", depth * 4)
+}
+
+fn write_array<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    prefix: &str,
+    values: &[T],
+    suffix: &str,
+) -> fmt::Result {
+    let separator = if f.alternate() { ", " } else { "," };
+    write!(f, "[{};", prefix)?;
+    if f.alternate() && !values.is_empty() {
+        write!(f, " ")?;
+    }
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}{}", v, suffix)?;
+    }
+    write!(f, "]")
+}
+
+impl Tag {
+    fn write_snbt(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        let pretty = f.alternate();
+        match *self {
+            Tag::End => Ok(()),
+            Tag::Byte(val) => write!(f, "{}b", val),
+            Tag::Short(val) => write!(f, "{}s", val),
+            Tag::Int(val) => write!(f, "{}", val),
+            Tag::Long(val) => write!(f, "{}L", val),
+            // Debug always includes a fraction or an exponent
+            Tag::Float(val) => write!(f, "{:?}f", val),
+            Tag::Double(val) => write!(f, "{:?}d", val),
+            Tag::ByteArray(ref val) => {
+                let val: Vec<_> = val.iter().map(|&v| v as i8).collect();
+                write_array(f, "B", &val, "B")
+            }
+            Tag::IntArray(ref val) => write_array(f, "I", val, ""),
+            Tag::LongArray(ref val) => write_array(f, "L", val, "L"),
+            Tag::String(ref val) => write_string(f, val),
+            Tag::List(ref val) => {
+                let nested = val.iter().any(|v| v.is_compound() || v.as_list().is_some());
+                write!(f, "[")?;
+                for (i, v) in val.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    if pretty && nested {
+                        writeln!(f)?;
+                        write_indent(f, depth + 1)?;
+                    } else if pretty && i > 0 {
+                        write!(f, " ")?;
+                    }
+                    v.write_snbt(f, depth + 1)?;
+                }
+                if pretty && nested {
+                    writeln!(f)?;
+                    write_indent(f, depth)?;
+                }
+                write!(f, "]")
+            }
+            Tag::Compound(ref val) => {
+                let mut keys: Vec<_> = val.keys().collect();
+                keys.sort();
+                write!(f, "{{")?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    if pretty {
+                        writeln!(f)?;
+                        write_indent(f, depth + 1)?;
+                    }
+                    write_key(f, key)?;
+                    write!(f, "{}", if pretty { ": " } else { ":" })?;
+                    val[*key].write_snbt(f, depth + 1)?;
+                }
+                if pretty && !keys.is_empty() {
+                    writeln!(f)?;
+                    write_indent(f, depth)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_snbt(f, 0)
+    }
+}
+
+/// Prints the tag, preceded by its name unless that is empty
+impl fmt::Display for NamedTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.0.is_empty() {
+            write_key(f, &self.0)?;
+            write!(f, ": ")?;
+        }
+        self.1.write_snbt(f, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snbt_round_trip() {
+        let src = r#"{Count:1b,Damage:3s,"display name":'say "hi"',list:[1.5d,-2.0d],
+            tag:{BlockEntityTag:{Items:[]},ids:[I;1,-2],Unbreakable:true},big:12L,f:1e-7f,
+            name:"minecraft:stone",n:01.5,bytes:[B;1B,-1B]}"#;
+        let tag: Tag = src.parse().unwrap();
+        assert_eq!(tag.get("Count").unwrap().as_byte(), Some(1));
+        assert_eq!(
+            tag.get("display name").unwrap().as_str(),
+            Some("say \"hi\"")
+        );
+        assert_eq!(
+            tag.get("tag").unwrap().get("ids").unwrap().as_int_array(),
+            Some(&[1, -2][..])
+        );
+        assert_eq!(tag.get("big").unwrap().as_long(), Some(12));
+        assert_eq!(tag.get("f").unwrap().as_float(), Some(1e-7));
+        assert_eq!(tag.get("name").unwrap().as_str(), Some("minecraft:stone"));
+        assert_eq!(tag.get("n").unwrap().as_double(), Some(1.5));
+        assert_eq!(
+            tag.get("bytes").unwrap().as_byte_array(),
+            Some(&[1, 255][..])
+        );
+
+        for printed in &[tag.to_string(), format!("{:#}", tag)] {
+            let reparsed: Tag = printed.parse().unwrap();
+            assert_eq!(reparsed.to_string(), tag.to_string());
+        }
+        assert!("[1b,2s]".parse::<Tag>().is_err());
+        assert!("{a:1".parse::<Tag>().is_err());
+    }
+}
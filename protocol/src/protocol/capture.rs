@@ -0,0 +1,261 @@
+//! Recording and replaying of play sessions.
+//!
+//! A capture file contains every raw packet frame `Conn::read_packet`
+//! received, after decryption but before decompression, together with the
+//! time it arrived and the protocol state and compression threshold that
+//! were active at that point. This is enough to feed a whole session back
+//! through the normal packet parsing path without a live server.
+
+use std::fs;
+use std::io;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use instant::{Duration, Instant};
+use lazy_static::lazy_static;
+
+use super::{Error, State};
+
+const MAGIC: &[u8; 5] = b"LFCAP";
+const FORMAT_VERSION: u8 = 1;
+
+lazy_static! {
+    /// The file given to `enable_capture` and how many sessions were
+    /// recorded so far
+    static ref CAPTURE_PATH: Mutex<Option<(PathBuf, u32)>> = Mutex::new(None);
+}
+
+/// Record play connections to the given file
+pub fn enable_capture<P: AsRef<Path>>(path: P) {
+    CAPTURE_PATH
+        .lock()
+        .unwrap()
+        .replace((path.as_ref().to_path_buf(), 0));
+}
+
+/// Returns the file to record the next session to, if capturing is enabled.
+///
+/// The first session uses the file as given, later ones (e.g. after
+/// reconnecting) get a numbered file of their own next to it instead of
+/// overwriting the earlier captures.
+pub fn next_capture_path() -> Option<PathBuf> {
+    let mut capture = CAPTURE_PATH.lock().unwrap();
+    let (path, sessions) = capture.as_mut()?;
+    *sessions += 1;
+    Some(session_path(path, *sessions))
+}
+
+fn session_path(path: &Path, session: u32) -> PathBuf {
+    if session <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, session, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, session),
+    };
+    path.with_file_name(name)
+}
+
+fn state_to_id(state: State) -> u8 {
+    match state {
+        State::Handshaking => 0,
+        State::Play => 1,
+        State::Status => 2,
+        State::Login => 3,
+    }
+}
+
+fn state_from_id(id: u8) -> Result<State, Error> {
+    Ok(match id {
+        0 => State::Handshaking,
+        1 => State::Play,
+        2 => State::Status,
+        3 => State::Login,
+        _ => return Err(Error::Err(format!("Bad state {} in packet capture", id))),
+    })
+}
+
+/// A single packet frame as it was read from the connection.
+#[derive(Debug)]
+pub struct CaptureRecord {
+    /// Time since the capture was started
+    pub timestamp: Duration,
+    pub state: State,
+    pub compression_threshold: i32,
+    /// The frame without its length prefix, still compressed if
+    /// compression was enabled
+    pub data: Vec<u8>,
+}
+
+pub struct CaptureWriter {
+    out: BufWriter<fs::File>,
+    start: Instant,
+}
+
+impl CaptureWriter {
+    pub fn create<P: AsRef<Path>>(path: P, protocol_version: i32) -> Result<CaptureWriter, Error> {
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        out.write_all(MAGIC)?;
+        out.write_u8(FORMAT_VERSION)?;
+        out.write_i32::<BigEndian>(protocol_version)?;
+        Ok(CaptureWriter {
+            out,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn write_record(
+        &mut self,
+        state: State,
+        compression_threshold: i32,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        self.out
+            .write_u64::<BigEndian>(self.start.elapsed().as_micros() as u64)?;
+        self.out.write_u8(state_to_id(state))?;
+        self.out.write_i32::<BigEndian>(compression_threshold)?;
+        self.out.write_u32::<BigEndian>(data.len() as u32)?;
+        self.out.write_all(data)?;
+        // Flush every record so a crash still leaves a usable capture behind
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+pub struct CaptureReader {
+    input: BufReader<fs::File>,
+    pub protocol_version: i32,
+    start: Option<Instant>,
+}
+
+impl CaptureReader {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<CaptureReader, Error> {
+        let mut input = BufReader::new(fs::File::open(path)?);
+        let mut magic = [0; 5];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::Err("Not a packet capture file".to_owned()));
+        }
+        let format_version = input.read_u8()?;
+        if format_version != FORMAT_VERSION {
+            return Err(Error::Err(format!(
+                "Unsupported packet capture format version {}",
+                format_version
+            )));
+        }
+        let protocol_version = input.read_i32::<BigEndian>()?;
+        Ok(CaptureReader {
+            input,
+            protocol_version,
+            start: None,
+        })
+    }
+
+    /// Returns the next record, or `None` once the end of the capture
+    /// has been reached.
+    pub fn next_record(&mut self) -> Result<Option<CaptureRecord>, Error> {
+        match self.read_record() {
+            Ok(record) => Ok(Some(record)),
+            // Also the end of a capture whose last record was cut off
+            Err(Error::IOError(err)) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn read_record(&mut self) -> Result<CaptureRecord, Error> {
+        let timestamp = Duration::from_micros(self.input.read_u64::<BigEndian>()?);
+        let state = state_from_id(self.input.read_u8()?)?;
+        let compression_threshold = self.input.read_i32::<BigEndian>()?;
+        let len = self.input.read_u32::<BigEndian>()? as usize;
+        let mut data = vec![0; len];
+        self.input.read_exact(&mut data)?;
+        Ok(CaptureRecord {
+            timestamp,
+            state,
+            compression_threshold,
+            data,
+        })
+    }
+
+    /// Like `next_record` but blocks until the record is due, so the
+    /// session is replayed with its original timing.
+    pub fn next_record_timed(&mut self) -> Result<Option<CaptureRecord>, Error> {
+        let record = self.next_record()?;
+        if let Some(record) = &record {
+            let start = *self.start.get_or_insert_with(Instant::now);
+            let elapsed = start.elapsed();
+            if record.timestamp > elapsed {
+                thread::sleep(record.timestamp - elapsed);
+            }
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_get_their_own_file() {
+        let path = Path::new("captures/session.lfcap");
+        assert_eq!(session_path(path, 1), path);
+        assert_eq!(session_path(path, 2), Path::new("captures/session.2.lfcap"));
+        assert_eq!(
+            session_path(Path::new("session"), 3),
+            Path::new("session.3")
+        );
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let path = std::env::temp_dir().join(format!("leafish-{}.lfcap", std::process::id()));
+        let mut writer = CaptureWriter::create(&path, 759).unwrap();
+        writer
+            .write_record(State::Login, -1, &[0x02, 0x01])
+            .unwrap();
+        writer.write_record(State::Play, 256, &[]).unwrap();
+        writer
+            .write_record(State::Play, 256, &[0x00, 0x26, 0x7f])
+            .unwrap();
+        drop(writer);
+
+        let mut reader = CaptureReader::open(&path).unwrap();
+        assert_eq!(reader.protocol_version, 759);
+        let first = reader.next_record().unwrap().unwrap();
+        assert_eq!(first.state, State::Login);
+        assert_eq!(first.compression_threshold, -1);
+        assert_eq!(first.data, [0x02, 0x01]);
+        let second = reader.next_record().unwrap().unwrap();
+        assert_eq!(
+            (second.state, second.compression_threshold),
+            (State::Play, 256)
+        );
+        assert!(second.data.is_empty());
+        assert!(second.timestamp >= first.timestamp);
+        assert_eq!(
+            reader.next_record().unwrap().unwrap().data,
+            [0x00, 0x26, 0x7f]
+        );
+        assert!(reader.next_record().unwrap().is_none());
+
+        // A record cut off in its data ends the capture
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() - 1]).unwrap();
+        let mut reader = CaptureReader::open(&path).unwrap();
+        assert!(reader.next_record().unwrap().is_some());
+        assert!(reader.next_record().unwrap().is_some());
+        assert!(reader.next_record().unwrap().is_none());
+
+        fs::write(&path, b"LFCAX\x01").unwrap();
+        assert!(CaptureReader::open(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,376 @@
+//! Buffered reading and writing of packet frames.
+//!
+//! Each `Conn` shares one `ReadHalf` and one `WriteHalf` with its clones.
+//! The read half pulls large chunks from the transport and decrypts every
+//! chunk in one go, so parsing length prefixes byte by byte no longer
+//! costs a syscall each. The write half assembles a whole frame in a
+//! reusable buffer, encrypts it in place and hands it to the transport
+//! with a single write. Both keep their zlib context around between
+//! packets instead of setting up a new one every time.
+
+use std::io;
+use std::io::{Read, Write};
+use std::mem;
+
+use cfb8::cipher::AsyncStreamCipher;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+
+use super::{Aes128Cfb, Error, Serializable, VarInt};
+
+/// How much is read from the transport at once
+const READ_BUFFER_SIZE: usize = 32 * 1024;
+/// Upper bound for the space reserved up front for a decompressed packet,
+/// the size is announced by the server so it can't be trusted blindly.
+const MAX_RESERVED_PACKET_SIZE: usize = 8 * 1024 * 1024;
+/// Frames larger than this are rejected instead of allocated
+pub(crate) const MAX_FRAME_SIZE: usize = 1000000000;
+
+pub(crate) struct ReadHalf {
+    buf: Box<[u8]>,
+    start: usize,
+    end: usize,
+    cipher: Option<Aes128Cfb>,
+    decompress: Decompress,
+}
+
+impl ReadHalf {
+    pub(crate) fn new() -> ReadHalf {
+        ReadHalf {
+            buf: vec![0; READ_BUFFER_SIZE].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            cipher: None,
+            decompress: Decompress::new(true),
+        }
+    }
+
+    /// Decrypts everything read from now on, including what is buffered
+    /// but wasn't consumed yet: the server encrypts everything following
+    /// its encryption request.
+    pub(crate) fn enable_encryption(&mut self, mut cipher: Aes128Cfb) {
+        cipher.decrypt(&mut self.buf[self.start..self.end]);
+        self.cipher = Some(cipher);
+    }
+
+    fn buffered(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    /// Reads the next chunk from the transport into the buffer, failing
+    /// if the transport is closed.
+    fn fill<R: Read + ?Sized>(&mut self, stream: &mut R) -> io::Result<()> {
+        if self.start == self.end {
+            self.start = 0;
+            self.end = 0;
+        } else if self.end == self.buf.len() {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        let read = stream.read(&mut self.buf[self.end..])?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if let Some(cipher) = self.cipher.as_mut() {
+            cipher.decrypt(&mut self.buf[self.end..self.end + read]);
+        }
+        self.end += read;
+        Ok(())
+    }
+
+    /// Copies buffered bytes into `out`, reading from the transport first
+    /// if nothing is buffered. Returns 0 once the transport is closed.
+    pub(crate) fn read<R: Read + ?Sized>(
+        &mut self,
+        stream: &mut R,
+        out: &mut [u8],
+    ) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+        if self.start == self.end {
+            match self.fill(stream) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+                Err(err) => return Err(err),
+            }
+        }
+        let len = out.len().min(self.end - self.start);
+        out[..len].copy_from_slice(&self.buf[self.start..self.start + len]);
+        self.start += len;
+        Ok(len)
+    }
+
+    /// Reads a single length prefixed frame without decoding it
+    pub(crate) fn read_frame<R: Read + ?Sized>(
+        &mut self,
+        stream: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let len = loop {
+            // A VarInt is at most 5 bytes long
+            let prefix = &self.buffered()[..self.buffered().len().min(5)];
+            if let Some(end) = prefix.iter().position(|b| b & 0x80 == 0) {
+                let len = VarInt::read_from(&mut &prefix[..=end])?.0 as usize;
+                self.start += end + 1;
+                break len;
+            }
+            if prefix.len() == 5 {
+                return Err(Error::Err("VarInt too big".to_owned()));
+            }
+            self.fill(stream)?;
+        };
+        if len > MAX_FRAME_SIZE {
+            return Err(Error::Err(format!("Frame of {} bytes is too large", len)));
+        }
+
+        let buffered = len.min(self.end - self.start);
+        let mut frame = Vec::with_capacity(len);
+        frame.extend_from_slice(&self.buf[self.start..self.start + buffered]);
+        self.start += buffered;
+        if buffered < len {
+            // Large frames skip the buffer and get decrypted in one piece
+            frame.resize(len, 0);
+            stream.read_exact(&mut frame[buffered..])?;
+            if let Some(cipher) = self.cipher.as_mut() {
+                cipher.decrypt(&mut frame[buffered..]);
+            }
+        }
+        Ok(frame)
+    }
+
+    /// The zlib context to inflate received packets with
+    pub(crate) fn decompressor(&mut self) -> &mut Decompress {
+        &mut self.decompress
+    }
+}
+
+/// Inflates a compressed packet, reusing the zlib context `ctx`. Like
+/// vanilla this fails if the packet doesn't inflate to exactly the size
+/// the frame announced.
+pub(crate) fn decompress(
+    ctx: &mut Decompress,
+    data: &[u8],
+    uncompressed_size: usize,
+) -> Result<Vec<u8>, Error> {
+    ctx.reset(true);
+    let mut out = Vec::with_capacity(uncompressed_size.min(MAX_RESERVED_PACKET_SIZE));
+    loop {
+        let consumed = ctx.total_in() as usize;
+        let produced = ctx.total_out();
+        if out.len() == out.capacity() {
+            out.reserve(out.capacity().max(64));
+        }
+        let status = ctx
+            .decompress_vec(&data[consumed..], &mut out, FlushDecompress::Finish)
+            .map_err(|err| Error::Err(format!("Failed to decompress packet: {}", err)))?;
+        if out.len() > uncompressed_size {
+            return Err(Error::Err(format!(
+                "Compressed packet inflates to more than the announced {} bytes",
+                uncompressed_size
+            )));
+        }
+        match status {
+            Status::StreamEnd if out.len() < uncompressed_size => {
+                return Err(Error::Err(format!(
+                    "Compressed packet inflates to {} bytes instead of the announced {}",
+                    out.len(),
+                    uncompressed_size
+                )));
+            }
+            Status::StreamEnd => return Ok(out),
+            _ if ctx.total_in() as usize == consumed && ctx.total_out() == produced => {
+                return Err(Error::Err("Truncated compressed packet".to_owned()));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) struct WriteHalf {
+    cipher: Option<Aes128Cfb>,
+    compress: Compress,
+    payload: Vec<u8>,
+    compressed: Vec<u8>,
+    frame: Vec<u8>,
+}
+
+impl WriteHalf {
+    pub(crate) fn new() -> WriteHalf {
+        WriteHalf {
+            cipher: None,
+            compress: Compress::new(Compression::default(), true),
+            payload: Vec::new(),
+            compressed: Vec::new(),
+            frame: Vec::new(),
+        }
+    }
+
+    pub(crate) fn enable_encryption(&mut self, cipher: Aes128Cfb) {
+        self.cipher = Some(cipher);
+    }
+
+    /// Frames a packet, compressing it if `compression_threshold` asks for
+    /// it, and writes it out. `write_payload` serializes the packet id and
+    /// body. Returns the size of the payload and of the frame on the wire.
+    pub(crate) fn write_frame<W, F>(
+        &mut self,
+        stream: &mut W,
+        compression_threshold: i32,
+        write_payload: F,
+    ) -> Result<(usize, usize), Error>
+    where
+        W: Write + ?Sized,
+        F: FnOnce(&mut Vec<u8>) -> Result<(), Error>,
+    {
+        let mut payload = mem::take(&mut self.payload);
+        let mut frame = mem::take(&mut self.frame);
+        payload.clear();
+        frame.clear();
+        let result = write_payload(&mut payload)
+            .and_then(|_| self.encode_frame(&payload, compression_threshold, &mut frame))
+            .and_then(|wire_len| {
+                stream.write_all(&frame)?;
+                Ok((payload.len(), wire_len))
+            });
+        self.payload = payload;
+        self.frame = frame;
+        result
+    }
+
+    /// Appends the encrypted frame for a serialized packet to `out` and
+    /// returns its size.
+    pub(crate) fn encode_frame(
+        &mut self,
+        payload: &[u8],
+        compression_threshold: i32,
+        out: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        let start = out.len();
+        if compression_threshold >= 0 && payload.len() as i32 > compression_threshold {
+            self.compress(payload)?;
+            let uncompressed_size = VarInt(payload.len() as i32);
+            VarInt((uncompressed_size.encoded_len() + self.compressed.len()) as i32)
+                .write_to(out)?;
+            uncompressed_size.write_to(out)?;
+            out.extend_from_slice(&self.compressed);
+        } else if compression_threshold >= 0 {
+            VarInt(payload.len() as i32 + 1).write_to(out)?;
+            VarInt(0).write_to(out)?;
+            out.extend_from_slice(payload);
+        } else {
+            VarInt(payload.len() as i32).write_to(out)?;
+            out.extend_from_slice(payload);
+        }
+
+        if let Some(cipher) = self.cipher.as_mut() {
+            cipher.encrypt(&mut out[start..]);
+        }
+        Ok(out.len() - start)
+    }
+
+    fn compress(&mut self, payload: &[u8]) -> Result<(), Error> {
+        self.compress.reset();
+        self.compressed.clear();
+        loop {
+            let consumed = self.compress.total_in() as usize;
+            if self.compressed.len() == self.compressed.capacity() {
+                self.compressed
+                    .reserve((payload.len() / 2).max(self.compressed.capacity()).max(64));
+            }
+            let status = self
+                .compress
+                .compress_vec(
+                    &payload[consumed..],
+                    &mut self.compressed,
+                    FlushCompress::Finish,
+                )
+                .map_err(|err| Error::Err(format!("Failed to compress packet: {}", err)))?;
+            if let Status::StreamEnd = status {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Writes raw bytes, encrypting them if needed
+    pub(crate) fn write_raw<W: Write + ?Sized>(
+        &mut self,
+        stream: &mut W,
+        data: &[u8],
+    ) -> io::Result<()> {
+        match self.cipher.as_mut() {
+            None => stream.write_all(data),
+            Some(cipher) => {
+                self.frame.clear();
+                self.frame.extend_from_slice(data);
+                cipher.encrypt(&mut self.frame);
+                stream.write_all(&self.frame)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out its data in tiny pieces to exercise refilling
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.0.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let mut writer = WriteHalf::new();
+        let mut wire = Vec::new();
+        let small = vec![1u8; 10];
+        let large = (0..100_000).map(|i| (i % 7) as u8).collect::<Vec<_>>();
+        for (data, threshold) in &[(&small, -1), (&small, 64), (&large, 64), (&large, -1)] {
+            writer
+                .write_frame(&mut wire, *threshold, |buf| {
+                    buf.extend_from_slice(data);
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let mut reader = ReadHalf::new();
+        let mut stream = Trickle(&wire);
+        assert_eq!(reader.read_frame(&mut stream).unwrap(), small);
+        let frame = reader.read_frame(&mut stream).unwrap();
+        assert_eq!(frame[0], 0);
+        assert_eq!(&frame[1..], &small[..]);
+        let frame = reader.read_frame(&mut stream).unwrap();
+        let mut cursor = io::Cursor::new(&frame);
+        let size = VarInt::read_from(&mut cursor).unwrap().0 as usize;
+        assert_eq!(size, large.len());
+        let rest = &frame[cursor.position() as usize..];
+        assert!(rest.len() < large.len());
+        assert_eq!(
+            decompress(reader.decompressor(), rest, size).unwrap(),
+            large
+        );
+        assert_eq!(reader.read_frame(&mut stream).unwrap(), large);
+        assert!(reader.read_frame(&mut stream).is_err());
+    }
+
+    #[test]
+    fn decompress_checks_announced_size() {
+        let mut writer = WriteHalf::new();
+        let data = vec![5u8; 1000];
+        writer.compress(&data).unwrap();
+        let compressed = writer.compressed.clone();
+
+        let mut ctx = Decompress::new(true);
+        assert_eq!(decompress(&mut ctx, &compressed, 1000).unwrap(), data);
+        assert!(decompress(&mut ctx, &compressed, 999).is_err());
+        assert!(decompress(&mut ctx, &compressed, 1001).is_err());
+        assert!(decompress(&mut ctx, &compressed[..compressed.len() / 2], 1000).is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Discovery of worlds opened to LAN.
+//!
+//! A vanilla client hosting a world on the local network multicasts
+//! `[MOTD]<motd>[/MOTD][AD]<port>[/AD]` to 224.0.2.60:4445 every 1.5
+//! seconds. `LanDiscovery` listens for these announcements on a background
+//! thread and keeps every world heard from recently.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use instant::{Duration, Instant};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use socket2::{Domain, Protocol, Socket, Type};
+
+pub const LAN_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 2, 60);
+pub const LAN_PORT: u16 = 4445;
+/// Worlds are dropped once they weren't announced for this long
+const LAN_EXPIRY: Duration = Duration::from_secs(5);
+/// How often the listener checks whether it is still wanted
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Announcement {
+    pub motd: String,
+    /// Usually just the port, the host being whoever sent the announcement
+    pub address: String,
+}
+
+impl Announcement {
+    pub fn parse(msg: &str) -> Option<Announcement> {
+        Some(Announcement {
+            motd: between(msg, "[MOTD]", "[/MOTD]")?.to_owned(),
+            address: between(msg, "[AD]", "[/AD]")?.to_owned(),
+        })
+    }
+}
+
+fn between<'a>(msg: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &msg[msg.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+#[derive(Clone, Debug)]
+pub struct LanServer {
+    pub motd: String,
+    /// `host:port` to connect to
+    pub address: String,
+    last_seen: Instant,
+}
+
+type Servers = Mutex<HashMap<String, LanServer>>;
+
+lazy_static! {
+    /// What the running listener found, shared by everyone looking for LAN
+    /// worlds, so the game only listens once
+    static ref ACTIVE: Mutex<Option<Arc<Servers>>> = Mutex::new(None);
+}
+
+/// Listens for LAN worlds for as long as it is alive
+pub struct LanDiscovery {
+    servers: Arc<Servers>,
+}
+
+impl LanDiscovery {
+    pub fn start() -> io::Result<LanDiscovery> {
+        let mut active = ACTIVE.lock().unwrap();
+        if let Some(servers) = active.as_ref() {
+            return Ok(LanDiscovery {
+                servers: servers.clone(),
+            });
+        }
+        let socket = bind_shared(LAN_PORT)?;
+        socket.join_multicast_v4(&LAN_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+        let servers = Arc::new(Mutex::new(HashMap::new()));
+        *active = Some(servers.clone());
+        let listener = servers.clone();
+        thread::spawn(move || listen(&socket, &listener));
+        Ok(LanDiscovery { servers })
+    }
+
+    /// The worlds which are still being announced, sorted by address
+    pub fn servers(&self) -> Vec<LanServer> {
+        let mut servers = self.servers.lock().unwrap();
+        servers.retain(|_, server| server.last_seen.elapsed() < LAN_EXPIRY);
+        let mut servers: Vec<_> = servers.values().cloned().collect();
+        servers.sort_by(|a, b| a.address.cmp(&b.address));
+        servers
+    }
+}
+
+/// Binds the port while leaving it open to others, like vanilla's
+/// `MulticastSocket` does, so a game looking for LAN worlds on the same
+/// machine doesn't keep us from listening
+fn bind_shared(port: u16) -> io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    socket.set_reuse_port(true)?;
+    socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)).into())?;
+    Ok(socket.into())
+}
+
+fn listen(socket: &UdpSocket, servers: &Arc<Servers>) {
+    let mut buf = [0; 1024];
+    loop {
+        let received = socket.recv_from(&mut buf);
+        {
+            // Stop once every `LanDiscovery` is gone, leaving only the
+            // references of the listener and `ACTIVE`
+            let mut active = ACTIVE.lock().unwrap();
+            if Arc::strong_count(servers) <= 2 {
+                *active = None;
+                return;
+            }
+        }
+        let (len, from) = match received {
+            Ok(received) => received,
+            Err(err)
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                continue
+            }
+            Err(err) => {
+                warn!("LAN discovery stopped: {}", err);
+                ACTIVE.lock().unwrap().take();
+                return;
+            }
+        };
+        let announcement = match Announcement::parse(&String::from_utf8_lossy(&buf[..len])) {
+            Some(announcement) => announcement,
+            None => {
+                debug!("Ignoring malformed LAN announcement from {}", from);
+                continue;
+            }
+        };
+        let address = if announcement.address.contains(':') {
+            announcement.address
+        } else {
+            format!("{}:{}", from.ip(), announcement.address)
+        };
+        servers.lock().unwrap().insert(
+            address.clone(),
+            LanServer {
+                motd: announcement.motd,
+                address,
+                last_seen: Instant::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_announcement() {
+        assert_eq!(
+            Announcement::parse("[MOTD]Steve - New World[/MOTD][AD]51234[/AD]"),
+            Some(Announcement {
+                motd: "Steve - New World".to_owned(),
+                address: "51234".to_owned(),
+            })
+        );
+        assert_eq!(Announcement::parse("[MOTD]Steve[/MOTD]"), None);
+        assert_eq!(Announcement::parse("[AD]51234"), None);
+    }
+
+    #[test]
+    fn port_is_shared() {
+        let first = bind_shared(0).unwrap();
+        let port = first.local_addr().unwrap().port();
+        let second = bind_shared(port).unwrap();
+        assert_eq!(second.local_addr().unwrap().port(), port);
+    }
+}
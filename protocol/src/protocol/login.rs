@@ -1,6 +1,7 @@
 use crate::protocol::microsoft::MicrosoftAccount;
 
 use super::offline_acc::OfflineAccount;
+use super::profile_key::ProfileKey;
 use dashmap::DashMap;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,10 @@ pub trait AccountImpl {
     fn refresh(&self, account: Account, token: &str) -> Result<Account, super::Error>;
 
     fn append_head_img_data(&self, account: &mut Account) -> Result<(), super::Error>;
+
+    /// The key pair used to sign chat messages (1.19+), accounts
+    /// which can't sign return None and fall back to unsigned chat.
+    fn profile_key(&self, account: &Account) -> Result<Option<ProfileKey>, super::Error>;
 }
 
 #[derive(Serialize, Deserialize)]
@@ -80,6 +85,15 @@ impl Account {
             .append_head_img_data(self)
     }
 
+    pub fn profile_key(&self) -> Result<Option<ProfileKey>, super::Error> {
+        ACCOUNT_IMPLS
+            .clone()
+            .get(&self.account_type)
+            .unwrap()
+            .clone()
+            .profile_key(self)
+    }
+
     pub fn login(
         username: &str, // accountname(not ingame name) (email or username(for old accounts))
         password: &str,
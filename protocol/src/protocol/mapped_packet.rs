@@ -7,9 +7,10 @@ use crate::protocol::mapped_packet::login::serverbound::{
     EncryptionResponse, LoginPluginResponse, LoginStart,
 };
 use crate::protocol::mapped_packet::play::clientbound::{
-    AcknowledgePlayerDigging, Advancements, Animation, BlockAction, BlockBreakAnimation,
-    BlockChange, BossBar, Camera, ChangeGameState, ChunkData, ChunkDataBulk, ChunkDataBulk_17,
-    ChunkData_17, ChunkData_Biomes3D, ChunkData_Biomes3D_bool, ChunkData_Biomes3D_i32,
+    AcknowledgeBlockChange, AcknowledgePlayerDigging, Advancements, Animation, BlockAction,
+    BlockBreakAnimation, BlockChange, BossBar, Camera, ChangeGameState, ChatPreview, ChunkData,
+    ChunkDataBulk, ChunkDataBulk_17, ChunkData_17, ChunkData_AndLight, ChunkData_Biomes3D,
+    ChunkData_Biomes3D_Bitmasks, ChunkData_Biomes3D_bool, ChunkData_Biomes3D_i32,
     ChunkData_HeightMap, ChunkData_NoEntities, ChunkData_NoEntities_u16, ChunkUnload,
     CoFHLib_SendUUID, CollectItem, CombatEvent, ConfirmTransaction, CraftRecipeResponse,
     DeclareCommands, DeclareRecipes, Disconnect, Effect, Entity, EntityAction, EntityAttach,
@@ -17,28 +18,30 @@ use crate::protocol::mapped_packet::play::clientbound::{
     EntityLook, EntityLookAndMove, EntityMetadata, EntityMove, EntityProperties,
     EntityRemoveEffect, EntitySoundEffect, EntityStatus, EntityTeleport, EntityUpdateNBT,
     EntityUsedBed, EntityVelocity, Explosion, FacePlayer, JoinGame, KeepAliveClientbound, Maps,
-    MultiBlockChange, NBTQueryResponse, NamedSoundEffect, OpenBook, Particle, PlayerAbilities,
-    PlayerInfo, PlayerInfo_String, PlayerListHeaderFooter, PluginMessageClientbound,
-    ResourcePackSend, Respawn, ScoreboardDisplay, ScoreboardObjective, SelectAdvancementTab,
-    ServerDifficulty, ServerMessage, SetCompression, SetCooldown, SetCurrentHotbarSlot,
-    SetExperience, SetPassengers, SignEditorOpen, SoundEffect, SpawnExperienceOrb,
-    SpawnGlobalEntity, SpawnMob, SpawnObject, SpawnPainting, SpawnPlayer, SpawnPosition,
-    Statistics, StopSound, TabCompleteReply, Tags, Teams, TeleportPlayer, TimeUpdate, Title,
-    TradeList, UnlockRecipes, UpdateBlockEntity, UpdateHealth, UpdateLight, UpdateScore,
-    UpdateSign, UpdateViewDistance, UpdateViewPosition, VehicleTeleport, WindowClose, WindowItems,
-    WindowOpen, WindowOpenHorse, WindowProperty, WindowSetSlot, WorldBorder,
+    MultiBlockChange, NBTQueryResponse, NamedSoundEffect, OpenBook, Particle, Ping,
+    PlayerAbilities, PlayerChatMessage, PlayerInfo, PlayerInfo_String, PlayerListHeaderFooter,
+    PluginMessageClientbound, ResourcePackSend, Respawn, ScoreboardDisplay, ScoreboardObjective,
+    SculkVibrationSignal, SelectAdvancementTab, ServerData, ServerDifficulty, ServerMessage,
+    SetCompression, SetCooldown, SetCurrentHotbarSlot, SetDisplayChatPreview, SetExperience,
+    SetPassengers, SignEditorOpen, SoundEffect, SpawnExperienceOrb, SpawnGlobalEntity, SpawnMob,
+    SpawnObject, SpawnPainting, SpawnPlayer, SpawnPosition, Statistics, StopSound,
+    TabCompleteReply, Tags, Teams, TeleportPlayer, TimeUpdate, Title, TradeList, UnlockRecipes,
+    UpdateBlockEntity, UpdateHealth, UpdateLight, UpdateScore, UpdateSign,
+    UpdateSimulationDistance, UpdateViewDistance, UpdateViewPosition, VehicleTeleport, WindowClose,
+    WindowItems, WindowOpen, WindowOpenHorse, WindowProperty, WindowSetSlot, WorldBorder,
 };
 use crate::protocol::mapped_packet::play::serverbound::{
-    AdvancementTab, ArmSwing, ChatMessage, ClickWindow, ClickWindowButton, ClientAbilities,
-    ClientSettings, ClientStatus, CloseWindow, ConfirmTransactionServerbound, CraftRecipeRequest,
-    CraftingBookData, CreativeInventoryAction, EditBook, EnchantItem, GenerateStructure,
-    HeldItemChange, KeepAliveServerbound, LockDifficulty, NameItem, PickItem, Player, PlayerAction,
-    PlayerBlockPlacement, PlayerDigging, PlayerLook, PlayerPosition, PlayerPositionLook,
-    PluginMessageServerbound, QueryBlockNBT, QueryEntityNBT, ResourcePackStatus, SelectTrade,
-    SetBeaconEffect, SetDifficulty, SetDisplayedRecipe, SetRecipeBookState, SetSign,
-    SpectateTeleport, SteerBoat, SteerVehicle, TabComplete, TeleportConfirm, UpdateCommandBlock,
-    UpdateCommandBlockMinecart, UpdateJigsawBlock_Joint, UpdateJigsawBlock_Type,
-    UpdateStructureBlock, UseEntity, UseItem, VehicleMove,
+    AdvancementTab, ArmSwing, ChatMessage, ChatPreviewServerbound, ClickWindow, ClickWindowButton,
+    ClientAbilities, ClientSettings, ClientStatus, CloseWindow, ConfirmTransactionServerbound,
+    CraftRecipeRequest, CraftingBookData, CreativeInventoryAction, EditBook, EditBook_Pages,
+    EnchantItem, GenerateStructure, HeldItemChange, KeepAliveServerbound, LockDifficulty, NameItem,
+    PickItem, Player, PlayerAction, PlayerBlockPlacement, PlayerDigging, PlayerLook,
+    PlayerPosition, PlayerPositionLook, PluginMessageServerbound, Pong, QueryBlockNBT,
+    QueryEntityNBT, ResourcePackStatus, SelectTrade, SetBeaconEffect, SetDifficulty,
+    SetDisplayedRecipe, SetRecipeBookState, SetSign, SpectateTeleport, SteerBoat, SteerVehicle,
+    TabComplete, TeleportConfirm, UpdateCommandBlock, UpdateCommandBlockMinecart,
+    UpdateJigsawBlock_Joint, UpdateJigsawBlock_Type, UpdateStructureBlock, UseEntity, UseItem,
+    VehicleMove,
 };
 use crate::protocol::mapped_packet::status::clientbound::{StatusPong, StatusResponse};
 use crate::protocol::mapped_packet::status::serverbound::{StatusPing, StatusRequest};
@@ -105,6 +108,10 @@ state_mapped_packets!(
             packet ChatMessage {
                 field message: String,
             }
+            packet ChatPreviewServerbound {
+                field query_id: i32,
+                field message: String,
+            }
             /// ClientStatus is sent to update the client's status
             packet ClientStatus {
                 field action_id: i32,
@@ -160,6 +167,11 @@ state_mapped_packets!(
                 field is_signing: bool,
                 field hand: Hand,
             }
+            packet EditBook_Pages {
+                field slot: i32,
+                field entries: Vec<String>,
+                field title: Option<String>,
+            }
             packet QueryEntityNBT {
                 field transaction_id: i32,
                 field entity_id: i32,
@@ -187,6 +199,10 @@ state_mapped_packets!(
             packet KeepAliveServerbound {
                 field id: i64,
             }
+            /// Pong is the reply to a Ping sent by the server.
+            packet Pong {
+                field id: i32,
+            }
             packet LockDifficulty {
                 field locked: bool,
             }
@@ -479,6 +495,15 @@ state_mapped_packets!(
                 field current_item: Option<u16>,
                 field metadata: Option<types::Metadata>,
             }
+            /// SculkVibrationSignal shows a vibration travelling towards a sculk
+            /// sensor.
+            packet SculkVibrationSignal {
+                field source_position: Position,
+                field destination_type: String,
+                field destination_position: Option<Position>,
+                field destination_entity_id: Option<i32>,
+                field arrival_ticks: i32,
+            }
 
             /// Animation is sent by the server to play an animation on a specific entity.
             packet Animation {
@@ -556,6 +581,30 @@ state_mapped_packets!(
                 field position: Option<u8>,
                 field sender: Option<UUID>,
             }
+            /// PlayerChatMessage is a chat message sent by a player (1.19+),
+            /// the content is already substituted with the unsigned content if
+            /// the server sent one.
+            packet PlayerChatMessage {
+                field content: format::Component,
+                /// Index into the chat type registry of the dimension codec
+                field message_type: i32,
+                field sender: UUID,
+                field sender_display_name: format::Component,
+                field team_name: Option<format::Component>,
+                field timestamp: i64,
+            }
+            packet ChatPreview {
+                field query_id: i32,
+                field message: Option<format::Component>,
+            }
+            packet SetDisplayChatPreview {
+                field enabled: bool,
+            }
+            packet ServerData {
+                field motd: Option<format::Component>,
+                field icon: Option<String>,
+                field previews_chat: bool,
+            }
             /// MultiBlockChange is used to update a batch of blocks in a single packet.
             packet MultiBlockChange {
                 field chunk_x: i32,
@@ -596,7 +645,9 @@ state_mapped_packets!(
             /// WindowItems sets every item in a window.
             packet WindowItems {
                 field id: u8,
+                field state_id: Option<i32>,
                 field items: Vec<Option<item::Stack>>,
+                field carried_item: Option<item::Stack>,
             }
             /// WindowProperty changes the value of a property of a window. Properties
             /// vary depending on the window type.
@@ -608,6 +659,7 @@ state_mapped_packets!(
             /// WindowSetSlot changes an itemstack in one of the slots in a window.
             packet WindowSetSlot {
                 field id: i8,
+                field state_id: Option<i32>,
                 field slot: i16,
                 field item: Option<item::Stack>,
             }
@@ -678,6 +730,35 @@ state_mapped_packets!(
             packet KeepAliveClientbound {
                 field id: i64,
             }
+            /// Ping is sent by the server to check the client is processing
+            /// packets. The client should reply with a Pong carrying the same ID.
+            packet Ping {
+                field id: i32,
+            }
+            /// ChunkData_Biomes3D_Bitmasks always carries a full chunk column,
+            /// with the sections present given by a bitset.
+            packet ChunkData_Biomes3D_Bitmasks {
+                field chunk_x: i32,
+                field chunk_z: i32,
+                field bitmask: BitSet,
+                field heightmaps: Option<nbt::NamedTag>,
+                field biomes: Vec<i32>,
+                field data: Vec<u8>,
+                field block_entities: Vec<Option<nbt::NamedTag>>,
+            }
+            /// ChunkData_AndLight sends a full chunk together with its light. The
+            /// light is packed like UpdateLight and the block entities are
+            /// positioned in the world.
+            packet ChunkData_AndLight {
+                field chunk_x: i32,
+                field chunk_z: i32,
+                field heightmaps: Option<nbt::NamedTag>,
+                field data: Vec<u8>,
+                field block_entities: Vec<UpdateBlockEntity>,
+                field sky_light_mask: i64,
+                field block_light_mask: i64,
+                field light_arrays: Vec<u8>,
+            }
             /// ChunkData sends or updates a single chunk on the client. If New is set
             /// then biome data should be sent too.
             packet ChunkData_Biomes3D_i32 {
@@ -1050,6 +1131,11 @@ state_mapped_packets!(
             packet UpdateViewDistance {
                 field view_distance: i32,
             }
+            /// UpdateSimulationDistance is sent when the server changes the
+            /// distance in which entities are ticked.
+            packet UpdateSimulationDistance {
+                field simulation_distance: i32,
+            }
             /// ScoreboardDisplay is used to set the display position of a scoreboard.
             packet ScoreboardDisplay {
                 field position: u8,
@@ -1253,6 +1339,9 @@ state_mapped_packets!(
                 field status: i32,
                 field successful: bool,
             }
+            packet AcknowledgeBlockChange {
+                field sequence: i32,
+            }
             packet UpdateLight {
                 field chunk_x: i32,
                 field chunk_z: i32,
@@ -1443,12 +1532,26 @@ impl MappablePacket for packet::Packet {
                     successful: digging.successful,
                 })
             }
+            packet::Packet::AcknowledgeBlockChange(ack) => {
+                mapped_packet::MappedPacket::AcknowledgeBlockChange(AcknowledgeBlockChange {
+                    sequence: ack.sequence.0,
+                })
+            }
             packet::Packet::AdvancementTab(advancement) => {
                 mapped_packet::MappedPacket::AdvancementTab(AdvancementTab {
                     action: advancement.action.0,
                     tab_id: advancement.tab_id,
                 })
             }
+            packet::Packet::SculkVibrationSignal(signal) => {
+                mapped_packet::MappedPacket::SculkVibrationSignal(SculkVibrationSignal {
+                    source_position: signal.source_position,
+                    destination_type: signal.destination_type,
+                    destination_position: signal.destination_position,
+                    destination_entity_id: signal.destination_entity_id.map(|x| x.0),
+                    arrival_ticks: signal.arrival_ticks.0,
+                })
+            }
             packet::Packet::Animation(animation) => {
                 mapped_packet::MappedPacket::Animation(Animation {
                     entity_id: animation.entity_id.0,
@@ -1536,6 +1639,32 @@ impl MappablePacket for packet::Packet {
                     message: chat_msg.message,
                 })
             }
+            packet::Packet::ChatMessage_Signed(chat_msg) => {
+                mapped_packet::MappedPacket::ChatMessage(ChatMessage {
+                    message: chat_msg.message,
+                })
+            }
+            packet::Packet::ChatCommand(command) => {
+                mapped_packet::MappedPacket::ChatMessage(ChatMessage {
+                    message: format!("/{}", command.command),
+                })
+            }
+            packet::Packet::ChatPreviewServerbound(preview) => {
+                mapped_packet::MappedPacket::ChatPreviewServerbound(ChatPreviewServerbound {
+                    query_id: preview.query_id,
+                    message: preview.message,
+                })
+            }
+            packet::Packet::ChatPreviewClientbound(preview) => {
+                mapped_packet::MappedPacket::ChatPreview(ChatPreview {
+                    query_id: preview.query_id,
+                    message: if preview.has_message {
+                        Some(preview.message)
+                    } else {
+                        None
+                    },
+                })
+            }
             packet::Packet::ChangeGameState(change_game_state) => {
                 mapped_packet::MappedPacket::ChangeGameState(ChangeGameState {
                     reason: change_game_state.reason,
@@ -1552,6 +1681,17 @@ impl MappablePacket for packet::Packet {
                     action_id: client_status.action_id as i32,
                 })
             }
+            packet::Packet::ClientSettings_Filtering(client_settings) => {
+                mapped_packet::MappedPacket::ClientSettings(ClientSettings {
+                    locale: client_settings.locale,
+                    view_distance: client_settings.view_distance,
+                    chat_mode: client_settings.chat_mode.0,
+                    chat_colors: client_settings.chat_colors,
+                    difficulty: None,
+                    displayed_skin_parts: client_settings.displayed_skin_parts,
+                    main_hand: Some(Hand::from(client_settings.main_hand.0)),
+                })
+            }
             packet::Packet::ClientSettings(client_settings) => {
                 mapped_packet::MappedPacket::ClientSettings(ClientSettings {
                     locale: client_settings.locale,
@@ -1651,6 +1791,58 @@ impl MappablePacket for packet::Packet {
                     block_entities: chunk_data.block_entities.data,
                 })
             }
+            packet::Packet::ChunkData_Biomes3D_Bitmasks(chunk_data) => {
+                mapped_packet::MappedPacket::ChunkData_Biomes3D_Bitmasks(
+                    ChunkData_Biomes3D_Bitmasks {
+                        chunk_x: chunk_data.chunk_x,
+                        chunk_z: chunk_data.chunk_z,
+                        bitmask: chunk_data.bitmask,
+                        heightmaps: chunk_data.heightmaps,
+                        biomes: chunk_data.biomes.data.iter().map(|x| x.0).collect(),
+                        data: chunk_data.data.data,
+                        block_entities: chunk_data.block_entities.data,
+                    },
+                )
+            }
+            packet::Packet::ChunkData_AndLight(chunk_data) => {
+                let mut light_arrays = vec![];
+                pack_light_arrays(
+                    &chunk_data.sky_light_mask,
+                    chunk_data.sky_light_arrays.data,
+                    &mut light_arrays,
+                );
+                pack_light_arrays(
+                    &chunk_data.block_light_mask,
+                    chunk_data.block_light_arrays.data,
+                    &mut light_arrays,
+                );
+                let (chunk_x, chunk_z) = (chunk_data.chunk_x, chunk_data.chunk_z);
+                mapped_packet::MappedPacket::ChunkData_AndLight(ChunkData_AndLight {
+                    chunk_x,
+                    chunk_z,
+                    heightmaps: chunk_data.heightmaps,
+                    data: chunk_data.data.data,
+                    block_entities: chunk_data
+                        .block_entities
+                        .data
+                        .into_iter()
+                        .map(|block_entity| UpdateBlockEntity {
+                            location: Position::new(
+                                (chunk_x << 4) | (block_entity.packed_xz >> 4) as i32,
+                                block_entity.y as i32,
+                                (chunk_z << 4) | (block_entity.packed_xz & 0xF) as i32,
+                            ),
+                            action: block_entity_action(block_entity.ty.0),
+                            nbt: block_entity.data,
+                            data_length: None,
+                            gzipped_nbt: None,
+                        })
+                        .collect(),
+                    sky_light_mask: chunk_data.sky_light_mask.low_bits() as i64,
+                    block_light_mask: chunk_data.block_light_mask.low_bits() as i64,
+                    light_arrays,
+                })
+            }
             packet::Packet::ChunkData_Biomes3D(chunk_data) => {
                 mapped_packet::MappedPacket::ChunkData_Biomes3D(ChunkData_Biomes3D {
                     chunk_x: chunk_data.chunk_x,
@@ -1722,6 +1914,26 @@ impl MappablePacket for packet::Packet {
             packet::Packet::Camera(camera) => mapped_packet::MappedPacket::Camera(Camera {
                 target_id: camera.target_id.0,
             }),
+            packet::Packet::ClickWindow_State(click_window) => {
+                mapped_packet::MappedPacket::ClickWindow(ClickWindow {
+                    id: click_window.id,
+                    slot: click_window.slot,
+                    button: click_window.button,
+                    action_number: click_window.state_id.0 as u16,
+                    mode: click_window.mode.0,
+                    clicked_item: click_window.carried_item,
+                })
+            }
+            packet::Packet::ClickWindow_ChangedSlots(click_window) => {
+                mapped_packet::MappedPacket::ClickWindow(ClickWindow {
+                    id: click_window.id,
+                    slot: click_window.slot,
+                    button: click_window.button,
+                    action_number: 0,
+                    mode: click_window.mode.0,
+                    clicked_item: click_window.carried_item,
+                })
+            }
             packet::Packet::ClickWindow(click_window) => {
                 mapped_packet::MappedPacket::ClickWindow(ClickWindow {
                     id: click_window.id,
@@ -1793,6 +2005,33 @@ impl MappablePacket for packet::Packet {
                     number_of_items: None,
                 })
             }
+            packet::Packet::EnterCombatEvent(_) => {
+                mapped_packet::MappedPacket::CombatEvent(CombatEvent {
+                    event: 0,
+                    direction: None,
+                    player_id: None,
+                    entity_id: None,
+                    message: None,
+                })
+            }
+            packet::Packet::EndCombatEvent(combat_event) => {
+                mapped_packet::MappedPacket::CombatEvent(CombatEvent {
+                    event: 1,
+                    direction: Some(combat_event.duration.0),
+                    player_id: None,
+                    entity_id: Some(combat_event.entity_id),
+                    message: None,
+                })
+            }
+            packet::Packet::DeathCombatEvent(combat_event) => {
+                mapped_packet::MappedPacket::CombatEvent(CombatEvent {
+                    event: 2,
+                    direction: None,
+                    player_id: Some(combat_event.player_id.0),
+                    entity_id: Some(combat_event.entity_id),
+                    message: Some(combat_event.message),
+                })
+            }
             packet::Packet::CombatEvent(combat_event) => {
                 mapped_packet::MappedPacket::CombatEvent(CombatEvent {
                     event: combat_event.event.0,
@@ -1988,6 +2227,11 @@ impl MappablePacket for packet::Packet {
                     on_ground: None,
                 })
             }
+            packet::Packet::EntityDestroy_Single(destroy) => {
+                mapped_packet::MappedPacket::EntityDestroy(EntityDestroy {
+                    entity_ids: vec![destroy.entity_id.0],
+                })
+            }
             packet::Packet::EntityDestroy(destroy) => {
                 mapped_packet::MappedPacket::EntityDestroy(EntityDestroy {
                     entity_ids: destroy.entity_ids.data.iter().map(|x| x.0).collect(),
@@ -1998,6 +2242,13 @@ impl MappablePacket for packet::Packet {
                     entity_ids: destroy.entity_ids.data,
                 })
             }
+            packet::Packet::EditBook_Pages(edit_book) => {
+                mapped_packet::MappedPacket::EditBook_Pages(EditBook_Pages {
+                    slot: edit_book.slot.0,
+                    entries: edit_book.entries.data,
+                    title: edit_book.title,
+                })
+            }
             packet::Packet::EditBook(edit_book) => {
                 mapped_packet::MappedPacket::EditBook(EditBook {
                     new_book: edit_book.new_book,
@@ -2049,6 +2300,12 @@ impl MappablePacket for packet::Packet {
                     verify_token: encryption_response.verify_token.data,
                 })
             }
+            packet::Packet::EncryptionResponse_Sig(encryption_response) => {
+                mapped_packet::MappedPacket::EncryptionResponse(EncryptionResponse {
+                    shared_secret: encryption_response.shared_secret.data,
+                    verify_token: encryption_response.verify_token.data,
+                })
+            }
             packet::Packet::EntityAction(action) => {
                 mapped_packet::MappedPacket::EntityAction(EntityAction {
                     entity_id: action.entity_id,
@@ -2078,6 +2335,15 @@ impl MappablePacket for packet::Packet {
                     hide_particles: Some(effect.hide_particles),
                 })
             }
+            packet::Packet::EntityEffect_FactorData(effect) => {
+                mapped_packet::MappedPacket::EntityEffect(EntityEffect {
+                    entity_id: effect.entity_id.0,
+                    effect_id: effect.effect_id.0 as i8,
+                    amplifier: effect.amplifier,
+                    duration: effect.duration.0,
+                    hide_particles: Some(effect.hide_particles),
+                })
+            }
             packet::Packet::EntityEffect_i32(effect) => {
                 mapped_packet::MappedPacket::EntityEffect(EntityEffect {
                     entity_id: effect.entity_id,
@@ -2126,6 +2392,21 @@ impl MappablePacket for packet::Packet {
                     metadata: metadata.metadata,
                 })
             }
+            packet::Packet::EntityProperties_VarIntLen(properties) => {
+                mapped_packet::MappedPacket::EntityProperties(EntityProperties {
+                    entity_id: properties.entity_id.0,
+                    properties: properties
+                        .properties
+                        .data
+                        .into_iter()
+                        .map(|x| EntityProperty {
+                            key: x.key,
+                            value: x.value,
+                            modifiers: x.modifiers.data,
+                        })
+                        .collect(),
+                })
+            }
             packet::Packet::EntityProperties(properties) => {
                 mapped_packet::MappedPacket::EntityProperties(EntityProperties {
                     entity_id: properties.entity_id.0,
@@ -2177,6 +2458,15 @@ impl MappablePacket for packet::Packet {
                     pitch: sound_effect.pitch,
                 })
             }
+            packet::Packet::EntitySoundEffect_Seed(sound_effect) => {
+                mapped_packet::MappedPacket::EntitySoundEffect(EntitySoundEffect {
+                    sound_id: sound_effect.sound_id.0,
+                    sound_category: sound_effect.sound_category.0,
+                    entity_id: sound_effect.entity_id.0,
+                    volume: sound_effect.volume,
+                    pitch: sound_effect.pitch,
+                })
+            }
             packet::Packet::EntityStatus(status) => {
                 mapped_packet::MappedPacket::EntityStatus(EntityStatus {
                     entity_id: status.entity_id,
@@ -2201,6 +2491,18 @@ impl MappablePacket for packet::Packet {
                     location: Position::new(used_bed.x, used_bed.y as i32, used_bed.z),
                 })
             }
+            packet::Packet::Explosion_VarInt(explosion) => {
+                mapped_packet::MappedPacket::Explosion(Explosion {
+                    x: explosion.x,
+                    y: explosion.y,
+                    z: explosion.z,
+                    radius: explosion.radius,
+                    records: explosion.records.data,
+                    velocity_x: explosion.velocity_x,
+                    velocity_y: explosion.velocity_y,
+                    velocity_z: explosion.velocity_z,
+                })
+            }
             packet::Packet::Explosion(explosion) => {
                 mapped_packet::MappedPacket::Explosion(Explosion {
                     x: explosion.x,
@@ -2382,6 +2684,56 @@ impl MappablePacket for packet::Packet {
                     is_flat: Some(join_game.is_flat),
                 })
             }
+            packet::Packet::JoinGame_DeathLocation(join_game) => {
+                let dimension = join_game
+                    .dimension_codec
+                    .as_ref()
+                    .and_then(|codec| dimension_type_from_codec(codec, &join_game.dimension_type));
+                mapped_packet::MappedPacket::JoinGame(JoinGame {
+                    entity_id: join_game.entity_id,
+                    is_hardcore: Some(join_game.is_hardcore),
+                    gamemode: join_game.gamemode,
+                    previous_gamemode: Some(join_game.previous_gamemode),
+                    world_names: Some(join_game.world_names.data),
+                    dimension_codec: join_game.dimension_codec,
+                    dimension,
+                    dimension_name: None,
+                    dimension_id: None,
+                    difficulty: None,
+                    level_type: None,
+                    world_name: Some(join_game.world_name),
+                    hashed_seed: Some(join_game.hashed_seed),
+                    max_players: join_game.max_players.0,
+                    view_distance: Some(join_game.view_distance.0),
+                    reduced_debug_info: Some(join_game.reduced_debug_info),
+                    enable_respawn_screen: Some(join_game.enable_respawn_screen),
+                    is_debug: Some(join_game.is_debug),
+                    is_flat: Some(join_game.is_flat),
+                })
+            }
+            packet::Packet::JoinGame_WorldNames_IsHard_SimDist(join_game) => {
+                mapped_packet::MappedPacket::JoinGame(JoinGame {
+                    entity_id: join_game.entity_id,
+                    is_hardcore: Some(join_game.is_hardcore),
+                    gamemode: join_game.gamemode,
+                    previous_gamemode: Some(join_game.previous_gamemode),
+                    world_names: Some(join_game.world_names.data),
+                    dimension_codec: join_game.dimension_codec,
+                    dimension: join_game.dimension,
+                    dimension_name: None,
+                    dimension_id: None,
+                    difficulty: None,
+                    level_type: None,
+                    world_name: Some(join_game.world_name),
+                    hashed_seed: Some(join_game.hashed_seed),
+                    max_players: join_game.max_players.0,
+                    view_distance: Some(join_game.view_distance.0),
+                    reduced_debug_info: Some(join_game.reduced_debug_info),
+                    enable_respawn_screen: Some(join_game.enable_respawn_screen),
+                    is_debug: Some(join_game.is_debug),
+                    is_flat: Some(join_game.is_flat),
+                })
+            }
             packet::Packet::JoinGame_HashedSeed_Respawn(join_game) => {
                 mapped_packet::MappedPacket::JoinGame(JoinGame {
                     entity_id: join_game.entity_id,
@@ -2410,6 +2762,8 @@ impl MappablePacket for packet::Packet {
                     id: keep_alive.id as i64,
                 })
             }
+            packet::Packet::Ping(ping) => mapped_packet::MappedPacket::Ping(Ping { id: ping.id }),
+            packet::Packet::Pong(pong) => mapped_packet::MappedPacket::Pong(Pong { id: pong.id }),
             packet::Packet::KeepAliveClientbound_i64(keep_alive) => {
                 mapped_packet::MappedPacket::KeepAliveClientbound(KeepAliveClientbound {
                     id: keep_alive.id,
@@ -2464,6 +2818,11 @@ impl MappablePacket for packet::Packet {
                     username: login_start.username,
                 })
             }
+            packet::Packet::LoginStart_Sig(login_start) => {
+                mapped_packet::MappedPacket::LoginStart(LoginStart {
+                    username: login_start.username,
+                })
+            }
             packet::Packet::LoginSuccess_String(login_success) => {
                 mapped_packet::MappedPacket::LoginSuccess_String(LoginSuccess_String {
                     uuid: login_success.uuid,
@@ -2476,6 +2835,24 @@ impl MappablePacket for packet::Packet {
                     username: login_success.username,
                 })
             }
+            packet::Packet::LoginSuccess_Properties(login_success) => {
+                mapped_packet::MappedPacket::LoginSuccess_UUID(LoginSuccess_UUID {
+                    uuid: login_success.uuid,
+                    username: login_success.username,
+                })
+            }
+            packet::Packet::Maps_OptionalIcons(maps) => mapped_packet::MappedPacket::Maps(Maps {
+                item_damage: maps.item_damage.0,
+                scale: Some(maps.scale),
+                tracking_position: Some(maps.tracking_position),
+                locked: Some(maps.locked),
+                icons: maps.icons.map(|x| x.data),
+                columns: Some(maps.columns),
+                rows: maps.rows,
+                x: maps.x,
+                z: maps.z,
+                data: maps.data.map(|x| x.data),
+            }),
             packet::Packet::Maps(maps) => mapped_packet::MappedPacket::Maps(Maps {
                 item_damage: maps.item_damage.0,
                 scale: Some(maps.scale),
@@ -2606,6 +2983,17 @@ impl MappablePacket for packet::Packet {
                     pitch: sound_effect.pitch,
                 })
             }
+            packet::Packet::NamedSoundEffect_Seed(sound_effect) => {
+                mapped_packet::MappedPacket::NamedSoundEffect(NamedSoundEffect {
+                    name: sound_effect.name,
+                    category: Some(sound_effect.category.0),
+                    x: sound_effect.x,
+                    y: sound_effect.y,
+                    z: sound_effect.z,
+                    volume: sound_effect.volume,
+                    pitch: sound_effect.pitch,
+                })
+            }
             packet::Packet::NamedSoundEffect_u8(sound_effect) => {
                 mapped_packet::MappedPacket::NamedSoundEffect(NamedSoundEffect {
                     name: sound_effect.name,
@@ -2614,7 +3002,7 @@ impl MappablePacket for packet::Packet {
                     y: sound_effect.y,
                     z: sound_effect.z,
                     volume: sound_effect.volume,
-                    pitch: sound_effect.pitch as f32, // TODO: Conversion?
+                    pitch: sound_effect.pitch as f32 / 63.0, // 63 is the normal pitch
                 })
             }
             packet::Packet::NamedSoundEffect_u8_NoCategory(sound_effect) => {
@@ -2625,7 +3013,7 @@ impl MappablePacket for packet::Packet {
                     y: sound_effect.y,
                     z: sound_effect.z,
                     volume: sound_effect.volume,
-                    pitch: sound_effect.pitch as f32, // TODO: Conversion?
+                    pitch: sound_effect.pitch as f32 / 63.0, // 63 is the normal pitch
                 })
             }
             packet::Packet::NameItem(name_item) => {
@@ -2654,6 +3042,13 @@ impl MappablePacket for packet::Packet {
                     face: digging.face,
                 })
             }
+            packet::Packet::PlayerDigging_Sequence(digging) => {
+                mapped_packet::MappedPacket::PlayerDigging(PlayerDigging {
+                    status: digging.status.0,
+                    location: digging.location,
+                    face: digging.face,
+                })
+            }
             packet::Packet::PlayerDigging_u8(digging) => {
                 mapped_packet::MappedPacket::PlayerDigging(PlayerDigging {
                     status: digging.status as i32,
@@ -2724,6 +3119,52 @@ impl MappablePacket for packet::Packet {
                     data2: None,
                 })
             }
+            packet::Packet::Particle_VarInt_Vibration(particle) => {
+                mapped_packet::MappedPacket::Particle(Particle {
+                    particle_id: Some(particle.particle_id.0),
+                    particle_name: None,
+                    long_distance: Some(particle.long_distance),
+                    x: particle.x,
+                    y: particle.y,
+                    z: particle.z,
+                    offset_x: particle.offset_x,
+                    offset_y: particle.offset_y,
+                    offset_z: particle.offset_z,
+                    speed: particle.speed,
+                    count: particle.count,
+                    block_state: Some(particle.block_state.0),
+                    red: Some(particle.red),
+                    green: Some(particle.green),
+                    blue: Some(particle.blue),
+                    scale: Some(particle.scale),
+                    item: None,
+                    data1: None,
+                    data2: None,
+                })
+            }
+            packet::Packet::Particle_f64_Vibration(particle) => {
+                mapped_packet::MappedPacket::Particle(Particle {
+                    particle_id: Some(particle.particle_id),
+                    particle_name: None,
+                    long_distance: Some(particle.long_distance),
+                    x: particle.x,
+                    y: particle.y,
+                    z: particle.z,
+                    offset_x: particle.offset_x,
+                    offset_y: particle.offset_y,
+                    offset_z: particle.offset_z,
+                    speed: particle.speed,
+                    count: particle.count,
+                    block_state: Some(particle.block_state.0),
+                    red: Some(particle.red),
+                    green: Some(particle.green),
+                    blue: Some(particle.blue),
+                    scale: Some(particle.scale),
+                    item: None,
+                    data1: None,
+                    data2: None,
+                })
+            }
             packet::Packet::Particle_f64(particle) => {
                 mapped_packet::MappedPacket::Particle(Particle {
                     particle_id: Some(particle.particle_id),
@@ -2843,6 +3284,18 @@ impl MappablePacket for packet::Packet {
                     inside_block: Some(block_placement.inside_block),
                 })
             }
+            packet::Packet::PlayerBlockPlacement_Sequence(block_placement) => {
+                mapped_packet::MappedPacket::PlayerBlockPlacement(PlayerBlockPlacement {
+                    location: block_placement.location,
+                    face: block_placement.face.0,
+                    hand: Some(block_placement.hand.0),
+                    hand_item: None,
+                    cursor_x: block_placement.cursor_x,
+                    cursor_y: block_placement.cursor_y,
+                    cursor_z: block_placement.cursor_z,
+                    inside_block: Some(block_placement.inside_block),
+                })
+            }
             packet::Packet::PlayerBlockPlacement_u8(block_placement) => {
                 mapped_packet::MappedPacket::PlayerBlockPlacement(PlayerBlockPlacement {
                     location: block_placement.location,
@@ -2992,6 +3445,23 @@ impl MappablePacket for packet::Packet {
                     copy_metadata: Some(respawn.copy_metadata),
                 })
             }
+            // The dimension type is resolved against the codec from JoinGame by the caller
+            packet::Packet::Respawn_DeathLocation(respawn) => {
+                mapped_packet::MappedPacket::Respawn(Respawn {
+                    dimension_tag: None,
+                    dimension_name: Some(respawn.dimension_type),
+                    world_name: Some(respawn.dimension_name),
+                    dimension: None,
+                    hashed_seed: Some(respawn.hashed_seed),
+                    difficulty: None,
+                    gamemode: respawn.gamemode,
+                    level_type: None,
+                    previous_gamemode: Some(respawn.previous_gamemode),
+                    is_debug: Some(respawn.is_debug),
+                    is_flat: Some(respawn.is_flat),
+                    copy_metadata: Some(respawn.copy_metadata),
+                })
+            }
             packet::Packet::Respawn_NBT(respawn) => mapped_packet::MappedPacket::Respawn(Respawn {
                 dimension_tag: respawn.dimension,
                 dimension_name: None,
@@ -3038,6 +3508,12 @@ impl MappablePacket for packet::Packet {
                     copy_metadata: None,
                 })
             }
+            packet::Packet::ResourcePackSend_Prompt(resource_pack) => {
+                mapped_packet::MappedPacket::ResourcePackSend(ResourcePackSend {
+                    url: resource_pack.url,
+                    hash: resource_pack.hash,
+                })
+            }
             packet::Packet::ResourcePackSend(resource_pack) => {
                 mapped_packet::MappedPacket::ResourcePackSend(ResourcePackSend {
                     url: resource_pack.url,
@@ -3173,6 +3649,22 @@ impl MappablePacket for packet::Packet {
                     velocity_z: spawn_object.velocity_z,
                 })
             }
+            packet::Packet::SpawnObject_HeadYaw(spawn_object) => {
+                mapped_packet::MappedPacket::SpawnObject(SpawnObject {
+                    entity_id: spawn_object.entity_id.0,
+                    uuid: Some(spawn_object.uuid),
+                    ty: spawn_object.ty.0,
+                    x: spawn_object.x,
+                    y: spawn_object.y,
+                    z: spawn_object.z,
+                    pitch: spawn_object.pitch,
+                    yaw: spawn_object.yaw,
+                    data: spawn_object.data.0,
+                    velocity_x: spawn_object.velocity_x,
+                    velocity_y: spawn_object.velocity_y,
+                    velocity_z: spawn_object.velocity_z,
+                })
+            }
             packet::Packet::SpawnObject_i32(spawn_object) => {
                 mapped_packet::MappedPacket::SpawnObject(SpawnObject {
                     entity_id: spawn_object.entity_id.0,
@@ -3217,6 +3709,33 @@ impl MappablePacket for packet::Packet {
                     sender: Some(server_msg.sender),
                 })
             }
+            packet::Packet::PlayerChatMessage(chat_msg) => {
+                mapped_packet::MappedPacket::PlayerChatMessage(PlayerChatMessage {
+                    content: if chat_msg.has_unsigned_content {
+                        chat_msg.unsigned_content
+                    } else {
+                        chat_msg.signed_content
+                    },
+                    message_type: chat_msg.message_type.0,
+                    sender: chat_msg.sender,
+                    sender_display_name: chat_msg.sender_display_name,
+                    team_name: if chat_msg.has_team_name {
+                        Some(chat_msg.team_name)
+                    } else {
+                        None
+                    },
+                    timestamp: chat_msg.timestamp,
+                })
+            }
+            // The vanilla chat types start with chat, system and game info
+            // which line up with the positions used before 1.19.
+            packet::Packet::SystemChatMessage(system_msg) => {
+                mapped_packet::MappedPacket::ServerMessage(ServerMessage {
+                    message: system_msg.content,
+                    position: Some(system_msg.message_type.0 as u8),
+                    sender: None,
+                })
+            }
             packet::Packet::ServerMessage_Position(server_msg) => {
                 mapped_packet::MappedPacket::ServerMessage(ServerMessage {
                     message: server_msg.message,
@@ -3454,6 +3973,17 @@ impl MappablePacket for packet::Packet {
                     pitch: sound.pitch,
                 })
             }
+            packet::Packet::SoundEffect_Seed(sound) => {
+                mapped_packet::MappedPacket::SoundEffect(SoundEffect {
+                    name: sound.name.0,
+                    category: sound.category.0,
+                    x: sound.x,
+                    y: sound.y,
+                    z: sound.z,
+                    volume: sound.volume,
+                    pitch: sound.pitch,
+                })
+            }
             packet::Packet::SoundEffect_u8(sound) => {
                 mapped_packet::MappedPacket::SoundEffect(SoundEffect {
                     name: sound.name.0,
@@ -3462,7 +3992,7 @@ impl MappablePacket for packet::Packet {
                     y: sound.y,
                     z: sound.z,
                     volume: sound.volume,
-                    pitch: sound.pitch as f32, // TODO: Convert this somehow?
+                    pitch: sound.pitch as f32 / 63.0, // 63 is the normal pitch
                 })
             }
             packet::Packet::SpawnExperienceOrb(exp_orb) => {
@@ -3541,6 +4071,11 @@ impl MappablePacket for packet::Packet {
                     direction: painting.direction as i32,
                 })
             }
+            packet::Packet::SpawnPosition_Angle(position) => {
+                mapped_packet::MappedPacket::SpawnPosition(SpawnPosition {
+                    location: position.location,
+                })
+            }
             packet::Packet::SpawnPosition(position) => {
                 mapped_packet::MappedPacket::SpawnPosition(SpawnPosition {
                     location: position.location,
@@ -3641,6 +4176,18 @@ impl MappablePacket for packet::Packet {
                     on_ground: None,
                 })
             }
+            packet::Packet::TeleportPlayer_WithDismount(tp_player) => {
+                mapped_packet::MappedPacket::TeleportPlayer(TeleportPlayer {
+                    x: tp_player.x,
+                    y: tp_player.y,
+                    z: tp_player.z,
+                    yaw: tp_player.yaw,
+                    pitch: tp_player.pitch,
+                    flags: Some(tp_player.flags),
+                    teleport_id: Some(tp_player.teleport_id.0),
+                    on_ground: None,
+                })
+            }
             packet::Packet::TeleportPlayer_WithConfirm(tp_player) => {
                 mapped_packet::MappedPacket::TeleportPlayer(TeleportPlayer {
                     x: tp_player.x,
@@ -3682,6 +4229,27 @@ impl MappablePacket for packet::Packet {
                     matches: reply.matches.data,
                 })
             }
+            packet::Packet::TagsWithTypes(tags) => {
+                let mut block_tags = vec![];
+                let mut item_tags = vec![];
+                let mut fluid_tags = vec![];
+                let mut entity_tags = vec![];
+                for group in tags.tags.data {
+                    match group.tag_type.as_str() {
+                        "minecraft:block" => block_tags = group.tags.data,
+                        "minecraft:item" => item_tags = group.tags.data,
+                        "minecraft:fluid" => fluid_tags = group.tags.data,
+                        "minecraft:entity_type" => entity_tags = group.tags.data,
+                        _ => {}
+                    }
+                }
+                mapped_packet::MappedPacket::Tags(Tags {
+                    block_tags,
+                    item_tags,
+                    fluid_tags,
+                    entity_tags: Some(entity_tags),
+                })
+            }
             packet::Packet::Tags(tags) => mapped_packet::MappedPacket::Tags(Tags {
                 block_tags: tags.block_tags.data,
                 item_tags: tags.item_tags.data,
@@ -3706,7 +4274,21 @@ impl MappablePacket for packet::Packet {
                 suffix: teams.suffix,
                 players: teams.players.map(|x| x.data),
                 color: teams.color,
-                data: Some(teams.data),
+                data: None,
+            }),
+            packet::Packet::Teams_NoCollision(teams) => mapped_packet::MappedPacket::Teams(Teams {
+                name: teams.name,
+                mode: teams.mode,
+                display_name: teams.display_name,
+                flags: teams.flags,
+                name_tag_visibility: teams.name_tag_visibility,
+                collision_rule: None,
+                formatting: None,
+                prefix: teams.prefix,
+                suffix: teams.suffix,
+                players: teams.players.map(|x| x.data),
+                color: teams.color,
+                data: None,
             }),
             packet::Packet::Teams_NoVisColor(teams) => mapped_packet::MappedPacket::Teams(Teams {
                 name: teams.name,
@@ -3736,6 +4318,66 @@ impl MappablePacket for packet::Packet {
                 color: None,
                 data: None,
             }),
+            packet::Packet::SetTitleText(title) => mapped_packet::MappedPacket::Title(Title {
+                action: 0,
+                title: Some(title.text),
+                sub_title: None,
+                action_bar_text: None,
+                fade_in: None,
+                fade_stay: None,
+                fade_out: None,
+                fade_in_comp: None,
+                fade_stay_comp: None,
+                fade_out_comp: None,
+            }),
+            packet::Packet::SetTitleSubtitle(title) => mapped_packet::MappedPacket::Title(Title {
+                action: 1,
+                title: None,
+                sub_title: Some(title.text),
+                action_bar_text: None,
+                fade_in: None,
+                fade_stay: None,
+                fade_out: None,
+                fade_in_comp: None,
+                fade_stay_comp: None,
+                fade_out_comp: None,
+            }),
+            packet::Packet::ActionBar(title) => mapped_packet::MappedPacket::Title(Title {
+                action: 2,
+                title: None,
+                sub_title: None,
+                action_bar_text: Some(title.text),
+                fade_in: None,
+                fade_stay: None,
+                fade_out: None,
+                fade_in_comp: None,
+                fade_stay_comp: None,
+                fade_out_comp: None,
+            }),
+            packet::Packet::SetTitleTimes(title) => mapped_packet::MappedPacket::Title(Title {
+                action: 3,
+                title: None,
+                sub_title: None,
+                action_bar_text: None,
+                fade_in: Some(title.fade_in),
+                fade_stay: Some(title.fade_stay),
+                fade_out: Some(title.fade_out),
+                fade_in_comp: None,
+                fade_stay_comp: None,
+                fade_out_comp: None,
+            }),
+            packet::Packet::ClearTitles(title) => mapped_packet::MappedPacket::Title(Title {
+                action: if title.reset { 5 } else { 4 },
+                title: None,
+                sub_title: None,
+                action_bar_text: None,
+                fade_in: None,
+                fade_stay: None,
+                fade_out: None,
+                fade_in_comp: None,
+                fade_stay_comp: None,
+                fade_out_comp: None,
+            }),
             packet::Packet::Title(title) => mapped_packet::MappedPacket::Title(Title {
                 action: title.action.0,
                 title: title.title,
@@ -3808,6 +4450,29 @@ impl MappablePacket for packet::Packet {
                     food_saturation: health.food_saturation,
                 })
             }
+            packet::Packet::UpdateLight_BitSet(light) => {
+                let mut light_arrays = vec![];
+                pack_light_arrays(
+                    &light.sky_light_mask,
+                    light.sky_light_arrays.data,
+                    &mut light_arrays,
+                );
+                pack_light_arrays(
+                    &light.block_light_mask,
+                    light.block_light_arrays.data,
+                    &mut light_arrays,
+                );
+                mapped_packet::MappedPacket::UpdateLight(UpdateLight {
+                    chunk_x: light.chunk_x.0,
+                    chunk_z: light.chunk_z.0,
+                    trust_edges: Some(light.trust_edges),
+                    sky_light_mask: light.sky_light_mask.low_bits() as i64,
+                    block_light_mask: light.block_light_mask.low_bits() as i64,
+                    empty_block_light_mask: light.empty_block_light_mask.low_bits() as i64,
+                    empty_sky_light_mask: light.empty_sky_light_mask.low_bits() as i64,
+                    light_arrays,
+                })
+            }
             packet::Packet::UpdateLight_WithTrust(light) => {
                 mapped_packet::MappedPacket::UpdateLight(UpdateLight {
                     chunk_x: light.chunk_x.0,
@@ -3847,6 +4512,15 @@ impl MappablePacket for packet::Packet {
                     gzipped_nbt: None,
                 })
             }
+            packet::Packet::UpdateBlockEntity_VarInt(block_entity) => {
+                mapped_packet::MappedPacket::UpdateBlockEntity(UpdateBlockEntity {
+                    location: block_entity.location,
+                    action: block_entity_action(block_entity.ty.0),
+                    nbt: block_entity.nbt,
+                    data_length: None,
+                    gzipped_nbt: None,
+                })
+            }
             packet::Packet::UpdateBlockEntity_Data(block_entity) => {
                 mapped_packet::MappedPacket::UpdateBlockEntity(UpdateBlockEntity {
                     location: Position::new(block_entity.x, block_entity.y as i32, block_entity.z),
@@ -4001,6 +4675,31 @@ impl MappablePacket for packet::Packet {
                     view_distance: view_distance.view_distance.0,
                 })
             }
+            packet::Packet::ServerData(server_data) => {
+                mapped_packet::MappedPacket::ServerData(ServerData {
+                    motd: if server_data.has_motd {
+                        Some(server_data.motd)
+                    } else {
+                        None
+                    },
+                    icon: if server_data.has_icon {
+                        Some(server_data.icon)
+                    } else {
+                        None
+                    },
+                    previews_chat: server_data.previews_chat,
+                })
+            }
+            packet::Packet::SetDisplayChatPreview(display) => {
+                mapped_packet::MappedPacket::SetDisplayChatPreview(SetDisplayChatPreview {
+                    enabled: display.enabled,
+                })
+            }
+            packet::Packet::UpdateSimulationDistance(simulation_distance) => {
+                mapped_packet::MappedPacket::UpdateSimulationDistance(UpdateSimulationDistance {
+                    simulation_distance: simulation_distance.simulation_distance.0,
+                })
+            }
             packet::Packet::UseEntity_Hand(use_entity) => {
                 mapped_packet::MappedPacket::UseEntity(UseEntity {
                     target_id: use_entity.target_id.0,
@@ -4048,6 +4747,11 @@ impl MappablePacket for packet::Packet {
             packet::Packet::UseItem(use_item) => mapped_packet::MappedPacket::UseItem(UseItem {
                 hand: use_item.hand.0,
             }),
+            packet::Packet::UseItem_Sequence(use_item) => {
+                mapped_packet::MappedPacket::UseItem(UseItem {
+                    hand: use_item.hand.0,
+                })
+            }
             packet::Packet::VehicleMove(vehicle_move) => {
                 mapped_packet::MappedPacket::VehicleMove(VehicleMove {
                     x: vehicle_move.x,
@@ -4066,10 +4770,20 @@ impl MappablePacket for packet::Packet {
                     pitch: teleport.pitch,
                 })
             }
+            packet::Packet::WindowItems_StateCarry(items) => {
+                mapped_packet::MappedPacket::WindowItems(WindowItems {
+                    id: items.id,
+                    state_id: Some(items.state_id.0),
+                    items: items.items.data,
+                    carried_item: items.carried_item,
+                })
+            }
             packet::Packet::WindowItems(items) => {
                 mapped_packet::MappedPacket::WindowItems(WindowItems {
                     id: items.id,
+                    state_id: None,
                     items: items.items.data,
+                    carried_item: None,
                 })
             }
             packet::Packet::WindowClose(close) => {
@@ -4122,13 +4836,100 @@ impl MappablePacket for packet::Packet {
                     value: property.value,
                 })
             }
+            packet::Packet::WindowSetSlot_State(set_slot) => {
+                mapped_packet::MappedPacket::WindowSetSlot(WindowSetSlot {
+                    id: set_slot.id,
+                    state_id: Some(set_slot.state_id.0),
+                    slot: set_slot.slot,
+                    item: set_slot.item,
+                })
+            }
             packet::Packet::WindowSetSlot(set_slot) => {
                 mapped_packet::MappedPacket::WindowSetSlot(WindowSetSlot {
                     id: set_slot.id,
+                    state_id: None,
                     slot: set_slot.slot,
                     item: set_slot.item,
                 })
             }
+            packet::Packet::WorldBorderSize(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 0,
+                    old_radius: None,
+                    new_radius: Some(border.diameter),
+                    speed: None,
+                    x: None,
+                    z: None,
+                    portal_boundary: None,
+                    warning_time: None,
+                    warning_blocks: None,
+                })
+            }
+            packet::Packet::WorldBorderLerpSize(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 1,
+                    old_radius: Some(border.old_diameter),
+                    new_radius: Some(border.new_diameter),
+                    speed: Some(border.speed.0),
+                    x: None,
+                    z: None,
+                    portal_boundary: None,
+                    warning_time: None,
+                    warning_blocks: None,
+                })
+            }
+            packet::Packet::WorldBorderCenter(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 2,
+                    old_radius: None,
+                    new_radius: None,
+                    speed: None,
+                    x: Some(border.x),
+                    z: Some(border.z),
+                    portal_boundary: None,
+                    warning_time: None,
+                    warning_blocks: None,
+                })
+            }
+            packet::Packet::WorldBorderInit(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 3,
+                    old_radius: Some(border.old_diameter),
+                    new_radius: Some(border.new_diameter),
+                    speed: Some(border.speed.0),
+                    x: Some(border.x),
+                    z: Some(border.z),
+                    portal_boundary: Some(border.portal_boundary.0),
+                    warning_time: Some(border.warning_time.0),
+                    warning_blocks: Some(border.warning_blocks.0),
+                })
+            }
+            packet::Packet::WorldBorderWarningDelay(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 4,
+                    old_radius: None,
+                    new_radius: None,
+                    speed: None,
+                    x: None,
+                    z: None,
+                    portal_boundary: None,
+                    warning_time: Some(border.warning_time.0),
+                    warning_blocks: None,
+                })
+            }
+            packet::Packet::WorldBorderWarningReach(border) => {
+                mapped_packet::MappedPacket::WorldBorder(WorldBorder {
+                    action: 5,
+                    old_radius: None,
+                    new_radius: None,
+                    speed: None,
+                    x: None,
+                    z: None,
+                    portal_boundary: None,
+                    warning_time: None,
+                    warning_blocks: Some(border.warning_blocks.0),
+                })
+            }
             packet::Packet::WorldBorder(border) => {
                 mapped_packet::MappedPacket::WorldBorder(WorldBorder {
                     action: border.action.0,
@@ -4152,3 +4953,45 @@ pub struct BlockChangeRecord {
     pub y: u8,
     pub block_id: i32,
 }
+
+/// Number of light sections kept from a BitSet mask, only the first long of
+/// the mask fits into the i64 masks of UpdateLight.
+const LIGHT_SECTIONS: usize = 64;
+
+/// Writes the light arrays sent alongside a BitSet mask in the layout used by
+/// older versions, dropping the arrays of sections outside of LIGHT_SECTIONS.
+fn pack_light_arrays(mask: &BitSet, arrays: Vec<LenPrefixedBytes<VarInt>>, out: &mut Vec<u8>) {
+    let bits = (0..mask.0.len() * 64).filter(|bit| mask.get(*bit));
+    for (bit, array) in bits.zip(arrays) {
+        if bit < LIGHT_SECTIONS {
+            array.write_to(out).unwrap();
+        }
+    }
+}
+
+/// Maps a block entity type id (1.18+) to the UpdateBlockEntity action of
+/// older versions, types without an action map to 0.
+fn block_entity_action(ty: i32) -> u8 {
+    match ty {
+        // Sign
+        7 => 9,
+        _ => 0,
+    }
+}
+
+/// Looks up the element of the named dimension type in the registry codec
+/// sent with JoinGame, 1.19 only references dimension types by name.
+pub fn dimension_type_from_codec(
+    codec: &crate::nbt::NamedTag,
+    name: &str,
+) -> Option<crate::nbt::NamedTag> {
+    codec
+        .1
+        .get("minecraft:dimension_type")?
+        .get("value")?
+        .as_list()?
+        .iter()
+        .find(|entry| entry.get("name").and_then(|n| n.as_str()) == Some(name))?
+        .get("element")
+        .map(|element| crate::nbt::NamedTag(String::new(), element.clone()))
+}
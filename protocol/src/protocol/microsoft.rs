@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::protocol::login::{Account, AccountImpl};
+use crate::protocol::profile_key::ProfileKey;
 use serde_json::json;
 use sha1::Digest;
 
@@ -84,6 +85,10 @@ impl AccountImpl for MicrosoftAccount {
     fn append_head_img_data(&self, _account: &mut Account) -> Result<(), super::Error> {
         Ok(())
     }
+
+    fn profile_key(&self, account: &Account) -> Result<Option<ProfileKey>, super::Error> {
+        ProfileKey::fetch(account.verification_tokens.get(2).unwrap()).map(Some)
+    }
 }
 
 fn twos_compliment(data: &mut [u8]) {
@@ -23,19 +23,19 @@ use std::fmt;
 use std::fs;
 use std::io;
 use std::io::{Read, Write};
-use std::net::{Shutdown, TcpStream};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 
 use aes::Aes128;
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
-use cfb8::cipher::{AsyncStreamCipher, NewCipher};
+use cfb8::cipher::NewCipher;
 use cfb8::Cfb8;
-use flate2::read::{ZlibDecoder, ZlibEncoder};
-use flate2::Compression;
+use flate2::Decompress;
 use instant::{Duration, Instant};
 use lazy_static::lazy_static;
-use log::{debug, warn};
+use log::{debug, info, warn};
 use num_traits::cast::{cast, NumCast};
 use serde::{Deserialize, Serialize};
 use trust_dns_resolver::config::ResolverConfig;
@@ -46,14 +46,23 @@ use crate::format;
 use crate::nbt;
 use crate::shared::{Position, Version};
 
+pub mod capture;
 pub mod forge;
+mod framing;
+pub mod lan;
 pub mod login;
 pub mod microsoft;
 pub mod offline_acc;
+pub mod profile_key;
+pub mod proxy;
+pub mod stats;
+pub mod transport;
 
-pub const SUPPORTED_PROTOCOLS: [i32; 21] = [
-    754, 753, 751, 736, 735, 578, 575, 498, 490, 485, 480, 477, 404, 340, 316, 315, 210, 109, 107,
-    47, 5,
+use self::transport::Transport;
+
+pub const SUPPORTED_PROTOCOLS: [i32; 25] = [
+    759, 758, 756, 755, 754, 753, 751, 736, 735, 578, 575, 498, 490, 485, 480, 477, 404, 340, 316,
+    315, 210, 109, 107, 47, 5,
 ];
 
 static CURRENT_PROTOCOL_VERSION: AtomicI32 = AtomicI32::new(SUPPORTED_PROTOCOLS[0]);
@@ -146,6 +155,73 @@ macro_rules! state_packets {
         }
         )+
 
+        impl Packet {
+            /// The name of the packet type, as used in the packet tables
+            pub fn name(&self) -> &'static str {
+                match self {
+                $(
+                    $(
+                        $(
+                    Packet::$name(_) => stringify!($name),
+                        )*
+                    )+
+                )+
+                }
+            }
+        }
+
+        impl PacketType for Packet {
+            fn packet_id(&self, version: i32) -> i32 {
+                match self {
+                $(
+                    $(
+                        $(
+                    Packet::$name(packet) => packet.packet_id(version),
+                        )*
+                    )+
+                )+
+                }
+            }
+
+            fn write<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+                match self {
+                $(
+                    $(
+                        $(
+                    Packet::$name(packet) => packet.write(buf),
+                        )*
+                    )+
+                )+
+                }
+            }
+        }
+
+        /// Returns the packet for the given state, direction and internal id
+        /// with all of its fields defaulted.
+        #[cfg(test)]
+        pub fn default_packet_by_internal_id(state: State, dir: Direction, internal_id: i32) -> Option<Packet> {
+            match state {
+                $(
+                    State::$stateName => {
+                        match dir {
+                            $(
+                                Direction::$dirName => {
+                                    match internal_id {
+                                    $(
+                                        self::$state::$dir::internal_ids::$name => {
+                                            Some(Packet::$name(Default::default()))
+                                        },
+                                    )*
+                                        _ => None
+                                    }
+                                }
+                            )+
+                        }
+                    }
+                )+
+            }
+        }
+
         /// Returns the packet for the given state, direction and id after parsing the fields
         /// from the buffer.
         pub fn packet_by_id<R: io::Read>(version: i32, state: State, dir: Direction, id: i32, mut buf: &mut R) -> Result<Option<Packet>, Error> {
@@ -247,6 +323,21 @@ macro_rules! protocol_packet_ids {
     })+) => {
         use $crate::protocol::*;
 
+        /// Returns the ids of all packets known for the given state and direction.
+        pub fn packet_ids(state: State, dir: Direction) -> &'static [i32] {
+            match state {
+                $(
+                    State::$stateName => {
+                        match dir {
+                            $(
+                                Direction::$dirName => &[$($id),*],
+                            )*
+                        }
+                    }
+                )*
+            }
+        }
+
         pub fn translate_internal_packet_id(state: State, dir: Direction, id: i32, to_internal: bool) -> i32 {
             match state {
                 $(
@@ -282,6 +373,7 @@ macro_rules! protocol_packet_ids {
 pub mod mapped_packet;
 pub mod packet;
 pub mod versions;
+mod writer;
 pub trait Serializable: Sized {
     fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error>;
     fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error>;
@@ -560,18 +652,20 @@ impl Default for Biomes3D {
 
 impl Serializable for Biomes3D {
     fn read_from<R: io::Read>(buf: &mut R) -> Result<Biomes3D, Error> {
-        let data: [i32; 1024] = [0; 1024];
+        let mut data: [i32; 1024] = [0; 1024];
 
         // Non-length-prefixed three-dimensional biome data
-        for item in &mut data.to_vec() {
-            let b: i32 = Serializable::read_from(buf)?;
-            *item = b;
+        for item in data.iter_mut() {
+            *item = Serializable::read_from(buf)?;
         }
 
         Ok(Biomes3D { data })
     }
-    fn write_to<W: io::Write>(&self, _buf: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        for biome in self.data.iter() {
+            biome.write_to(buf)?;
+        }
+        Ok(())
     }
 }
 
@@ -836,6 +930,19 @@ impl Lengthable for VarInt {
     }
 }
 
+impl VarInt {
+    /// The number of bytes `write_to` produces for this value
+    pub fn encoded_len(&self) -> usize {
+        let mut val = self.0 as u32;
+        let mut len = 1;
+        while val & !0x7F != 0 {
+            val >>= 7;
+            len += 1;
+        }
+        len
+    }
+}
+
 impl Serializable for VarInt {
     /// Decodes a `VarInt` from the Reader
     fn read_from<R: io::Read>(buf: &mut R) -> Result<VarInt, Error> {
@@ -992,6 +1099,49 @@ impl fmt::Debug for VarLong {
     }
 }
 
+/// BitSet is a length prefixed array of longs used for bitmasks which may
+/// not fit into a single long, e.g. the sections of a chunk since 1.17.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct BitSet(pub Vec<u64>);
+
+impl BitSet {
+    pub fn get(&self, bit: usize) -> bool {
+        self.0
+            .get(bit / 64)
+            .is_some_and(|word| word & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns the lowest 64 bits, which is all of the set for worlds no
+    /// taller than the pre 1.17 limit.
+    pub fn low_bits(&self) -> u64 {
+        self.0.first().copied().unwrap_or(0)
+    }
+}
+
+impl Serializable for BitSet {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<BitSet, Error> {
+        Ok(BitSet(LenPrefixed::<VarInt, u64>::read_from(buf)?.data))
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        VarInt(self.0.len() as i32).write_to(buf)?;
+        for word in &self.0 {
+            word.write_to(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BitSet(")?;
+        for word in self.0.iter().rev() {
+            write!(f, "{:064b}", word)?;
+        }
+        write!(f, ")")
+    }
+}
+
 impl Serializable for Position {
     fn read_from<R: io::Read>(buf: &mut R) -> Result<Position, Error> {
         let pos = buf.read_u64::<BigEndian>()?;
@@ -1089,19 +1239,109 @@ impl ::std::fmt::Display for Error {
 
 type Aes128Cfb = Cfb8<Aes128>;
 
+/// How long establishing a TCP connection may take before giving up
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a connection attempt gets on its own before the next address is
+/// tried alongside it, as suggested by RFC 8305
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Resolves every IPv4 and IPv6 address of a host, alternating between the
+/// two families so a broken one can't hold up the other for long. The
+/// family the system prefers goes first.
+pub fn resolve(host: &str, port: u16) -> Result<Vec<SocketAddr>, Error> {
+    let (first, second): (Vec<_>, Vec<_>) = {
+        let addrs: Vec<_> = (host, port).to_socket_addrs()?.collect();
+        let prefer_v6 = addrs.first().map_or(false, SocketAddr::is_ipv6);
+        addrs
+            .into_iter()
+            .partition(|addr| addr.is_ipv6() == prefer_v6)
+    };
+    let mut addrs = Vec::with_capacity(first.len() + second.len());
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    loop {
+        match (first.next(), second.next()) {
+            (None, None) => break,
+            (a, b) => addrs.extend(a.into_iter().chain(b)),
+        }
+    }
+    Ok(addrs)
+}
+
+/// Opens a TCP connection to one of `addrs`. The addresses are tried in
+/// order, starting the next attempt whenever the previous one failed or
+/// didn't succeed within `CONNECTION_ATTEMPT_DELAY`, and the first one to
+/// connect wins.
+pub fn connect_any(addrs: &[SocketAddr]) -> Result<(TcpStream, SocketAddr), Error> {
+    let (send, recv) = mpsc::channel();
+    let mut remaining = addrs.iter();
+    let mut pending = 0;
+    let mut last_err = None;
+    loop {
+        if let Some(&addr) = remaining.next() {
+            debug!("Trying {}", addr);
+            let send = send.clone();
+            thread::spawn(move || {
+                // Connections that lose the race are simply dropped
+                let _ = send.send((addr, TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)));
+            });
+            pending += 1;
+        } else if pending == 0 {
+            break;
+        }
+
+        let result = if remaining.len() > 0 {
+            recv.recv_timeout(CONNECTION_ATTEMPT_DELAY).ok()
+        } else {
+            recv.recv().ok()
+        };
+        if let Some((addr, result)) = result {
+            pending -= 1;
+            match result {
+                Ok(stream) => return Ok((stream, addr)),
+                Err(err) => {
+                    debug!("Failed to connect to {}: {}", addr, err);
+                    last_err = Some(err);
+                }
+            }
+        }
+    }
+    Err(match last_err {
+        Some(err) => err.into(),
+        None => Error::Err("No address to connect to".to_owned()),
+    })
+}
+
+/// Opens a TCP connection to whichever address of the host answers first
+pub fn connect_tcp(host: &str, port: u16) -> Result<TcpStream, Error> {
+    let addrs = resolve(host, port)?;
+    if addrs.is_empty() {
+        return Err(Error::Err(format!("Couldn't resolve {}", host)));
+    }
+    Ok(connect_any(&addrs)?.0)
+}
+
 pub struct Conn {
-    stream: TcpStream,
+    stream: Box<dyn Transport>,
     pub host: String,
     pub port: u16,
+    /// The address the connection was made to, unknown when going through
+    /// a proxy or not running over TCP
+    pub remote_addr: Option<SocketAddr>,
     direction: Direction,
     pub protocol_version: i32,
     pub state: State,
 
-    read_cipher: Arc<Mutex<Option<Aes128Cfb>>>,
-    write_cipher: Arc<Mutex<Option<Aes128Cfb>>>,
+    reader: Arc<Mutex<framing::ReadHalf>>,
+    writer: Arc<Mutex<framing::WriteHalf>>,
+    queue: Arc<Mutex<Option<mpsc::Sender<writer::Outgoing>>>>,
+    write_error: Arc<Mutex<Option<String>>>,
 
     pub compression_threshold: i32,
-    pub send: Arc<Mutex<Option<bool>>>,
+
+    capture: Option<Arc<Mutex<capture::CaptureWriter>>>,
+    replay: Option<Arc<Mutex<capture::CaptureReader>>>,
+    stats: Arc<Mutex<stats::NetworkStats>>,
 }
 
 lazy_static! {
@@ -1120,11 +1360,19 @@ impl Conn {
                 addresses.push((record.target().to_string(), record.port()));
             }
         }
-        addresses.push((hostname.to_string(), 25565));
         addresses
     }
 
+    /// Connects to the target, going through the default proxy if one is configured
     pub fn new(target: &str, protocol_version: i32) -> Result<Conn, Error> {
+        Conn::new_with_proxy(target, protocol_version, proxy::default_proxy()?.as_ref())
+    }
+
+    pub fn new_with_proxy(
+        target: &str,
+        protocol_version: i32,
+        proxy: Option<&proxy::Proxy>,
+    ) -> Result<Conn, Error> {
         CURRENT_PROTOCOL_VERSION.store(protocol_version, Ordering::Relaxed);
         let mut address = target.to_string();
         let mut port = 25565;
@@ -1139,71 +1387,204 @@ impl Conn {
         }
 
         use std::str::FromStr;
+        let mut targets = vec![];
         if std::net::IpAddr::from_str(&address).is_err() {
             debug!("address: {} is not an IP", address);
-            for (adresse, port) in Conn::get_server_addresses(&address) {
-                debug!("{}'s ip may be {}:{}.", target, adresse, port);
-                if let Ok(conn) = Conn::try_stream(&adresse, port, protocol_version) {
-                    return Ok(conn);
+            targets = Conn::get_server_addresses(&address);
+        }
+        targets.push((address, port));
+
+        if let Some(proxy) = proxy {
+            // The proxy resolves the names itself, so there is nothing to race
+            let mut last_err = None;
+            for (address, port) in &targets {
+                debug!(
+                    "connecting to {}:{} through proxy {}:{}",
+                    address, port, proxy.host, proxy.port
+                );
+                match proxy.connect(address, *port) {
+                    Ok(stream) => {
+                        return Ok(Conn::from_transport(
+                            Box::new(stream),
+                            Direction::Serverbound,
+                            address,
+                            *port,
+                            protocol_version,
+                        ))
+                    }
+                    Err(err) => last_err = Some(err),
                 }
             }
+            return Err(last_err.unwrap());
         }
 
-        debug!("{}'s ip may be {}:{}.", target, address, port);
-        Conn::try_stream(&address, port, protocol_version)
-    }
-
-    fn try_stream(address: &str, port: u16, protocol_version: i32) -> Result<Conn, Error> {
-        let stream = TcpStream::connect(format!("{}:{}", address, port))?;
-        Ok(Conn {
+        // Race the addresses of every target at once, a dead SRV target or
+        // a broken address family shouldn't keep us waiting
+        let mut addrs: Vec<(SocketAddr, usize)> = vec![];
+        for (i, (address, port)) in targets.iter().enumerate() {
+            match resolve(address, *port) {
+                Ok(resolved) => {
+                    debug!("{}'s ip may be {:?}", target, resolved);
+                    for addr in resolved {
+                        if !addrs.iter().any(|(known, _)| *known == addr) {
+                            addrs.push((addr, i));
+                        }
+                    }
+                }
+                Err(err) => debug!("Failed to resolve {}: {}", address, err),
+            }
+        }
+        if addrs.is_empty() {
+            return Err(Error::Err(format!("Couldn't resolve {}", target)));
+        }
+        let (stream, addr) = connect_any(&addrs.iter().map(|(addr, _)| *addr).collect::<Vec<_>>())?;
+        let (address, port) = &targets[addrs.iter().find(|(a, _)| *a == addr).unwrap().1];
+        info!("Connected to {} at {}", target, addr);
+        let mut conn = Conn::from_transport(
+            Box::new(stream),
+            Direction::Serverbound,
+            address,
+            *port,
+            protocol_version,
+        );
+        conn.remote_addr = Some(addr);
+        Ok(conn)
+    }
+
+    /// Creates a connection on top of an already established transport.
+    ///
+    /// `direction` is the direction of the packets this end sends, so
+    /// `Direction::Serverbound` for a client.
+    pub fn from_transport(
+        stream: Box<dyn Transport>,
+        direction: Direction,
+        host: &str,
+        port: u16,
+        protocol_version: i32,
+    ) -> Conn {
+        Conn {
             stream,
-            host: address.to_string(),
+            host: host.to_string(),
             port,
-            direction: Direction::Serverbound,
+            remote_addr: None,
+            direction,
             state: State::Handshaking,
             protocol_version,
-            read_cipher: Arc::new(Mutex::new(None)),
-            write_cipher: Arc::new(Mutex::new(None)),
+            reader: Arc::new(Mutex::new(framing::ReadHalf::new())),
+            writer: Arc::new(Mutex::new(framing::WriteHalf::new())),
+            queue: Arc::new(Mutex::new(None)),
+            write_error: Arc::new(Mutex::new(None)),
             compression_threshold: -1,
-            send: Arc::new(Mutex::new(None)),
-        })
+            capture: None,
+            replay: None,
+            stats: Arc::new(Mutex::new(stats::NetworkStats::new())),
+        }
     }
 
-    pub fn write_packet<T: PacketType>(&mut self, packet: T) -> Result<(), Error> {
-        let mut buf = Vec::new();
-        VarInt(packet.packet_id(self.protocol_version)).write_to(&mut buf)?;
-        packet.write(&mut buf)?;
+    /// Creates a connection which reads its packets from a capture file
+    /// instead of a server. Anything written to it is discarded.
+    pub fn replay<P: AsRef<std::path::Path>>(path: P) -> Result<Conn, Error> {
+        let reader = capture::CaptureReader::open(path)?;
+        let protocol_version = reader.protocol_version;
+        CURRENT_PROTOCOL_VERSION.store(protocol_version, Ordering::Relaxed);
+        let mut conn = Conn::from_transport(
+            Box::new(transport::NullTransport),
+            Direction::Serverbound,
+            "replay",
+            0,
+            protocol_version,
+        );
+        conn.state = State::Play;
+        conn.replay = Some(Arc::new(Mutex::new(reader)));
+        Ok(conn)
+    }
 
-        let mut extra = if self.compression_threshold >= 0 {
-            1
-        } else {
-            0
-        };
-        if self.compression_threshold >= 0 && buf.len() as i32 > self.compression_threshold {
-            extra = 0;
-            let uncompressed_size = buf.len();
-            let mut new = Vec::new();
-            VarInt(uncompressed_size as i32).write_to(&mut new)?;
-            let mut write = ZlibEncoder::new(io::Cursor::new(buf), Compression::default());
-            write.read_to_end(&mut new)?;
-            if is_network_debug() {
-                debug!(
-                    "Compressed for sending {} bytes to {} since > threshold {}, new={:?}",
-                    uncompressed_size,
-                    new.len(),
-                    self.compression_threshold,
-                    new
-                );
+    pub fn is_replay(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// The traffic counters of this connection, shared with all of its clones
+    pub fn stats(&self) -> Arc<Mutex<stats::NetworkStats>> {
+        self.stats.clone()
+    }
+
+    /// Starts recording every packet read from this connection to a capture file
+    pub fn start_capture<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), Error> {
+        let writer = capture::CaptureWriter::create(path, self.protocol_version)?;
+        self.capture = Some(Arc::new(Mutex::new(writer)));
+        Ok(())
+    }
+
+    /// Moves sending packets to a background thread, see `writer`. This
+    /// applies to all clones of the connection and is meant to be done
+    /// once the login finished.
+    pub fn start_writer(&mut self) -> Result<(), Error> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.is_none() {
+            queue.replace(writer::spawn(
+                self.stream.try_clone_boxed()?,
+                self.writer.clone(),
+                self.stats.clone(),
+                self.write_error.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Marks the end of a game tick, sending everything queued since the
+    /// last one together.
+    pub fn end_batch(&self) {
+        if let Some(queue) = self.queue.lock().unwrap().as_ref() {
+            let _ = queue.send(writer::Outgoing::Flush);
+        }
+    }
+
+    /// The reason the writer thread stopped, if it failed
+    pub fn write_error(&self) -> Option<String> {
+        self.write_error.lock().unwrap().clone()
+    }
+
+    pub fn write_packet<T: PacketType>(&mut self, packet: T) -> Result<(), Error> {
+        if let Some(queue) = self.queue.lock().unwrap().as_ref() {
+            if let Some(err) = self.write_error() {
+                return Err(Error::Err(err));
             }
-            buf = new;
+            let mut payload = Vec::new();
+            VarInt(packet.packet_id(self.protocol_version)).write_to(&mut payload)?;
+            packet.write(&mut payload)?;
+            return queue
+                .send(writer::Outgoing::Packet {
+                    name: packet_type_name::<T>(),
+                    payload,
+                    compression_threshold: self.compression_threshold,
+                })
+                .map_err(|_| {
+                    Error::Err(
+                        self.write_error()
+                            .unwrap_or_else(|| "Connection closed".to_owned()),
+                    )
+                });
         }
-        let lock = self.send.clone();
-        let _lock = lock.lock();
-        VarInt(buf.len() as i32 + extra).write_to(self)?;
-        if self.compression_threshold >= 0 && extra == 1 {
-            VarInt(0).write_to(self)?;
+
+        let protocol_version = self.protocol_version;
+        let (payload_len, wire_len) = self.writer.lock().unwrap().write_frame(
+            &mut *self.stream,
+            self.compression_threshold,
+            |buf| {
+                VarInt(packet.packet_id(protocol_version)).write_to(buf)?;
+                packet.write(buf)
+            },
+        )?;
+        if is_network_debug() && wire_len < payload_len {
+            debug!(
+                "Compressed for sending {} bytes to {} since > threshold {}",
+                payload_len, wire_len, self.compression_threshold,
+            );
         }
-        self.write_all(&buf)?;
+        self.stats
+            .lock()
+            .unwrap()
+            .record_sent(packet_type_name::<T>(), wire_len, payload_len);
         Ok(())
     }
 
@@ -1282,23 +1663,45 @@ impl Conn {
         buf: &mut R,
         compression_threshold: i32,
     ) -> Result<(i32, Box<io::Cursor<Vec<u8>>>), Error> {
+        let ibuf = Conn::read_raw_frame_from(buf)?;
+        Conn::decode_raw_frame(ibuf, compression_threshold, &mut Decompress::new(true))
+    }
+
+    /// Reads a single length prefixed frame without decoding it
+    fn read_raw_frame_from<R: io::Read>(buf: &mut R) -> Result<Vec<u8>, Error> {
         let len = VarInt::read_from(buf)?.0 as usize;
-        if len > 1000000000 {
-            panic!("Tried to read more than 1GB of data!");
+        if len > framing::MAX_FRAME_SIZE {
+            return Err(Error::Err(format!("Frame of {} bytes is too large", len)));
         }
         let mut ibuf = vec![0; len];
         buf.read_exact(&mut ibuf)?;
+        Ok(ibuf)
+    }
 
+    #[allow(clippy::type_complexity)]
+    fn decode_raw_frame(
+        ibuf: Vec<u8>,
+        compression_threshold: i32,
+        decompress: &mut Decompress,
+    ) -> Result<(i32, Box<io::Cursor<Vec<u8>>>), Error> {
+        let len = ibuf.len();
         let mut buf = io::Cursor::new(ibuf);
 
         if compression_threshold >= 0 {
             let uncompressed_size = VarInt::read_from(&mut buf)?.0;
+            if uncompressed_size < 0 || uncompressed_size as usize > framing::MAX_FRAME_SIZE {
+                return Err(Error::Err(format!(
+                    "Bad uncompressed packet size {}",
+                    uncompressed_size
+                )));
+            }
             if uncompressed_size != 0 {
-                let mut new = Vec::with_capacity(uncompressed_size as usize);
-                {
-                    let mut reader = ZlibDecoder::new(buf);
-                    reader.read_to_end(&mut new)?;
-                }
+                let start = buf.position() as usize;
+                let new = framing::decompress(
+                    decompress,
+                    &buf.get_ref()[start..],
+                    uncompressed_size as usize,
+                )?;
                 if is_network_debug() {
                     debug!(
                         "Decompressed threshold={} len={} uncompressed_size={} to {} bytes",
@@ -1317,8 +1720,39 @@ impl Conn {
     }
 
     pub fn read_packet(&mut self) -> Result<packet::Packet, Error> {
-        let compression_threshold = self.compression_threshold;
-        let (id, mut buf) = Conn::read_raw_packet_from(self, compression_threshold)?;
+        let frame = if let Some(replay) = self.replay.clone() {
+            let record = replay.lock().unwrap().next_record_timed()?;
+            match record {
+                Some(record) => {
+                    self.state = record.state;
+                    self.compression_threshold = record.compression_threshold;
+                    record.data
+                }
+                None => return Err(Error::Err("End of packet capture".to_owned())),
+            }
+        } else {
+            self.reader.lock().unwrap().read_frame(&mut *self.stream)?
+        };
+        if let Some(capture) = &self.capture {
+            let res = capture.lock().unwrap().write_record(
+                self.state,
+                self.compression_threshold,
+                &frame,
+            );
+            // A broken capture shouldn't take the connection down with it
+            if let Err(err) = res {
+                warn!("Failed to write packet capture, stopping it: {}", err);
+                self.capture = None;
+            }
+        }
+        let wire_len = VarInt(frame.len() as i32).encoded_len() + frame.len();
+        let decode_start = Instant::now();
+        let (id, mut buf) = Conn::decode_raw_frame(
+            frame,
+            self.compression_threshold,
+            self.reader.lock().unwrap().decompressor(),
+        )?;
+        let payload_len = buf.get_ref().len();
 
         let dir = match self.direction {
             Direction::Clientbound => Direction::Serverbound,
@@ -1353,6 +1787,12 @@ impl Conn {
                         ibuf.len() - pos
                     )));
                 }
+                self.stats.lock().unwrap().record_received(
+                    val.name(),
+                    wire_len,
+                    payload_len,
+                    decode_start.elapsed(),
+                );
                 Ok(val)
             }
             None => Err(Error::Err("missing packet".to_owned())),
@@ -1362,8 +1802,8 @@ impl Conn {
     pub fn enable_encyption(&mut self, key: &[u8]) {
         let read_cipher = Aes128Cfb::new_from_slices(key, key).unwrap();
         let write_cipher = Aes128Cfb::new_from_slices(key, key).unwrap();
-        self.read_cipher.lock().unwrap().replace(read_cipher);
-        self.write_cipher.lock().unwrap().replace(write_cipher);
+        self.reader.lock().unwrap().enable_encryption(read_cipher);
+        self.writer.lock().unwrap().enable_encryption(write_cipher);
     }
 
     pub fn set_compression(&mut self, threshold: i32) {
@@ -1371,7 +1811,9 @@ impl Conn {
     }
 
     pub fn close(&self) {
-        self.stream.shutdown(Shutdown::Both).unwrap();
+        // Lets the writer thread finish once it drained the queue
+        self.queue.lock().unwrap().take();
+        self.stream.close().unwrap();
     }
 
     pub fn do_status(mut self) -> Result<(Status, Duration), Error> {
@@ -1505,6 +1947,76 @@ impl Conn {
         ))
     }
 
+    /// Pings a server which predates the netty rewrite (1.6 and older).
+    ///
+    /// This sends the 1.6 `MC|PingHost` request, which older servers
+    /// simply ignore after reading the leading 0xFE 0x01, so the reply
+    /// is understood by every legacy server.
+    pub fn do_legacy_status(mut self) -> Result<(Status, Duration), Error> {
+        // The protocol version of 1.6.4, the last legacy release
+        const LEGACY_PROTOCOL_VERSION: u8 = 78;
+
+        fn write_utf16(buf: &mut Vec<u8>, val: &str) -> Result<(), Error> {
+            let chars: Vec<u16> = val.encode_utf16().collect();
+            buf.write_u16::<BigEndian>(chars.len() as u16)?;
+            for c in chars {
+                buf.write_u16::<BigEndian>(c)?;
+            }
+            Ok(())
+        }
+
+        let mut data = vec![LEGACY_PROTOCOL_VERSION];
+        write_utf16(&mut data, &self.host)?;
+        data.write_i32::<BigEndian>(self.port as i32)?;
+
+        let mut request = vec![0xFE, 0x01, 0xFA];
+        write_utf16(&mut request, "MC|PingHost")?;
+        request.write_u16::<BigEndian>(data.len() as u16)?;
+        request.extend_from_slice(&data);
+
+        let start = Instant::now();
+        self.write_all(&request)?;
+
+        // The server answers with a disconnect packet holding the status
+        if self.read_u8()? != 0xFF {
+            return Err(Error::Err("Wrong packet".to_owned()));
+        }
+        let len = self.read_u16::<BigEndian>()? as usize;
+        let mut chars = Vec::with_capacity(len);
+        for _ in 0..len {
+            chars.push(self.read_u16::<BigEndian>()?);
+        }
+        let ping = start.elapsed();
+        let reply = String::from_utf16_lossy(&chars);
+
+        Ok((parse_legacy_status(&reply)?, ping))
+    }
+
+    /// Pings a server, falling back to the legacy ping for servers which
+    /// don't understand the modern status protocol.
+    pub fn ping(
+        target: &str,
+        protocol_version: i32,
+        proxy: Option<&proxy::Proxy>,
+    ) -> Result<(Status, Duration), Error> {
+        let err = match Conn::new_with_proxy(target, protocol_version, proxy)?.do_status() {
+            Ok(res) => return Ok(res),
+            Err(err) => err,
+        };
+        debug!(
+            "Status request to {} failed ({}), trying legacy ping",
+            target, err
+        );
+        // The failed attempt may have left the connection in any state,
+        // so start over with a fresh one.
+        Conn::new_with_proxy(target, protocol_version, proxy)
+            .and_then(|conn| conn.do_legacy_status())
+            .map_err(|legacy_err| {
+                debug!("Legacy ping to {} failed: {}", target, legacy_err);
+                err
+            })
+    }
+
     pub fn get_version(&self) -> Version {
         Version::from_id(self.protocol_version as u32)
     }
@@ -1558,6 +2070,61 @@ pub struct Status {
     pub fml_network_version: Option<i64>,
 }
 
+/// Parses the reply to a legacy ping.
+///
+/// Servers since 1.4 reply with `§1\0protocol\0version\0motd\0online\0max`,
+/// older ones with just `motd§online§max`.
+fn parse_legacy_status(reply: &str) -> Result<Status, Error> {
+    let invalid_status = || Error::Err("Invalid status".to_owned());
+    let parse_count = |val: &str| val.trim().parse::<i32>().map_err(|_| invalid_status());
+
+    let (version, motd, online, max) = if let Some(rest) = reply.strip_prefix("§1\0") {
+        let fields: Vec<&str> = rest.split('\0').collect();
+        if fields.len() != 5 {
+            return Err(invalid_status());
+        }
+        let protocol = fields[0].parse::<i32>().map_err(|_| invalid_status())?;
+        (
+            StatusVersion {
+                name: fields[1].to_owned(),
+                protocol,
+            },
+            fields[2],
+            fields[3],
+            fields[4],
+        )
+    } else {
+        // The MOTD may contain formatting codes itself, so take the counts
+        // from the end.
+        let mut fields = reply.rsplitn(3, '§');
+        let max = fields.next().ok_or_else(invalid_status)?;
+        let online = fields.next().ok_or_else(invalid_status)?;
+        let motd = fields.next().ok_or_else(invalid_status)?;
+        (
+            StatusVersion {
+                name: "Legacy".to_owned(),
+                protocol: 0,
+            },
+            motd,
+            online,
+            max,
+        )
+    };
+
+    Ok(Status {
+        version,
+        players: StatusPlayers {
+            max: parse_count(max)?,
+            online: parse_count(online)?,
+            sample: Vec::new(),
+        },
+        description: format::Component::from_legacy_str(motd, &format::Modifier::default()),
+        favicon: None,
+        forge_mods: vec![],
+        fml_network_version: None,
+    })
+}
+
 #[derive(Debug)]
 pub struct StatusVersion {
     pub name: String,
@@ -1579,32 +2146,17 @@ pub struct StatusPlayer {
 
 impl Read for Conn {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        match self.read_cipher.lock().unwrap().as_mut() {
-            Option::None => self.stream.read(buf),
-            Option::Some(cipher) => {
-                let ret = self.stream.read(buf)?;
-                cipher.decrypt(&mut buf[..ret]);
-
-                Ok(ret)
-            }
-        }
+        self.reader.lock().unwrap().read(&mut *self.stream, buf)
     }
 }
 
 impl Write for Conn {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        match self.write_cipher.lock().unwrap().as_mut() {
-            Option::None => self.stream.write(buf),
-            Option::Some(cipher) => {
-                let mut data = vec![0; buf.len()];
-                data[..buf.len()].clone_from_slice(buf);
-
-                cipher.encrypt(&mut data);
-
-                self.stream.write_all(&data)?;
-                Ok(buf.len())
-            }
-        }
+        self.writer
+            .lock()
+            .unwrap()
+            .write_raw(&mut *self.stream, buf)?;
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -1615,22 +2167,130 @@ impl Write for Conn {
 impl Clone for Conn {
     fn clone(&self) -> Self {
         Conn {
-            stream: self.stream.try_clone().unwrap(),
+            stream: self.stream.try_clone_boxed().unwrap(),
             host: self.host.clone(),
             port: self.port,
+            remote_addr: self.remote_addr,
             direction: self.direction,
             state: self.state,
             protocol_version: self.protocol_version,
-            read_cipher: self.read_cipher.clone(),
-            write_cipher: self.write_cipher.clone(),
+            reader: self.reader.clone(),
+            writer: self.writer.clone(),
+            queue: self.queue.clone(),
+            write_error: self.write_error.clone(),
             compression_threshold: self.compression_threshold,
-            send: self.send.clone(),
+            capture: self.capture.clone(),
+            replay: self.replay.clone(),
+            stats: self.stats.clone(),
         }
     }
 }
 
+/// The name of a packet struct without its module path
+fn packet_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 pub trait PacketType {
     fn packet_id(&self, protocol_version: i32) -> i32;
 
     fn write<W: io::Write>(&self, buf: &mut W) -> Result<(), Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::transport::MemoryStream;
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn connect_any_skips_dead_addresses() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let alive = listener.local_addr().unwrap();
+        // Closed again right away, so connecting to it is refused
+        let dead = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let (_, addr) = connect_any(&[dead, alive]).unwrap();
+        assert_eq!(addr, alive);
+        assert!(connect_any(&[dead]).is_err());
+    }
+
+    #[test]
+    fn legacy_status_replies() {
+        let status =
+            parse_legacy_status("§1\u{0}78\u{0}1.6.4\u{0}§aA server\u{0}3\u{0}20").unwrap();
+        assert_eq!(status.version.protocol, 78);
+        assert_eq!(status.version.name, "1.6.4");
+        assert_eq!(status.description.to_string(), "A server");
+        assert_eq!((status.players.online, status.players.max), (3, 20));
+
+        // The counts are taken from the end, the MOTD has codes of its own
+        let status = parse_legacy_status("§aHello §lworld§3§20").unwrap();
+        assert_eq!(status.version.protocol, 0);
+        assert_eq!(status.description.to_string(), "Hello world");
+        assert_eq!((status.players.online, status.players.max), (3, 20));
+
+        for reply in [
+            "",
+            "motd",
+            "motd§3",
+            "motd§3§lots",
+            "§1\u{0}78\u{0}1.6.4\u{0}motd\u{0}3",
+            "§1\u{0}new\u{0}1.6.4\u{0}motd\u{0}3\u{0}20",
+            "§1\u{0}78\u{0}1.6.4\u{0}motd\u{0}3\u{0}20\u{0}extra",
+        ] {
+            assert!(parse_legacy_status(reply).is_err(), "{:?}", reply);
+        }
+    }
+
+    fn legacy_reply(id: u8, len: u16, text: &str) -> Vec<u8> {
+        let mut reply = vec![id];
+        reply.write_u16::<BigEndian>(len).unwrap();
+        for c in text.encode_utf16() {
+            reply.write_u16::<BigEndian>(c).unwrap();
+        }
+        reply
+    }
+
+    /// Pings a fake server answering with `reply`, returns the result and
+    /// the start of the request
+    fn legacy_ping(reply: Vec<u8>) -> (Result<(Status, Duration), Error>, [u8; 3]) {
+        let (client, mut server) = MemoryStream::pair();
+        let server = thread::spawn(move || {
+            let mut request = [0; 3];
+            server.read_exact(&mut request).unwrap();
+            server.write_all(&reply).unwrap();
+            // Whatever was sent is all the server has to say
+            server.close().unwrap();
+            request
+        });
+        let conn = Conn::from_transport(
+            Box::new(client),
+            Direction::Serverbound,
+            "localhost",
+            25565,
+            78,
+        );
+        let result = conn.do_legacy_status();
+        (result, server.join().unwrap())
+    }
+
+    #[test]
+    fn legacy_ping_over_pipe() {
+        let text = "§1\u{0}78\u{0}1.6.4\u{0}Legacy\u{0}1\u{0}8";
+        let len = text.encode_utf16().count() as u16;
+        let (result, request) = legacy_ping(legacy_reply(0xFF, len, text));
+        let (status, _) = result.unwrap();
+        assert_eq!(status.version.name, "1.6.4");
+        assert_eq!(status.players.max, 8);
+        assert_eq!(request, [0xFE, 0x01, 0xFA]);
+
+        // Not a disconnect, and cut off in the middle of the text
+        assert!(legacy_ping(legacy_reply(0x02, 0, "")).0.is_err());
+        assert!(legacy_ping(legacy_reply(0xFF, 40, "§1")).0.is_err());
+        assert!(legacy_ping(vec![0xFF, 0x00]).0.is_err());
+    }
+}
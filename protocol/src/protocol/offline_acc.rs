@@ -1,4 +1,5 @@
 use crate::protocol::login::{Account, AccountImpl, AccountType};
+use crate::protocol::profile_key::ProfileKey;
 pub struct OfflineAccount {}
 
 impl AccountImpl for OfflineAccount {
@@ -29,4 +30,8 @@ impl AccountImpl for OfflineAccount {
     fn append_head_img_data(&self, _account: &mut Account) -> Result<(), super::Error> {
         Ok(())
     }
+
+    fn profile_key(&self, _account: &Account) -> Result<Option<ProfileKey>, super::Error> {
+        Ok(None)
+    }
 }
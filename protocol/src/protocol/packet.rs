@@ -81,6 +81,31 @@ state_packets!(
             packet ChatMessage {
                 field message: String =,
             }
+            /// 1.19 chat messages carry a signature made with the player's
+            /// profile key, which is empty for unsigned messages.
+            packet ChatMessage_Signed {
+                field message: String =,
+                /// Milliseconds since the unix epoch
+                field timestamp: i64 =,
+                field salt: i64 =,
+                field signature: LenPrefixedBytes<VarInt> =,
+                field signed_preview: bool =,
+            }
+            /// ChatCommand is sent instead of ChatMessage for messages
+            /// prefixed by '/' (without the slash) since 1.19.
+            packet ChatCommand {
+                field command: String =,
+                field timestamp: i64 =,
+                field salt: i64 =,
+                field argument_signatures: LenPrefixed<VarInt, packet::ArgumentSignature> =,
+                field signed_preview: bool =,
+            }
+            /// ChatPreviewServerbound asks the server to preview the passed
+            /// message as it would be decorated.
+            packet ChatPreviewServerbound {
+                field query_id: i32 =,
+                field message: String =,
+            }
             /// ClientStatus is sent to update the client's status
             packet ClientStatus {
                 field action_id: VarInt =,
@@ -97,6 +122,15 @@ state_packets!(
                 field displayed_skin_parts: u8 =,
                 field main_hand: VarInt =,
             }
+            packet ClientSettings_Filtering {
+                field locale: String =,
+                field view_distance: u8 =,
+                field chat_mode: VarInt =,
+                field chat_colors: bool =,
+                field displayed_skin_parts: u8 =,
+                field main_hand: VarInt =,
+                field disable_text_filtering: bool =,
+            }
             packet ClientSettings_u8 {
                 field locale: String =,
                 field view_distance: u8 =,
@@ -153,6 +187,26 @@ state_packets!(
                 field mode: u8 =,
                 field clicked_item: Option<item::Stack> =,
             }
+            /// ClickWindow_State is the 1.17.1 form of ClickWindow, the client reports
+            /// the slots it expects to have changed along with the last state id
+            /// it received.
+            packet ClickWindow_State {
+                field id: u8 =,
+                field state_id: VarInt =,
+                field slot: i16 =,
+                field button: u8 =,
+                field mode: VarInt =,
+                field changed_slots: LenPrefixed<VarInt, packet::ChangedSlot> =,
+                field carried_item: Option<item::Stack> =,
+            }
+            packet ClickWindow_ChangedSlots {
+                field id: u8 =,
+                field slot: i16 =,
+                field button: u8 =,
+                field mode: VarInt =,
+                field changed_slots: LenPrefixed<VarInt, packet::ChangedSlot> =,
+                field carried_item: Option<item::Stack> =,
+            }
             /// CloseWindow is sent when the client closes a window.
             packet CloseWindow {
                 field id: u8 =,
@@ -173,6 +227,12 @@ state_packets!(
                 field is_signing: bool =,
                 field hand: VarInt =,
             }
+            packet EditBook_Pages {
+                field slot: VarInt =,
+                field entries: LenPrefixed<VarInt, String> =,
+                field has_title: bool =,
+                field title: Option<String> = when(|p: &EditBook_Pages| p.has_title),
+            }
             packet QueryEntityNBT {
                 field transaction_id: VarInt =,
                 field entity_id: VarInt =,
@@ -225,6 +285,10 @@ state_packets!(
             packet KeepAliveServerbound_i32 {
                 field id: i32 =,
             }
+            /// Pong is the reply to a Ping from the server.
+            packet Pong {
+                field id: i32 =,
+            }
             packet LockDifficulty {
                 field locked: bool =,
             }
@@ -310,6 +374,12 @@ state_packets!(
                 field location: Position =,
                 field face: u8 =,
             }
+            packet PlayerDigging_Sequence {
+                field status: VarInt =,
+                field location: Position =,
+                field face: u8 =,
+                field sequence: VarInt =,
+            }
             packet PlayerDigging_u8 {
                 field status: u8 =,
                 field location: Position =,
@@ -518,11 +588,25 @@ state_packets!(
                 field cursor_z: f32 =,
                 field inside_block: bool =, //1.14 added insideblock
             }
+            packet PlayerBlockPlacement_Sequence {
+                field hand: VarInt =,
+                field location: Position =,
+                field face: VarInt =,
+                field cursor_x: f32 =,
+                field cursor_y: f32 =,
+                field cursor_z: f32 =,
+                field inside_block: bool =,
+                field sequence: VarInt =,
+            }
 
             /// UseItem is sent when the client tries to use an item.
             packet UseItem {
                 field hand: VarInt =,
             }
+            packet UseItem_Sequence {
+                field hand: VarInt =,
+                field sequence: VarInt =,
+            }
         }
         clientbound Clientbound {
             /// SpawnObject is used to spawn an object or vehicle into the world when it
@@ -582,6 +666,23 @@ state_packets!(
                 field velocity_y: i16 =,
                 field velocity_z: i16 =,
             }
+            /// 1.19 spawns all entities (including mobs and paintings) with
+            /// this packet.
+            packet SpawnObject_HeadYaw {
+                field entity_id: VarInt =,
+                field uuid: UUID =,
+                field ty: VarInt =,
+                field x: f64 =,
+                field y: f64 =,
+                field z: f64 =,
+                field pitch: i8 =,
+                field yaw: i8 =,
+                field head_yaw: i8 =,
+                field data: VarInt =,
+                field velocity_x: i16 =,
+                field velocity_y: i16 =,
+                field velocity_z: i16 =,
+            }
             /// SpawnExperienceOrb spawns a single experience orb into the world when
             /// it is in range of the client. The count controls the amount of experience
             /// gained when collected.
@@ -776,6 +877,15 @@ state_packets!(
                 field current_item: u16 =,
                 field metadata: types::Metadata =,
             }
+            /// SculkVibrationSignal shows a vibration travelling towards a sculk
+            /// sensor.
+            packet SculkVibrationSignal {
+                field source_position: Position =,
+                field destination_type: String =,
+                field destination_position: Option<Position> = when(|p: &SculkVibrationSignal| p.destination_type == "minecraft:block"),
+                field destination_entity_id: Option<VarInt> = when(|p: &SculkVibrationSignal| p.destination_type == "minecraft:entity"),
+                field arrival_ticks: VarInt =,
+            }
 
             /// Animation is sent by the server to play an animation on a specific entity.
             packet Animation {
@@ -807,6 +917,13 @@ state_packets!(
                 field action: u8 =,
                 field nbt: Option<nbt::NamedTag> =,
             }
+            /// UpdateBlockEntity_VarInt identifies the block entity by its
+            /// registry id instead of an update action.
+            packet UpdateBlockEntity_VarInt {
+                field location: Position =,
+                field ty: VarInt =,
+                field nbt: Option<nbt::NamedTag> =,
+            }
             packet UpdateBlockEntity_Data {
                 field x: i32 =,
                 field y: i16 =,
@@ -890,6 +1007,49 @@ state_packets!(
             packet ServerMessage_NoPosition {
                 field message: format::Component =,
             }
+            /// PlayerChatMessage is a (possibly signed) chat message sent by a
+            /// player, the server may replace its content with an unsigned,
+            /// decorated variant.
+            packet PlayerChatMessage {
+                field signed_content: format::Component =,
+                field has_unsigned_content: bool =,
+                field unsigned_content: format::Component = when(|p: &PlayerChatMessage| p.has_unsigned_content),
+                /// Index into the chat type registry of the dimension codec
+                field message_type: VarInt =,
+                field sender: UUID =,
+                field sender_display_name: format::Component =,
+                field has_team_name: bool =,
+                field team_name: format::Component = when(|p: &PlayerChatMessage| p.has_team_name),
+                field timestamp: i64 =,
+                field salt: i64 =,
+                field signature: LenPrefixedBytes<VarInt> =,
+            }
+            /// SystemChatMessage is an unsigned message sent by the server itself.
+            packet SystemChatMessage {
+                field content: format::Component =,
+                /// Index into the chat type registry of the dimension codec
+                field message_type: VarInt =,
+            }
+            /// ChatPreviewClientbound answers a ChatPreviewServerbound query.
+            packet ChatPreviewClientbound {
+                field query_id: i32 =,
+                field has_message: bool =,
+                field message: format::Component = when(|p: &ChatPreviewClientbound| p.has_message),
+            }
+            /// SetDisplayChatPreview toggles chat previews for the client.
+            packet SetDisplayChatPreview {
+                field enabled: bool =,
+            }
+            /// ServerData is sent after joining, as well as when the
+            /// server's MOTD or icon change.
+            packet ServerData {
+                field has_motd: bool =,
+                field motd: format::Component = when(|p: &ServerData| p.has_motd),
+                field has_icon: bool =,
+                /// Base64 encoded PNG
+                field icon: String = when(|p: &ServerData| p.has_icon),
+                field previews_chat: bool =,
+            }
             /// MultiBlockChange is used to update a batch of blocks in a single packet.
             packet MultiBlockChange_Packed {
                 field chunk_section_pos: u64 =,
@@ -953,6 +1113,12 @@ state_packets!(
                 field id: u8 =,
                 field items: LenPrefixed<i16, Option<item::Stack>> =,
             }
+            packet WindowItems_StateCarry {
+                field id: u8 =,
+                field state_id: VarInt =,
+                field items: LenPrefixed<VarInt, Option<item::Stack>> =,
+                field carried_item: Option<item::Stack> =,
+            }
             /// WindowProperty changes the value of a property of a window. Properties
             /// vary depending on the window type.
             packet WindowProperty {
@@ -966,6 +1132,12 @@ state_packets!(
                 field slot: i16 =,
                 field item: Option<item::Stack> =,
             }
+            packet WindowSetSlot_State {
+                field id: i8 =,
+                field state_id: VarInt =,
+                field slot: i16 =,
+                field item: Option<item::Stack> =,
+            }
             /// SetCooldown disables a set item (by id) for the set number of ticks
             packet SetCooldown {
                 field item_id: VarInt =,
@@ -992,6 +1164,16 @@ state_packets!(
                 field volume: f32 =,
                 field pitch: f32 =,
             }
+            packet NamedSoundEffect_Seed {
+                field name: String =,
+                field category: VarInt =,
+                field x: i32 =,
+                field y: i32 =,
+                field z: i32 =,
+                field volume: f32 =,
+                field pitch: f32 =,
+                field seed: i64 =,
+            }
             packet NamedSoundEffect_u8 {
                 field name: String =,
                 field category: VarInt =,
@@ -1031,6 +1213,16 @@ state_packets!(
                 field velocity_y: f32 =,
                 field velocity_z: f32 =,
             }
+            packet Explosion_VarInt {
+                field x: f32 =,
+                field y: f32 =,
+                field z: f32 =,
+                field radius: f32 =,
+                field records: LenPrefixed<VarInt, packet::ExplosionRecord> =,
+                field velocity_x: f32 =,
+                field velocity_y: f32 =,
+                field velocity_z: f32 =,
+            }
             /// ChunkUnload tells the client to unload the chunk at the specified
             /// position.
             packet ChunkUnload {
@@ -1060,6 +1252,38 @@ state_packets!(
             packet KeepAliveClientbound_i32 {
                 field id: i32 =,
             }
+            /// Ping is sent by the server to check the client is responding, the
+            /// client should reply with a Pong carrying the same id.
+            packet Ping {
+                field id: i32 =,
+            }
+            /// ChunkData_Biomes3D_Bitmasks always sends a full chunk, the sections
+            /// present are given by a BitSet instead of a single int.
+            packet ChunkData_Biomes3D_Bitmasks {
+                field chunk_x: i32 =,
+                field chunk_z: i32 =,
+                field bitmask: BitSet =,
+                field heightmaps: Option<nbt::NamedTag> =,
+                field biomes: LenPrefixed<VarInt, VarInt> =,
+                field data: LenPrefixedBytes<VarInt> =,
+                field block_entities: LenPrefixed<VarInt, Option<nbt::NamedTag>> =,
+            }
+            /// ChunkData_AndLight sends a full chunk together with its light, the
+            /// biomes are part of each section.
+            packet ChunkData_AndLight {
+                field chunk_x: i32 =,
+                field chunk_z: i32 =,
+                field heightmaps: Option<nbt::NamedTag> =,
+                field data: LenPrefixedBytes<VarInt> =,
+                field block_entities: LenPrefixed<VarInt, packet::ChunkBlockEntity> =,
+                field trust_edges: bool =,
+                field sky_light_mask: BitSet =,
+                field block_light_mask: BitSet =,
+                field empty_sky_light_mask: BitSet =,
+                field empty_block_light_mask: BitSet =,
+                field sky_light_arrays: LenPrefixed<VarInt, LenPrefixedBytes<VarInt>> =,
+                field block_light_arrays: LenPrefixed<VarInt, LenPrefixedBytes<VarInt>> =,
+            }
             /// ChunkData sends or updates a single chunk on the client. If New is set
             /// then biome data should be sent too.
             packet ChunkData_Biomes3D_VarInt {
@@ -1162,6 +1386,62 @@ state_packets!(
             }
             /// Particle spawns particles at the target location with the various
             /// modifiers.
+            packet Particle_VarInt_Vibration {
+                field particle_id: VarInt =,
+                field long_distance: bool =,
+                field x: f64 =,
+                field y: f64 =,
+                field z: f64 =,
+                field offset_x: f32 =,
+                field offset_y: f32 =,
+                field offset_z: f32 =,
+                field speed: f32 =,
+                field count: i32 =,
+                field block_state: VarInt = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 2 || p.particle_id.0 == 3 || p.particle_id.0 == 25),
+                field red: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 14 || p.particle_id.0 == 15),
+                field green: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 14 || p.particle_id.0 == 15),
+                field blue: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 14 || p.particle_id.0 == 15),
+                field scale: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 14 || p.particle_id.0 == 15),
+                field to_red: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 15),
+                field to_green: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 15),
+                field to_blue: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 15),
+                field roll: f32 = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 30),
+                field item: Option<item::Stack> = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 39),
+                field destination_type: String = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 40),
+                field destination_position: Option<Position> = when(|p: &Particle_VarInt_Vibration| p.destination_type == "minecraft:block"),
+                field destination_entity_id: Option<VarInt> = when(|p: &Particle_VarInt_Vibration| p.destination_type == "minecraft:entity"),
+                field destination_eye_height: f32 = when(|p: &Particle_VarInt_Vibration| p.destination_type == "minecraft:entity"),
+                field arrival_ticks: VarInt = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 40),
+                field delay: VarInt = when(|p: &Particle_VarInt_Vibration| p.particle_id.0 == 93),
+            }
+            packet Particle_f64_Vibration {
+                field particle_id: i32 =,
+                field long_distance: bool =,
+                field x: f64 =,
+                field y: f64 =,
+                field z: f64 =,
+                field offset_x: f32 =,
+                field offset_y: f32 =,
+                field offset_z: f32 =,
+                field speed: f32 =,
+                field count: i32 =,
+                field block_state: VarInt = when(|p: &Particle_f64_Vibration| p.particle_id == 4 || p.particle_id == 25),
+                field red: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 15 || p.particle_id == 16),
+                field green: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 15 || p.particle_id == 16),
+                field blue: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 15 || p.particle_id == 16),
+                field scale: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 15 || p.particle_id == 16),
+                field to_red: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 16),
+                field to_green: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 16),
+                field to_blue: f32 = when(|p: &Particle_f64_Vibration| p.particle_id == 16),
+                field item: Option<item::Stack> = when(|p: &Particle_f64_Vibration| p.particle_id == 36),
+                field origin_x: f64 = when(|p: &Particle_f64_Vibration| p.particle_id == 37),
+                field origin_y: f64 = when(|p: &Particle_f64_Vibration| p.particle_id == 37),
+                field origin_z: f64 = when(|p: &Particle_f64_Vibration| p.particle_id == 37),
+                field destination_type: String = when(|p: &Particle_f64_Vibration| p.particle_id == 37),
+                field destination_position: Option<Position> = when(|p: &Particle_f64_Vibration| p.destination_type == "minecraft:block"),
+                field destination_entity_id: Option<VarInt> = when(|p: &Particle_f64_Vibration| p.destination_type == "minecraft:entity"),
+                field arrival_ticks: VarInt = when(|p: &Particle_f64_Vibration| p.particle_id == 37),
+            }
             packet Particle_f64 {
                 field particle_id: i32 =,
                 field long_distance: bool =,
@@ -1276,6 +1556,80 @@ state_packets!(
                 /// Whether the world is a superflat world
                 field is_flat: bool =,
             }
+            packet JoinGame_DeathLocation {
+                /// The entity id the client will be referenced by
+                field entity_id: i32 =,
+                /// Whether hardcore mode is enabled
+                field is_hardcore: bool =,
+                /// The starting gamemode of the client
+                field gamemode: u8 =,
+                /// The previous gamemode of the client
+                field previous_gamemode: u8 =,
+                /// Identifiers for all worlds on the server
+                field world_names: LenPrefixed<VarInt, String> =,
+                /// Represents the registries (dimension types, biomes and chat types)
+                field dimension_codec: Option<nbt::NamedTag> =,
+                /// Name of the dimension type in the codec
+                field dimension_type: String =,
+                /// The world being spawned into
+                field world_name: String =,
+                /// Truncated SHA-256 hash of world's seed
+                field hashed_seed: i64 =,
+                /// The max number of players on the server
+                field max_players: VarInt =,
+                /// The render distance (2-32)
+                field view_distance: VarInt =,
+                /// The distance in which entities are ticked (2-32)
+                field simulation_distance: VarInt =,
+                /// Whether the client should reduce the amount of debug
+                /// information it displays in F3 mode
+                field reduced_debug_info: bool =,
+                /// Whether to prompt or immediately respawn
+                field enable_respawn_screen: bool =,
+                /// Whether the world is in debug mode
+                field is_debug: bool =,
+                /// Whether the world is a superflat world
+                field is_flat: bool =,
+                /// Where the player died last, used by recovery compasses
+                field has_death_location: bool =,
+                field death_dimension_name: String = when(|p: &JoinGame_DeathLocation| p.has_death_location),
+                field death_location: Position = when(|p: &JoinGame_DeathLocation| p.has_death_location),
+            }
+            packet JoinGame_WorldNames_IsHard_SimDist {
+                /// The entity id the client will be referenced by
+                field entity_id: i32 =,
+                /// Whether hardcore mode is enabled
+                field is_hardcore: bool =,
+                /// The starting gamemode of the client
+                field gamemode: u8 =,
+                /// The previous gamemode of the client
+                field previous_gamemode: u8 =,
+                /// Identifiers for all worlds on the server
+                field world_names: LenPrefixed<VarInt, String> =,
+                /// Represents a dimension registry
+                field dimension_codec: Option<nbt::NamedTag> =,
+                /// The dimension the client is starting in
+                field dimension: Option<nbt::NamedTag> =,
+                /// The world being spawned into
+                field world_name: String =,
+                /// Truncated SHA-256 hash of world's seed
+                field hashed_seed: i64 =,
+                /// The max number of players on the server
+                field max_players: VarInt =,
+                /// The render distance (2-32)
+                field view_distance: VarInt =,
+                /// The distance in which entities are ticked (2-32)
+                field simulation_distance: VarInt =,
+                /// Whether the client should reduce the amount of debug
+                /// information it displays in F3 mode
+                field reduced_debug_info: bool =,
+                /// Whether to prompt or immediately respawn
+                field enable_respawn_screen: bool =,
+                /// Whether the world is in debug mode
+                field is_debug: bool =,
+                /// Whether the world is a superflat world
+                field is_flat: bool =,
+            }
             packet JoinGame_WorldNames {
                 /// The entity id the client will be referenced by
                 field entity_id: i32 =,
@@ -1388,6 +1742,18 @@ state_packets!(
                 field level_type: String =,
             }
             /// Maps updates a single map's contents
+            packet Maps_OptionalIcons {
+                field item_damage: VarInt =,
+                field scale: i8 =,
+                field locked: bool =,
+                field tracking_position: bool =,
+                field icons: Option<LenPrefixed<VarInt, packet::MapIcon>> = when(|p: &Maps_OptionalIcons| p.tracking_position),
+                field columns: u8 =,
+                field rows: Option<u8> = when(|p: &Maps_OptionalIcons| p.columns > 0),
+                field x: Option<u8> = when(|p: &Maps_OptionalIcons| p.columns > 0),
+                field z: Option<u8> = when(|p: &Maps_OptionalIcons| p.columns > 0),
+                field data: Option<LenPrefixedBytes<VarInt>> = when(|p: &Maps_OptionalIcons| p.columns > 0),
+            }
             packet Maps {
                 field item_damage: VarInt =,
                 field scale: i8 =,
@@ -1540,6 +1906,18 @@ state_packets!(
                 field entity_id: Option<i32> = when(|p: &CombatEvent| p.event.0 == 1 || p.event.0 == 2),
                 field message: Option<format::Component> = when(|p: &CombatEvent| p.event.0 == 2),
             }
+            packet EndCombatEvent {
+                field duration: VarInt =,
+                field entity_id: i32 =,
+            }
+            packet EnterCombatEvent {
+                field empty: () =,
+            }
+            packet DeathCombatEvent {
+                field player_id: VarInt =,
+                field entity_id: i32 =,
+                field message: format::Component =,
+            }
             /// PlayerInfo is sent by the server for every player connected to the server
             /// to provide skin and username information as well as ping and gamemode info.
             packet PlayerInfo {
@@ -1571,6 +1949,16 @@ state_packets!(
                 field flags: u8 =,
                 field teleport_id: VarInt =,
             }
+            packet TeleportPlayer_WithDismount {
+                field x: f64 =,
+                field y: f64 =,
+                field z: f64 =,
+                field yaw: f32 =,
+                field pitch: f32 =,
+                field flags: u8 =,
+                field teleport_id: VarInt =,
+                field dismount_vehicle: bool =,
+            }
             packet TeleportPlayer_NoConfirm {
                 field x: f64 =,
                 field y: f64 =,
@@ -1634,6 +2022,9 @@ state_packets!(
             packet EntityDestroy_u8 {
                 field entity_ids: LenPrefixed<u8, i32> =,
             }
+            packet EntityDestroy_Single {
+                field entity_id: VarInt =,
+            }
             /// EntityRemoveEffect removes an effect from an entity.
             packet EntityRemoveEffect {
                 field entity_id: VarInt =,
@@ -1650,6 +2041,13 @@ state_packets!(
                 field url: String =,
                 field hash: String =,
             }
+            packet ResourcePackSend_Prompt {
+                field url: String =,
+                field hash: String =,
+                field forced: bool =,
+                field has_prompt: bool =,
+                field prompt: Option<format::Component> = when(|p: &ResourcePackSend_Prompt| p.has_prompt),
+            }
             /// Respawn is sent to respawn the player after death or when they move worlds.
             packet Respawn_Gamemode {
                 field dimension: i32 =,
@@ -1664,6 +2062,19 @@ state_packets!(
                 field gamemode: u8 =,
                 field level_type: String =,
             }
+            packet Respawn_DeathLocation {
+                field dimension_type: String =,
+                field dimension_name: String =,
+                field hashed_seed: i64 =,
+                field gamemode: u8 =,
+                field previous_gamemode: u8 =,
+                field is_debug: bool =,
+                field is_flat: bool =,
+                field copy_metadata: bool =,
+                field has_death_location: bool =,
+                field death_dimension_name: String = when(|p: &Respawn_DeathLocation| p.has_death_location),
+                field death_location: Position = when(|p: &Respawn_DeathLocation| p.has_death_location),
+            }
             packet Respawn_NBT {
                 field dimension: Option<nbt::NamedTag> =,
                 field world_name: String =,
@@ -1718,6 +2129,34 @@ state_packets!(
                 field warning_time: Option<VarInt> = when(|p: &WorldBorder| p.action.0 == 3 || p.action.0 == 4),
                 field warning_blocks: Option<VarInt> = when(|p: &WorldBorder| p.action.0 == 3 || p.action.0 == 5),
             }
+            packet WorldBorderInit {
+                field x: f64 =,
+                field z: f64 =,
+                field old_diameter: f64 =,
+                field new_diameter: f64 =,
+                field speed: VarLong =,
+                field portal_boundary: VarInt =,
+                field warning_blocks: VarInt =,
+                field warning_time: VarInt =,
+            }
+            packet WorldBorderCenter {
+                field x: f64 =,
+                field z: f64 =,
+            }
+            packet WorldBorderLerpSize {
+                field old_diameter: f64 =,
+                field new_diameter: f64 =,
+                field speed: VarLong =,
+            }
+            packet WorldBorderSize {
+                field diameter: f64 =,
+            }
+            packet WorldBorderWarningDelay {
+                field warning_time: VarInt =,
+            }
+            packet WorldBorderWarningReach {
+                field warning_blocks: VarInt =,
+            }
             /// Camera causes the client to spectate the entity with the passed id.
             /// Use the player's id to de-spectate.
             packet Camera {
@@ -1736,6 +2175,11 @@ state_packets!(
             packet UpdateViewDistance {
                 field view_distance: VarInt =,
             }
+            /// UpdateSimulationDistance is sent when the server changes the
+            /// distance in which entities are ticked.
+            packet UpdateSimulationDistance {
+                field simulation_distance: VarInt =,
+            }
             /// ScoreboardDisplay is used to set the display position of a scoreboard.
             packet ScoreboardDisplay {
                 field position: u8 =,
@@ -1852,7 +2296,6 @@ state_packets!(
             packet Teams_u8 {
                 field name: String =,
                 field mode: u8 =,
-                field data: Vec<u8> =,
                 field display_name: Option<String> = when(|p: &Teams_u8| p.mode == 0 || p.mode == 2),
                 field prefix: Option<String> = when(|p: &Teams_u8| p.mode == 0 || p.mode == 2),
                 field suffix: Option<String> = when(|p: &Teams_u8| p.mode == 0 || p.mode == 2),
@@ -1862,6 +2305,17 @@ state_packets!(
                 field color: Option<i8> = when(|p: &Teams_u8| p.mode == 0 || p.mode == 2),
                 field players: Option<LenPrefixed<VarInt, String>> = when(|p: &Teams_u8| p.mode == 0 || p.mode == 3 || p.mode == 4),
             }
+            packet Teams_NoCollision {
+                field name: String =,
+                field mode: u8 =,
+                field display_name: Option<String> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field prefix: Option<String> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field suffix: Option<String> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field flags: Option<u8> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field name_tag_visibility: Option<String> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field color: Option<i8> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 2),
+                field players: Option<LenPrefixed<VarInt, String>> = when(|p: &Teams_NoCollision| p.mode == 0 || p.mode == 3 || p.mode == 4),
+            }
             packet Teams_NoVisColor {
                 field name: String =,
                 field mode: u8 =,
@@ -1869,7 +2323,7 @@ state_packets!(
                 field prefix: Option<String> = when(|p: &Teams_NoVisColor| p.mode == 0 || p.mode == 2),
                 field suffix: Option<String> = when(|p: &Teams_NoVisColor| p.mode == 0 || p.mode == 2),
                 field flags: Option<u8> = when(|p: &Teams_NoVisColor| p.mode == 0 || p.mode == 2),
-                field players: Option<LenPrefixed<VarInt, String>> = when(|p: &Teams_NoVisColor| p.mode == 0 || p.mode == 3 || p.mode == 4),
+                field players: Option<LenPrefixed<i16, String>> = when(|p: &Teams_NoVisColor| p.mode == 0 || p.mode == 3 || p.mode == 4),
             }
             /// UpdateScore is used to update or remove an item from a scoreboard
             /// objective.
@@ -1890,6 +2344,10 @@ state_packets!(
             packet SpawnPosition {
                 field location: Position =,
             }
+            packet SpawnPosition_Angle {
+                field location: Position =,
+                field angle: f32 =,
+            }
             packet SpawnPosition_i32 {
                 field x: i32 =,
                 field y: i32 =,
@@ -1934,6 +2392,25 @@ state_packets!(
                 field fade_stay: Option<format::Component> = when(|p: &Title_notext_component| p.action.0 == 2),
                 field fade_out: Option<format::Component> = when(|p: &Title_notext_component| p.action.0 == 2),
             }
+            /// ActionBar, SetTitleText, SetTitleSubtitle, SetTitleTimes and
+            /// ClearTitles replace the single Title packet since 1.17.
+            packet ActionBar {
+                field text: String =,
+            }
+            packet SetTitleText {
+                field text: format::Component =,
+            }
+            packet SetTitleSubtitle {
+                field text: format::Component =,
+            }
+            packet SetTitleTimes {
+                field fade_in: i32 =,
+                field fade_stay: i32 =,
+                field fade_out: i32 =,
+            }
+            packet ClearTitles {
+                field reset: bool =,
+            }
             /// UpdateSign sets or changes the text on a sign.
             packet UpdateSign {
                 field location: Position =,
@@ -1961,6 +2438,16 @@ state_packets!(
                 field volume: f32 =,
                 field pitch: f32 =,
             }
+            packet SoundEffect_Seed {
+                field name: VarInt =,
+                field category: VarInt =,
+                field x: i32 =,
+                field y: i32 =,
+                field z: i32 =,
+                field volume: f32 =,
+                field pitch: f32 =,
+                field seed: i64 =,
+            }
             packet SoundEffect_u8 {
                 field name: VarInt =,
                 field category: VarInt =,
@@ -1978,6 +2465,14 @@ state_packets!(
                 field volume: f32 =,
                 field pitch: f32 =,
             }
+            packet EntitySoundEffect_Seed {
+                field sound_id: VarInt =,
+                field sound_category: VarInt =,
+                field entity_id: VarInt =,
+                field volume: f32 =,
+                field pitch: f32 =,
+                field seed: i64 =,
+            }
             /// PlayerListHeaderFooter updates the header/footer of the player list.
             packet PlayerListHeaderFooter {
                 field header: format::Component =,
@@ -2045,6 +2540,10 @@ state_packets!(
                 field entity_id: i32 =,
                 field properties: LenPrefixed<i32, packet::EntityProperty_i16> =,
             }
+            packet EntityProperties_VarIntLen {
+                field entity_id: VarInt =,
+                field properties: LenPrefixed<VarInt, packet::EntityProperty> =,
+            }
             /// EntityEffect applies a status effect to an entity for a given duration.
             packet EntityEffect {
                 field entity_id: VarInt =,
@@ -2053,6 +2552,15 @@ state_packets!(
                 field duration: VarInt =,
                 field hide_particles: bool =,
             }
+            packet EntityEffect_FactorData {
+                field entity_id: VarInt =,
+                field effect_id: VarInt =,
+                field amplifier: i8 =,
+                field duration: VarInt =,
+                field hide_particles: bool =,
+                field has_factor_data: bool =,
+                field factor_codec: Option<nbt::NamedTag> = when(|p: &EntityEffect_FactorData| p.has_factor_data),
+            }
             packet EntityEffect_i32 {
                 field entity_id: i32 =,
                 field effect_id: i8 =,
@@ -2073,12 +2581,20 @@ state_packets!(
                 field fluid_tags: LenPrefixed<VarInt, packet::Tags> =,
                 field entity_tags: LenPrefixed<VarInt, packet::Tags> =,
             }
+            packet TagsWithTypes {
+                field tags: LenPrefixed<VarInt, packet::TagGroup> =,
+            }
             packet AcknowledgePlayerDigging {
                 field location: Position =,
                 field block: VarInt =,
                 field status: VarInt =,
                 field successful: bool =,
             }
+            /// AcknowledgeBlockChange replaces AcknowledgePlayerDigging in 1.19,
+            /// confirming every block interaction up to the sequence number.
+            packet AcknowledgeBlockChange {
+                field sequence: VarInt =,
+            }
             packet UpdateLight_WithTrust {
                 field chunk_x: VarInt =,
                 field chunk_z: VarInt =,
@@ -2098,8 +2614,19 @@ state_packets!(
                 field empty_sky_light_mask: VarLong =,
                 field light_arrays: Vec<u8> =,
             }
-            packet TradeList_WithoutRestock {
-                field id: VarInt =,
+            packet UpdateLight_BitSet {
+                field chunk_x: VarInt =,
+                field chunk_z: VarInt =,
+                field trust_edges: bool =,
+                field sky_light_mask: BitSet =,
+                field block_light_mask: BitSet =,
+                field empty_sky_light_mask: BitSet =,
+                field empty_block_light_mask: BitSet =,
+                field sky_light_arrays: LenPrefixed<VarInt, LenPrefixedBytes<VarInt>> =,
+                field block_light_arrays: LenPrefixed<VarInt, LenPrefixedBytes<VarInt>> =,
+            }
+            packet TradeList_WithoutRestock {
+                field id: VarInt =,
                 field trades: LenPrefixed<u8, packet::Trade> =,
                 field villager_level: VarInt =,
                 field experience: VarInt =,
@@ -2126,6 +2653,14 @@ state_packets!(
             packet LoginStart {
                 field username: String =,
             }
+            /// 1.19 adds the profile public key used to sign chat messages
+            packet LoginStart_Sig {
+                field username: String =,
+                field has_sig_data: bool =,
+                field expires_at: i64 = when(|p: &LoginStart_Sig| p.has_sig_data),
+                field public_key: LenPrefixedBytes<VarInt> = when(|p: &LoginStart_Sig| p.has_sig_data),
+                field key_signature: LenPrefixedBytes<VarInt> = when(|p: &LoginStart_Sig| p.has_sig_data),
+            }
             /// EncryptionResponse is sent as a reply to EncryptionRequest. All
             /// packets following this one must be encrypted with AES/CFB8
             /// encryption.
@@ -2141,6 +2676,15 @@ state_packets!(
                 field shared_secret: LenPrefixedBytes<i16> =,
                 field verify_token: LenPrefixedBytes<i16> =,
             }
+            /// 1.19 lets clients with a profile key sign the verify token
+            /// instead of encrypting it
+            packet EncryptionResponse_Sig {
+                field shared_secret: LenPrefixedBytes<VarInt> =,
+                field has_verify_token: bool =,
+                field verify_token: LenPrefixedBytes<VarInt> = when(|p: &EncryptionResponse_Sig| p.has_verify_token),
+                field salt: i64 = when(|p: &EncryptionResponse_Sig| !p.has_verify_token),
+                field message_signature: LenPrefixedBytes<VarInt> = when(|p: &EncryptionResponse_Sig| !p.has_verify_token),
+            }
             packet LoginPluginResponse {
                 field message_id: VarInt =,
                 field successful: bool =,
@@ -2184,6 +2728,11 @@ state_packets!(
                 field uuid: UUID =,
                 field username: String =,
             }
+            packet LoginSuccess_Properties {
+                field uuid: UUID =,
+                field username: String =,
+                field properties: LenPrefixed<VarInt, packet::PlayerProperty> =,
+            }
             /// SetInitialCompression sets the compression threshold during the
             /// login state.
             packet SetInitialCompression {
@@ -2341,6 +2890,56 @@ impl Serializable for ChunkMeta {
     }
 }
 
+/// ChunkBlockEntity is a block entity sent as part of a chunk, positioned
+/// relative to the chunk.
+#[derive(Debug, Default)]
+pub struct ChunkBlockEntity {
+    pub packed_xz: u8,
+    pub y: i16,
+    pub ty: VarInt,
+    pub data: Option<nbt::NamedTag>,
+}
+
+impl Serializable for ChunkBlockEntity {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
+        Ok(ChunkBlockEntity {
+            packed_xz: Serializable::read_from(buf)?,
+            y: Serializable::read_from(buf)?,
+            ty: Serializable::read_from(buf)?,
+            data: Serializable::read_from(buf)?,
+        })
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.packed_xz.write_to(buf)?;
+        self.y.write_to(buf)?;
+        self.ty.write_to(buf)?;
+        self.data.write_to(buf)
+    }
+}
+
+/// ChangedSlot is a slot the client expects to change when clicking in a
+/// window.
+#[derive(Debug, Default)]
+pub struct ChangedSlot {
+    pub slot: i16,
+    pub item: Option<item::Stack>,
+}
+
+impl Serializable for ChangedSlot {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
+        Ok(ChangedSlot {
+            slot: Serializable::read_from(buf)?,
+            item: Serializable::read_from(buf)?,
+        })
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.slot.write_to(buf)?;
+        self.item.write_to(buf)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ExplosionRecord {
     pub x: i8,
@@ -2433,7 +3032,9 @@ impl Serializable for Advancement {
 
     fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
         self.id.write_to(buf)?;
+        (self.parent_id.is_some() as u8).write_to(buf)?;
         self.parent_id.write_to(buf)?;
+        (self.display_data.is_some() as u8).write_to(buf)?;
         self.display_data.write_to(buf)?;
         self.criteria.write_to(buf)?;
         self.requirements.write_to(buf)
@@ -2537,6 +3138,7 @@ impl Serializable for CriterionProgress {
 
     fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
         self.id.write_to(buf)?;
+        (self.date_of_achieving.is_some() as u8).write_to(buf)?;
         self.date_of_achieving.write_to(buf)
     }
 }
@@ -2562,11 +3164,20 @@ impl Serializable for EntityEquipment {
 }
 
 // Top-bit terminated array of EntityEquipment
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct EntityEquipments {
     pub equipments: Vec<EntityEquipment>,
 }
 
+impl Default for EntityEquipments {
+    // The array can't be empty, so default to an empty main hand
+    fn default() -> Self {
+        EntityEquipments {
+            equipments: vec![EntityEquipment::default()],
+        }
+    }
+}
+
 impl Serializable for EntityEquipments {
     fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
         let mut equipments: Vec<EntityEquipment> = vec![];
@@ -2587,8 +3198,17 @@ impl Serializable for EntityEquipments {
         Ok(EntityEquipments { equipments })
     }
 
-    fn write_to<W: io::Write>(&self, _buf: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        if self.equipments.is_empty() {
+            return Err(Error::Err("Entity equipment can't be empty".to_owned()));
+        }
+        let last = self.equipments.len() - 1;
+        for (i, e) in self.equipments.iter().enumerate() {
+            let more = if i == last { 0 } else { 0x80 };
+            (e.slot & 0x7f | more).write_to(buf)?;
+            e.item.write_to(buf)?;
+        }
+        Ok(())
     }
 }
 
@@ -2706,6 +3326,13 @@ impl Serializable for PlayerInfoData {
                             }
                         },
                     };
+                    // 1.19 appends the player's profile public key, chat
+                    // signatures aren't verified so it is skipped.
+                    if super::current_protocol_version() >= 759 && bool::read_from(buf)? {
+                        let _expires_at = i64::read_from(buf)?;
+                        let _public_key = LenPrefixedBytes::<VarInt>::read_from(buf)?;
+                        let _key_signature = LenPrefixedBytes::<VarInt>::read_from(buf)?;
+                    }
                     m.players.push(p);
                 }
                 1 => m.players.push(PlayerDetail::UpdateGamemode {
@@ -2733,8 +3360,51 @@ impl Serializable for PlayerInfoData {
         Ok(m)
     }
 
-    fn write_to<W: io::Write>(&self, _: &mut W) -> Result<(), Error> {
-        unimplemented!() // I'm lazy
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.action.write_to(buf)?;
+        VarInt(self.players.len() as i32).write_to(buf)?;
+        for player in &self.players {
+            match player {
+                PlayerDetail::Add {
+                    uuid,
+                    name,
+                    properties,
+                    gamemode,
+                    ping,
+                    display,
+                } => {
+                    uuid.write_to(buf)?;
+                    name.write_to(buf)?;
+                    VarInt(properties.len() as i32).write_to(buf)?;
+                    for prop in properties {
+                        prop.write_to(buf)?;
+                    }
+                    gamemode.write_to(buf)?;
+                    ping.write_to(buf)?;
+                    display.is_some().write_to(buf)?;
+                    display.write_to(buf)?;
+                    // The profile key isn't kept when reading, so none is sent
+                    if super::current_protocol_version() >= 759 {
+                        false.write_to(buf)?;
+                    }
+                }
+                PlayerDetail::UpdateGamemode { uuid, gamemode } => {
+                    uuid.write_to(buf)?;
+                    gamemode.write_to(buf)?;
+                }
+                PlayerDetail::UpdateLatency { uuid, ping } => {
+                    uuid.write_to(buf)?;
+                    ping.write_to(buf)?;
+                }
+                PlayerDetail::UpdateDisplayName { uuid, display } => {
+                    uuid.write_to(buf)?;
+                    display.is_some().write_to(buf)?;
+                    display.write_to(buf)?;
+                }
+                PlayerDetail::Remove { uuid } => uuid.write_to(buf)?,
+            }
+        }
+        Ok(())
     }
 }
 
@@ -2774,16 +3444,71 @@ pub enum PlayerDetail {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PlayerProperty {
     pub name: String,
     pub value: String,
     pub signature: Option<String>,
 }
 
+impl Serializable for PlayerProperty {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
+        Ok(PlayerProperty {
+            name: Serializable::read_from(buf)?,
+            value: Serializable::read_from(buf)?,
+            signature: if bool::read_from(buf)? {
+                Some(Serializable::read_from(buf)?)
+            } else {
+                None
+            },
+        })
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.name.write_to(buf)?;
+        self.value.write_to(buf)?;
+        self.signature.is_some().write_to(buf)?;
+        if let Some(signature) = &self.signature {
+            signature.write_to(buf)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ArgumentSignature {
+    pub name: String,
+    pub signature: LenPrefixedBytes<VarInt>,
+}
+
+impl Serializable for ArgumentSignature {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
+        Ok(ArgumentSignature {
+            name: Serializable::read_from(buf)?,
+            signature: Serializable::read_from(buf)?,
+        })
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.name.write_to(buf)?;
+        self.signature.write_to(buf)
+    }
+}
+
 use crate::item;
 use crate::item::Stack;
+use crate::protocol::profile_key::ProfileKey;
 use std::f32::consts::PI;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Sequence number of the last block interaction (1.19+), the server
+/// acknowledges it with AcknowledgeBlockChange once it applied the change.
+static BLOCK_SEQUENCE: AtomicI32 = AtomicI32::new(0);
+
+fn next_block_sequence() -> VarInt {
+    VarInt(BLOCK_SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1)
+}
 
 type RecipeIngredient = LenPrefixed<VarInt, Option<item::Stack>>;
 
@@ -2973,8 +3698,105 @@ impl Serializable for Recipe {
         Ok(Recipe { id, ty, data })
     }
 
-    fn write_to<W: io::Write>(&self, _: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        if super::current_protocol_version() >= 477 {
+            self.ty.write_to(buf)?;
+            self.id.write_to(buf)?;
+        } else {
+            self.id.write_to(buf)?;
+            let ty = self.ty.strip_prefix("minecraft:").unwrap_or(&self.ty);
+            ty.to_owned().write_to(buf)?;
+        }
+
+        match &self.data {
+            RecipeData::Shapeless {
+                group,
+                ingredients,
+                result,
+            } => {
+                group.write_to(buf)?;
+                ingredients.write_to(buf)?;
+                result.write_to(buf)
+            }
+            RecipeData::Shaped {
+                width,
+                height,
+                group,
+                ingredients,
+                result,
+            } => {
+                if ingredients.len() != width.0 as usize * height.0 as usize {
+                    return Err(Error::Err(format!(
+                        "Shaped recipe {} has {} ingredients for {}x{}",
+                        self.id,
+                        ingredients.len(),
+                        width.0,
+                        height.0
+                    )));
+                }
+                width.write_to(buf)?;
+                height.write_to(buf)?;
+                group.write_to(buf)?;
+                for ingredient in ingredients {
+                    ingredient.write_to(buf)?;
+                }
+                result.write_to(buf)
+            }
+            RecipeData::Smelting {
+                group,
+                ingredient,
+                result,
+                experience,
+                cooking_time,
+            }
+            | RecipeData::Blasting {
+                group,
+                ingredient,
+                result,
+                experience,
+                cooking_time,
+            }
+            | RecipeData::Smoking {
+                group,
+                ingredient,
+                result,
+                experience,
+                cooking_time,
+            }
+            | RecipeData::Campfire {
+                group,
+                ingredient,
+                result,
+                experience,
+                cooking_time,
+            } => {
+                group.write_to(buf)?;
+                ingredient.write_to(buf)?;
+                result.write_to(buf)?;
+                experience.write_to(buf)?;
+                cooking_time.write_to(buf)
+            }
+            RecipeData::Stonecutting {
+                group,
+                ingredient,
+                result,
+            } => {
+                group.write_to(buf)?;
+                ingredient.write_to(buf)?;
+                result.write_to(buf)
+            }
+            RecipeData::Smithing {
+                base,
+                addition,
+                result,
+            } => {
+                base.write_to(buf)?;
+                addition.write_to(buf)?;
+                result.write_to(buf)
+            }
+            // The special recipes are identified by their type alone
+            _ => Ok(()),
+        }
     }
 }
 
@@ -2992,8 +3814,30 @@ impl Serializable for Tags {
         })
     }
 
-    fn write_to<W: io::Write>(&self, _: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.tag_name.write_to(buf)?;
+        self.entries.write_to(buf)
+    }
+}
+
+/// TagGroup holds the tags of a single registry, e.g. `minecraft:block`.
+#[derive(Debug, Default)]
+pub struct TagGroup {
+    pub tag_type: String,
+    pub tags: LenPrefixed<VarInt, Tags>,
+}
+
+impl Serializable for TagGroup {
+    fn read_from<R: io::Read>(buf: &mut R) -> Result<Self, Error> {
+        Ok(TagGroup {
+            tag_type: Serializable::read_from(buf)?,
+            tags: Serializable::read_from(buf)?,
+        })
+    }
+
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.tag_type.write_to(buf)?;
+        self.tags.write_to(buf)
     }
 }
 
@@ -3035,8 +3879,21 @@ impl Serializable for Trade {
         })
     }
 
-    fn write_to<W: io::Write>(&self, _: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.input_item_1.write_to(buf)?;
+        self.output_item.write_to(buf)?;
+        self.has_second_item.write_to(buf)?;
+        self.input_item_2.write_to(buf)?;
+        self.trades_disabled.write_to(buf)?;
+        self.tool_uses.write_to(buf)?;
+        self.max_trade_uses.write_to(buf)?;
+        self.xp.write_to(buf)?;
+        self.special_price.write_to(buf)?;
+        self.price_multiplier.write_to(buf)?;
+        if super::current_protocol_version() >= 498 {
+            self.demand.unwrap_or_default().write_to(buf)?;
+        }
+        Ok(())
     }
 }
 
@@ -3051,6 +3908,58 @@ pub struct CommandNode {
     pub suggestions_type: Option<String>,
 }
 
+/// Argument parsers by their registry id, as sent since 1.19
+const COMMAND_PARSERS_1_19: [&str; 48] = [
+    "brigadier:bool",
+    "brigadier:float",
+    "brigadier:double",
+    "brigadier:integer",
+    "brigadier:long",
+    "brigadier:string",
+    "minecraft:entity",
+    "minecraft:game_profile",
+    "minecraft:block_pos",
+    "minecraft:column_pos",
+    "minecraft:vec3",
+    "minecraft:vec2",
+    "minecraft:block_state",
+    "minecraft:block_predicate",
+    "minecraft:item_stack",
+    "minecraft:item_predicate",
+    "minecraft:color",
+    "minecraft:component",
+    "minecraft:message",
+    "minecraft:nbt_compound_tag",
+    "minecraft:nbt_tag",
+    "minecraft:nbt_path",
+    "minecraft:objective",
+    "minecraft:objective_criteria",
+    "minecraft:operation",
+    "minecraft:particle",
+    "minecraft:angle",
+    "minecraft:rotation",
+    "minecraft:scoreboard_slot",
+    "minecraft:score_holder",
+    "minecraft:swizzle",
+    "minecraft:team",
+    "minecraft:item_slot",
+    "minecraft:resource_location",
+    "minecraft:mob_effect",
+    "minecraft:function",
+    "minecraft:entity_anchor",
+    "minecraft:int_range",
+    "minecraft:float_range",
+    "minecraft:item_enchantment",
+    "minecraft:entity_summon",
+    "minecraft:dimension",
+    "minecraft:time",
+    "minecraft:resource_or_tag",
+    "minecraft:resource",
+    "minecraft:template_mirror",
+    "minecraft:template_rotation",
+    "minecraft:uuid",
+];
+
 #[derive(Debug, Eq, PartialEq)]
 enum CommandNodeType {
     Root,
@@ -3076,6 +3985,11 @@ pub enum CommandProperty {
         min: Option<i32>,
         max: Option<i32>,
     },
+    Long {
+        flags: u8,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
     String {
         token_type: VarInt,
     },
@@ -3125,6 +4039,14 @@ pub enum CommandProperty {
     EntitySummon,
     Dimension,
     UUID,
+    Resource {
+        registry: String,
+    },
+    ResourceOrTag {
+        registry: String,
+    },
+    TemplateMirror,
+    TemplateRotation,
     ForgeModId,
     ForgeEnum {
         cls: String,
@@ -3158,10 +4080,17 @@ impl Serializable for CommandNode {
             } else {
                 None
             };
-        let parser: Option<String> = if node_type == CommandNodeType::Argument {
-            Serializable::read_from(buf)?
-        } else {
+        let parser: Option<String> = if node_type != CommandNodeType::Argument {
             None
+        } else if super::current_protocol_version() >= 759 {
+            // 1.19 replaced the parser identifiers with registry ids
+            let id = VarInt::read_from(buf)?.0;
+            match COMMAND_PARSERS_1_19.get(id as usize) {
+                Some(parser) => Some(parser.to_string()),
+                None => panic!("unsupported command node parser id {}", id),
+            }
+        } else {
+            Serializable::read_from(buf)?
         };
 
         let properties: Option<CommandProperty> = if let Some(ref parse) = parser {
@@ -3209,6 +4138,20 @@ impl Serializable for CommandNode {
                     };
                     CommandProperty::Integer { flags, min, max }
                 }
+                "brigadier:long" => {
+                    let flags = Serializable::read_from(buf)?;
+                    let min = if flags & 0x01 != 0 {
+                        Some(Serializable::read_from(buf)?)
+                    } else {
+                        None
+                    };
+                    let max = if flags & 0x02 != 0 {
+                        Some(Serializable::read_from(buf)?)
+                    } else {
+                        None
+                    };
+                    CommandProperty::Long { flags, min, max }
+                }
                 "brigadier:string" => CommandProperty::String {
                     token_type: Serializable::read_from(buf)?,
                 },
@@ -3258,6 +4201,14 @@ impl Serializable for CommandNode {
                 "minecraft:entity_summon" => CommandProperty::EntitySummon,
                 "minecraft:dimension" => CommandProperty::Dimension,
                 "minecraft:uuid" => CommandProperty::UUID,
+                "minecraft:resource" => CommandProperty::Resource {
+                    registry: Serializable::read_from(buf)?,
+                },
+                "minecraft:resource_or_tag" => CommandProperty::ResourceOrTag {
+                    registry: Serializable::read_from(buf)?,
+                },
+                "minecraft:template_mirror" => CommandProperty::TemplateMirror,
+                "minecraft:template_rotation" => CommandProperty::TemplateRotation,
                 "forge:modid" => CommandProperty::ForgeModId,
                 "forge:enum" => CommandProperty::ForgeEnum {
                     cls: Serializable::read_from(buf)?,
@@ -3285,8 +4236,98 @@ impl Serializable for CommandNode {
         })
     }
 
-    fn write_to<W: io::Write>(&self, _: &mut W) -> Result<(), Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        self.flags.write_to(buf)?;
+        self.children.write_to(buf)?;
+
+        let node_type = self.flags & 0x03;
+        if self.flags & 0x08 != 0 {
+            self.redirect_node.unwrap_or_default().write_to(buf)?;
+        }
+        if node_type == 1 || node_type == 2 {
+            self.name.clone().unwrap_or_default().write_to(buf)?;
+        }
+        if node_type == 2 {
+            let parser = self.parser.clone().unwrap_or_default();
+            if super::current_protocol_version() >= 759 {
+                match COMMAND_PARSERS_1_19.iter().position(|p| *p == parser) {
+                    Some(id) => VarInt(id as i32).write_to(buf)?,
+                    None => {
+                        return Err(Error::Err(format!(
+                            "Command node parser {} has no id in 1.19",
+                            parser
+                        )))
+                    }
+                }
+            } else {
+                parser.write_to(buf)?;
+            }
+            if let Some(properties) = &self.properties {
+                properties.write_to(buf)?;
+            }
+        }
+        if self.flags & 0x10 != 0 {
+            self.suggestions_type
+                .clone()
+                .unwrap_or_default()
+                .write_to(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl CommandProperty {
+    /// Writes the properties of the parser, the parser itself is written
+    /// by the node.
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), Error> {
+        match self {
+            CommandProperty::Double { flags, min, max } => {
+                flags.write_to(buf)?;
+                if flags & 0x01 != 0 {
+                    min.unwrap_or_default().write_to(buf)?;
+                }
+                if flags & 0x02 != 0 {
+                    max.unwrap_or_default().write_to(buf)?;
+                }
+            }
+            CommandProperty::Float { flags, min, max } => {
+                flags.write_to(buf)?;
+                if flags & 0x01 != 0 {
+                    min.unwrap_or_default().write_to(buf)?;
+                }
+                if flags & 0x02 != 0 {
+                    max.unwrap_or_default().write_to(buf)?;
+                }
+            }
+            CommandProperty::Integer { flags, min, max } => {
+                flags.write_to(buf)?;
+                if flags & 0x01 != 0 {
+                    min.unwrap_or_default().write_to(buf)?;
+                }
+                if flags & 0x02 != 0 {
+                    max.unwrap_or_default().write_to(buf)?;
+                }
+            }
+            CommandProperty::Long { flags, min, max } => {
+                flags.write_to(buf)?;
+                if flags & 0x01 != 0 {
+                    min.unwrap_or_default().write_to(buf)?;
+                }
+                if flags & 0x02 != 0 {
+                    max.unwrap_or_default().write_to(buf)?;
+                }
+            }
+            CommandProperty::String { token_type } => token_type.write_to(buf)?,
+            CommandProperty::Entity { flags } | CommandProperty::ScoreHolder { flags } => {
+                flags.write_to(buf)?
+            }
+            CommandProperty::Range { decimals } => decimals.write_to(buf)?,
+            CommandProperty::Resource { registry }
+            | CommandProperty::ResourceOrTag { registry } => registry.write_to(buf)?,
+            CommandProperty::ForgeEnum { cls } => cls.write_to(buf)?,
+            _ => {}
+        }
+        Ok(())
     }
 }
 
@@ -3497,12 +4538,19 @@ pub fn send_digging(
             location: pos,
             face: face_index,
         })
-    } else {
+    } else if version < Version::V1_19 {
         conn.write_packet(packet::play::serverbound::PlayerDigging {
             status: VarInt(status.ordinal()),
             location: pos,
             face: face_index,
         })
+    } else {
+        conn.write_packet(packet::play::serverbound::PlayerDigging_Sequence {
+            status: VarInt(status.ordinal()),
+            location: pos,
+            face: face_index,
+            sequence: next_block_sequence(),
+        })
     }
 }
 
@@ -3523,10 +4571,15 @@ pub fn send_use_item(
             cursor_y: (cursor_position.y * 16.0) as u8,
             cursor_z: (cursor_position.z * 16.0) as u8,
         })
-    } else {
+    } else if version < Version::V1_19 {
         conn.write_packet(packet::play::serverbound::UseItem {
             hand: VarInt(hand.ordinal()),
         })
+    } else {
+        conn.write_packet(packet::play::serverbound::UseItem_Sequence {
+            hand: VarInt(hand.ordinal()),
+            sequence: next_block_sequence(),
+        })
     }
 }
 
@@ -3561,7 +4614,18 @@ pub fn send_block_place(
     item: Option<Stack>,
 ) -> Result<(), Error> {
     let version = conn.get_version();
-    if version >= Version::V1_14 {
+    if version >= Version::V1_19 {
+        conn.write_packet(packet::play::serverbound::PlayerBlockPlacement_Sequence {
+            location: pos,
+            face: VarInt(face as i32),
+            hand: VarInt(hand.ordinal()),
+            cursor_x: cursor_position.x as f32,
+            cursor_y: cursor_position.y as f32,
+            cursor_z: cursor_position.z as f32,
+            inside_block: false,
+            sequence: next_block_sequence(),
+        })
+    } else if version >= Version::V1_14 {
         conn.write_packet(
             packet::play::serverbound::PlayerBlockPlacement_insideblock {
                 location: pos,
@@ -3637,7 +4701,7 @@ pub fn send_client_settings(
             chat_colors,
             displayed_skin_parts,
         })
-    } else {
+    } else if version < Version::V1_17 {
         conn.write_packet(packet::play::serverbound::ClientSettings {
             locale,
             view_distance,
@@ -3646,6 +4710,57 @@ pub fn send_client_settings(
             displayed_skin_parts,
             main_hand: VarInt(main_hand.ordinal()),
         })
+    } else {
+        conn.write_packet(packet::play::serverbound::ClientSettings_Filtering {
+            locale,
+            view_distance,
+            chat_mode: VarInt(chat_mode as i32),
+            chat_colors,
+            displayed_skin_parts,
+            main_hand: VarInt(main_hand.ordinal()),
+            disable_text_filtering: false,
+        })
+    }
+}
+
+/// Sends a chat message or command (prefixed by '/'). Since 1.19 messages
+/// are signed with the profile key if there is one, without a key they
+/// are sent with an empty signature which servers accept unless they
+/// enforce secure chat.
+pub fn send_chat_message(
+    conn: &mut Conn,
+    message: String,
+    sender: &UUID,
+    profile_key: Option<&ProfileKey>,
+    salt: i64,
+) -> Result<(), Error> {
+    let version = conn.get_version();
+    if version < Version::V1_19 {
+        return conn.write_packet(packet::play::serverbound::ChatMessage { message });
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as i64);
+    if let Some(command) = message.strip_prefix('/') {
+        // Message arguments of commands aren't signed
+        conn.write_packet(packet::play::serverbound::ChatCommand {
+            command: command.to_owned(),
+            timestamp,
+            salt,
+            argument_signatures: LenPrefixed::new(vec![]),
+            signed_preview: false,
+        })
+    } else {
+        let signature = profile_key
+            .map(|key| key.sign_chat(sender, timestamp, salt, &message))
+            .unwrap_or_default();
+        conn.write_packet(packet::play::serverbound::ChatMessage_Signed {
+            message,
+            timestamp,
+            salt,
+            signature: LenPrefixedBytes::new(signature),
+            signed_preview: false,
+        })
     }
 }
 
@@ -3733,7 +4848,7 @@ pub fn send_click_container(
             mode,
             clicked_item,
         })
-    } else {
+    } else if version < Version::V1_17 {
         conn.write_packet(packet::play::serverbound::ClickWindow {
             id,
             slot,
@@ -3742,9 +4857,378 @@ pub fn send_click_container(
             mode: VarInt(mode as i32),
             clicked_item,
         })
+    } else if conn.protocol_version < 756 {
+        // The server works out which slots changed itself and resyncs
+        // the window when our prediction doesn't match, so none are sent.
+        conn.write_packet(packet::play::serverbound::ClickWindow_ChangedSlots {
+            id,
+            slot,
+            button,
+            mode: VarInt(mode as i32),
+            changed_slots: LenPrefixed::new(vec![]),
+            carried_item: clicked_item,
+        })
+    } else {
+        // From 1.17.1 the action number is the state id of the window.
+        conn.write_packet(packet::play::serverbound::ClickWindow_State {
+            id,
+            state_id: VarInt(action_number as i32),
+            slot,
+            button,
+            mode: VarInt(mode as i32),
+            changed_slots: LenPrefixed::new(vec![]),
+            carried_item: clicked_item,
+        })
     }
 }
 
 pub fn send_close_window(conn: &mut Conn, id: u8) -> Result<(), Error> {
     conn.write_packet(packet::play::serverbound::CloseWindow { id })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::versions::{
+        packet_ids_for_version, translate_internal_packet_id_for_version,
+    };
+    use crate::shared::Position;
+    use std::io::Cursor;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// The tests switch the global protocol version, so they take turns
+    static PROTOCOL_VERSION: Mutex<()> = Mutex::new(());
+
+    fn use_version(version: i32) -> MutexGuard<'static, ()> {
+        let guard = PROTOCOL_VERSION
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        super::super::CURRENT_PROTOCOL_VERSION.store(version, Ordering::Relaxed);
+        guard
+    }
+
+    fn encode(packet: &Packet) -> Vec<u8> {
+        let mut data = Vec::new();
+        packet.write(&mut data).unwrap();
+        data
+    }
+
+    /// The body of a packet, built field by field
+    #[derive(Default)]
+    struct Fixture(Vec<u8>);
+
+    impl Fixture {
+        fn put<T: Serializable>(mut self, val: T) -> Fixture {
+            val.write_to(&mut self.0).unwrap();
+            self
+        }
+
+        fn string(self, val: &str) -> Fixture {
+            self.put(val.to_owned())
+        }
+
+        /// A named compound holding just an item id, as slots and trades
+        /// carry them
+        fn item_tag(self, id: &str) -> Fixture {
+            self.put(10u8)
+                .put(0u16)
+                .put(8u8)
+                .put(2u16)
+                .put(b'i')
+                .put(b'd')
+                .put(id.len() as u16)
+                .bytes(id.as_bytes())
+                .put(0u8)
+        }
+
+        fn bytes(mut self, val: &[u8]) -> Fixture {
+            self.0.extend_from_slice(val);
+            self
+        }
+    }
+
+    /// Decodes the fixture as the clientbound play packet and checks that
+    /// it encodes back into the same bytes.
+    fn assert_round_trip(version: i32, internal_id: i32, fixture: Fixture) {
+        let _version = use_version(version);
+        let (state, dir) = (State::Play, Direction::Clientbound);
+        let id = translate_internal_packet_id_for_version(version, state, dir, internal_id, false);
+        let data = fixture.0;
+        let mut buf = Cursor::new(&data);
+        let packet = packet_by_id(version, state, dir, id, &mut buf)
+            .unwrap_or_else(|err| panic!("packet {:#x} in {}: {}", id, version, err))
+            .unwrap();
+        assert_eq!(
+            buf.position() as usize,
+            data.len(),
+            "{:?} in {} wasn't read completely",
+            packet,
+            version
+        );
+        assert_eq!(encode(&packet), data, "{:?} in {}", packet, version);
+    }
+
+    #[test]
+    fn command_nodes_round_trip() {
+        let ids = play::clientbound::internal_ids::DeclareCommands;
+        // 1.19 parsers are registry ids
+        assert_round_trip(
+            759,
+            ids,
+            Fixture::default()
+                .put(VarInt(4))
+                // Root
+                .put(0x00u8)
+                .put(VarInt(2))
+                .put(VarInt(1))
+                .put(VarInt(3))
+                // Executable literal with an argument
+                .put(0x05u8)
+                .put(VarInt(1))
+                .put(VarInt(2))
+                .string("give")
+                // Executable integer argument with bounds and suggestions
+                .put(0x16u8)
+                .put(VarInt(0))
+                .string("count")
+                .put(VarInt(3))
+                .put(0x03u8)
+                .put(1i32)
+                .put(64i32)
+                .string("minecraft:ask_server")
+                // Literal redirecting to the first one
+                .put(0x09u8)
+                .put(VarInt(0))
+                .put(VarInt(1))
+                .string("gv")
+                .put(VarInt(0)),
+        );
+        // Before that they are named
+        assert_round_trip(
+            758,
+            ids,
+            Fixture::default()
+                .put(VarInt(4))
+                .put(0x00u8)
+                .put(VarInt(3))
+                .put(VarInt(1))
+                .put(VarInt(2))
+                .put(VarInt(3))
+                .put(0x02u8)
+                .put(VarInt(0))
+                .string("speed")
+                .string("brigadier:double")
+                .put(0x01u8)
+                .put(0.5f64)
+                .put(0x02u8)
+                .put(VarInt(0))
+                .string("text")
+                .string("brigadier:string")
+                .put(VarInt(2))
+                .put(0x02u8)
+                .put(VarInt(0))
+                .string("targets")
+                .string("minecraft:score_holder")
+                .put(0x01u8)
+                .put(VarInt(0)),
+        );
+    }
+
+    #[test]
+    fn recipes_round_trip() {
+        let stack = |fixture: Fixture, id: i32, count: u8| {
+            fixture.put(true).put(VarInt(id)).put(count).put(0u8)
+        };
+        let mut fixture = Fixture::default()
+            .put(VarInt(3))
+            .string("minecraft:crafting_shaped")
+            .string("minecraft:torch")
+            .put(VarInt(1))
+            .put(VarInt(2))
+            .string("")
+            .put(VarInt(1));
+        fixture = stack(fixture, 806, 1).put(VarInt(1));
+        fixture = stack(fixture, 811, 1);
+        fixture = stack(fixture, 266, 4)
+            .string("minecraft:smelting")
+            .string("minecraft:cooked_beef")
+            .string("food")
+            .put(VarInt(1));
+        fixture = stack(fixture, 899, 1);
+        fixture = stack(fixture, 900, 1)
+            .put(0.35f32)
+            .put(VarInt(200))
+            .string("minecraft:crafting_special_armordye")
+            .string("minecraft:armor_dye");
+        assert_round_trip(
+            759,
+            play::clientbound::internal_ids::DeclareRecipes,
+            fixture,
+        );
+    }
+
+    #[test]
+    fn tags_round_trip() {
+        assert_round_trip(
+            759,
+            play::clientbound::internal_ids::TagsWithTypes,
+            Fixture::default()
+                .put(VarInt(1))
+                .string("minecraft:block")
+                .put(VarInt(2))
+                .string("minecraft:logs")
+                .put(VarInt(2))
+                .put(VarInt(41))
+                .put(VarInt(42))
+                .string("minecraft:planks")
+                .put(VarInt(1))
+                .put(VarInt(13)),
+        );
+        assert_round_trip(
+            404,
+            play::clientbound::internal_ids::Tags,
+            Fixture::default()
+                .put(VarInt(1))
+                .string("minecraft:logs")
+                .put(VarInt(1))
+                .put(VarInt(37))
+                .put(VarInt(0))
+                .put(VarInt(1))
+                .string("minecraft:water")
+                .put(VarInt(2))
+                .put(VarInt(1))
+                .put(VarInt(2)),
+        );
+    }
+
+    #[test]
+    fn trades_round_trip() {
+        assert_round_trip(
+            498,
+            play::clientbound::internal_ids::TradeList_WithRestock,
+            Fixture::default()
+                .put(VarInt(7))
+                .put(1u8)
+                .item_tag("minecraft:wheat")
+                .item_tag("minecraft:emerald")
+                .put(true)
+                .item_tag("minecraft:carrot")
+                .put(false)
+                .put(3i32)
+                .put(16i32)
+                .put(2i32)
+                .put(-1i32)
+                .put(0.05f32)
+                .put(4i32)
+                .put(VarInt(2))
+                .put(VarInt(10))
+                .put(true)
+                .put(true),
+        );
+    }
+
+    #[test]
+    fn particle_data_round_trip() {
+        let particle = |id: i32| {
+            Fixture::default()
+                .put(VarInt(id))
+                .put(true)
+                .put(1.5f64)
+                .put(64.0f64)
+                .put(-3.5f64)
+                .put(0.25f32)
+                .put(0.5f32)
+                .put(0.25f32)
+                .put(0.1f32)
+                .put(8i32)
+        };
+        let ids = play::clientbound::internal_ids::Particle_VarInt_Vibration;
+        // Block
+        assert_round_trip(759, ids, particle(2).put(VarInt(1234)));
+        // Dust colour transition
+        assert_round_trip(
+            759,
+            ids,
+            (0..7).fold(particle(15), |fixture, i| fixture.put(i as f32 / 7.0)),
+        );
+        // Item
+        assert_round_trip(
+            759,
+            ids,
+            particle(39).put(true).put(VarInt(1)).put(3u8).put(0u8),
+        );
+        // Vibration towards a block
+        assert_round_trip(
+            759,
+            ids,
+            particle(40)
+                .string("minecraft:block")
+                .put(Position::new(12, -40, 7))
+                .put(VarInt(20)),
+        );
+
+        let particle = |id: i32| {
+            Fixture::default()
+                .put(id)
+                .put(false)
+                .put(1.5f32)
+                .put(64.0f32)
+                .put(-3.5f32)
+                .put(0.0f32)
+                .put(0.0f32)
+                .put(0.0f32)
+                .put(1.0f32)
+                .put(1i32)
+        };
+        let ids = play::clientbound::internal_ids::Particle_Data13;
+        assert_round_trip(
+            404,
+            ids,
+            particle(11).put(1.0f32).put(0.5f32).put(0.0f32).put(2.0f32),
+        );
+        assert_round_trip(404, ids, particle(20).put(VarInt(66)));
+        assert_round_trip(404, ids, particle(27).item_tag("minecraft:stone"));
+    }
+
+    #[test]
+    fn clientbound_round_trip() {
+        let _version = use_version(SUPPORTED_PROTOCOLS[0]);
+        // A stream of zeros decodes into a consistent packet for everything
+        // but metadata, which never sees its terminator. Those start out
+        // with the defaults instead.
+        let zeros = vec![0u8; 1 << 16];
+        let dir = Direction::Clientbound;
+        for &version in SUPPORTED_PROTOCOLS.iter() {
+            super::super::CURRENT_PROTOCOL_VERSION.store(version, Ordering::Relaxed);
+            for &state in &[State::Handshaking, State::Status, State::Login, State::Play] {
+                for &id in packet_ids_for_version(version, state, dir) {
+                    let seed = match packet_by_id(version, state, dir, id, &mut Cursor::new(&zeros))
+                    {
+                        Ok(Some(packet)) => packet,
+                        _ => {
+                            let internal_id = translate_internal_packet_id_for_version(
+                                version, state, dir, id, true,
+                            );
+                            default_packet_by_internal_id(state, dir, internal_id).unwrap()
+                        }
+                    };
+                    assert_eq!(seed.packet_id(version), id, "{:?} in {}", seed, version);
+
+                    let data = encode(&seed);
+                    let mut buf = Cursor::new(&data);
+                    let decoded = packet_by_id(version, state, dir, id, &mut buf)
+                        .unwrap_or_else(|err| panic!("{:?} in {}: {}", seed, version, err))
+                        .unwrap();
+                    assert_eq!(
+                        buf.position() as usize,
+                        data.len(),
+                        "{:?} in {} wasn't read completely",
+                        decoded,
+                        version
+                    );
+                    assert_eq!(encode(&decoded), data, "{:?} in {}", decoded, version);
+                }
+            }
+        }
+    }
+}
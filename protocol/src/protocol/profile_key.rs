@@ -0,0 +1,177 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Profile public keys as introduced with 1.19. Mojang hands out a key pair
+//! per account which the client uses to sign its chat messages, the server
+//! learns the public half (together with Mojang's signature of it) on login.
+
+use base64::Engine;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::{SignatureEncoding, Signer};
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::protocol::{Error, Serializable, UUID};
+
+const CERTIFICATES_URL: &str = "https://api.minecraftservices.com/player/certificates";
+
+#[derive(Clone)]
+pub struct ProfileKey {
+    /// Expiry of the key in milliseconds since the unix epoch
+    pub expires_at: i64,
+    /// DER encoded (X.509 SubjectPublicKeyInfo) public key
+    pub public_key: Vec<u8>,
+    /// Mojang's signature of the public key
+    pub key_signature: Vec<u8>,
+    private_key: RsaPrivateKey,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Certificates {
+    key_pair: KeyPair,
+    public_key_signature: String,
+    expires_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyPair {
+    private_key: String,
+    public_key: String,
+}
+
+impl ProfileKey {
+    /// Requests the key pair of the account owning the passed access token.
+    pub fn fetch(access_token: &str) -> Result<ProfileKey, Error> {
+        let client = reqwest::blocking::Client::new();
+        let res = client
+            .post(CERTIFICATES_URL)
+            .bearer_auth(access_token)
+            .header(reqwest::header::CONTENT_LENGTH, 0)
+            .send()?;
+        if !res.status().is_success() {
+            return Err(Error::Err(format!(
+                "Failed to fetch profile key: {}",
+                res.status()
+            )));
+        }
+        let certificates: Certificates = serde_json::from_str(&res.text()?)?;
+        ProfileKey::from_certificates(certificates)
+    }
+
+    fn from_certificates(certificates: Certificates) -> Result<ProfileKey, Error> {
+        let private_key =
+            RsaPrivateKey::from_pkcs8_der(&decode_pem(&certificates.key_pair.private_key)?)
+                .map_err(|err| Error::Err(format!("Invalid profile private key: {}", err)))?;
+        Ok(ProfileKey {
+            expires_at: parse_timestamp(&certificates.expires_at)?,
+            public_key: decode_pem(&certificates.key_pair.public_key)?,
+            key_signature: decode_base64(&certificates.public_key_signature)?,
+            private_key,
+        })
+    }
+
+    /// Signs the passed data with SHA256withRSA, the scheme used for
+    /// everything signed with a profile key.
+    pub fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let signing_key = SigningKey::<Sha256>::new(self.private_key.clone());
+        signing_key.sign(data).to_vec()
+    }
+
+    /// Signature sent in place of the verify token during login.
+    pub fn sign_verify_token(&self, verify_token: &[u8], salt: i64) -> Vec<u8> {
+        let mut data = verify_token.to_vec();
+        data.extend_from_slice(&salt.to_be_bytes());
+        self.sign(&data)
+    }
+
+    /// Signature of a chat message, `timestamp` is in milliseconds since
+    /// the unix epoch like in the chat packet itself.
+    pub fn sign_chat(&self, sender: &UUID, timestamp: i64, salt: i64, message: &str) -> Vec<u8> {
+        let mut data = salt.to_be_bytes().to_vec();
+        sender.write_to(&mut data).unwrap();
+        data.extend_from_slice(&(timestamp / 1000).to_be_bytes());
+        data.extend_from_slice(
+            serde_json::json!({ "text": message })
+                .to_string()
+                .as_bytes(),
+        );
+        self.sign(&data)
+    }
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, Error> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|err| Error::Err(format!("Invalid base64 in profile key: {}", err)))
+}
+
+/// Strips the PEM armor, mojang labels its PKCS#8/X.509 keys
+/// as "RSA" keys, so the label can't be trusted anyway.
+fn decode_pem(pem: &str) -> Result<Vec<u8>, Error> {
+    let body = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+    decode_base64(body.trim())
+}
+
+/// Parses an ISO-8601 UTC timestamp (e.g. `2022-06-10T17:39:53.166823Z`)
+/// into milliseconds since the unix epoch.
+fn parse_timestamp(timestamp: &str) -> Result<i64, Error> {
+    let invalid = || Error::Err(format!("Invalid timestamp: {}", timestamp));
+    let (date, time) = timestamp.split_once('T').ok_or_else(invalid)?;
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>());
+    let (year, month, day) = match (date.next(), date.next(), date.next()) {
+        (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) => (year, month, day),
+        _ => return Err(invalid()),
+    };
+    let time = time.trim_end_matches('Z');
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':').map(|part| part.parse::<i64>());
+    let (hour, minute, second) = match (time.next(), time.next(), time.next()) {
+        (Some(Ok(hour)), Some(Ok(minute)), Some(Ok(second))) => (hour, minute, second),
+        _ => return Err(invalid()),
+    };
+    let millis = format!("{:0<3}", fraction)[..3]
+        .parse::<i64>()
+        .map_err(|_| invalid())?;
+
+    // Days since the epoch for a proleptic gregorian date
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    Ok(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000 + millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_expiry_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z").unwrap(), 0);
+        assert_eq!(
+            parse_timestamp("2022-06-10T17:39:53.166823Z").unwrap(),
+            1_654_882_793_166
+        );
+    }
+}
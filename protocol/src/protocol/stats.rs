@@ -0,0 +1,195 @@
+//! Traffic counters for a connection.
+//!
+//! Every `Conn` (and all of its clones) shares a single `NetworkStats`
+//! which counts packets and bytes per packet type in both directions,
+//! how well compression is doing and how long decoding takes. The reader
+//! loop of the client adds the time it spends handling each packet, also
+//! when that happens on another thread, so a lagging session can be
+//! attributed to a chunk flood, entity spam or our own processing.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use instant::{Duration, Instant};
+
+/// How far back `bytes_per_second` looks
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PacketStats {
+    pub count: u64,
+    /// Bytes on the wire, including the frame header and after compression
+    pub bytes: u64,
+    /// Time spent decompressing and parsing, only tracked for received packets
+    pub decode_time: Duration,
+    /// Time the client spent acting on the packet after decoding it
+    pub handle_time: Duration,
+}
+
+#[derive(Default)]
+pub struct TrafficStats {
+    packets: HashMap<&'static str, PacketStats>,
+    /// Bytes on the wire
+    wire_bytes: u64,
+    /// Bytes of packet data before compression
+    payload_bytes: u64,
+    recent: VecDeque<(Instant, u64)>,
+}
+
+impl TrafficStats {
+    fn record(&mut self, name: &'static str, wire_len: usize, payload_len: usize) {
+        let entry = self.packets.entry(name).or_default();
+        entry.count += 1;
+        entry.bytes += wire_len as u64;
+        self.wire_bytes += wire_len as u64;
+        self.payload_bytes += payload_len as u64;
+
+        let now = Instant::now();
+        self.recent.push_back((now, wire_len as u64));
+        self.expire(now);
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some(&(time, _)) = self.recent.front() {
+            if now.duration_since(time) <= RATE_WINDOW {
+                break;
+            }
+            self.recent.pop_front();
+        }
+    }
+
+    pub fn packet(&self, name: &str) -> Option<&PacketStats> {
+        self.packets.get(name)
+    }
+
+    pub fn total_packets(&self) -> u64 {
+        self.packets.values().map(|p| p.count).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.wire_bytes
+    }
+
+    /// Packet data size divided by its size on the wire, 1.0 when nothing
+    /// was compressed.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.wire_bytes == 0 {
+            1.0
+        } else {
+            self.payload_bytes as f64 / self.wire_bytes as f64
+        }
+    }
+
+    /// Average throughput over the last few seconds
+    pub fn bytes_per_second(&mut self) -> f64 {
+        self.expire(Instant::now());
+        let bytes: u64 = self.recent.iter().map(|&(_, len)| len).sum();
+        bytes as f64 / RATE_WINDOW.as_secs_f64()
+    }
+
+    /// The packet types which used the most bytes, largest first
+    pub fn top(&self, limit: usize) -> Vec<(&'static str, PacketStats)> {
+        let mut packets: Vec<_> = self.packets.iter().map(|(k, v)| (*k, *v)).collect();
+        packets.sort_by_key(|&(name, stats)| (Reverse(stats.bytes), name));
+        packets.truncate(limit);
+        packets
+    }
+}
+
+#[derive(Default)]
+pub struct NetworkStats {
+    pub received: TrafficStats,
+    pub sent: TrafficStats,
+    started: Option<Instant>,
+}
+
+impl NetworkStats {
+    pub fn new() -> NetworkStats {
+        NetworkStats {
+            started: Some(Instant::now()),
+            ..Default::default()
+        }
+    }
+
+    pub fn record_received(
+        &mut self,
+        name: &'static str,
+        wire_len: usize,
+        payload_len: usize,
+        decode_time: Duration,
+    ) {
+        self.received.record(name, wire_len, payload_len);
+        self.received.packets.get_mut(name).unwrap().decode_time += decode_time;
+    }
+
+    pub fn record_sent(&mut self, name: &'static str, wire_len: usize, payload_len: usize) {
+        self.sent.record(name, wire_len, payload_len);
+    }
+
+    /// Adds the time spent acting on a received packet
+    pub fn record_handled(&mut self, name: &'static str, handle_time: Duration) {
+        self.received.packets.entry(name).or_default().handle_time += handle_time;
+    }
+
+    pub fn reset(&mut self) {
+        *self = NetworkStats::new();
+    }
+
+    /// Formats every counter as a table, one packet type per line
+    pub fn dump(&self) -> String {
+        let elapsed = self
+            .started
+            .map_or(Duration::from_secs(0), |started| started.elapsed());
+        let mut out = String::new();
+        let _ = writeln!(out, "Network statistics over {:.1}s", elapsed.as_secs_f64());
+        for (label, traffic) in [("Received", &self.received), ("Sent", &self.sent)] {
+            let _ = writeln!(
+                out,
+                "{}: {} packets, {} bytes, compression {:.2}x",
+                label,
+                traffic.total_packets(),
+                traffic.total_bytes(),
+                traffic.compression_ratio(),
+            );
+            for (name, stats) in traffic.top(usize::MAX) {
+                let _ = writeln!(
+                    out,
+                    "  {:<40} {:>8} pkts {:>12} bytes {:>10.2}ms decode {:>10.2}ms handle",
+                    name,
+                    stats.count,
+                    stats.bytes,
+                    stats.decode_time.as_secs_f64() * 1000.0,
+                    stats.handle_time.as_secs_f64() * 1000.0,
+                );
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_per_packet() {
+        let mut stats = NetworkStats::new();
+        stats.record_received("ChunkData", 1000, 4000, Duration::from_millis(2));
+        stats.record_received("ChunkData", 1000, 4000, Duration::from_millis(2));
+        stats.record_received("KeepAliveClientbound_VarInt", 4, 4, Duration::default());
+        stats.record_handled("ChunkData", Duration::from_millis(1));
+        stats.record_sent("KeepAliveServerbound_VarInt", 4, 4);
+
+        let chunks = stats.received.packet("ChunkData").unwrap();
+        assert_eq!(chunks.count, 2);
+        assert_eq!(chunks.bytes, 2000);
+        assert_eq!(chunks.decode_time, Duration::from_millis(4));
+        assert_eq!(chunks.handle_time, Duration::from_millis(1));
+        assert_eq!(stats.received.top(1)[0].0, "ChunkData");
+        assert_eq!(stats.received.total_packets(), 3);
+        assert!((stats.received.compression_ratio() - 8004.0 / 2004.0).abs() < 1e-9);
+        assert_eq!(stats.sent.total_bytes(), 4);
+        assert!(stats.received.bytes_per_second() > 0.0);
+    }
+}
@@ -0,0 +1,263 @@
+//! Byte streams a `Conn` can run on top of.
+//!
+//! Besides plain TCP this provides an in-process duplex pipe, which allows
+//! exercising the login, encryption and compression code paths without
+//! opening any sockets, and a null transport used when replaying captures.
+
+use std::collections::VecDeque;
+use std::io;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// A bidirectional byte stream which can be shared between the reading
+/// and the writing half of a connection.
+pub trait Transport: Read + Write + Send + Sync {
+    /// Returns a new handle to the same underlying stream
+    fn try_clone_boxed(&self) -> io::Result<Box<dyn Transport>>;
+
+    /// Shuts down both directions of the stream, waking up any blocked readers
+    fn close(&self) -> io::Result<()>;
+}
+
+impl Transport for TcpStream {
+    fn try_clone_boxed(&self) -> io::Result<Box<dyn Transport>> {
+        Ok(Box::new(self.try_clone()?))
+    }
+
+    fn close(&self) -> io::Result<()> {
+        self.shutdown(Shutdown::Both)
+    }
+}
+
+#[derive(Default)]
+struct PipeState {
+    data: VecDeque<u8>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    ready: Condvar,
+}
+
+impl Pipe {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_all();
+    }
+}
+
+/// One end of an in-memory duplex pipe, see `MemoryStream::pair`.
+#[derive(Clone)]
+pub struct MemoryStream {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+}
+
+impl MemoryStream {
+    /// Creates two connected streams, everything written to one of them
+    /// can be read from the other.
+    pub fn pair() -> (MemoryStream, MemoryStream) {
+        let a = Arc::new(Pipe::default());
+        let b = Arc::new(Pipe::default());
+        (
+            MemoryStream {
+                incoming: a.clone(),
+                outgoing: b.clone(),
+            },
+            MemoryStream {
+                incoming: b,
+                outgoing: a,
+            },
+        )
+    }
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.incoming.state.lock().unwrap();
+        while state.data.is_empty() && !state.closed {
+            state = self.incoming.ready.wait(state).unwrap();
+        }
+        let len = buf.len().min(state.data.len());
+        for (dst, src) in buf.iter_mut().zip(state.data.drain(..len)) {
+            *dst = src;
+        }
+        Ok(len)
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.outgoing.state.lock().unwrap();
+        if state.closed {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+        }
+        state.data.extend(buf);
+        self.outgoing.ready.notify_all();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Transport for MemoryStream {
+    fn try_clone_boxed(&self) -> io::Result<Box<dyn Transport>> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn close(&self) -> io::Result<()> {
+        self.incoming.close();
+        self.outgoing.close();
+        Ok(())
+    }
+}
+
+/// A transport which never has anything to read and discards all writes.
+#[derive(Clone, Copy, Default)]
+pub struct NullTransport;
+
+impl Read for NullTransport {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl Write for NullTransport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Transport for NullTransport {
+    fn try_clone_boxed(&self) -> io::Result<Box<dyn Transport>> {
+        Ok(Box::new(*self))
+    }
+
+    fn close(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::packet::handshake::serverbound::Handshake;
+    use crate::protocol::packet::login::serverbound::LoginStart;
+    use crate::protocol::packet::Packet;
+    use crate::protocol::{Conn, Direction, State, VarInt};
+
+    fn conn_pair() -> (Conn, Conn) {
+        let (a, b) = MemoryStream::pair();
+        let client =
+            Conn::from_transport(Box::new(a), Direction::Serverbound, "localhost", 25565, 754);
+        let server =
+            Conn::from_transport(Box::new(b), Direction::Clientbound, "localhost", 25565, 754);
+        (client, server)
+    }
+
+    #[test]
+    fn pipe_round_trip() {
+        let (mut a, mut b) = MemoryStream::pair();
+        a.write_all(b"leafish").unwrap();
+        let mut buf = [0; 7];
+        b.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"leafish");
+        a.close().unwrap();
+        assert_eq!(b.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn handshake_over_pipe() {
+        let (mut client, mut server) = conn_pair();
+        client
+            .write_packet(Handshake {
+                protocol_version: VarInt(754),
+                host: "localhost".to_owned(),
+                port: 25565,
+                next: VarInt(2),
+            })
+            .unwrap();
+        match server.read_packet().unwrap() {
+            Packet::Handshake(handshake) => {
+                assert_eq!(handshake.protocol_version.0, 754);
+                assert_eq!(handshake.host, "localhost");
+                assert_eq!(handshake.next.0, 2);
+            }
+            packet => panic!("unexpected packet {:?}", packet),
+        }
+    }
+
+    #[test]
+    fn queued_writes_over_pipe() {
+        let (mut client, mut server) = conn_pair();
+        client.state = State::Login;
+        server.state = State::Login;
+        client.set_compression(4);
+        server.set_compression(4);
+        client.start_writer().unwrap();
+
+        for name in &["a", "a_much_longer_name"] {
+            client
+                .write_packet(LoginStart {
+                    username: name.to_string(),
+                })
+                .unwrap();
+        }
+        client.end_batch();
+        for name in &["a", "a_much_longer_name"] {
+            match server.read_packet().unwrap() {
+                Packet::LoginStart(start) => assert_eq!(start.username, *name),
+                packet => panic!("unexpected packet {:?}", packet),
+            }
+        }
+
+        server.close();
+        client
+            .write_packet(LoginStart {
+                username: "late".to_owned(),
+            })
+            .unwrap();
+        client.end_batch();
+        while client.write_error().is_none() {
+            std::thread::yield_now();
+        }
+        assert!(client
+            .write_packet(LoginStart {
+                username: "too late".to_owned(),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn login_with_compression_and_encryption() {
+        let (mut client, mut server) = conn_pair();
+        client.state = State::Login;
+        server.state = State::Login;
+        client.set_compression(4);
+        server.set_compression(4);
+        let key = [7; 16];
+        client.enable_encyption(&key);
+        server.enable_encyption(&key);
+
+        for name in &["a", "a_much_longer_name"] {
+            client
+                .write_packet(LoginStart {
+                    username: name.to_string(),
+                })
+                .unwrap();
+            match server.read_packet().unwrap() {
+                Packet::LoginStart(start) => assert_eq!(start.username, *name),
+                packet => panic!("unexpected packet {:?}", packet),
+            }
+        }
+    }
+}
@@ -12,6 +12,10 @@ mod v1_14_4;
 mod v1_15;
 mod v1_16_1;
 mod v1_16_4;
+mod v1_17;
+mod v1_17_1;
+mod v1_18_2;
+mod v1_19;
 mod v1_7_10;
 mod v1_8_9;
 mod v1_9;
@@ -20,9 +24,35 @@ mod v1_9_2;
 // https://wiki.vg/Protocol_History
 // https://wiki.vg/Protocol_version_numbers#Versions_after_the_Netty_rewrite
 
+/// One release name for each of `SUPPORTED_PROTOCOLS`, in the same order
+pub const PROTOCOL_NAMES: [&str; 25] = [
+    "1.19", "1.18.2", "1.17.1", "1.17", "1.16.5", "1.16.3", "1.16.2", "1.16.1", "1.16", "1.15.2",
+    "1.15.1", "1.14.4", "1.14.3", "1.14.2", "1.14.1", "1.14", "1.13.2", "1.12.2", "1.11.2", "1.11",
+    "1.10.2", "1.9.2", "1.9", "1.8.9", "1.7.10",
+];
+
+/// Whether the client can speak the given protocol version.
+///
+/// Only exact protocol numbers count: a release range such as 1.18 also
+/// covers versions which have no packet table here.
+pub fn is_supported_protocol(protocol_version: i32) -> bool {
+    SUPPORTED_PROTOCOLS.contains(&protocol_version)
+}
+
 pub fn protocol_name_to_protocol_version(s: String) -> i32 {
-    match s.as_ref() {
+    try_protocol_name_to_protocol_version(&s)
+        .unwrap_or_else(|| panic!("Unrecognized protocol name: {}", s))
+}
+
+/// Like `protocol_name_to_protocol_version`, but returns `None` for names
+/// which are neither a known release nor a supported protocol number.
+pub fn try_protocol_name_to_protocol_version(s: &str) -> Option<i32> {
+    Some(match s {
         "" => SUPPORTED_PROTOCOLS[0],
+        "1.19" => 759,
+        "1.18.2" => 758,
+        "1.17.1" => 756,
+        "1.17" => 755,
         "1.16.5" => 754,
         "1.16.4" => 754,
         "1.16.3" => 753,
@@ -46,13 +76,12 @@ pub fn protocol_name_to_protocol_version(s: String) -> i32 {
         "1.8.9" => 47,
         "1.7.10" => 5,
         _ => {
-            if let Ok(n) = s.parse::<i32>() {
-                n
-            } else {
-                panic!("Unrecognized protocol name: {}", s)
-            }
+            return s
+                .parse::<i32>()
+                .ok()
+                .filter(|&version| is_supported_protocol(version))
         }
-    }
+    })
 }
 
 pub fn translate_internal_packet_id_for_version(
@@ -63,6 +92,10 @@ pub fn translate_internal_packet_id_for_version(
     to_internal: bool,
 ) -> i32 {
     match version {
+        759 => v1_19::translate_internal_packet_id(state, dir, id, to_internal),
+        758 => v1_18_2::translate_internal_packet_id(state, dir, id, to_internal),
+        756 => v1_17_1::translate_internal_packet_id(state, dir, id, to_internal),
+        755 => v1_17::translate_internal_packet_id(state, dir, id, to_internal),
         754 | 753 | 751 => v1_16_4::translate_internal_packet_id(state, dir, id, to_internal),
         736 => v1_16_1::translate_internal_packet_id(state, dir, id, to_internal),
         735 => v1_16_1::translate_internal_packet_id(state, dir, id, to_internal),
@@ -85,3 +118,63 @@ pub fn translate_internal_packet_id_for_version(
         _ => panic!("unsupported protocol version: {}", version),
     }
 }
+
+/// Returns the ids of all packets the given version knows for the state
+/// and direction.
+pub fn packet_ids_for_version(version: i32, state: State, dir: Direction) -> &'static [i32] {
+    match version {
+        759 => v1_19::packet_ids(state, dir),
+        758 => v1_18_2::packet_ids(state, dir),
+        756 => v1_17_1::packet_ids(state, dir),
+        755 => v1_17::packet_ids(state, dir),
+        754 | 753 | 751 => v1_16_4::packet_ids(state, dir),
+        736 => v1_16_1::packet_ids(state, dir),
+        735 => v1_16_1::packet_ids(state, dir),
+        578 => v1_15::packet_ids(state, dir),
+        575 => v1_15::packet_ids(state, dir),
+        498 => v1_14_4::packet_ids(state, dir),
+        490 => v1_14_3::packet_ids(state, dir),
+        485 => v1_14_2::packet_ids(state, dir),
+        480 => v1_14_1::packet_ids(state, dir),
+        477 => v1_14::packet_ids(state, dir),
+        404 => v1_13_2::packet_ids(state, dir),
+        340 => v1_12_2::packet_ids(state, dir),
+        316 => v1_11_2::packet_ids(state, dir),
+        315 => v1_11_2::packet_ids(state, dir),
+        210 => v1_10_2::packet_ids(state, dir),
+        109 => v1_9_2::packet_ids(state, dir),
+        107 => v1_9::packet_ids(state, dir),
+        47 => v1_8_9::packet_ids(state, dir),
+        5 => v1_7_10::packet_ids(state, dir),
+        _ => panic!("unsupported protocol version: {}", version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_names_match_supported_protocols() {
+        for (name, &version) in PROTOCOL_NAMES.iter().zip(SUPPORTED_PROTOCOLS.iter()) {
+            assert_eq!(try_protocol_name_to_protocol_version(name), Some(version));
+        }
+        assert_eq!(try_protocol_name_to_protocol_version("340"), Some(340));
+        assert_eq!(try_protocol_name_to_protocol_version("1.2.5"), None);
+        assert_eq!(try_protocol_name_to_protocol_version("757"), None);
+        assert_eq!(try_protocol_name_to_protocol_version("123"), None);
+    }
+
+    #[test]
+    fn only_exact_protocols_are_supported() {
+        for &version in SUPPORTED_PROTOCOLS.iter() {
+            assert!(is_supported_protocol(version));
+        }
+        // 1.18/1.18.1 and 1.19.1/1.19.2 share a release with supported
+        // versions but not their packet ids
+        assert!(!is_supported_protocol(757));
+        assert!(!is_supported_protocol(760));
+        assert!(!is_supported_protocol(761));
+        assert!(!is_supported_protocol(4));
+    }
+}
@@ -0,0 +1,106 @@
+//! Background thread sending the packets of a connection.
+//!
+//! Once `Conn::start_writer` was called, `Conn::write_packet` only
+//! serializes the packet and queues it, so the caller never waits for the
+//! socket. The writer thread frames, compresses and encrypts queued
+//! packets into one batch and writes it out when the game marks the end
+//! of a tick with `Conn::end_batch`, or after `MAX_BATCH_DELAY` in case no
+//! tick comes along. A failed write closes the transport and is kept so
+//! the client can report it when disconnecting.
+
+use std::io::Write;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use instant::{Duration, Instant};
+use log::debug;
+
+use super::framing::WriteHalf;
+use super::stats::NetworkStats;
+use super::transport::Transport;
+use super::Error;
+
+/// How long a queued packet waits for the end of the tick at most
+const MAX_BATCH_DELAY: Duration = Duration::from_millis(50);
+
+pub(crate) enum Outgoing {
+    Packet {
+        name: &'static str,
+        payload: Vec<u8>,
+        compression_threshold: i32,
+    },
+    /// Ends the current batch, writing it out
+    Flush,
+}
+
+pub(crate) fn spawn(
+    mut stream: Box<dyn Transport>,
+    writer: Arc<Mutex<WriteHalf>>,
+    stats: Arc<Mutex<NetworkStats>>,
+    error: Arc<Mutex<Option<String>>>,
+) -> Sender<Outgoing> {
+    let (send, recv) = mpsc::channel();
+    thread::spawn(move || {
+        let result = run(&mut *stream, &writer, &stats, &recv);
+        // Queueing has to fail from here on, before the error shows
+        drop(recv);
+        if let Err(err) = result {
+            debug!("Writer thread stopped: {}", err);
+            error.lock().unwrap().replace(err.to_string());
+            let _ = stream.close();
+        }
+    });
+    send
+}
+
+fn run(
+    stream: &mut dyn Transport,
+    writer: &Mutex<WriteHalf>,
+    stats: &Mutex<NetworkStats>,
+    recv: &Receiver<Outgoing>,
+) -> Result<(), Error> {
+    let mut batch = Vec::new();
+    // Wait for the first packet of the next batch, the thread ends once
+    // every sender is gone.
+    while let Ok(mut msg) = recv.recv() {
+        let deadline = Instant::now() + MAX_BATCH_DELAY;
+        while let Outgoing::Packet {
+            name,
+            payload,
+            compression_threshold,
+        } = msg
+        {
+            // Only held while encoding, so changing the cipher doesn't
+            // wait for the batch to fill up
+            let wire_len =
+                writer
+                    .lock()
+                    .unwrap()
+                    .encode_frame(&payload, compression_threshold, &mut batch)?;
+            stats
+                .lock()
+                .unwrap()
+                .record_sent(name, wire_len, payload.len());
+
+            let now = Instant::now();
+            let timeout = if deadline > now {
+                deadline - now
+            } else {
+                Duration::from_secs(0)
+            };
+            msg = match recv.recv_timeout(timeout) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    Outgoing::Flush
+                }
+            };
+        }
+        if !batch.is_empty() {
+            stream.write_all(&batch)?;
+            stream.flush()?;
+            batch.clear();
+        }
+    }
+    Ok(())
+}
@@ -1,45 +1,77 @@
 use crate::format::Color;
+use lazy_static::lazy_static;
 use serde::{
     de::{MapAccess, SeqAccess, Visitor},
-    Deserialize, Deserializer,
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::RwLock;
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum ComponentData {
     Chat(Chat),
     Str(String),
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ClickEvent {
     pub action: String,
     pub value: String,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Contents {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<Vec<ComponentData>>,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct HoverEvent {
     pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contents: Option<Contents>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<String>,
 }
 
+/// The score of an entity in an objective, the server fills in `value`
+/// before sending it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    pub name: String,
+    pub objective: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// A node of the component tree. Its content is the first of `text`,
+/// `translate`, `score`, `selector` and `keybind` which is set, followed by
+/// the `extra` children, which inherit its style.
 #[derive(Default, Clone, PartialEq)]
 pub struct Chat {
     pub translate: Option<String>,
+    pub keybind: Option<String>,
+    pub score: Option<Score>,
+    pub selector: Option<String>,
+    /// Put between the entities a selector matched. Selectors are shown
+    /// unresolved, so it's only kept to serialize the component again.
+    pub separator: Option<Box<ComponentData>>,
 
     pub color: Option<Color>,
     pub bold: Option<bool>,
@@ -93,6 +125,10 @@ impl<'de> Visitor<'de> for ChatVisitor {
         while let Some(key) = access.next_key::<String>()? {
             match key.as_str() {
                 "translate" => chat.translate = Some(access.next_value()?),
+                "keybind" => chat.keybind = Some(access.next_value()?),
+                "score" => chat.score = Some(access.next_value()?),
+                "selector" => chat.selector = Some(access.next_value()?),
+                "separator" => chat.separator = Some(access.next_value()?),
 
                 "color" => chat.color = Some(access.next_value()?),
                 "bold" => chat.bold = Some(access.next_value()?),
@@ -116,6 +152,70 @@ impl<'de> Visitor<'de> for ChatVisitor {
         Ok(chat)
     }
 }
+
+/// Writes the set fields only, so a parsed tree serializes back the way it
+/// was sent.
+impl Serialize for Chat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(text) = &self.text {
+            map.serialize_entry("text", text)?;
+        }
+        if let Some(translate) = &self.translate {
+            map.serialize_entry("translate", translate)?;
+        }
+        if !self.with.is_empty() {
+            map.serialize_entry("with", &self.with)?;
+        }
+        if let Some(score) = &self.score {
+            map.serialize_entry("score", score)?;
+        }
+        if let Some(selector) = &self.selector {
+            map.serialize_entry("selector", selector)?;
+        }
+        if let Some(separator) = &self.separator {
+            map.serialize_entry("separator", separator)?;
+        }
+        if let Some(keybind) = &self.keybind {
+            map.serialize_entry("keybind", keybind)?;
+        }
+        if let Some(color) = &self.color {
+            map.serialize_entry("color", &color.to_string())?;
+        }
+        if let Some(bold) = self.bold {
+            map.serialize_entry("bold", &bold)?;
+        }
+        if let Some(italic) = self.italic {
+            map.serialize_entry("italic", &italic)?;
+        }
+        if let Some(underlined) = self.underlined {
+            map.serialize_entry("underlined", &underlined)?;
+        }
+        if let Some(strikethrough) = self.strikethrough {
+            map.serialize_entry("strikethrough", &strikethrough)?;
+        }
+        if let Some(obfuscated) = self.obfuscated {
+            map.serialize_entry("obfuscated", &obfuscated)?;
+        }
+        if let Some(insertion) = &self.insertion {
+            map.serialize_entry("insertion", insertion)?;
+        }
+        if let Some(click_event) = &self.click_event {
+            map.serialize_entry("clickEvent", click_event)?;
+        }
+        if let Some(hover_event) = &self.hover_event {
+            map.serialize_entry("hoverEvent", hover_event)?;
+        }
+        if let Some(extra) = &self.extra {
+            map.serialize_entry("extra", extra)?;
+        }
+        map.end()
+    }
+}
+
 impl<'de> Deserialize<'de> for ChatSections {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -139,6 +239,18 @@ impl<'de> Deserialize<'de> for ChatSections {
                 })
             }
 
+            fn visit_str<E>(self, text: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ChatSections {
+                    sections: vec![Chat {
+                        text: Some(text.to_owned()),
+                        ..Chat::default()
+                    }],
+                })
+            }
+
             fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
             where
                 A: SeqAccess<'de>,
@@ -155,12 +267,36 @@ impl<'de> Deserialize<'de> for ChatSections {
     }
 }
 
+impl Serialize for ChatSections {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.sections.as_slice() {
+            [section] => section.serialize(serializer),
+            sections => sections.serialize(serializer),
+        }
+    }
+}
+
 impl std::fmt::Debug for Chat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut dbg = f.debug_struct("Chat");
         if self.translate.is_some() {
             dbg.field("translate", &self.translate);
         }
+        if self.keybind.is_some() {
+            dbg.field("keybind", &self.keybind);
+        }
+        if self.score.is_some() {
+            dbg.field("score", &self.score);
+        }
+        if self.selector.is_some() {
+            dbg.field("selector", &self.selector);
+        }
+        if self.separator.is_some() {
+            dbg.field("separator", &self.separator);
+        }
         if self.color.is_some() {
             dbg.field("color", &self.color);
         }
@@ -201,72 +337,230 @@ impl std::fmt::Debug for Chat {
     }
 }
 
-// TODO: Add multiple language support.
-pub fn translate(str: &str) -> &str {
+/// A piece of a translation's format string
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatPart {
+    Text(String),
+    /// The index into the `with` arguments
+    Arg(usize),
+}
+
+/// Splits a format string at its `%s` and `%1$s` placeholders. Placeholders
+/// without an index take the arguments in order, `%%` is a literal `%`.
+pub fn split_format(format: &str) -> Vec<FormatPart> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut next_arg = 0;
+    let mut rest = format;
+    while let Some(i) = rest.find('%') {
+        text.push_str(&rest[..i]);
+        let spec = &rest[i + 1..];
+        if let Some(after) = spec.strip_prefix('%') {
+            text.push('%');
+            rest = after;
+            continue;
+        }
+        let digits = spec
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(spec.len());
+        let (index, after) = match spec[digits..].strip_prefix('$') {
+            Some(after) if digits > 0 => (
+                spec[..digits]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1)),
+                after,
+            ),
+            _ => (None, spec),
+        };
+        match after.strip_prefix(['s', 'd']) {
+            Some(after) => {
+                if !text.is_empty() {
+                    parts.push(FormatPart::Text(std::mem::take(&mut text)));
+                }
+                parts.push(FormatPart::Arg(index.unwrap_or_else(|| {
+                    next_arg += 1;
+                    next_arg - 1
+                })));
+                rest = after;
+            }
+            None => {
+                text.push('%');
+                rest = spec;
+            }
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(FormatPart::Text(text));
+    }
+    parts
+}
+
+type KeybindResolver = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+lazy_static! {
+    static ref KEYBIND_RESOLVER: RwLock<Option<KeybindResolver>> = RwLock::new(None);
+}
+
+/// Sets where the names of the keys the user bound to key bindings like
+/// `key.jump` come from. Bindings it doesn't know use the vanilla default.
+pub fn set_keybind_resolver<F>(resolver: F)
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    *KEYBIND_RESOLVER.write().unwrap() = Some(Box::new(resolver));
+}
+
+/// Returns the name of the key bound to a key binding, or the binding
+/// itself if it is unknown.
+pub fn keybind(name: &str) -> String {
+    if let Some(key) = KEYBIND_RESOLVER
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|resolver| resolver(name))
+    {
+        return key;
+    }
+    default_keybind(name).to_owned()
+}
+
+/// The key vanilla binds a key binding to by default
+fn default_keybind(name: &str) -> &str {
+    match name {
+        "key.forward" => "W",
+        "key.left" => "A",
+        "key.back" => "S",
+        "key.right" => "D",
+        "key.jump" => "Space",
+        "key.sneak" => "Left Shift",
+        "key.sprint" => "Left Control",
+        "key.inventory" => "E",
+        "key.swapOffhand" => "F",
+        "key.drop" => "Q",
+        "key.use" => "Right Button",
+        "key.attack" => "Left Button",
+        "key.pickItem" => "Middle Button",
+        "key.chat" => "T",
+        "key.playerlist" => "Tab",
+        "key.command" => "/",
+        "key.socialInteractions" => "P",
+        "key.screenshot" => "F2",
+        "key.togglePerspective" => "F5",
+        "key.fullscreen" => "F11",
+        "key.advancements" => "L",
+        "key.hotbar.1" => "1",
+        "key.hotbar.2" => "2",
+        "key.hotbar.3" => "3",
+        "key.hotbar.4" => "4",
+        "key.hotbar.5" => "5",
+        "key.hotbar.6" => "6",
+        "key.hotbar.7" => "7",
+        "key.hotbar.8" => "8",
+        "key.hotbar.9" => "9",
+        _ => name,
+    }
+}
+
+lazy_static! {
+    static ref LANGUAGE: RwLock<HashMap<String, String>> = RwLock::new(HashMap::new());
+}
+
+/// Replaces the translations of the selected language, as read from its
+/// language files.
+pub fn set_language(translations: HashMap<String, String>) {
+    *LANGUAGE.write().unwrap() = translations;
+}
+
+/// Returns the format string of a translation key in the selected language,
+/// or the key itself if it is unknown. Arguments are referenced by `%s` or
+/// `%1$s`.
+pub fn translate(key: &str) -> String {
+    if let Some(translation) = LANGUAGE.read().unwrap().get(key) {
+        return translation.clone();
+    }
+    english(key).to_owned()
+}
+
+/// Translates the key and fills in plain text arguments, for strings of
+/// the UI.
+pub fn translate_with(key: &str, args: &[&str]) -> String {
+    split_format(&translate(key))
+        .into_iter()
+        .map(|part| match part {
+            FormatPart::Text(text) => text,
+            FormatPart::Arg(index) => args.get(index).copied().unwrap_or_default().to_owned(),
+        })
+        .collect()
+}
+
+/// The translations used until the language files are loaded
+fn english(str: &str) -> &str {
     match str {
             "commands.message.sameTarget" => "You can't send a private message to yourself!",
-            "commands.op.success" => "Made {} a server operator",                       // minecraft 1.12.2 - "Opped {}"
+            "commands.op.success" => "Made %s a server operator",                       // minecraft 1.12.2 - "Opped %s"
             "commands.op.failed" => "Nothing changed. The player alreadys an operator", // Does not exits in minecraf 1.12.2
-            "commands.deop.success" => "Made {} no longer a server operator",           // minecraft 1.12.2 - "De-opped {}"
-            "commands.deop.failed" => "Nothing changed. The player is not an operator", // minecraft 1.12.2 - "Could not de-op {}"
-            "commands.time.set" => "Set the time to {}",
-            "commands.time.query" => "Time is {}",
-            "commands.time.add" => "Added {} to the time",
-            "commands.tp.success.coordinates" => "Teleported {} to {}, {}, {}",
-            "commands.teleport.success.location.single" => "Teleported {} to {}, {}, {}",
-            "commands.tp.success" => "Teleported {} to {}",
-            "commands.teleport.success.entity.single" => "Teleported {} to {}",
-            "commands.players.list" => "There are {}/{} players online:",
-            "commands.fill.success" => "{} blocks filled",
-            "commands.function.unknown" => "Unknown function '{}'",
-            "commands.gamerule.success" => "Game rule {} has been updated to {}",
-            "commands.kick.success.reason" => "Kicked {} from the game: {}",
-            "commands.kick.success" => "Kicked {} from the game",
-            "commands.kill.successful" => "Killed {}",
-            "commands.locate.success" => "Located {} at {} (y?) {}",
-            "commands.locate.failure" => "Unable to locate any {} feature",
+            "commands.deop.success" => "Made %s no longer a server operator",           // minecraft 1.12.2 - "De-opped %s"
+            "commands.deop.failed" => "Nothing changed. The player is not an operator", // minecraft 1.12.2 - "Could not de-op %s"
+            "commands.time.set" => "Set the time to %s",
+            "commands.time.query" => "Time is %s",
+            "commands.time.add" => "Added %s to the time",
+            "commands.tp.success.coordinates" => "Teleported %s to %s, %s, %s",
+            "commands.teleport.success.location.single" => "Teleported %s to %s, %s, %s",
+            "commands.tp.success" => "Teleported %s to %s",
+            "commands.teleport.success.entity.single" => "Teleported %s to %s",
+            "commands.players.list" => "There are %s/%s players online:",
+            "commands.fill.success" => "%s blocks filled",
+            "commands.function.unknown" => "Unknown function '%s'",
+            "commands.gamerule.success" => "Game rule %s has been updated to %s",
+            "commands.kick.success.reason" => "Kicked %s from the game: %s",
+            "commands.kick.success" => "Kicked %s from the game",
+            "commands.kill.successful" => "Killed %s",
+            "commands.locate.success" => "Located %s at %s (y?) %s",
+            "commands.locate.failure" => "Unable to locate any %s feature",
             "commands.reload.success" => "Successfully reloaded loot tables, advancements and functions",
-            "commands.recipe.give.success.one" => "Successfully given {} the recipe for {}",
-            "commands.recipe.alreadyHave" => "Player {} already has a recipe for {}",
-            "commands.seed.success" => "Seed: {}",
+            "commands.recipe.give.success.one" => "Successfully given %s the recipe for %s",
+            "commands.recipe.alreadyHave" => "Player %s already has a recipe for %s",
+            "commands.seed.success" => "Seed: %s",
             "commands.setblock.success" => "Block placed",
-            "commands.clear.success" => "Cleared the inventory of {}, removing {} items",
+            "commands.clear.success" => "Cleared the inventory of %s, removing %s items",
             "commands.weather.clear" => "Changing to clear weather",
             "commands.weather.set.clear" => "Set the weather to clear",
             "commands.weather.rain" => "Changing to rainy weather",
             "commands.weather.thunder" => "Changing to rain and thunder",
-            "commands.setidletimeout.success" => "Successfully set the idle timeout to {} minutes.",
-            "commands.setworldspawn.success" => "Set the world spawn point to ({}, {}, {})",
-            "commands.spreadplayers.success.players" => "Successfully spread {} player(s) around {} {}",
-            "commands.spreadplayers.spreading.players" => "Spreading {} player(s) {} blocks around {}, {} (min {} blocks apart)",
-            "commands.spreadplayers.info.players" => "(Average distance between players is {} blocks apart after {} iterations)",
+            "commands.setidletimeout.success" => "Successfully set the idle timeout to %s minutes.",
+            "commands.setworldspawn.success" => "Set the world spawn point to (%s, %s, %s)",
+            "commands.spreadplayers.success.players" => "Successfully spread %s player(s) around %s %s",
+            "commands.spreadplayers.spreading.players" => "Spreading %s player(s) %s blocks around %s, %s (min %s blocks apart)",
+            "commands.spreadplayers.info.players" => "(Average distance between players is %s blocks apart after %s iterations)",
             "commands.summon.success" => "Object successfully summoned",
             "commands.stop.start" => "Server stopping...",
-            "commands.stopsound.success.soundSource" => "Stopped source '{}' for {}",
-            "commands.testfor.success" => "Found {}",
+            "commands.stopsound.success.soundSource" => "Stopped source '%s' for %s",
+            "commands.testfor.success" => "Found %s",
             "commands.downfall.success" => "Toggled downfall",
 
-            "commands.worldborder.get.success" => "World border is currently {} blocks wide",
-            "commands.worldborder.setSlowly.grow.success" => "Growing world border to {} blocks wide (up from {} blocks) over {} seconds",
-            "commands.worldborder.setSlowly.shrink.success" => "Shrinking world border to {} blocks wide (down from {} blocks) over {} seconds",
-            "commands.worldborder.center.success" => "Set world border center to {}, {}",
-            "commands.worldborder.damage.buffer.success" => "Set world border damage buffer to {} blocks (from {} blocks)",
-            "commands.worldborder.damage.amount.success" => "Set world border damage amount to {} per block (from {} per block}",
-            "commands.worldborder.warning.time.success" => "Set world border warning to {} seconds away (from {} seconds)",
-            "commands.worldborder.warning.distance.success" => "Set world border warning to {} blocks away (from {} blocks)",
+            "commands.worldborder.get.success" => "World border is currently %s blocks wide",
+            "commands.worldborder.setSlowly.grow.success" => "Growing world border to %s blocks wide (up from %s blocks) over %s seconds",
+            "commands.worldborder.setSlowly.shrink.success" => "Shrinking world border to %s blocks wide (down from %s blocks) over %s seconds",
+            "commands.worldborder.center.success" => "Set world border center to %s, %s",
+            "commands.worldborder.damage.buffer.success" => "Set world border damage buffer to %s blocks (from %s blocks)",
+            "commands.worldborder.damage.amount.success" => "Set world border damage amount to %s per block (from %s per block)",
+            "commands.worldborder.warning.time.success" => "Set world border warning to %s seconds away (from %s seconds)",
+            "commands.worldborder.warning.distance.success" => "Set world border warning to %s blocks away (from %s blocks)",
 
             "commands.whitelist.reloaded" => "Reload the whitelist",
-            "commands.whitelist.add.success" => "Added {} to the whitelist",
-            "commands.whitelist.remove.success" => "Removed {} from the whitelist",
-            "commands.whitelist.list" => "There are {} whitelisted players: {}",            // This would not work right on minecraft 1.12.2. Format for 1.12.2 "There are {} (out of {} seen) whitelisted players:"
+            "commands.whitelist.add.success" => "Added %s to the whitelist",
+            "commands.whitelist.remove.success" => "Removed %s from the whitelist",
+            "commands.whitelist.list" => "There are %s whitelisted players: %s",            // This would not work right on minecraft 1.12.2. Format for 1.12.2 "There are %s (out of %s seen) whitelisted players:"
             "commands.whitelist.none" => "There are no whitelisted players",
             "commands.whitelist.enabled" => "Whitelist is now turned on",                   // 1.12.2 format Turned on the whitelist
             "commands.whitelist.disabled" => "Whitelist is now turned off",                 // 1.12.2 format "Turned off the whitelist"
 
 
-            "commands.testforblock.success" => "Successfully found the block at {}, {}, {}",
-            "commands.testforblock.failed.tile" => "The block at {}, {}, {} is {} (expected: {})",
-            "commands.compare.success" => "{} blocks compared",
+            "commands.testforblock.success" => "Successfully found the block at %s, %s, %s",
+            "commands.testforblock.failed.tile" => "The block at %s, %s, %s is %s (expected: %s)",
+            "commands.compare.success" => "%s blocks compared",
             "commands.compare.failed" => "Source and destination are not identical",
 
             "commands.save.start" => "Saving...",
@@ -296,140 +590,140 @@ pub fn translate(str: &str) -> &str {
             "commands.scoreboard.teams.list.empty" => "There are no teams registered on the scoreboard",
             "commands.scoreboard.teams.option.usage" => "/scoreboard teams option <team> <friendlyfire:color:seeFriendlyInvisibles:nameTagVisibility:deathMessageVisibility:collisionRule> <value>",
             "commands.scoreboard.teams.add.usage" => "/scoreboard teams add <name> [display name ...]",
-            "commands.scoreboard.teams.add.success" => "Added new team '{}' successfully",
-            "commands.scoreboard.teams.add.alreadyExists" => "A team with the name '{}' already exists",
+            "commands.scoreboard.teams.add.success" => "Added new team '%s' successfully",
+            "commands.scoreboard.teams.add.alreadyExists" => "A team with the name '%s' already exists",
             "commands.scoreboard.teams.remove.usage" => "/scoreboard teams remove <name>",
             "commands.scoreboard.teams.join.usage" => "/scoreboard teams join <team> [player]",
-            "commands.scoreboard.teams.join.success" => "Added {} player(s) to team {}: {}",
-            "commands.scoreboard.teams.leave.success" => "Removed {} player(s) from their teams: {}",
-            "commands.scoreboard.teams.leave.failure" => "Could not remove {} player(s) from their teams: {}",
-            "commands.scoreboard.teams.list.count" => "Showing {} teams on the scoreboard:",
-            "commands.scoreboard.teams.list.entry" => "- {}: '{}' has 1 players",
-            "commands.scoreboard.teams.list.player.count" => "Showing {} player(s) in team {}:",
+            "commands.scoreboard.teams.join.success" => "Added %s player(s) to team %s: %s",
+            "commands.scoreboard.teams.leave.success" => "Removed %s player(s) from their teams: %s",
+            "commands.scoreboard.teams.leave.failure" => "Could not remove %s player(s) from their teams: %s",
+            "commands.scoreboard.teams.list.count" => "Showing %s teams on the scoreboard:",
+            "commands.scoreboard.teams.list.entry" => "- %s: '%s' has 1 players",
+            "commands.scoreboard.teams.list.player.count" => "Showing %s player(s) in team %s:",
 
-            "commands.replaceitem.noContainer" => "Block at {}, {}, {} is not a container",
-            "commands.generic.parameter.invalid" => "'{}' is not a valid parameter",
-            "commands.replaceitem.success" => "Replaced slot {} with {} * {}",
+            "commands.replaceitem.noContainer" => "Block at %s, %s, %s is not a container",
+            "commands.generic.parameter.invalid" => "'%s' is not a valid parameter",
+            "commands.replaceitem.success" => "Replaced slot %s with %s * %s",
 
-            "commands.unban.success" => "Unbanned player {}",
-            "commands.unban.failed" => "Could not unban player {}",
-            "commands.ban.success" => "Banned player {}",
+            "commands.unban.success" => "Unbanned player %s",
+            "commands.unban.failed" => "Could not unban player %s",
+            "commands.ban.success" => "Banned player %s",
 
 
-            "commands.xp.success" => "Given {} experience to {}",
-            "commands.xp.success.levels" => "Given {} levels to {}",
-            "commands.xp.success.negative.levels" => "Taken {} levels from {}",
+            "commands.xp.success" => "Given %s experience to %s",
+            "commands.xp.success.levels" => "Given %s levels to %s",
+            "commands.xp.success.negative.levels" => "Taken %s levels from %s",
             "commands.xp.failure.widthdrawXp" => "Cannot give player negative experience points",
 
-            "commands.experience.add.points.success.single" => "Gave {} experience points to {}",
-            "commands.experience.add.points.success.multiple" => "Gave {} experience points to {} players",
-            "commands.experience.set.points.success.single" => "Set {} experience points on {}",
-            "commands.experience.set.points.success.multiple" => "Set {} experience points on {} players",
-            "commands.experience.query.points" => "{} has {} experience points",
+            "commands.experience.add.points.success.single" => "Gave %s experience points to %s",
+            "commands.experience.add.points.success.multiple" => "Gave %s experience points to %s players",
+            "commands.experience.set.points.success.single" => "Set %s experience points on %s",
+            "commands.experience.set.points.success.multiple" => "Set %s experience points on %s players",
+            "commands.experience.query.points" => "%s has %s experience points",
 
-            "commands.experience.add.levels.success.single" => "Gave {} experience levels to {}",
-            "commands.experience.add.levels.success.multiple" => "Gave {} experience levels to {} players",
-            "commands.experience.set.levels.success.single" => "Set {} experience levels on {}",
-            "commands.experience.set.levels.success.multiple" => "Set {} experience levels on {} players",
-            "commands.experience.query.levels" => "{} has {} experience levels",
+            "commands.experience.add.levels.success.single" => "Gave %s experience levels to %s",
+            "commands.experience.add.levels.success.multiple" => "Gave %s experience levels to %s players",
+            "commands.experience.set.levels.success.single" => "Set %s experience levels on %s",
+            "commands.experience.set.levels.success.multiple" => "Set %s experience levels on %s players",
+            "commands.experience.query.levels" => "%s has %s experience levels",
 
 
             "commands.enchant.success" => "Enchanting succeeded",
             "commands.enchant.noItem" => "The target doesn't hold an item",
 
             "commands.debug.start" => "Started debug profiling",
-            "commands.debug.stop" => "Stopped debug profiling after {} seconds ({} ticks)",
+            "commands.debug.stop" => "Stopped debug profiling after %s seconds (%s ticks)",
 
-            "commands.difficulty.success" => "Set game difficulty to {}",
+            "commands.difficulty.success" => "Set game difficulty to %s",
             "options.difficulty.easy" => "Easy",
             "options.difficulty.normal" => "Normal",
             "options.difficulty.hard" => "Hard",
 
 
-            "commands.advancement.revoke.through.success" => "Revoked '{}', all ancestors and all descendants ({} total revoked) from {}",
-            "commands.advancement.revoke.everything.success" => "Revoked every advancement ({} total revoked) from {}",
-            "commands.advancement.revoke.from.success" => "Revoked '{}' and all descendants ({} total revoked) from {}",
-            "commands.advancement.revoke.from.failed" => "Couln't revoke the advancement '{}' or its descendants from {} because they haven't started any",
-            "commands.advancement.revoke.only.success" => "Revoked the entire advancement '{}' from {}",
-            "commands.advancement.revoke.only.failed" => "Couldn't revoke the advancement '{}' from {} because they haven't started it",
+            "commands.advancement.revoke.through.success" => "Revoked '%s', all ancestors and all descendants (%s total revoked) from %s",
+            "commands.advancement.revoke.everything.success" => "Revoked every advancement (%s total revoked) from %s",
+            "commands.advancement.revoke.from.success" => "Revoked '%s' and all descendants (%s total revoked) from %s",
+            "commands.advancement.revoke.from.failed" => "Couln't revoke the advancement '%s' or its descendants from %s because they haven't started any",
+            "commands.advancement.revoke.only.success" => "Revoked the entire advancement '%s' from %s",
+            "commands.advancement.revoke.only.failed" => "Couldn't revoke the advancement '%s' from %s because they haven't started it",
 
-            "commands.advancement.grant.only.success" => "Granted the entire advancement '{}' to {}",
-            "commands.advancement.grant.only.failed" => "Couldn't grant the advancement '{}' to {} because they already have it",
-            "commands.advancement.grant.from.success" => "Granted '{}' and all descendants ({} total granted) to {}",
-            "commands.advancement.grant.from.failed" => "Couldn't grant the advancement '{}' or its descendants to {} because they already have them all",
-            "commands.advancement.grant.everything.success" => "Granted every advancement ({} total granted) to {}",
-            "commands.advancement.grant.everything.failed" => "Couldn't grant any advancements to {} because they already have them all",
+            "commands.advancement.grant.only.success" => "Granted the entire advancement '%s' to %s",
+            "commands.advancement.grant.only.failed" => "Couldn't grant the advancement '%s' to %s because they already have it",
+            "commands.advancement.grant.from.success" => "Granted '%s' and all descendants (%s total granted) to %s",
+            "commands.advancement.grant.from.failed" => "Couldn't grant the advancement '%s' or its descendants to %s because they already have them all",
+            "commands.advancement.grant.everything.success" => "Granted every advancement (%s total granted) to %s",
+            "commands.advancement.grant.everything.failed" => "Couldn't grant any advancements to %s because they already have them all",
 
-            "commands.advancement.grant.many.to.one.success" => "Granted {} advancements to {}",
-            "commands.advancement.grant.one.to.many.success" => "Granted the advancement {} to {} players",
-            "commands.advancement.advancementNotFound" => "No advancement was found by the name '{}'",
+            "commands.advancement.grant.many.to.one.success" => "Granted %s advancements to %s",
+            "commands.advancement.grant.one.to.many.success" => "Granted the advancement %s to %s players",
+            "commands.advancement.advancementNotFound" => "No advancement was found by the name '%s'",
 
 
             "commands.datapack.list.available.none" => "There are no more data packs available",
-            "commands.datapack.list.enabled.success" => "There are {} data packs enabled: {}",
-            "pack.nameAndSource" => "{} {}",
+            "commands.datapack.list.enabled.success" => "There are %s data packs enabled: %s",
+            "pack.nameAndSource" => "%s %s",
             "pack.source.builtin" => "built-in",
-            "commands.execute.failed" => "Failed to execute '{}' as {}",
+            "commands.execute.failed" => "Failed to execute '%s' as %s",
             "commands.generic.player.unspecified" => "You must specify which player you wish to perform this action on.",
-            "commands.message.display.outgoing" => "You whisper to {}: {}",
-            "commands.message.display.incoming" => "{} whispers to you: {}",
-            "commands.generic.usage" => "Usage: {}",
+            "commands.message.display.outgoing" => "You whisper to %s: %s",
+            "commands.message.display.incoming" => "%s whispers to you: %s",
+            "commands.generic.usage" => "Usage: %s",
             "commands.generic.notFound" => "Unknown command. Try /help for a list of commands",
             "command.unknown.command" => "Unknown or incomplete command. see below for error",
             "command.unknown.argument" => "Incorrect argument for command",
             "command.context.here" => "<--[HERE]",
-            "commands.generic.entity.notFound" => "Entity '{}' cannot be found",
+            "commands.generic.entity.notFound" => "Entity '%s' cannot be found",
             "argument.entity.notfound.entity" => "No entity was found",
-            "commands.generic.player.notFound" => "Player '{}' cannot be found",
+            "commands.generic.player.notFound" => "Player '%s' cannot be found",
             "argument.entity.notfound.player" => "No player was found",
             "argument.player.toomany" => "Only one player is allowed, but the provided selector allows more than one",
             "argument.player.entities" => "Only players may be affected by this command. provided selector includes entities",
-            "argument.item.id.invalid" => "Unknown item '{}'",
-            "argument.component.invalid" => "Invalid chat component: {}",
-            "commands.generic.num.invalid" => "'{}' is not a valid number",
-            "commands.generic.boolean.invalid" => "'{}' is not true or false",
+            "argument.item.id.invalid" => "Unknown item '%s'",
+            "argument.component.invalid" => "Invalid chat component: %s",
+            "commands.generic.num.invalid" => "'%s' is not a valid number",
+            "commands.generic.boolean.invalid" => "'%s' is not true or false",
             "commands.generic.help" => "Usage: /help [page:command name]",
-            "commands.generic.num.tooBig" => "The number you have entered ({}) is too big, it must be at most {}",
-            "commands.generic.num.tooSmall" => "The number you have entered ({}) is too small, it must be at least {}",
+            "commands.generic.num.tooBig" => "The number you have entered (%s) is too big, it must be at most %s",
+            "commands.generic.num.tooSmall" => "The number you have entered (%s) is too small, it must be at least %s",
 
-            "commands.give.success" => "Given {} * {} to {}",
-            "commands.give.success.single" => "Gave {} {} to {}",
-            "commands.give.item.notFound" => "There is no such item with name: {}",
-            "commands.give.tagError" => "Data tag parsing failed: {}",
-            "commands.give.block.notFound" => "There is no such block with name {}",
+            "commands.give.success" => "Given %s * %s to %s",
+            "commands.give.success.single" => "Gave %s %s to %s",
+            "commands.give.item.notFound" => "There is no such item with name: %s",
+            "commands.give.tagError" => "Data tag parsing failed: %s",
+            "commands.give.block.notFound" => "There is no such block with name %s",
 
-            "multiplayer.player.joined" => "{} joined the game",
-            "multiplayer.player.left" => "{} left the game",
+            "multiplayer.player.joined" => "%s joined the game",
+            "multiplayer.player.left" => "%s left the game",
             "multiplayer.disconnect.kicked" => "Kicked by an operator.",
             "multiplayer.disconnect.server_shutdown" => "Server closed",
 
-            "chat.type.admin" => "[{}: {}]",
-            "chat.type.text" => "<{}> {}",
-            "chat.type.announcement" => "[{}] {}",
-            "chat.type.emote" => "* {} {}",
-            "chat.square_brackets" => "[{}]",
+            "chat.type.admin" => "[%s: %s]",
+            "chat.type.text" => "<%s> %s",
+            "chat.type.announcement" => "[%s] %s",
+            "chat.type.emote" => "* %s %s",
+            "chat.square_brackets" => "[%s]",
 
-            "chat.type.advancement.task" => "{} has made the advancement {}",
-            "chat.type.advancement.challenge" => "{} has completed the challenge {}",
-            "chat.type.advancement.goal" => "{} has reached the goal {}",
-            "commands.tellraw.jsonException" => "Invalid json: {}",
+            "chat.type.advancement.task" => "%s has made the advancement %s",
+            "chat.type.advancement.challenge" => "%s has completed the challenge %s",
+            "chat.type.advancement.goal" => "%s has reached the goal %s",
+            "commands.tellraw.jsonException" => "Invalid json: %s",
 
-            "gameMode.changed" => "Your game mode has been updated to {}",
-            "commands.gamemode.success.self" => "Set own game mode to {}",
-            "commands.gamemode.success.other" => "Set {}'s game mode to {}",
+            "gameMode.changed" => "Your game mode has been updated to %s",
+            "commands.gamemode.success.self" => "Set own game mode to %s",
+            "commands.gamemode.success.other" => "Set %s's game mode to %s",
             "gameMode.creative" => "Creative Mode",
             "gameMode.adventure" => "Adventure Mode",
             "gameMode.survival" => "Survival Mode",
             "gameMode.spectator" => "Spectator Mode",
 
-            "death.attack.mob" => "{} was slain by {}",
-            "death.attack.arrow" => "{} was shot by {}",
-            "death.attack.player" => "{} was slain by {}",
-            "death.attack.explosion.player" => "{} was blown up by {}",
-            "death.attack.lava" => "{} tried to swim in lava",
-            "death.attack.drowned" => "{} drowned",
-            "death.attack.outOfWorld" => "{} fell out of the world",
-            "death.fell.accident.generic" => "{} fell from a high place",
+            "death.attack.mob" => "%s was slain by %s",
+            "death.attack.arrow" => "%s was shot by %s",
+            "death.attack.player" => "%s was slain by %s",
+            "death.attack.explosion.player" => "%s was blown up by %s",
+            "death.attack.lava" => "%s tried to swim in lava",
+            "death.attack.drowned" => "%s drowned",
+            "death.attack.outOfWorld" => "%s fell out of the world",
+            "death.fell.accident.generic" => "%s fell from a high place",
 
 
             "advancements.adventure.kill_a_mob.title" => "Monster Hunter",
@@ -455,18 +749,18 @@ pub fn translate(str: &str) -> &str {
             "advancements.end.respawn_dragon.title" => "The End... Again...",
 
 
-            "commands.help.header" => "--- Showing help page {} of {} (/help <page>) ---",
+            "commands.help.header" => "--- Showing help page %s of %s (/help <page>) ---",
             "commands.help.footer" => "Tip Use the <tab> key while typing a command to auto-complete the command or its arguments",
             "commands.help.failed" => "Unknown command or insufficient permissions",
             "commands.advancement.usage" => "/advancement <grant:revoke:test> <player>",
             "commands.ban.usage" => "/ban <name> [reason ...]",
             "commands.banip.usage" => "/ban-ip <address:name> [reason ...]",
-            "commands.unbanip.success" => "Unbanned IP address {}",
+            "commands.unbanip.success" => "Unbanned IP address %s",
             "commands.unbanip.invalid" => "You have entered an invalid IP address",
-            "commands.banip.success" => "Banned IP address {}",
+            "commands.banip.success" => "Banned IP address %s",
             "commands.banlist.usage" => "/banlist [ips:players]",
-            "commands.banlist.players" => "There are {} total banned players:",
-            "commands.banlist.ips" => "There are {} total banned IP addresses:",
+            "commands.banlist.players" => "There are %s total banned players:",
+            "commands.banlist.ips" => "There are %s total banned IP addresses:",
 
             "commands.title.success" => "Title command successfully executed",
 
@@ -540,6 +834,39 @@ pub fn translate(str: &str) -> &str {
             "commands.worldborder.warning.usage" => "/worldborder warning <time:distance> ...",
             "commands.xp.usage" => "/xp <amount> [player] OR /xp <amount>L [player]",
 
+            "gui.done" => "Done",
+            "gui.cancel" => "Cancel",
+            "menu.disconnect" => "Disconnect",
+            "selectServer.refresh" => "Refresh",
+            "options.sounds" => "Music & Sounds...",
+            "options.percent_value" => "%s: %s%%",
+            "soundCategory.master" => "Master Volume",
+            "soundCategory.music" => "Music",
+            "soundCategory.record" => "Jukebox/Note Blocks",
+            "soundCategory.weather" => "Weather",
+            "soundCategory.block" => "Blocks",
+            "soundCategory.hostile" => "Hostile Creatures",
+            "soundCategory.neutral" => "Friendly Creatures",
+            "soundCategory.player" => "Players",
+            "soundCategory.ambient" => "Ambient/Environment",
+            "soundCategory.voice" => "Voice/Speech",
+            "options.video" => "Video Settings...",
+            "options.controls" => "Controls...",
+            "options.language" => "Language...",
+            "options.languageWarning" => "Language translations may not be 100%% accurate",
+            "options.skinCustomisation" => "Skin Customization...",
+            "deathScreen.title" => "You Died!",
+            "deathScreen.respawn" => "Respawn",
+            "deathScreen.titleScreen" => "Title Screen",
+            "gui.toMenu" => "Back to server list",
+            "lanServer.scanning" => "Scanning for games on your local network",
+            "leafish.connecting" => "Connecting to",
+            "leafish.disconnected" => "Disconnected",
+            "leafish.reconnect" => "Reconnect",
+            "leafish.reconnecting" => "Reconnecting in %ss",
+            "leafish.lanWorlds" => "LAN worlds",
+            "leafish.lanUnavailable" => "LAN discovery is unavailable: %s",
+
             "block.minecraft.dirt" => "Dirt",
             "item.minecraft.diamond" => "Diamond",
 
@@ -62,6 +62,14 @@ impl Metadata {
         self.map.insert(index, val.wrap());
     }
 
+    /// Entries ordered by index, so the same metadata always encodes to the
+    /// same bytes.
+    fn sorted_entries(&self) -> Vec<(&i32, &Value)> {
+        let mut entries = self.map.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(k, _)| **k);
+        entries
+    }
+
     fn read_from18<R: io::Read>(buf: &mut R) -> Result<Self, protocol::Error> {
         let mut m = Self::new();
         loop {
@@ -102,7 +110,7 @@ impl Metadata {
     }
 
     fn write_to18<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
-        for (k, v) in &self.map {
+        for (k, v) in self.sorted_entries() {
             if (*k as u8) > 0x1f {
                 panic!("write metadata index {:x} > 0x1f", *k as u8);
             }
@@ -136,13 +144,13 @@ impl Metadata {
                     u8::write_to(&(ty_index | (5 << TYPE_SHIFT)), buf)?;
                     val.write_to(buf)?;
                 }
-                Value::Vector(ref val) => {
+                Value::Rotation(ref val) => {
                     u8::write_to(&(ty_index | (6 << TYPE_SHIFT)), buf)?;
                     val[0].write_to(buf)?;
                     val[1].write_to(buf)?;
                     val[2].write_to(buf)?;
                 }
-                Value::Rotation(ref val) => {
+                Value::Vector(ref val) => {
                     u8::write_to(&(ty_index | (7 << TYPE_SHIFT)), buf)?;
                     val[0].write_to(buf)?;
                     val[1].write_to(buf)?;
@@ -215,7 +223,7 @@ impl Metadata {
     }
 
     fn write_to19<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
-        for (k, v) in &self.map {
+        for (k, v) in self.sorted_entries() {
             (*k as u8).write_to(buf)?;
             match *v {
                 Value::Byte(ref val) => {
@@ -271,13 +279,12 @@ impl Metadata {
                     val.write_to(buf)?;
                 }
                 Value::Block(ref val) => {
-                    u8::write_to(&11, buf)?;
+                    u8::write_to(&12, buf)?;
                     protocol::VarInt(*val as i32).write_to(buf)?;
                 }
-                Value::NBTTag(ref _val) => {
+                Value::NBTTag(ref val) => {
                     u8::write_to(&13, buf)?;
-                    // TODO: write NBT tags metadata
-                    //nbt::Tag(*val).write_to(buf)?;
+                    write_named_tag(val, buf)?;
                 }
                 _ => panic!("unexpected metadata"),
             }
@@ -340,7 +347,7 @@ impl Metadata {
                         m.put_raw(index, nbt::NamedTag(name, tag));
                     }
                 }
-                15 => panic!("TODO: particle"),
+                15 => m.put_raw(index, ParticleData::read_from(buf)?),
                 16 => m.put_raw(index, VillagerData::read_from(buf)?),
                 17 => {
                     if bool::read_from(buf)? {
@@ -350,6 +357,31 @@ impl Metadata {
                     }
                 }
                 18 => m.put_raw(index, PoseData::read_from(buf)?),
+                19 => {
+                    m.map
+                        .insert(index, Value::CatVariant(protocol::VarInt::read_from(buf)?));
+                }
+                20 => {
+                    m.map
+                        .insert(index, Value::FrogVariant(protocol::VarInt::read_from(buf)?));
+                }
+                21 => {
+                    let val = if bool::read_from(buf)? {
+                        Some(GlobalPosition {
+                            dimension: String::read_from(buf)?,
+                            position: Position::read_from(buf)?,
+                        })
+                    } else {
+                        None
+                    };
+                    m.put_raw(index, val);
+                }
+                22 => {
+                    m.map.insert(
+                        index,
+                        Value::PaintingVariant(protocol::VarInt::read_from(buf)?),
+                    );
+                }
                 _ => return Err(protocol::Error::Err("unknown metadata type".to_owned())),
             }
         }
@@ -357,7 +389,7 @@ impl Metadata {
     }
 
     fn write_to113<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
-        for (k, v) in &self.map {
+        for (k, v) in self.sorted_entries() {
             (*k as u8).write_to(buf)?;
             match *v {
                 Value::Byte(ref val) => {
@@ -420,10 +452,9 @@ impl Metadata {
                     u8::write_to(&13, buf)?;
                     protocol::VarInt(*val as i32).write_to(buf)?;
                 }
-                Value::NBTTag(ref _val) => {
+                Value::NBTTag(ref val) => {
                     u8::write_to(&14, buf)?;
-                    // TODO: write NBT tags metadata
-                    //nbt::Tag(*val).write_to(buf)?;
+                    write_named_tag(val, buf)?;
                 }
                 Value::Particle(ref val) => {
                     u8::write_to(&15, buf)?;
@@ -441,6 +472,26 @@ impl Metadata {
                     u8::write_to(&18, buf)?;
                     val.write_to(buf)?;
                 }
+                Value::CatVariant(ref val) => {
+                    u8::write_to(&19, buf)?;
+                    val.write_to(buf)?;
+                }
+                Value::FrogVariant(ref val) => {
+                    u8::write_to(&20, buf)?;
+                    val.write_to(buf)?;
+                }
+                Value::OptionalGlobalPosition(ref val) => {
+                    u8::write_to(&21, buf)?;
+                    val.is_some().write_to(buf)?;
+                    if let Some(val) = val {
+                        val.dimension.write_to(buf)?;
+                        val.position.write_to(buf)?;
+                    }
+                }
+                Value::PaintingVariant(ref val) => {
+                    u8::write_to(&22, buf)?;
+                    val.write_to(buf)?;
+                }
                 _ => panic!("unexpected metadata"),
             }
         }
@@ -514,6 +565,23 @@ pub enum Value {
     Villager(VillagerData),
     OptionalVarInt(Option<protocol::VarInt>),
     Pose(PoseData),
+    CatVariant(protocol::VarInt),
+    FrogVariant(protocol::VarInt),
+    OptionalGlobalPosition(Option<GlobalPosition>),
+    PaintingVariant(protocol::VarInt),
+}
+
+fn write_named_tag<W: io::Write>(tag: &nbt::NamedTag, buf: &mut W) -> Result<(), protocol::Error> {
+    u8::write_to(&10, buf)?;
+    nbt::write_string(buf, &tag.0)?;
+    tag.1.write_to(buf)
+}
+
+/// A position in a specific dimension, e.g. the last death location
+#[derive(Debug)]
+pub struct GlobalPosition {
+    pub dimension: String,
+    pub position: Position,
 }
 
 #[derive(Debug)]
@@ -650,19 +718,86 @@ impl Serializable for ParticleData {
         })
     }
 
-    fn write_to<W: io::Write>(&self, _buf: &mut W) -> Result<(), protocol::Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
+        let id = match self {
+            ParticleData::AmbientEntityEffect => 0,
+            ParticleData::AngryVillager => 1,
+            ParticleData::Barrier => 2,
+            ParticleData::Block { .. } => 3,
+            ParticleData::Bubble => 4,
+            ParticleData::Cloud => 5,
+            ParticleData::Crit => 6,
+            ParticleData::DamageIndicator => 7,
+            ParticleData::DragonBreath => 8,
+            ParticleData::DrippingLava => 9,
+            ParticleData::DrippingWater => 10,
+            ParticleData::Dust { .. } => 11,
+            ParticleData::Effect => 12,
+            ParticleData::ElderGuardian => 13,
+            ParticleData::EnchantedHit => 14,
+            ParticleData::Enchant => 15,
+            ParticleData::EndRod => 16,
+            ParticleData::EntityEffect => 17,
+            ParticleData::ExplosionEmitter => 18,
+            ParticleData::Explosion => 19,
+            ParticleData::FallingDust { .. } => 20,
+            ParticleData::Firework => 21,
+            ParticleData::Fishing => 22,
+            ParticleData::Flame => 23,
+            ParticleData::HappyVillager => 24,
+            ParticleData::Heart => 25,
+            ParticleData::InstantEffect => 26,
+            ParticleData::Item { .. } => 27,
+            ParticleData::ItemSlime => 28,
+            ParticleData::ItemSnowball => 29,
+            ParticleData::LargeSmoke => 30,
+            ParticleData::Lava => 31,
+            ParticleData::Mycelium => 32,
+            ParticleData::Note => 33,
+            ParticleData::Poof => 34,
+            ParticleData::Portal => 35,
+            ParticleData::Rain => 36,
+            ParticleData::Smoke => 37,
+            ParticleData::Spit => 38,
+            ParticleData::SquidInk => 39,
+            ParticleData::SweepAttack => 40,
+            ParticleData::TotemOfUndying => 41,
+            ParticleData::Underwater => 42,
+            ParticleData::Splash => 43,
+            ParticleData::Witch => 44,
+            ParticleData::BubblePop => 45,
+            ParticleData::CurrentDown => 46,
+            ParticleData::BubbleColumnUp => 47,
+            ParticleData::Nautilus => 48,
+            ParticleData::Dolphin => 49,
+        };
+        protocol::VarInt(id).write_to(buf)?;
+        match self {
+            ParticleData::Block { block_state } | ParticleData::FallingDust { block_state } => {
+                block_state.write_to(buf)
+            }
+            ParticleData::Dust {
+                red,
+                green,
+                blue,
+                scale,
+            } => {
+                red.write_to(buf)?;
+                green.write_to(buf)?;
+                blue.write_to(buf)?;
+                scale.write_to(buf)
+            }
+            ParticleData::Item { item } => item.write_to(buf),
+            _ => Ok(()),
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct VillagerData {
-    #[allow(dead_code)]
-    villager_type: protocol::VarInt,
-    #[allow(dead_code)]
-    profession: protocol::VarInt,
-    #[allow(dead_code)]
-    level: protocol::VarInt,
+    pub villager_type: protocol::VarInt,
+    pub profession: protocol::VarInt,
+    pub level: protocol::VarInt,
 }
 
 impl Serializable for VillagerData {
@@ -677,8 +812,10 @@ impl Serializable for VillagerData {
         })
     }
 
-    fn write_to<W: io::Write>(&self, _buf: &mut W) -> Result<(), protocol::Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
+        self.villager_type.write_to(buf)?;
+        self.profession.write_to(buf)?;
+        self.level.write_to(buf)
     }
 }
 
@@ -691,6 +828,13 @@ pub enum PoseData {
     SpinAttack,
     Sneaking,
     Dying,
+    LongJumping,
+    Croaking,
+    UsingTongue,
+    Roaring,
+    Sniffing,
+    Emerging,
+    Digging,
 }
 
 impl Serializable for PoseData {
@@ -704,12 +848,35 @@ impl Serializable for PoseData {
             4 => PoseData::SpinAttack,
             5 => PoseData::Sneaking,
             6 => PoseData::Dying,
+            7 => PoseData::LongJumping,
+            8 => PoseData::Croaking,
+            9 => PoseData::UsingTongue,
+            10 => PoseData::Roaring,
+            11 => PoseData::Sniffing,
+            12 => PoseData::Emerging,
+            13 => PoseData::Digging,
             _ => panic!("unknown pose data: {}", n.0),
         })
     }
 
-    fn write_to<W: io::Write>(&self, _buf: &mut W) -> Result<(), protocol::Error> {
-        unimplemented!()
+    fn write_to<W: io::Write>(&self, buf: &mut W) -> Result<(), protocol::Error> {
+        let n = match self {
+            PoseData::Standing => 0,
+            PoseData::FallFlying => 1,
+            PoseData::Sleeping => 2,
+            PoseData::Swimming => 3,
+            PoseData::SpinAttack => 4,
+            PoseData::Sneaking => 5,
+            PoseData::Dying => 6,
+            PoseData::LongJumping => 7,
+            PoseData::Croaking => 8,
+            PoseData::UsingTongue => 9,
+            PoseData::Roaring => 10,
+            PoseData::Sniffing => 11,
+            PoseData::Emerging => 12,
+            PoseData::Digging => 13,
+        };
+        protocol::VarInt(n).write_to(buf)
     }
 }
 
@@ -958,6 +1125,30 @@ impl MetaValue for PoseData {
     }
 }
 
+impl MetaValue for ParticleData {
+    fn unwrap(value: &Value) -> &Self {
+        match *value {
+            Value::Particle(ref val) => val,
+            _ => panic!("incorrect key"),
+        }
+    }
+    fn wrap(self) -> Value {
+        Value::Particle(self)
+    }
+}
+
+impl MetaValue for Option<GlobalPosition> {
+    fn unwrap(value: &Value) -> &Self {
+        match *value {
+            Value::OptionalGlobalPosition(ref val) => val,
+            _ => panic!("incorrect key"),
+        }
+    }
+    fn wrap(self) -> Value {
+        Value::OptionalGlobalPosition(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -981,4 +1172,55 @@ mod test {
             None => panic!("failed"),
         }
     }
+
+    #[test]
+    fn round_trip() {
+        let mut m = Metadata::new();
+        m.put_raw(0, 3i8);
+        m.put_raw(1, 300i32);
+        m.put_raw(2, "name".to_owned());
+        m.put_raw(5, Some(protocol::UUID::default()));
+        m.put_raw::<Option<GlobalPosition>>(7, None);
+        m.put_raw(9, PoseData::Croaking);
+
+        let mut data = Vec::new();
+        m.write_to113(&mut data).unwrap();
+        let read = Metadata::read_from113(&mut io::Cursor::new(&data)).unwrap();
+        let mut again = Vec::new();
+        read.write_to113(&mut again).unwrap();
+        assert_eq!(data, again);
+    }
+
+    #[test]
+    fn particle_data_round_trip() {
+        let mut data = Vec::new();
+        for particle in &[
+            ParticleData::Block {
+                block_state: protocol::VarInt(1234),
+            },
+            ParticleData::Dust {
+                red: 1.0,
+                green: 0.5,
+                blue: 0.25,
+                scale: 2.0,
+            },
+            ParticleData::FallingDust {
+                block_state: protocol::VarInt(66),
+            },
+            ParticleData::Dolphin,
+        ] {
+            particle.write_to(&mut data).unwrap();
+        }
+        let mut buf = io::Cursor::new(&data);
+        let mut again = Vec::new();
+        for _ in 0..4 {
+            ParticleData::read_from(&mut buf)
+                .unwrap()
+                .write_to(&mut again)
+                .unwrap();
+        }
+        assert_eq!(buf.position() as usize, data.len());
+        assert_eq!(data, again);
+        assert_eq!(&data[..3], &[3, 0xd2, 0x09]);
+    }
 }
@@ -44,16 +44,4 @@ impl Version {
             _ => Version::Other,
         }
     }
-
-    pub fn is_supported(&self) -> bool {
-        !matches!(
-            self,
-            Version::Old
-                | Version::New
-                | Version::Other
-                | Version::V1_17
-                | Version::V1_18
-                | Version::V1_19
-        )
-    }
 }
@@ -208,6 +208,7 @@ impl Console {
                     ComponentType::new("] ", None),
                     ComponentType::new(&format!("{}", record.args()), None),
                 ],
+                source: None,
             };
             self.history.push(component);
             self.dirty = true;
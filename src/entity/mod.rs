@@ -83,7 +83,7 @@ pub fn add_systems(sched: &mut Schedule, render_sched: &mut Schedule) {
         );
 
     block_entity::add_systems(sched);
-    crate::particle::block_break_effect::add_systems(sched);
+    crate::particle::add_systems(sched, render_sched);
 }
 
 /// Location of an entity in the world.
@@ -99,7 +99,12 @@ pub fn create_local(m: &mut Manager) -> Entity {
     entity.id()
 }
 
-pub fn create_remote(m: &mut Manager, name: Component) -> Entity {
+pub fn create_remote(
+    m: &mut Manager,
+    name: String,
+    display_name: Component,
+    has_name_tag: bool,
+) -> Entity {
     let mut entity = m.world.spawn_empty();
     entity
         .insert(Position::new(0.0, 0.0, 0.0))
@@ -111,12 +116,17 @@ pub fn create_remote(m: &mut Manager, name: Component) -> Entity {
             Point3::new(-0.3, 0.0, -0.3),
             Point3::new(0.3, 1.8, 0.3),
         )))
-        .insert(PlayerModel::new(name, true, true, false))
+        .insert(PlayerModel::new(display_name, true, has_name_tag, false))
+        .insert(PlayerName(name))
         .insert(Light::new())
         .insert(EntityType::Player);
     entity.id()
 }
 
+/// The name of another player, which teams refer to them by
+#[derive(Component)]
+pub struct PlayerName(pub String);
+
 #[derive(Component)]
 pub struct PlayerModel {
     model: Option<model::ModelHandle>,
@@ -161,6 +171,12 @@ impl PlayerModel {
             self.dirty.store(true, Ordering::Release);
         }
     }
+
+    pub fn set_name_tag(&mut self, name: Component, visible: bool) {
+        self.display_name = name;
+        self.has_name_tag = visible;
+        self.dirty.store(true, Ordering::Release);
+    }
 }
 
 fn update_render_players(
@@ -462,24 +478,25 @@ fn add_player(renderer: Arc<Renderer>, player_model: &mut PlayerModel) {
             y_scale: 0.16,
             x_scale: 0.01,
         };
-        state.build(&player_model.display_name, Some(format::Color::Black));
-        // TODO: Remove black shadow and add dark, transparent box around name
+        state.build(&player_model.display_name, Some(format::Color::White));
+        // TODO: Add dark, transparent box around name
         let width = state.width;
-        // Center align text
+        let colours: Vec<_> = state.text.iter().map(|v| (v.r, v.g, v.b)).collect();
+        // Center align text, with a shadow a quarter as bright
         for vert in &mut state.text {
             vert.x += width * 0.5;
-            vert.r = 64;
-            vert.g = 64;
-            vert.b = 64;
+            vert.r /= 4;
+            vert.g /= 4;
+            vert.b /= 4;
         }
         name_verts.extend_from_slice(&state.text);
-        for vert in &mut state.text {
+        for (vert, &(r, g, b)) in state.text.iter_mut().zip(&colours) {
             vert.x -= 0.01;
             vert.y -= 0.01;
             vert.z -= 0.05;
-            vert.r = 255;
-            vert.g = 255;
-            vert.b = 255;
+            vert.r = r;
+            vert.g = g;
+            vert.b = b;
         }
         name_verts.extend_from_slice(&state.text);
     }
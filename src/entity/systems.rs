@@ -339,6 +339,9 @@ impl ApplyDigging<'_, '_> {
         if let Some(effect) = effect.take() {
             self.commands.entity(effect).despawn();
         }
+        let debris =
+            crate::particle::block_debris(state.block, state.position, &mut rand::thread_rng());
+        self.commands.spawn_batch(debris);
         world.set_block(state.position, block::Block::Air {});
 
         if send_digging {
@@ -0,0 +1,231 @@
+use crate::entity::EntityType;
+
+pub fn to_id(entity_type: EntityType) -> i16 {
+    match entity_type {
+        EntityType::AreaEffectCloud => 0,
+        EntityType::ArmorStand => 1,
+        EntityType::Arrow => 2,
+        EntityType::Bat => 4,
+        EntityType::Bee => 5,
+        EntityType::Blaze => 6,
+        EntityType::Boat => 7,
+        EntityType::Cat => 8,
+        EntityType::CaveSpider => 9,
+        EntityType::Chicken => 10,
+        EntityType::Cod => 11,
+        EntityType::Cow => 12,
+        EntityType::Creeper => 13,
+        EntityType::Dolphin => 14,
+        EntityType::Donkey => 15,
+        EntityType::DragonFireball => 16,
+        EntityType::DroppedItem => 41,
+        EntityType::Drowned => 17,
+        EntityType::Egg => 89,
+        EntityType::ElderGuardian => 18,
+        EntityType::Enderman => 21,
+        EntityType::Endermite => 22,
+        EntityType::EnderCrystal => 19,
+        EntityType::EnderDragon => 20,
+        EntityType::EnderPearl => 90,
+        EntityType::EnderSignal => 26,
+        EntityType::Evoker => 23,
+        EntityType::EvokerFangs => 24,
+        EntityType::ExperienceOrb => 25,
+        EntityType::FallingBlock => 27,
+        EntityType::Fireball => 43,
+        EntityType::Firework => 28,
+        EntityType::FishingHook => 112,
+        EntityType::Fox => 29,
+        EntityType::Ghast => 30,
+        EntityType::Giant => 31,
+        EntityType::Guardian => 35,
+        EntityType::Hoglin => 36,
+        EntityType::Horse => 37,
+        EntityType::Husk => 38,
+        EntityType::Illusioner => 39,
+        EntityType::IronGolem => 40,
+        EntityType::ItemFrame => 42,
+        EntityType::LeashHitch => 44,
+        EntityType::Lightning => 45,
+        EntityType::Llama => 46,
+        EntityType::LlamaSpit => 47,
+        EntityType::MagmaCube => 48,
+        EntityType::Minecart => 50,
+        EntityType::MinecartChest => 51,
+        EntityType::MinecartCommand => 52,
+        EntityType::MinecartFurnace => 53,
+        EntityType::MinecartHopper => 54,
+        EntityType::MinecartMobSpawner => 55,
+        EntityType::MinecartTnt => 56,
+        EntityType::Mule => 57,
+        EntityType::MushroomCow => 58,
+        EntityType::Ocelot => 59,
+        EntityType::Painting => 60,
+        EntityType::Panda => 61,
+        EntityType::Parrot => 62,
+        EntityType::Phantom => 63,
+        EntityType::Pig => 64,
+        EntityType::Piglin => 65,
+        EntityType::PiglinBrute => 66,
+        EntityType::Pillager => 67,
+        EntityType::Player => 111,
+        EntityType::PolarBear => 68,
+        EntityType::PrimedTnt => 69,
+        EntityType::Pufferfish => 70,
+        EntityType::Rabbit => 71,
+        EntityType::Ravager => 72,
+        EntityType::Salmon => 73,
+        EntityType::Sheep => 74,
+        EntityType::Shulker => 75,
+        EntityType::ShulkerBullet => 76,
+        EntityType::Silverfish => 77,
+        EntityType::Skeleton => 78,
+        EntityType::SkeletonHorse => 79,
+        EntityType::Slime => 80,
+        EntityType::SmallFireball => 81,
+        EntityType::Snowball => 83,
+        EntityType::Snowman => 82,
+        EntityType::SpectralArrow => 84,
+        EntityType::Spider => 85,
+        EntityType::SplashPotion => 92,
+        EntityType::Squid => 86,
+        EntityType::Stray => 87,
+        EntityType::Strider => 88,
+        EntityType::ThrownExpBottle => 91,
+        EntityType::TraderLlama => 94,
+        EntityType::Trident => 93,
+        EntityType::TropicalFish => 95,
+        EntityType::Turtle => 96,
+        EntityType::Vex => 97,
+        EntityType::Villager => 98,
+        EntityType::Vindicator => 99,
+        EntityType::WanderingTrader => 100,
+        EntityType::Witch => 101,
+        EntityType::Wither => 102,
+        EntityType::WitherSkeleton => 103,
+        EntityType::WitherSkull => 104,
+        EntityType::Wolf => 105,
+        EntityType::Zoglin => 106,
+        EntityType::Zombie => 107,
+        EntityType::ZombieHorse => 108,
+        EntityType::ZombieVillager => 109,
+        EntityType::ZombifiedPiglin => 110,
+        EntityType::Unknown => -1,
+        _ => -1,
+    }
+}
+
+pub fn to_entity_type(type_id: i16) -> EntityType {
+    match type_id {
+        0 => EntityType::AreaEffectCloud,
+        1 => EntityType::ArmorStand,
+        2 => EntityType::Arrow,
+        4 => EntityType::Bat,
+        5 => EntityType::Bee,
+        6 => EntityType::Blaze,
+        7 => EntityType::Boat,
+        8 => EntityType::Cat,
+        9 => EntityType::CaveSpider,
+        10 => EntityType::Chicken,
+        11 => EntityType::Cod,
+        12 => EntityType::Cow,
+        13 => EntityType::Creeper,
+        14 => EntityType::Dolphin,
+        15 => EntityType::Donkey,
+        16 => EntityType::DragonFireball,
+        41 => EntityType::DroppedItem,
+        17 => EntityType::Drowned,
+        89 => EntityType::Egg,
+        18 => EntityType::ElderGuardian,
+        21 => EntityType::Enderman,
+        22 => EntityType::Endermite,
+        19 => EntityType::EnderCrystal,
+        20 => EntityType::EnderDragon,
+        90 => EntityType::EnderPearl,
+        26 => EntityType::EnderSignal,
+        23 => EntityType::Evoker,
+        24 => EntityType::EvokerFangs,
+        25 => EntityType::ExperienceOrb,
+        27 => EntityType::FallingBlock,
+        43 => EntityType::Fireball,
+        28 => EntityType::Firework,
+        112 => EntityType::FishingHook,
+        29 => EntityType::Fox,
+        30 => EntityType::Ghast,
+        31 => EntityType::Giant,
+        35 => EntityType::Guardian,
+        36 => EntityType::Hoglin,
+        37 => EntityType::Horse,
+        38 => EntityType::Husk,
+        39 => EntityType::Illusioner,
+        40 => EntityType::IronGolem,
+        42 => EntityType::ItemFrame,
+        44 => EntityType::LeashHitch,
+        45 => EntityType::Lightning,
+        46 => EntityType::Llama,
+        47 => EntityType::LlamaSpit,
+        48 => EntityType::MagmaCube,
+        50 => EntityType::Minecart,
+        51 => EntityType::MinecartChest,
+        52 => EntityType::MinecartCommand,
+        53 => EntityType::MinecartFurnace,
+        54 => EntityType::MinecartHopper,
+        55 => EntityType::MinecartMobSpawner,
+        56 => EntityType::MinecartTnt,
+        57 => EntityType::Mule,
+        58 => EntityType::MushroomCow,
+        59 => EntityType::Ocelot,
+        60 => EntityType::Painting,
+        61 => EntityType::Panda,
+        62 => EntityType::Parrot,
+        63 => EntityType::Phantom,
+        64 => EntityType::Pig,
+        65 => EntityType::Piglin,
+        66 => EntityType::PiglinBrute,
+        67 => EntityType::Pillager,
+        111 => EntityType::Player,
+        68 => EntityType::PolarBear,
+        69 => EntityType::PrimedTnt,
+        70 => EntityType::Pufferfish,
+        71 => EntityType::Rabbit,
+        72 => EntityType::Ravager,
+        73 => EntityType::Salmon,
+        74 => EntityType::Sheep,
+        75 => EntityType::Shulker,
+        76 => EntityType::ShulkerBullet,
+        77 => EntityType::Silverfish,
+        78 => EntityType::Skeleton,
+        79 => EntityType::SkeletonHorse,
+        80 => EntityType::Slime,
+        81 => EntityType::SmallFireball,
+        83 => EntityType::Snowball,
+        82 => EntityType::Snowman,
+        84 => EntityType::SpectralArrow,
+        85 => EntityType::Spider,
+        92 => EntityType::SplashPotion,
+        86 => EntityType::Squid,
+        87 => EntityType::Stray,
+        88 => EntityType::Strider,
+        91 => EntityType::ThrownExpBottle,
+        94 => EntityType::TraderLlama,
+        93 => EntityType::Trident,
+        95 => EntityType::TropicalFish,
+        96 => EntityType::Turtle,
+        97 => EntityType::Vex,
+        98 => EntityType::Villager,
+        99 => EntityType::Vindicator,
+        100 => EntityType::WanderingTrader,
+        101 => EntityType::Witch,
+        102 => EntityType::Wither,
+        103 => EntityType::WitherSkeleton,
+        104 => EntityType::WitherSkull,
+        105 => EntityType::Wolf,
+        106 => EntityType::Zoglin,
+        107 => EntityType::Zombie,
+        108 => EntityType::ZombieHorse,
+        109 => EntityType::ZombieVillager,
+        110 => EntityType::ZombifiedPiglin,
+        -1 => EntityType::Unknown,
+        _ => EntityType::Unknown,
+    }
+}
@@ -0,0 +1,231 @@
+use crate::entity::EntityType;
+
+pub fn to_id(entity_type: EntityType) -> i16 {
+    match entity_type {
+        EntityType::AreaEffectCloud => 1,
+        EntityType::ArmorStand => 2,
+        EntityType::Arrow => 3,
+        EntityType::Bat => 5,
+        EntityType::Bee => 6,
+        EntityType::Blaze => 7,
+        EntityType::Boat => 8,
+        EntityType::Cat => 10,
+        EntityType::CaveSpider => 11,
+        EntityType::Chicken => 12,
+        EntityType::Cod => 13,
+        EntityType::Cow => 14,
+        EntityType::Creeper => 15,
+        EntityType::Dolphin => 16,
+        EntityType::Donkey => 17,
+        EntityType::DragonFireball => 18,
+        EntityType::DroppedItem => 44,
+        EntityType::Drowned => 19,
+        EntityType::Egg => 93,
+        EntityType::ElderGuardian => 20,
+        EntityType::Enderman => 23,
+        EntityType::Endermite => 24,
+        EntityType::EnderCrystal => 21,
+        EntityType::EnderDragon => 22,
+        EntityType::EnderPearl => 94,
+        EntityType::EnderSignal => 28,
+        EntityType::Evoker => 25,
+        EntityType::EvokerFangs => 26,
+        EntityType::ExperienceOrb => 27,
+        EntityType::FallingBlock => 29,
+        EntityType::Fireball => 46,
+        EntityType::Firework => 30,
+        EntityType::FishingHook => 117,
+        EntityType::Fox => 31,
+        EntityType::Ghast => 33,
+        EntityType::Giant => 34,
+        EntityType::Guardian => 38,
+        EntityType::Hoglin => 39,
+        EntityType::Horse => 40,
+        EntityType::Husk => 41,
+        EntityType::Illusioner => 42,
+        EntityType::IronGolem => 43,
+        EntityType::ItemFrame => 45,
+        EntityType::LeashHitch => 47,
+        EntityType::Lightning => 48,
+        EntityType::Llama => 49,
+        EntityType::LlamaSpit => 50,
+        EntityType::MagmaCube => 51,
+        EntityType::Minecart => 53,
+        EntityType::MinecartChest => 54,
+        EntityType::MinecartCommand => 55,
+        EntityType::MinecartFurnace => 56,
+        EntityType::MinecartHopper => 57,
+        EntityType::MinecartMobSpawner => 58,
+        EntityType::MinecartTnt => 59,
+        EntityType::Mule => 60,
+        EntityType::MushroomCow => 61,
+        EntityType::Ocelot => 62,
+        EntityType::Painting => 63,
+        EntityType::Panda => 64,
+        EntityType::Parrot => 65,
+        EntityType::Phantom => 66,
+        EntityType::Pig => 67,
+        EntityType::Piglin => 68,
+        EntityType::PiglinBrute => 69,
+        EntityType::Pillager => 70,
+        EntityType::Player => 116,
+        EntityType::PolarBear => 71,
+        EntityType::PrimedTnt => 72,
+        EntityType::Pufferfish => 73,
+        EntityType::Rabbit => 74,
+        EntityType::Ravager => 75,
+        EntityType::Salmon => 76,
+        EntityType::Sheep => 77,
+        EntityType::Shulker => 78,
+        EntityType::ShulkerBullet => 79,
+        EntityType::Silverfish => 80,
+        EntityType::Skeleton => 81,
+        EntityType::SkeletonHorse => 82,
+        EntityType::Slime => 83,
+        EntityType::SmallFireball => 84,
+        EntityType::Snowball => 86,
+        EntityType::Snowman => 85,
+        EntityType::SpectralArrow => 87,
+        EntityType::Spider => 88,
+        EntityType::SplashPotion => 96,
+        EntityType::Squid => 89,
+        EntityType::Stray => 90,
+        EntityType::Strider => 91,
+        EntityType::ThrownExpBottle => 95,
+        EntityType::TraderLlama => 98,
+        EntityType::Trident => 97,
+        EntityType::TropicalFish => 99,
+        EntityType::Turtle => 100,
+        EntityType::Vex => 101,
+        EntityType::Villager => 102,
+        EntityType::Vindicator => 103,
+        EntityType::WanderingTrader => 104,
+        EntityType::Witch => 106,
+        EntityType::Wither => 107,
+        EntityType::WitherSkeleton => 108,
+        EntityType::WitherSkull => 109,
+        EntityType::Wolf => 110,
+        EntityType::Zoglin => 111,
+        EntityType::Zombie => 112,
+        EntityType::ZombieHorse => 113,
+        EntityType::ZombieVillager => 114,
+        EntityType::ZombifiedPiglin => 115,
+        EntityType::Unknown => -1,
+        _ => -1,
+    }
+}
+
+pub fn to_entity_type(type_id: i16) -> EntityType {
+    match type_id {
+        1 => EntityType::AreaEffectCloud,
+        2 => EntityType::ArmorStand,
+        3 => EntityType::Arrow,
+        5 => EntityType::Bat,
+        6 => EntityType::Bee,
+        7 => EntityType::Blaze,
+        8 => EntityType::Boat,
+        10 => EntityType::Cat,
+        11 => EntityType::CaveSpider,
+        12 => EntityType::Chicken,
+        13 => EntityType::Cod,
+        14 => EntityType::Cow,
+        15 => EntityType::Creeper,
+        16 => EntityType::Dolphin,
+        17 => EntityType::Donkey,
+        18 => EntityType::DragonFireball,
+        44 => EntityType::DroppedItem,
+        19 => EntityType::Drowned,
+        93 => EntityType::Egg,
+        20 => EntityType::ElderGuardian,
+        23 => EntityType::Enderman,
+        24 => EntityType::Endermite,
+        21 => EntityType::EnderCrystal,
+        22 => EntityType::EnderDragon,
+        94 => EntityType::EnderPearl,
+        28 => EntityType::EnderSignal,
+        25 => EntityType::Evoker,
+        26 => EntityType::EvokerFangs,
+        27 => EntityType::ExperienceOrb,
+        29 => EntityType::FallingBlock,
+        46 => EntityType::Fireball,
+        30 => EntityType::Firework,
+        117 => EntityType::FishingHook,
+        31 => EntityType::Fox,
+        33 => EntityType::Ghast,
+        34 => EntityType::Giant,
+        38 => EntityType::Guardian,
+        39 => EntityType::Hoglin,
+        40 => EntityType::Horse,
+        41 => EntityType::Husk,
+        42 => EntityType::Illusioner,
+        43 => EntityType::IronGolem,
+        45 => EntityType::ItemFrame,
+        47 => EntityType::LeashHitch,
+        48 => EntityType::Lightning,
+        49 => EntityType::Llama,
+        50 => EntityType::LlamaSpit,
+        51 => EntityType::MagmaCube,
+        53 => EntityType::Minecart,
+        54 => EntityType::MinecartChest,
+        55 => EntityType::MinecartCommand,
+        56 => EntityType::MinecartFurnace,
+        57 => EntityType::MinecartHopper,
+        58 => EntityType::MinecartMobSpawner,
+        59 => EntityType::MinecartTnt,
+        60 => EntityType::Mule,
+        61 => EntityType::MushroomCow,
+        62 => EntityType::Ocelot,
+        63 => EntityType::Painting,
+        64 => EntityType::Panda,
+        65 => EntityType::Parrot,
+        66 => EntityType::Phantom,
+        67 => EntityType::Pig,
+        68 => EntityType::Piglin,
+        69 => EntityType::PiglinBrute,
+        70 => EntityType::Pillager,
+        116 => EntityType::Player,
+        71 => EntityType::PolarBear,
+        72 => EntityType::PrimedTnt,
+        73 => EntityType::Pufferfish,
+        74 => EntityType::Rabbit,
+        75 => EntityType::Ravager,
+        76 => EntityType::Salmon,
+        77 => EntityType::Sheep,
+        78 => EntityType::Shulker,
+        79 => EntityType::ShulkerBullet,
+        80 => EntityType::Silverfish,
+        81 => EntityType::Skeleton,
+        82 => EntityType::SkeletonHorse,
+        83 => EntityType::Slime,
+        84 => EntityType::SmallFireball,
+        86 => EntityType::Snowball,
+        85 => EntityType::Snowman,
+        87 => EntityType::SpectralArrow,
+        88 => EntityType::Spider,
+        96 => EntityType::SplashPotion,
+        89 => EntityType::Squid,
+        90 => EntityType::Stray,
+        91 => EntityType::Strider,
+        95 => EntityType::ThrownExpBottle,
+        98 => EntityType::TraderLlama,
+        97 => EntityType::Trident,
+        99 => EntityType::TropicalFish,
+        100 => EntityType::Turtle,
+        101 => EntityType::Vex,
+        102 => EntityType::Villager,
+        103 => EntityType::Vindicator,
+        104 => EntityType::WanderingTrader,
+        106 => EntityType::Witch,
+        107 => EntityType::Wither,
+        108 => EntityType::WitherSkeleton,
+        109 => EntityType::WitherSkull,
+        110 => EntityType::Wolf,
+        111 => EntityType::Zoglin,
+        112 => EntityType::Zombie,
+        113 => EntityType::ZombieHorse,
+        114 => EntityType::ZombieVillager,
+        115 => EntityType::ZombifiedPiglin,
+        -1 => EntityType::Unknown,
+        _ => EntityType::Unknown,
+    }
+}
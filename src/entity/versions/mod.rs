@@ -8,6 +8,8 @@ mod mapping1_13_2;
 mod mapping1_14_4;
 mod mapping1_15_2;
 mod mapping1_16_5;
+mod mapping1_17_1;
+mod mapping1_19;
 mod mapping1_7_10;
 mod mapping1_8_8;
 mod mapping1_9_4;
@@ -26,6 +28,9 @@ pub fn to_id(entity_type: EntityType, version: Version) -> i16 {
         Version::V1_15 => mapping1_15_2::to_id(entity_type),
         Version::V1_16 => mapping1_16_5::to_id(entity_type),
         Version::V1_16_2 => mapping1_16_5::to_id(entity_type),
+        Version::V1_17 => mapping1_17_1::to_id(entity_type),
+        Version::V1_18 => mapping1_17_1::to_id(entity_type),
+        Version::V1_19 => mapping1_19::to_id(entity_type),
         _ => -1,
     }
 }
@@ -43,6 +48,9 @@ pub fn to_entity_type(id: i16, version: Version) -> EntityType {
         Version::V1_15 => mapping1_15_2::to_entity_type(id),
         Version::V1_16 => mapping1_16_5::to_entity_type(id),
         Version::V1_16_2 => mapping1_16_5::to_entity_type(id),
+        Version::V1_17 => mapping1_17_1::to_entity_type(id),
+        Version::V1_18 => mapping1_17_1::to_entity_type(id),
+        Version::V1_19 => mapping1_19::to_entity_type(id),
         _ => EntityType::Unknown,
     }
 }
@@ -509,13 +509,17 @@ impl InventoryContext {
     }
 
     pub fn on_confirm_transaction(&self, id: u8, action_number: i16, _accepted: bool) {
-        if id as i32 == self.player_inventory.read().id() {
-            self.player_inventory
-                .write()
-                .set_client_state_id(action_number);
+        self.set_state_id(id as i32, action_number);
+    }
+
+    /// Stores the state id the server last sent for the window, it is sent
+    /// back with the next click in that window.
+    pub fn set_state_id(&self, id: i32, state_id: i16) {
+        if id == self.player_inventory.read().id() {
+            self.player_inventory.write().set_client_state_id(state_id);
         } else if let Some(inventory) = &self.safe_inventory {
             let mut inventory = inventory.write();
-            if id as i32 != inventory.id() {
+            if id != inventory.id() {
                 warn!(
                     "Expected inventory id {}, but instead got {id}",
                     inventory.id()
@@ -523,7 +527,7 @@ impl InventoryContext {
                 return;
             }
 
-            inventory.set_client_state_id(action_number);
+            inventory.set_client_state_id(state_id);
         }
     }
 }
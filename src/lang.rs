@@ -0,0 +1,154 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loads the language files of the selected locale from the resource packs
+//! into `translate`, which both server components and the UI go through.
+//! Files are `lang/<locale>.json` since 1.13 and `lang/<locale>.lang` with
+//! one `key=value` per line before that.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use leafish_protocol::translate;
+use log::{info, warn};
+
+use crate::resources;
+
+pub const DEFAULT_LANGUAGE: &str = "en_us";
+
+#[derive(Clone, Debug)]
+pub struct Language {
+    pub code: String,
+    pub name: String,
+    pub region: String,
+}
+
+/// The languages listed by the resources, sorted by name
+pub fn available(res: &resources::Manager) -> Vec<Language> {
+    let mut languages = HashMap::new();
+    for meta in res.open_root_all("pack.mcmeta").into_iter().rev() {
+        let meta: serde_json::Value = match serde_json::from_reader(meta) {
+            Ok(meta) => meta,
+            Err(err) => {
+                warn!("Invalid pack.mcmeta: {}", err);
+                continue;
+            }
+        };
+        let list = match meta.get("language").and_then(|v| v.as_object()) {
+            Some(list) => list,
+            None => continue,
+        };
+        for (code, info) in list {
+            let field = |name| {
+                info.get(name)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_owned()
+            };
+            languages.insert(
+                code.to_lowercase(),
+                Language {
+                    code: code.to_lowercase(),
+                    name: field("name"),
+                    region: field("region"),
+                },
+            );
+        }
+    }
+    languages
+        .entry(DEFAULT_LANGUAGE.to_owned())
+        .or_insert_with(|| Language {
+            code: DEFAULT_LANGUAGE.to_owned(),
+            name: "English".to_owned(),
+            region: "United States".to_owned(),
+        });
+    let mut languages: Vec<_> = languages.into_values().collect();
+    languages.sort_by(|a, b| (&a.name, &a.region).cmp(&(&b.name, &b.region)));
+    languages
+}
+
+/// Makes `translate` use the language, falling back to English for the keys
+/// it doesn't translate.
+pub fn load(res: &resources::Manager, code: &str) {
+    let mut translations = HashMap::new();
+    read_language(res, DEFAULT_LANGUAGE, &mut translations);
+    if code != DEFAULT_LANGUAGE {
+        read_language(res, code, &mut translations);
+    }
+    info!("Loaded {} translations for {}", translations.len(), code);
+    translate::set_language(translations);
+}
+
+fn read_language(res: &resources::Manager, code: &str, translations: &mut HashMap<String, String>) {
+    // Packs are returned highest priority first, so later ones win
+    let files = res.open_all("minecraft", &format!("lang/{}.json", code));
+    for file in files.into_iter().rev() {
+        match serde_json::from_reader::<_, HashMap<String, String>>(file) {
+            Ok(entries) => translations.extend(entries),
+            Err(err) => warn!("Invalid language file {}.json: {}", code, err),
+        }
+    }
+    let files = res.open_all("minecraft", &format!("lang/{}.lang", legacy_code(code)));
+    for file in files.into_iter().rev() {
+        translations.extend(parse_lang(file));
+    }
+}
+
+/// `en_us` is called `en_US` in legacy packs
+fn legacy_code(code: &str) -> String {
+    match code.split_once('_') {
+        Some((language, region)) => format!("{}_{}", language, region.to_uppercase()),
+        None => code.to_owned(),
+    }
+}
+
+fn parse_lang(mut file: Box<dyn Read>) -> Vec<(String, String)> {
+    let mut data = Vec::new();
+    if let Err(err) = file.read_to_end(&mut data) {
+        warn!("Failed to read language file: {}", err);
+    }
+    String::from_utf8_lossy(&data)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_codes_are_upper_case_regions() {
+        assert_eq!(legacy_code("en_us"), "en_US");
+        assert_eq!(legacy_code("pt_br"), "pt_BR");
+        assert_eq!(legacy_code("lol"), "lol");
+    }
+
+    #[test]
+    fn parse_legacy_lang() {
+        let file = "# comment\n\ngui.done=Done\n  menu.quit=Quit Game  \nbroken\nchat.type.text=<%s> %s=\n";
+        let entries = parse_lang(Box::new(file.as_bytes()));
+        assert_eq!(
+            entries,
+            vec![
+                ("gui.done".to_owned(), "Done".to_owned()),
+                ("menu.quit".to_owned(), "Quit Game".to_owned()),
+                ("chat.type.text".to_owned(), "<%s> %s=".to_owned()),
+            ]
+        );
+    }
+}
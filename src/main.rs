@@ -23,6 +23,7 @@ use atomic_float::AtomicF64;
 use copypasta::nop_clipboard;
 use copypasta::ClipboardContext;
 use copypasta::ClipboardProvider;
+use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use glutin::config::Api;
 use glutin::config::ConfigTemplateBuilder;
 use glutin::context::ContextApi;
@@ -39,10 +40,9 @@ use instant::Duration;
 use leafish_protocol::protocol::login::AccountType;
 use log::{debug, error, info, warn};
 use raw_window_handle::HasRawWindowHandle;
-use shared::Version;
 use std::fs;
 use std::num::NonZeroU32;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::time::Instant;
 use winit::keyboard::Key;
 use winit::keyboard::ModifiersState;
@@ -69,6 +69,7 @@ use leafish_protocol::types;
 pub mod chunk_builder;
 pub mod entity;
 mod inventory;
+pub mod lang;
 pub mod model;
 pub mod particle;
 pub mod paths;
@@ -77,10 +78,12 @@ pub mod resources;
 pub mod screen;
 pub mod server;
 pub mod settings;
+pub mod sound;
 pub mod ui;
 pub mod world;
 
 use crate::entity::Rotation;
+use crate::format::{Component, ComponentType};
 use crate::render::hud::HudContext;
 use crate::settings::*;
 use leafish_protocol::protocol::login::Account;
@@ -95,6 +98,18 @@ use std::thread;
 
 // TODO: Improve calculate light performance and fix capturesnapshot
 
+/// Delay before the first automatic reconnect, doubled for every further try
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+
+/// Everything needed to join a server again
+#[derive(Clone)]
+pub struct ServerTarget {
+    pub address: String,
+    pub proxy: Option<String>,
+    pub protocol_version: Option<i32>,
+}
+
 pub struct Game {
     renderer: Arc<render::Renderer>,
     screen_sys: Arc<screen::ScreenSystem>,
@@ -108,6 +123,7 @@ pub struct Game {
     server: ArcSwapOption<server::Server>,
     focused: AtomicBool,
     chunk_builder: Mutex<chunk_builder::ChunkBuilder>,
+    sound: Mutex<sound::Manager>,
 
     connect_error: ArcSwapOption<Error>,
 
@@ -120,6 +136,19 @@ pub struct Game {
     logo_pressed: AtomicBool,
     fullscreen: AtomicBool,
     current_account: Arc<Mutex<Option<Account>>>,
+
+    last_server: Mutex<Option<ServerTarget>>,
+    reconnect_attempts: AtomicU32,
+    reconnect_at: Mutex<Option<Instant>>,
+    pending_join: Mutex<Option<PendingJoin>>,
+}
+
+/// A connection attempt running in the background
+struct PendingJoin {
+    target: ServerTarget,
+    hud_context: Arc<RwLock<HudContext>>,
+    result: Receiver<Result<Arc<server::Server>, Error>>,
+    reconnecting: bool,
 }
 
 impl Game {
@@ -203,78 +232,220 @@ impl Game {
         self.ctrl_pressed.store(pressed, Ordering::Release);
     }
 
+    /// Connects to `address` in the background, detecting the protocol
+    /// version with a ping unless `protocol_version` pins one for this
+    /// server. Progress is reported through `status` and the outcome
+    /// arrives through the returned channel.
     pub fn connect_to(
         &self,
         address: &str,
+        server_proxy: Option<&str>,
+        protocol_version: Option<i32>,
         hud_context: Arc<RwLock<HudContext>>,
-    ) -> Result<(), Error> {
-        let default_protocol_version = self.settings.get_int(IntSetting::DefaultProtocolVersion);
-        let (protocol_version, forge_mods, fml_network_version) =
-            match protocol::Conn::new(address, default_protocol_version)
-                .and_then(|conn| conn.do_status())
-            {
-                Ok(res) => {
-                    info!(
-                        "Detected server protocol version {}",
-                        res.0.version.protocol
-                    );
-                    (
-                        res.0.version.protocol,
-                        res.0.forge_mods,
-                        res.0.fml_network_version,
-                    )
-                }
-                Err(err) => {
-                    warn!(
-                        "Error pinging server {} to get protocol version: {:?}, defaulting to {}",
-                        address, err, default_protocol_version
-                    );
-                    (default_protocol_version, vec![], None)
-                }
-            };
-        if !Version::from_id(protocol_version as u32).is_supported() {
-            return Err(Error::Err(format!(
-                "The server's version isn't supported!\n(protocol version: {})",
-                protocol_version
-            )));
-        }
+        status: Sender<String>,
+    ) -> Receiver<Result<Arc<server::Server>, Error>> {
+        let default_protocol_version = protocol_version
+            .unwrap_or_else(|| self.settings.get_int(IntSetting::DefaultProtocolVersion));
         let address = address.to_owned();
+        let server_proxy = server_proxy.map(str::to_owned);
         let resources = self.resource_manager.clone();
         let renderer = self.renderer.clone();
         let screen_sys = self.screen_sys.clone();
         let account = self.current_account.clone();
-        let result = thread::spawn(move || {
-            server::Server::connect(
-                resources,
-                account.lock().as_ref().unwrap(),
-                &address,
-                protocol_version,
-                forge_mods,
-                fml_network_version,
-                renderer,
-                hud_context.clone(),
-                screen_sys,
-            )
-        })
-        .join();
-        match result {
-            Ok(result) => {
-                match result {
-                    Ok(srv) => {
-                        self.server.store(Some(srv));
-                        Ok(())
-                    }
-                    Err(err) => {
-                        let str = err.to_string();
-                        self.connect_error.store(Some(Arc::new(err)));
-                        // self.server.disconnect_reason = Some(Component::from_string(&*err.to_string()));
-                        Err(Error::Err(str))
+        let (send, recv) = unbounded();
+        thread::spawn(move || {
+            let connect = || {
+                let proxy = protocol::proxy::resolve(server_proxy.as_deref())?;
+                let (detected_protocol_version, forge_mods, fml_network_version) =
+                    match protocol::Conn::ping(&address, default_protocol_version, proxy.as_ref()) {
+                        Ok(res) => {
+                            info!(
+                                "Detected server protocol version {}",
+                                res.0.version.protocol
+                            );
+                            (
+                                res.0.version.protocol,
+                                res.0.forge_mods,
+                                res.0.fml_network_version,
+                            )
+                        }
+                        Err(err) => {
+                            warn!(
+                                "Error pinging server {} to get protocol version: {:?}, defaulting to {}",
+                                address, err, default_protocol_version
+                            );
+                            (default_protocol_version, vec![], None)
+                        }
+                    };
+                // Proxies and protocol translators report a version of their own,
+                // so a version set for the server always wins over the detected one
+                let protocol_version = match protocol_version {
+                    Some(protocol_version) => {
+                        info!(
+                            "Using protocol version {} configured for {}",
+                            protocol_version, address
+                        );
+                        protocol_version
                     }
+                    None => detected_protocol_version,
+                };
+                if !protocol::versions::is_supported_protocol(protocol_version) {
+                    return Err(Error::Err(format!(
+                        "The server's version isn't supported!\n(protocol version: {})",
+                        protocol_version
+                    )));
+                }
+                server::Server::connect(
+                    resources,
+                    account.lock().as_ref().unwrap(),
+                    &address,
+                    proxy.as_ref(),
+                    protocol_version,
+                    forge_mods,
+                    fml_network_version,
+                    renderer,
+                    hud_context,
+                    screen_sys,
+                    &status,
+                )
+            };
+            let _ = send.send(connect());
+        });
+        recv
+    }
+
+    /// Shows the connecting screen while joining `target` in the
+    /// background, `tick_join` takes over once that is done.
+    pub fn join_server(&self, target: ServerTarget) {
+        self.start_join(target, false);
+    }
+
+    fn start_join(&self, target: ServerTarget, reconnecting: bool) {
+        let (status_send, status_recv) = unbounded();
+        self.screen_sys
+            .clone()
+            .replace_screen(Box::new(screen::connecting::Connecting::new(
+                &target.address,
+                status_recv,
+            )));
+        let hud_context = Arc::new(RwLock::new(HudContext::new()));
+        let result = self.connect_to(
+            &target.address,
+            target.proxy.as_deref(),
+            target.protocol_version,
+            hud_context.clone(),
+            status_send,
+        );
+        self.pending_join.lock().replace(PendingJoin {
+            target,
+            hud_context,
+            result,
+            reconnecting,
+        });
+    }
+
+    /// Finishes joining a server once the connection attempt is over,
+    /// switching to the hud and remembering the server for reconnecting.
+    /// A failed attempt goes back to the server list, or to the disconnect
+    /// screen when reconnecting.
+    fn tick_join(&self) {
+        let mut pending = self.pending_join.lock();
+        let result = match pending.as_ref().map(|join| join.result.try_recv()) {
+            None | Some(Err(TryRecvError::Empty)) => return,
+            Some(Ok(result)) => result,
+            Some(Err(TryRecvError::Disconnected)) => Err(Error::Err("Unknown".to_string())),
+        };
+        let join = pending.take().unwrap();
+        drop(pending);
+        match result {
+            Ok(server) => {
+                self.server.store(Some(server));
+                self.screen_sys
+                    .clone()
+                    .replace_screen(Box::new(render::hud::Hud::new(join.hud_context)));
+                self.last_server.lock().replace(join.target);
+                self.reconnect_attempts.store(0, Ordering::Relaxed);
+            }
+            Err(err) => {
+                let reason = Component::new(ComponentType::new(&err.to_string(), None));
+                self.connect_error.store(Some(Arc::new(err)));
+                if join.reconnecting {
+                    self.on_disconnected(Some(reason));
+                } else {
+                    self.screen_sys
+                        .clone()
+                        .replace_screen(Box::new(screen::ServerList::new(Some(reason))));
                 }
             }
-            Err(_) => Err(Error::Err("Unknown".to_string())),
         }
     }
+
+    /// Joins the last server again
+    pub fn reconnect(&self) {
+        self.cancel_reconnect();
+        let target = self.last_server.lock().clone();
+        if let Some(target) = target {
+            self.start_join(target, true);
+        }
+    }
+
+    pub fn cancel_reconnect(&self) {
+        self.reconnect_at.lock().take();
+    }
+
+    /// Switches the language of the UI and of the text sent by servers.
+    /// Screens pick it up when they are shown again.
+    pub fn set_language(&self, code: &str) {
+        self.settings.set_string(StringSetting::Language, code);
+        lang::load(&self.resource_manager.read(), code);
+    }
+
+    /// Picks the screen to show after leaving a server. Leaving on purpose
+    /// goes back to the server list, losing the connection shows the reason
+    /// along with the option to reconnect, which happens on its own with a
+    /// growing delay if `auto_reconnect` is set.
+    fn on_disconnected(&self, reason: Option<Component>) {
+        self.sound.lock().stop_all();
+        let has_target = self.last_server.lock().is_some();
+        let screen: Box<dyn screen::Screen> = match reason {
+            Some(reason) if has_target => {
+                let reconnect_at = if self.settings.get_bool(BoolSetting::AutoReconnect) {
+                    let attempt = self.reconnect_attempts.fetch_add(1, Ordering::Relaxed);
+                    let delay = RECONNECT_DELAY
+                        .checked_mul(1 << attempt.min(16))
+                        .map_or(MAX_RECONNECT_DELAY, |delay| delay.min(MAX_RECONNECT_DELAY));
+                    info!("Reconnecting in {}s", delay.as_secs());
+                    Some(Instant::now() + delay)
+                } else {
+                    None
+                };
+                *self.reconnect_at.lock() = reconnect_at;
+                Box::new(screen::disconnected::Disconnected::new(
+                    reason,
+                    reconnect_at,
+                ))
+            }
+            reason => Box::new(screen::ServerList::new(reason)),
+        };
+        self.screen_sys.close_closable_screens();
+        self.screen_sys.replace_screen(screen);
+    }
+
+    pub fn replay_capture(
+        &self,
+        path: &str,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Result<(), Error> {
+        let server = server::Server::replay(
+            self.resource_manager.clone(),
+            path,
+            self.renderer.clone(),
+            hud_context,
+            self.screen_sys.clone(),
+        )?;
+        self.server.store(Some(server));
+        Ok(())
+    }
 }
 
 #[derive(StructOpt, Debug)]
@@ -288,6 +459,14 @@ struct Opt {
     #[structopt(short = "N", long = "network-parse-packet")]
     network_parse_packet: Option<String>,
 
+    /// Record all packets received while playing to a capture file
+    #[structopt(long = "network-capture")]
+    network_capture: Option<String>,
+
+    /// Replay a capture file recorded with --network-capture
+    #[structopt(long = "network-replay")]
+    network_replay: Option<String>,
+
     #[structopt(long)]
     uuid: Option<String>,
     #[structopt(long)]
@@ -326,6 +505,10 @@ fn main() {
     info!("settings all loaded!");
 
     con.lock().configure(&settings);
+    let proxy_settings = settings.clone();
+    protocol::proxy::set_default_proxy(move || proxy_settings.get_string(StringSetting::Proxy));
+    let bound_keys = keybinds.clone();
+    leafish_protocol::translate::set_keybind_resolver(move |name| bound_keys.key_name(name));
     let vsync = settings.get_bool(BoolSetting::Vsync);
 
     let (res, mut resui) = resources::Manager::new(
@@ -492,6 +675,7 @@ fn main() {
         console: con,
         should_close: AtomicBool::new(false),
         chunk_builder: Mutex::new(chunk_builder::ChunkBuilder::new(resource_manager, textures)),
+        sound: Mutex::new(sound::Manager::with_default_output()),
         connect_error: ArcSwapOption::empty(),
         last_mouse_x: AtomicF64::new(0.0),
         last_mouse_y: AtomicF64::new(0.0),
@@ -505,6 +689,10 @@ fn main() {
         current_account: active_account,
         settings,
         keybinds,
+        last_server: Mutex::new(None),
+        reconnect_attempts: AtomicU32::new(0),
+        reconnect_at: Mutex::new(None),
+        pending_join: Mutex::new(None),
     };
     if opt.network_debug {
         protocol::enable_network_debug();
@@ -519,6 +707,20 @@ fn main() {
         return;
     }
 
+    if let Some(filename) = opt.network_capture {
+        protocol::capture::enable_capture(filename);
+    }
+
+    if let Some(filename) = opt.network_replay {
+        let hud_context = Arc::new(RwLock::new(HudContext::new()));
+        match game.replay_capture(&filename, hud_context.clone()) {
+            Ok(_) => game
+                .screen_sys
+                .add_screen(Box::new(render::hud::Hud::new(hud_context))),
+            Err(err) => error!("Failed to replay packet capture {}: {}", filename, err),
+        }
+    }
+
     let mut last_resource_version = 0;
 
     let game = Rc::new(RefCell::new(game));
@@ -587,26 +789,30 @@ fn tick_all(
     last_resource_version: &mut usize,
     vsync: bool,
 ) {
+    game.tick_join();
     let server = game.server.load();
     if let Some(server) = server.as_ref() {
         if !server.is_connected() {
             let disconnect_reason = server.disconnect_data.write().disconnect_reason.take();
-            game.screen_sys.close_closable_screens();
-            game.screen_sys
-                .replace_screen(Box::new(screen::ServerList::new(disconnect_reason)));
+            game.on_disconnected(disconnect_reason);
             game.server.store(None);
             game.renderer.reset();
         } else if server.disconnect_gracefully.load(Ordering::Relaxed) {
             server.finish_disconnect();
             let disconnect_reason = server.disconnect_data.write().disconnect_reason.take();
-            game.screen_sys.close_closable_screens();
-            game.screen_sys
-                .replace_screen(Box::new(screen::ServerList::new(disconnect_reason)));
+            game.on_disconnected(disconnect_reason);
             game.server.store(None);
             game.renderer.reset();
         }
     } else {
         game.chunk_builder.lock().reset();
+        let reconnect_due = game
+            .reconnect_at
+            .lock()
+            .map_or(false, |at| at <= Instant::now());
+        if reconnect_due {
+            game.reconnect();
+        }
     }
     let now = Instant::now();
     let diff = now.duration_since(*last_frame);
@@ -623,6 +829,10 @@ fn tick_all(
         let try_res = game.resource_manager.try_write();
         if let Some(mut res) = try_res {
             res.tick(resui, ui_container, delta);
+            // Packs were added, which may bring language files
+            if res.version() != *last_resource_version {
+                lang::load(&res, &game.settings.get_string(StringSetting::Language));
+            }
             res.version()
         } else {
             // TODO: why does game.resource_manager.write() sometimes deadlock?
@@ -645,6 +855,17 @@ fn tick_all(
         server.tick(delta, game);
     }
 
+    {
+        let mut sound = game.sound.lock();
+        let camera = game.renderer.camera.lock();
+        sound.set_listener(
+            cgmath::Vector3::new(camera.pos.x, camera.pos.y, camera.pos.z),
+            camera.yaw,
+        );
+        sound.update_volumes(&game.settings);
+        sound.tick(diff.as_secs_f64());
+    }
+
     // Check if window is valid, it might be minimized
     if physical_width == 0 || physical_height == 0 {
         return;
@@ -1,6 +1,18 @@
+use std::f64::consts::PI;
+
 use bevy_ecs::prelude::*;
+use cgmath::Vector3;
+use rand::Rng;
+
+use crate::ecs::SystemExecStage;
+use crate::shared::Position;
+use crate::world::block;
+
+use self::sprite::Particle;
 
 pub mod block_break_effect;
+pub mod kinds;
+pub mod sprite;
 
 #[derive(Component, Copy, Clone)]
 pub struct EntityMetadata(pub Entity);
@@ -9,3 +21,254 @@ pub struct EntityMetadata(pub Entity);
 pub enum ParticleType {
     BlockBreak,
 }
+
+pub fn add_systems(sched: &mut Schedule, render_sched: &mut Schedule) {
+    block_break_effect::add_systems(sched);
+    sched.add_systems(sprite::tick_particles.in_set(SystemExecStage::Normal));
+    render_sched.add_systems(
+        sprite::render_particles
+            .in_set(SystemExecStage::Render)
+            .after(SystemExecStage::Normal),
+    );
+}
+
+/// Particles as the server describes them
+pub struct Emitter {
+    /// The modern name of the particle
+    pub name: &'static str,
+    pub position: Vector3<f64>,
+    /// Spread around the position, or the direction when `count` is 0
+    pub offset: Vector3<f64>,
+    pub speed: f64,
+    pub count: i32,
+    pub block: Option<block::Block>,
+    pub colour: Option<[u8; 3]>,
+}
+
+impl Emitter {
+    pub fn particles<R: Rng>(&self, rng: &mut R) -> Vec<Particle> {
+        if self.name == "explosion_emitter" {
+            return explosion(self.position, 4.0, rng);
+        }
+        let look = match kinds::look(self.name) {
+            Some(look) => look,
+            None => return vec![],
+        };
+        let spawn = |position, velocity, rng: &mut R| match self.block {
+            Some(block) if look.frames.is_empty() => {
+                Some(Particle::block(block, position, velocity, rng))
+            }
+            _ if look.frames.is_empty() => None,
+            _ => {
+                let particle = Particle::new(&look, position, velocity, rng);
+                Some(match self.colour {
+                    Some(colour) => particle.with_colour(colour),
+                    None => particle,
+                })
+            }
+        };
+
+        if self.count == 0 {
+            // Spell particles abuse the direction for their colour
+            if self.name.ends_with("entity_effect") {
+                let colour = [self.offset.x, self.offset.y, self.offset.z]
+                    .map(|c| (c.clamp(0.0, 1.0) * 255.0) as u8);
+                return spawn(self.position, Vector3::new(0.0, 0.0, 0.0), rng)
+                    .map(|particle| particle.with_colour(colour))
+                    .into_iter()
+                    .collect();
+            }
+            return spawn(self.position, self.offset * self.speed, rng)
+                .into_iter()
+                .collect();
+        }
+        let count = (self.count as usize).min(sprite::MAX_PARTICLES);
+        (0..count)
+            .filter_map(|_| {
+                let position = self.position
+                    + Vector3::new(
+                        gaussian(rng) * self.offset.x,
+                        gaussian(rng) * self.offset.y,
+                        gaussian(rng) * self.offset.z,
+                    );
+                let velocity =
+                    Vector3::new(gaussian(rng), gaussian(rng), gaussian(rng)) * self.speed;
+                spawn(position, velocity, rng)
+            })
+            .collect()
+    }
+}
+
+/// The pieces flying off a broken block
+pub fn block_debris<R: Rng>(block: block::Block, pos: Position, rng: &mut R) -> Vec<Particle> {
+    if matches!(block, block::Block::Air {}) {
+        return vec![];
+    }
+    const PIECES: i32 = 4;
+    let mut particles = Vec::with_capacity((PIECES * PIECES * PIECES) as usize);
+    for x in 0..PIECES {
+        for y in 0..PIECES {
+            for z in 0..PIECES {
+                let offset = Vector3::new(
+                    (x as f64 + 0.5) / PIECES as f64,
+                    (y as f64 + 0.5) / PIECES as f64,
+                    (z as f64 + 0.5) / PIECES as f64,
+                );
+                let position = Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64) + offset;
+                let velocity = (offset - Vector3::new(0.5, 0.5, 0.5)) * 0.2
+                    + Vector3::new(0.0, rng.gen_range(0.05..0.15), 0.0);
+                particles.push(Particle::block(block, position, velocity, rng));
+            }
+        }
+    }
+    particles
+}
+
+/// The swirl of a splash potion breaking
+pub fn potion_splash<R: Rng>(
+    position: Vector3<f64>,
+    colour: [u8; 3],
+    instant: bool,
+    rng: &mut R,
+) -> Vec<Particle> {
+    let look = kinds::look(if instant { "instant_effect" } else { "effect" }).unwrap();
+    (0..100)
+        .map(|_| {
+            let speed = rng.gen_range(0.0..0.4);
+            let angle = rng.gen_range(0.0..2.0 * PI);
+            let velocity = Vector3::new(
+                angle.cos() * speed,
+                rng.gen_range(0.01..0.2),
+                angle.sin() * speed,
+            );
+            let shade = rng.gen_range(0.75..1.0);
+            let colour = colour.map(|c| (c as f64 * shade) as u8);
+            let position = position + velocity * 0.5 + Vector3::new(0.0, 0.2, 0.0);
+            Particle::new(&look, position, velocity, rng).with_colour(colour)
+        })
+        .collect()
+}
+
+/// The blast and smoke of an explosion
+pub fn explosion<R: Rng>(position: Vector3<f64>, radius: f64, rng: &mut R) -> Vec<Particle> {
+    let blast = kinds::look("explosion").unwrap();
+    let smoke = kinds::look("poof").unwrap();
+    let mut particles = vec![];
+    let blasts = if radius >= 2.0 { 8 } else { 1 };
+    for _ in 0..blasts {
+        let spread = if blasts > 1 { radius } else { 0.0 };
+        let offset = Vector3::new(
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+        ) * spread;
+        particles.push(Particle::new(
+            &blast,
+            position + offset,
+            Vector3::new(0.0, 0.0, 0.0),
+            rng,
+        ));
+    }
+    for _ in 0..(radius * 8.0) as usize {
+        let direction = Vector3::new(gaussian(rng), gaussian(rng), gaussian(rng));
+        particles.push(Particle::new(
+            &smoke,
+            position + direction * (radius * 0.25),
+            direction * 0.1,
+            rng,
+        ));
+    }
+    particles
+}
+
+/// Smoke puffing out of a block, like from a dispenser
+pub fn smoke<R: Rng>(pos: Position, rng: &mut R) -> Vec<Particle> {
+    let look = kinds::look("smoke").unwrap();
+    let centre = Vector3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.5, pos.z as f64 + 0.5);
+    (0..10)
+        .map(|_| {
+            let velocity = Vector3::new(
+                rng.gen_range(-0.02..0.02),
+                rng.gen_range(0.0..0.02),
+                rng.gen_range(-0.02..0.02),
+            );
+            let offset = Vector3::new(
+                rng.gen_range(-0.4..0.4),
+                rng.gen_range(-0.4..0.4),
+                rng.gen_range(-0.4..0.4),
+            );
+            Particle::new(&look, centre + offset, velocity, rng)
+        })
+        .collect()
+}
+
+/// Flames and smoke around a spawner which just spawned a mob
+pub fn spawner_flames<R: Rng>(pos: Position, rng: &mut R) -> Vec<Particle> {
+    let flame = kinds::look("flame").unwrap();
+    let smoke = kinds::look("smoke").unwrap();
+    let mut particles = vec![];
+    for _ in 0..20 {
+        let position = Vector3::new(
+            pos.x as f64 + 0.5 + rng.gen_range(-1.0..1.0),
+            pos.y as f64 + 0.5 + rng.gen_range(-1.0..1.0),
+            pos.z as f64 + 0.5 + rng.gen_range(-1.0..1.0),
+        );
+        let velocity = Vector3::new(0.0, 0.0, 0.0);
+        particles.push(Particle::new(&smoke, position, velocity, rng));
+        particles.push(Particle::new(&flame, position, velocity, rng));
+    }
+    particles
+}
+
+/// A normally distributed value with a standard deviation of 1
+fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sprite::Sprite;
+    use super::*;
+
+    fn emitter(name: &'static str, count: i32) -> Emitter {
+        Emitter {
+            name,
+            position: Vector3::new(0.0, 64.0, 0.0),
+            offset: Vector3::new(1.0, 0.0, 0.5),
+            speed: 0.5,
+            count,
+            block: None,
+            colour: None,
+        }
+    }
+
+    #[test]
+    fn emitter_particles() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(emitter("flame", 5).particles(&mut rng).len(), 5);
+        assert!(emitter("flash", 5).particles(&mut rng).is_empty());
+        // Blasts and smoke of a radius of 4
+        assert_eq!(
+            emitter("explosion_emitter", 1).particles(&mut rng).len(),
+            40
+        );
+
+        // Without a count the offset is the direction
+        let flame = emitter("flame", 0).particles(&mut rng);
+        assert_eq!(flame.len(), 1);
+        assert_eq!(flame[0].velocity, Vector3::new(0.5, 0.0, 0.25));
+
+        // or the colour of spell particles
+        let spell = emitter("entity_effect", 0).particles(&mut rng);
+        assert_eq!(spell[0].colour, [255, 0, 127]);
+
+        let mut debris = emitter("block", 3);
+        assert!(debris.particles(&mut rng).is_empty());
+        debris.block = Some(block::Block::Air {});
+        let debris = debris.particles(&mut rng);
+        assert_eq!(debris.len(), 3);
+        assert!(matches!(debris[0].sprite, Sprite::Block { .. }));
+    }
+}
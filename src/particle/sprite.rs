@@ -0,0 +1,368 @@
+//! Short lived billboards, moved once a tick and drawn as a single model.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+use cgmath::{InnerSpace, Vector3};
+use log::warn;
+use rand::Rng;
+use serde_json::Value;
+
+use super::kinds::Look;
+use crate::render::model::{self, ModelHandle};
+use crate::render::Renderer;
+use crate::resources;
+use crate::server::{RendererResource, WorldResource};
+use crate::shared::Position as BPos;
+use crate::world::{block, World};
+
+/// Old particles are dropped beyond this, like vanilla does per kind
+pub const MAX_PARTICLES: usize = 4096;
+
+/// Parent models are followed this deep for the particle texture
+const MAX_MODEL_DEPTH: usize = 16;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Sprite {
+    /// Textures shown one after another over the particle's life
+    Frames(&'static [&'static str]),
+    /// A small piece of a block's particle texture, at `u` and `v`
+    Block { block: block::Block, u: f32, v: f32 },
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Particle {
+    pub position: Vector3<f64>,
+    pub velocity: Vector3<f64>,
+    pub gravity: f64,
+    pub friction: f64,
+    pub collides: bool,
+    pub on_ground: bool,
+    pub size: f32,
+    pub colour: [u8; 3],
+    pub bright: bool,
+    pub age: u32,
+    pub lifetime: u32,
+    pub sprite: Sprite,
+}
+
+impl Particle {
+    pub fn new<R: Rng>(
+        look: &Look,
+        position: Vector3<f64>,
+        velocity: Vector3<f64>,
+        rng: &mut R,
+    ) -> Particle {
+        Particle {
+            position,
+            velocity,
+            gravity: look.gravity,
+            friction: look.friction,
+            collides: look.collides,
+            on_ground: false,
+            size: look.size * rng.gen_range(0.75..1.25),
+            colour: look.colour,
+            bright: look.bright,
+            age: 0,
+            lifetime: look.lifetime + rng.gen_range(0..=look.lifetime),
+            sprite: Sprite::Frames(look.frames),
+        }
+    }
+
+    /// A piece of `block`, which looks like the `block` particle
+    pub fn block<R: Rng>(
+        block: block::Block,
+        position: Vector3<f64>,
+        velocity: Vector3<f64>,
+        rng: &mut R,
+    ) -> Particle {
+        let look = super::kinds::look("block").unwrap();
+        Particle {
+            sprite: Sprite::Block {
+                block,
+                u: rng.gen_range(0.0..0.75),
+                v: rng.gen_range(0.0..0.75),
+            },
+            size: look.size * rng.gen_range(0.5..1.0),
+            ..Particle::new(&look, position, velocity, rng)
+        }
+    }
+
+    pub fn with_colour(mut self, colour: [u8; 3]) -> Particle {
+        self.colour = colour;
+        self
+    }
+
+    fn texture_name(&self) -> Option<&'static str> {
+        match self.sprite {
+            Sprite::Frames(frames) if !frames.is_empty() => {
+                let frame = self.age as usize * frames.len() / self.lifetime.max(1) as usize;
+                Some(frames[frame.min(frames.len() - 1)])
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The model all particles are drawn into
+#[derive(Resource, Default)]
+pub struct ParticleModel {
+    model: Option<ModelHandle>,
+    /// The particle texture of block models, by model
+    block_textures: HashMap<(String, String), String>,
+    resources_version: usize,
+}
+
+pub fn tick_particles(
+    world: Res<WorldResource>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Particle)>,
+) {
+    let world = &world.0;
+    let mut alive = Vec::new();
+    for (entity, mut particle) in query.iter_mut() {
+        particle.age += 1;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let gravity = particle.gravity;
+        particle.velocity.y -= gravity;
+        let velocity = particle.velocity;
+        move_particle(world, &mut particle, velocity);
+        let friction = particle.friction;
+        particle.velocity *= friction;
+        if particle.on_ground {
+            particle.velocity.x *= 0.7;
+            particle.velocity.z *= 0.7;
+        }
+        alive.push((entity, particle.age));
+    }
+    if alive.len() > MAX_PARTICLES {
+        alive.sort_unstable_by_key(|(_, age)| std::cmp::Reverse(*age));
+        for (entity, _) in &alive[..alive.len() - MAX_PARTICLES] {
+            commands.entity(*entity).despawn();
+        }
+    }
+}
+
+/// Moves one axis at a time, stopping at collidable blocks
+fn move_particle(world: &World, particle: &mut Particle, velocity: Vector3<f64>) {
+    if !particle.collides {
+        particle.position += velocity;
+        return;
+    }
+    for axis in 0..3 {
+        if velocity[axis] == 0.0 {
+            continue;
+        }
+        let mut next = particle.position;
+        next[axis] += velocity[axis];
+        if is_solid(world, next) {
+            particle.velocity[axis] = 0.0;
+            if axis == 1 && velocity.y < 0.0 {
+                particle.on_ground = true;
+            }
+        } else {
+            particle.position = next;
+            if axis == 1 {
+                particle.on_ground = false;
+            }
+        }
+    }
+}
+
+fn is_solid(world: &World, point: Vector3<f64>) -> bool {
+    let pos = BPos::new(
+        point.x.floor() as i32,
+        point.y.floor() as i32,
+        point.z.floor() as i32,
+    );
+    let block = world.get_block(pos);
+    if !block.get_material().collidable {
+        return false;
+    }
+    let offset = Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64);
+    block.get_collision_boxes().into_iter().any(|bound| {
+        let (min, max) = (bound.min + offset, bound.max + offset);
+        (0..3).all(|axis| point[axis] >= min[axis] && point[axis] <= max[axis])
+    })
+}
+
+pub fn render_particles(
+    renderer: Res<RendererResource>,
+    world: Res<WorldResource>,
+    mut particle_model: ResMut<ParticleModel>,
+    query: Query<&Particle>,
+) {
+    let renderer = &renderer.0;
+    let world = &world.0;
+    let version = renderer.resources.read().version();
+    if version != particle_model.resources_version {
+        particle_model.resources_version = version;
+        particle_model.block_textures.clear();
+    }
+
+    let (right, up) = {
+        let camera = renderer.camera.lock();
+        let forward = Vector3::new(
+            camera.yaw.sin() * camera.pitch.cos(),
+            camera.pitch.sin(),
+            camera.yaw.cos() * camera.pitch.cos(),
+        );
+        let right = Vector3::new(-camera.yaw.cos(), 0.0, camera.yaw.sin());
+        (right, right.cross(forward).normalize())
+    };
+    let sky_offset = renderer.light_data.lock().sky_offset;
+
+    let mut verts = Vec::new();
+    let mut textures = HashMap::new();
+    for particle in query.iter() {
+        let texture = match particle.sprite {
+            Sprite::Block { block, u, v } => {
+                let name = particle_model.block_texture(renderer, block);
+                let texture = textures
+                    .entry(name.clone())
+                    .or_insert_with(|| Renderer::get_texture(renderer.get_textures_ref(), &name));
+                texture.relative(u, v, 0.25, 0.25)
+            }
+            _ => match particle.texture_name() {
+                Some(name) => textures
+                    .entry(name.to_owned())
+                    .or_insert_with(|| Renderer::get_texture(renderer.get_textures_ref(), name))
+                    .clone(),
+                None => continue,
+            },
+        };
+        let light = if particle.bright {
+            1.0
+        } else {
+            light_at(world, particle.position, sky_offset)
+        };
+        let [r, g, b] = particle.colour.map(|c| (c as f32 * light) as u8);
+        let size = particle.size as f64;
+        let corners = [
+            (-1.0, -1.0, 0.0, 1.0),
+            (1.0, -1.0, 1.0, 1.0),
+            (-1.0, 1.0, 0.0, 0.0),
+            (1.0, 1.0, 1.0, 0.0),
+        ];
+        // Back faces are culled, so each quad is added facing both ways
+        for flip in [false, true] {
+            for &(x, y, tx, ty) in &corners {
+                let x = if flip { -x } else { x };
+                let tx = if flip { 1.0 - tx } else { tx };
+                let pos = particle.position + right * (x * size) + up * (y * size);
+                verts.push(model::Vertex {
+                    x: pos.x as f32,
+                    y: pos.y as f32,
+                    z: pos.z as f32,
+                    texture: texture.clone(),
+                    texture_x: tx,
+                    texture_y: ty,
+                    r,
+                    g,
+                    b,
+                    a: 255,
+                    id: 0,
+                });
+            }
+        }
+    }
+
+    let mut models = renderer.models.lock();
+    match &particle_model.model {
+        Some(model) => models.update_model(model, verts),
+        None => {
+            let model = models.create_model(model::DEFAULT, vec![verts], renderer.clone());
+            particle_model.model = Some(model);
+        }
+    }
+}
+
+/// How lit a particle is, from 0.25 in the dark to 1.0
+fn light_at(world: &World, position: Vector3<f64>, sky_offset: f32) -> f32 {
+    let pos = BPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    );
+    let block_light = world.get_block_light(pos) as f32 / 15.0;
+    let sky_light = world.get_sky_light(pos) as f32 / 15.0 * sky_offset;
+    0.25 + 0.75 * block_light.max(sky_light)
+}
+
+impl ParticleModel {
+    fn block_texture(&mut self, renderer: &Arc<Renderer>, block: block::Block) -> String {
+        let (plugin, name) = block.get_model();
+        let key = (plugin.to_owned(), name.to_owned());
+        if let Some(texture) = self.block_textures.get(&key) {
+            return texture.clone();
+        }
+        let texture =
+            find_block_texture(&renderer.resources.read(), plugin, name).unwrap_or_else(|| {
+                warn!("No particle texture for block {}:{}", plugin, name);
+                "leafish:missing_texture".to_owned()
+            });
+        self.block_textures.insert(key, texture.clone());
+        texture
+    }
+}
+
+/// The `particle` texture of the first model of a block state
+fn find_block_texture(res: &resources::Manager, plugin: &str, name: &str) -> Option<String> {
+    let states: Value =
+        serde_json::from_reader(res.open(plugin, &format!("blockstates/{}.json", name))?).ok()?;
+    let first_model = |value: &Value| -> Option<String> {
+        let value = match value {
+            Value::Array(models) => models.first()?,
+            value => value,
+        };
+        value.get("model")?.as_str().map(str::to_owned)
+    };
+    let mut model = match states.get("variants").and_then(Value::as_object) {
+        Some(variants) => variants.values().find_map(first_model),
+        None => states
+            .get("multipart")?
+            .as_array()?
+            .iter()
+            .find_map(|part| first_model(part.get("apply")?)),
+    }?;
+
+    let mut variables = HashMap::new();
+    let mut texture = "#particle".to_owned();
+    for _ in 0..MAX_MODEL_DEPTH {
+        let path = match model.split_once(':') {
+            Some((_, path)) => path,
+            None => &model,
+        };
+        let path = if path.contains('/') {
+            path.to_owned()
+        } else {
+            format!("block/{}", path)
+        };
+        let json: Value =
+            serde_json::from_reader(res.open(plugin, &format!("models/{}.json", path))?).ok()?;
+        if let Some(vars) = json.get("textures").and_then(Value::as_object) {
+            for (var, value) in vars {
+                if let Some(value) = value.as_str() {
+                    variables
+                        .entry(var.clone())
+                        .or_insert_with(|| value.to_owned());
+                }
+            }
+        }
+        for _ in 0..MAX_MODEL_DEPTH {
+            match texture.strip_prefix('#').and_then(|var| variables.get(var)) {
+                Some(value) => texture = value.clone(),
+                None => break,
+            }
+        }
+        if !texture.starts_with('#') {
+            return Some(texture);
+        }
+        model = json.get("parent")?.as_str()?.to_owned();
+    }
+    None
+}
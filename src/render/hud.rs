@@ -80,6 +80,10 @@ pub struct HudContext {
     dirty_game_mode: bool,
     pub action_bar: Option<Component>,
     dirty_action_bar: bool,
+    player_list_shown: bool,
+    player_list_header: Option<Component>,
+    player_list_footer: Option<Component>,
+    dirty_player_list: bool,
 }
 
 impl Default for render::hud::HudContext {
@@ -127,6 +131,10 @@ impl HudContext {
             dirty_game_mode: false,
             action_bar: None,
             dirty_action_bar: false,
+            player_list_shown: false,
+            player_list_header: None,
+            player_list_footer: None,
+            dirty_player_list: false,
         }
     }
 
@@ -212,6 +220,29 @@ impl HudContext {
         self.action_bar = Some(message);
         self.dirty_action_bar = true;
     }
+
+    pub fn show_player_list(&mut self, shown: bool) {
+        self.player_list_shown = shown;
+        self.dirty_player_list = true;
+    }
+
+    pub fn set_player_list_header_footer(
+        &mut self,
+        header: format::Component,
+        footer: format::Component,
+    ) {
+        // Servers clear them by sending empty texts
+        let non_empty = |text: format::Component| Some(text).filter(|t| !t.to_string().is_empty());
+        self.player_list_header = non_empty(header);
+        self.player_list_footer = non_empty(footer);
+        self.dirty_player_list = true;
+    }
+
+    pub fn update_player_list(&mut self) {
+        if self.player_list_shown {
+            self.dirty_player_list = true;
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -232,8 +263,19 @@ pub struct Hud {
     chat_elements: Vec<FormattedRef>,
     chat_background_elements: Vec<ImageRef>,
     action_bar_text_elements: Option<FormattedRef>,
+    scoreboard_elements: Vec<ImageRef>,
+    scoreboard_text_elements: Vec<FormattedRef>,
+    scoreboard_version: usize,
+    boss_bar_elements: Vec<ImageRef>,
+    boss_bar_text_elements: Vec<FormattedRef>,
+    boss_bar_version: usize,
+    player_list_elements: Vec<ImageRef>,
+    player_list_text_elements: Vec<FormattedRef>,
+    // Skins kept loaded for the faces in the player list
+    player_list_skins: Vec<String>,
     hud_context: Arc<RwLock<HudContext>>,
     last_tick: Instant,
+    last_debug_update: Instant,
     render_chat: bool,
 }
 
@@ -256,8 +298,18 @@ impl Hud {
             chat_elements: vec![],
             chat_background_elements: vec![],
             action_bar_text_elements: None,
+            scoreboard_elements: vec![],
+            scoreboard_text_elements: vec![],
+            scoreboard_version: 0,
+            boss_bar_elements: vec![],
+            boss_bar_text_elements: vec![],
+            boss_bar_version: 0,
+            player_list_elements: vec![],
+            player_list_text_elements: vec![],
+            player_list_skins: vec![],
             hud_context,
             last_tick: Instant::now(),
+            last_debug_update: Instant::now(),
             render_chat: false,
         }
     }
@@ -277,6 +329,9 @@ impl Screen for Hud {
             self.render_crosshair(&renderer, ui_container);
             self.render_chat(&renderer, ui_container);
             self.render_action_bar(&renderer, ui_container);
+            self.render_scoreboard(&renderer, ui_container);
+            self.render_boss_bars(&renderer, ui_container);
+            self.render_player_list(&renderer, ui_container);
             let game_mode = self.hud_context.read().game_mode;
             if matches!(game_mode, GameMode::Adventure | GameMode::Survival) {
                 self.render_health(&renderer, ui_container);
@@ -291,7 +346,7 @@ impl Screen for Hud {
     fn deinit(
         &mut self,
         _screen_sys: &ScreenSystem,
-        _renderer: Arc<Renderer>,
+        renderer: Arc<Renderer>,
         _ui_container: &mut Container,
     ) {
         self.elements.clear();
@@ -307,6 +362,11 @@ impl Screen for Hud {
         self.debug_elements.clear();
         self.chat_elements.clear();
         self.chat_background_elements.clear();
+        self.scoreboard_elements.clear();
+        self.scoreboard_text_elements.clear();
+        self.boss_bar_elements.clear();
+        self.boss_bar_text_elements.clear();
+        self.clear_player_list(&renderer);
     }
 
     fn on_active(
@@ -345,7 +405,12 @@ impl Screen for Hud {
             return;
         }
         if self.hud_context.read().debug {
-            self.render_debug(&renderer, ui_container);
+            if !self.last_debug_enabled
+                || self.last_debug_update.elapsed().as_millis() >= DEBUG_REFRESH_MS
+            {
+                self.debug_elements.clear();
+                self.render_debug(&renderer, ui_container);
+            }
             self.last_debug_enabled = true;
         } else if self.last_debug_enabled {
             self.debug_elements.clear();
@@ -422,6 +487,23 @@ impl Screen for Hud {
         if self.hud_context.read().dirty_action_bar {
             self.render_action_bar(&renderer, ui_container);
         }
+        if self.hud_context.read().dirty_player_list {
+            self.render_player_list(&renderer, ui_container);
+        }
+        let versions = self.hud_context.read().server.as_ref().map(|server| {
+            (
+                server.scoreboard.read().version(),
+                server.boss_bars.read().version(),
+            )
+        });
+        if let Some((scoreboard_version, boss_bar_version)) = versions {
+            if scoreboard_version != self.scoreboard_version {
+                self.render_scoreboard(&renderer, ui_container);
+            }
+            if boss_bar_version != self.boss_bar_version {
+                self.render_boss_bars(&renderer, ui_container);
+            }
+        }
         if (self
             .hud_context
             .read()
@@ -893,9 +975,351 @@ impl Hud {
         self.elements.push(image);
     }
 
-    // TODO: make use of "render_scoreboard"
-    #[allow(dead_code)]
-    fn render_scoreboard(&mut self, _renderer: &Arc<Renderer>, _ui_container: &mut Container) {}
+    fn clear_player_list(&mut self, renderer: &Arc<Renderer>) {
+        self.player_list_elements.clear();
+        self.player_list_text_elements.clear();
+        let textures = renderer.get_textures_ref().read();
+        for url in self.player_list_skins.drain(..) {
+            textures.release_skin(&url);
+        }
+    }
+
+    fn render_player_list(&mut self, renderer: &Arc<Renderer>, ui_container: &mut Container) {
+        self.clear_player_list(renderer);
+        self.hud_context.write().dirty_player_list = false;
+        let hud_context = self.hud_context.read();
+        if !hud_context.player_list_shown {
+            return;
+        }
+        let entries = match hud_context.server.as_ref() {
+            Some(server) => server.player_list(),
+            None => return,
+        };
+        let header = hud_context
+            .player_list_header
+            .as_ref()
+            .map_or_else(Vec::new, split_lines);
+        let footer = hud_context
+            .player_list_footer
+            .as_ref()
+            .map_or_else(Vec::new, split_lines);
+        drop(hud_context);
+
+        // Laid out in gui pixels like vanilla, up to 20 rows per column
+        const MAX_ROWS: usize = 20;
+        const LINE_HEIGHT: f64 = 9.0;
+        const FACE_WIDTH: f64 = 9.0;
+        const PING_WIDTH: f64 = 13.0;
+        const COLUMN_GAP: f64 = 5.0;
+        let icon_scale = Hud::icon_scale(renderer);
+        let scale = icon_scale / 2.0;
+        let screen_width = renderer.screen_data.read().safe_width as f64 / icon_scale;
+        let text_width =
+            |text: &Component| renderer.ui.lock().size_of_string(&text.to_string()) / 2.0;
+
+        let mut columns = 1;
+        let mut rows = entries.len();
+        while rows > MAX_ROWS {
+            columns += 1;
+            rows = (entries.len() + columns - 1) / columns;
+        }
+        let name_width = entries
+            .iter()
+            .map(|entry| text_width(&entry.name))
+            .fold(0.0, f64::max);
+        let column_width = ((FACE_WIDTH + name_width + PING_WIDTH) * columns as f64)
+            .min(screen_width - 50.0)
+            / columns as f64;
+        let list_width = column_width * columns as f64 + (columns - 1) as f64 * COLUMN_GAP;
+        let width = header
+            .iter()
+            .chain(&footer)
+            .map(text_width)
+            .fold(list_width, f64::max);
+
+        // Everything is placed by its left edge, relative to the middle of the screen
+        let image =
+            |texture: &str, coords: (f64, f64, f64, f64), x: f64, y: f64, w: f64, h: f64| {
+                ui::ImageBuilder::new()
+                    .draw_index(HUD_PRIORITY + 2)
+                    .texture(texture)
+                    .texture_coords(coords)
+                    .alignment(VAttach::Top, HAttach::Center)
+                    .position((x + w / 2.0) * icon_scale, y * icon_scale)
+                    .size(w * icon_scale, h * icon_scale)
+            };
+        let text = |component: Component, x: f64, y: f64, transparency: f64| {
+            let w = text_width(&component);
+            ui::FormattedBuilder::new()
+                .draw_index(HUD_PRIORITY + 3)
+                .scale_x(scale)
+                .scale_y(scale)
+                .alignment(VAttach::Top, HAttach::Center)
+                .position((x + w / 2.0) * icon_scale, y * icon_scale)
+                .transparency(transparency)
+                .text(component)
+        };
+
+        let top = 10.0;
+        let mut y = top;
+        for line in header {
+            let x = -text_width(&line) / 2.0;
+            self.player_list_text_elements
+                .push(text(line, x, y, 1.0).create(ui_container));
+            y += LINE_HEIGHT;
+        }
+        if y > top {
+            y += 1.0;
+        }
+        let list_top = y;
+        let left = -list_width / 2.0;
+        for (i, entry) in entries.into_iter().enumerate() {
+            let x = left + (i / rows) as f64 * (column_width + COLUMN_GAP);
+            let y = list_top + (i % rows) as f64 * LINE_HEIGHT;
+            self.player_list_elements.push(
+                image(
+                    "leafish:solid",
+                    (0.0, 0.0, 1.0, 1.0),
+                    x,
+                    y,
+                    column_width,
+                    8.0,
+                )
+                .colour((255, 255, 255, 32))
+                .create(ui_container),
+            );
+
+            let skin = match &entry.skin_url {
+                Some(url) => {
+                    let skin = renderer.get_skin(renderer.get_textures_ref(), url);
+                    self.player_list_skins.push(url.clone());
+                    skin.name
+                }
+                None => "minecraft:entity/steve".to_owned(),
+            };
+            // The face, then the hat over it, from the 64x64 skin
+            for u in [32.0, 160.0] {
+                self.player_list_elements
+                    .push(image(&skin, (u, 32.0, 32.0, 32.0), x, y, 8.0, 8.0).create(ui_container));
+            }
+
+            // Spectators are faded out like in vanilla
+            let transparency = if entry.gamemode == GameMode::Spectator {
+                0.56
+            } else {
+                1.0
+            };
+            self.player_list_text_elements
+                .push(text(entry.name, x + FACE_WIDTH, y, transparency).create(ui_container));
+
+            let bars = match entry.ping {
+                ping if ping < 0 => 5.0,
+                ping if ping < 150 => 0.0,
+                ping if ping < 300 => 1.0,
+                ping if ping < 600 => 2.0,
+                ping if ping < 1000 => 3.0,
+                _ => 4.0,
+            };
+            self.player_list_elements.push(
+                image(
+                    "minecraft:gui/icons",
+                    (0.0, 176.0 + bars * 8.0, 10.0, 8.0),
+                    x + column_width - 11.0,
+                    y,
+                    10.0,
+                    8.0,
+                )
+                .create(ui_container),
+            );
+        }
+        y = list_top + rows as f64 * LINE_HEIGHT;
+        if !footer.is_empty() {
+            y += 1.0;
+        }
+        for line in footer {
+            let x = -text_width(&line) / 2.0;
+            self.player_list_text_elements
+                .push(text(line, x, y, 1.0).create(ui_container));
+            y += LINE_HEIGHT;
+        }
+
+        self.player_list_elements.push(
+            ui::ImageBuilder::new()
+                .draw_index(HUD_PRIORITY + 1)
+                .texture("leafish:solid")
+                .alignment(VAttach::Top, HAttach::Center)
+                .position(0.0, (top - 1.0) * icon_scale)
+                .size((width + 2.0) * icon_scale, (y - top + 1.0) * icon_scale)
+                .colour((0, 0, 0, 128))
+                .create(ui_container),
+        );
+    }
+
+    fn render_boss_bars(&mut self, renderer: &Arc<Renderer>, ui_container: &mut Container) {
+        self.boss_bar_elements.clear();
+        self.boss_bar_text_elements.clear();
+        let hud_context = self.hud_context.read();
+        let server = match hud_context.server.as_ref() {
+            Some(server) => server,
+            None => return,
+        };
+        let boss_bars = server.boss_bars.read();
+        self.boss_bar_version = boss_bars.version();
+
+        const BAR_WIDTH: f64 = 182.0;
+        const BAR_HEIGHT: f64 = 5.0;
+        const BAR_SPACING: f64 = 19.0;
+        let icon_scale = Hud::icon_scale(renderer);
+        // Like vanilla, bars stop a third of the way down the screen
+        let max_y = renderer.screen_data.read().safe_height as f64 / 3.0;
+        let mut y = 12.0;
+        for bar in boss_bars.bars() {
+            if y * icon_scale >= max_y {
+                break;
+            }
+            let row = bar.colour.row() as f64 * 10.0;
+            let overlay_row = match bar.notches {
+                6 => Some(80.0),
+                10 => Some(90.0),
+                12 => Some(100.0),
+                20 => Some(110.0),
+                _ => None,
+            };
+            let progress = (bar.health as f64 * BAR_WIDTH).round();
+            // The background, then the progress over it, each with its notches
+            let mut parts = vec![(row, BAR_WIDTH)];
+            parts.extend(overlay_row.map(|overlay| (overlay, BAR_WIDTH)));
+            if progress > 0.0 {
+                parts.push((row + BAR_HEIGHT, progress));
+                parts.extend(overlay_row.map(|overlay| (overlay + BAR_HEIGHT, progress)));
+            }
+            for (v, width) in parts {
+                self.boss_bar_elements.push(
+                    ui::ImageBuilder::new()
+                        .draw_index(HUD_PRIORITY)
+                        .texture("minecraft:gui/bars")
+                        .texture_coords((0.0, v, width, BAR_HEIGHT))
+                        .alignment(VAttach::Top, HAttach::Center)
+                        .position(-(BAR_WIDTH - width) / 2.0 * icon_scale, y * icon_scale)
+                        .size(width * icon_scale, BAR_HEIGHT * icon_scale)
+                        .create(ui_container),
+                );
+            }
+            self.boss_bar_text_elements.push(
+                ui::FormattedBuilder::new()
+                    .draw_index(HUD_PRIORITY)
+                    .scale_x(icon_scale / 2.0)
+                    .scale_y(icon_scale / 2.0)
+                    .alignment(VAttach::Top, HAttach::Center)
+                    .position(0.0, (y - 10.0) * icon_scale)
+                    .text(bar.title.clone())
+                    .create(ui_container),
+            );
+            y += BAR_SPACING;
+        }
+    }
+
+    fn render_scoreboard(&mut self, renderer: &Arc<Renderer>, ui_container: &mut Container) {
+        self.scoreboard_elements.clear();
+        self.scoreboard_text_elements.clear();
+        let hud_context = self.hud_context.read();
+        let server = match hud_context.server.as_ref() {
+            Some(server) => server,
+            None => return,
+        };
+        let scoreboard = server.scoreboard.read();
+        self.scoreboard_version = scoreboard.version();
+        let sidebar = match scoreboard.sidebar(&server.own_name().unwrap_or_default()) {
+            Some(sidebar) => sidebar,
+            None => return,
+        };
+
+        // Widths are in pixels of the gui texture, like the icons
+        let icon_scale = Hud::icon_scale(renderer);
+        let scale = icon_scale / 2.0;
+        let text_width = |text: &str| renderer.ui.lock().size_of_string(text) / 2.0;
+        let scores: Vec<String> = sidebar
+            .lines
+            .iter()
+            .map(|(_, score)| score.to_string())
+            .collect();
+        let separator = text_width(": ");
+        let width = sidebar
+            .lines
+            .iter()
+            .zip(&scores)
+            .map(|((name, _), score)| text_width(&name.to_string()) + separator + text_width(score))
+            .fold(text_width(&sidebar.title.to_string()), f64::max);
+
+        const LINE_HEIGHT: f64 = 9.0;
+        const MARGIN: f64 = 1.0;
+        let top = -((sidebar.lines.len() + 1) as f64) * LINE_HEIGHT / 2.0;
+        // Offsets of the centre of each line from the middle of the screen
+        let line_y = |line: usize| (top + (line as f64 + 0.5) * LINE_HEIGHT) * icon_scale;
+
+        for (line, alpha) in [(0, 102), (1, 76)] {
+            let lines = if line == 0 { 1 } else { sidebar.lines.len() };
+            if lines == 0 {
+                continue;
+            }
+            let height = lines as f64 * LINE_HEIGHT;
+            let y = (top + line as f64 * LINE_HEIGHT + height / 2.0) * icon_scale;
+            self.scoreboard_elements.push(
+                ui::ImageBuilder::new()
+                    .draw_index(HUD_PRIORITY)
+                    .texture("leafish:solid")
+                    .alignment(VAttach::Middle, HAttach::Right)
+                    .position(MARGIN * icon_scale, y)
+                    .size((width + 2.0) * icon_scale, height * icon_scale)
+                    .colour((0, 0, 0, alpha))
+                    .create(ui_container),
+            );
+        }
+
+        let title_width = text_width(&sidebar.title.to_string());
+        self.scoreboard_text_elements.push(
+            ui::FormattedBuilder::new()
+                .draw_index(HUD_PRIORITY + 1)
+                .scale_x(scale)
+                .scale_y(scale)
+                .alignment(VAttach::Middle, HAttach::Right)
+                .position(
+                    (MARGIN + 1.0 + (width - title_width) / 2.0) * icon_scale,
+                    line_y(0),
+                )
+                .text(sidebar.title.clone())
+                .create(ui_container),
+        );
+        for (i, ((name, _), score)) in sidebar.lines.iter().zip(&scores).enumerate() {
+            let name_width = text_width(&name.to_string());
+            self.scoreboard_text_elements.push(
+                ui::FormattedBuilder::new()
+                    .draw_index(HUD_PRIORITY + 1)
+                    .scale_x(scale)
+                    .scale_y(scale)
+                    .alignment(VAttach::Middle, HAttach::Right)
+                    .position(
+                        (MARGIN + 1.0 + width - name_width) * icon_scale,
+                        line_y(i + 1),
+                    )
+                    .text(name.clone())
+                    .create(ui_container),
+            );
+            self.scoreboard_text_elements.push(
+                ui::FormattedBuilder::new()
+                    .draw_index(HUD_PRIORITY + 1)
+                    .scale_x(scale)
+                    .scale_y(scale)
+                    .alignment(VAttach::Middle, HAttach::Right)
+                    .position((MARGIN + 1.0) * icon_scale, line_y(i + 1))
+                    .text(Component::new(format::ComponentType::new(
+                        score,
+                        Some(format::Color::Red),
+                    )))
+                    .create(ui_container),
+            );
+        }
+    }
 
     // TODO: make use of "render_title"
     #[allow(dead_code)]
@@ -1013,21 +1437,48 @@ impl Hud {
     }
 
     pub fn render_debug(&mut self, renderer: &Arc<Renderer>, ui_container: &mut Container) {
+        self.last_debug_update = Instant::now();
         let hud_context = self.hud_context.read();
         let icon_scale = Hud::icon_scale(renderer);
         let scale = icon_scale / 2.0;
-        self.debug_elements.push(
-            ui::TextBuilder::new()
-                .draw_index(HUD_PRIORITY)
-                .alignment(VAttach::Top, HAttach::Left)
-                .scale_x(scale)
-                .scale_y(scale)
-                .position(icon_scale, icon_scale)
-                .text(format!("FPS: {}", hud_context.fps))
-                .colour((0, 102, 204, 255))
-                .shadow(false)
-                .create(ui_container),
-        );
+        let mut lines = vec![format!("FPS: {}", hud_context.fps)];
+        if let Some(server) = hud_context.server.as_ref() {
+            let mut stats = server.network_stats.lock().unwrap();
+            let stats = &mut *stats;
+            for (label, traffic) in [("In", &mut stats.received), ("Out", &mut stats.sent)] {
+                lines.push(format!(
+                    "{}: {}/s, {} packets, compression {:.2}x",
+                    label,
+                    format_bytes(traffic.bytes_per_second()),
+                    traffic.total_packets(),
+                    traffic.compression_ratio(),
+                ));
+            }
+            for (name, packet) in stats.received.top(DEBUG_TOP_PACKETS) {
+                lines.push(format!(
+                    "  {}: {} ({}, decode {:.1}ms, handle {:.1}ms)",
+                    name,
+                    packet.count,
+                    format_bytes(packet.bytes as f64),
+                    packet.decode_time.as_secs_f64() * 1000.0,
+                    packet.handle_time.as_secs_f64() * 1000.0,
+                ));
+            }
+        }
+        for (i, line) in lines.into_iter().enumerate() {
+            self.debug_elements.push(
+                ui::TextBuilder::new()
+                    .draw_index(HUD_PRIORITY)
+                    .alignment(VAttach::Top, HAttach::Left)
+                    .scale_x(scale)
+                    .scale_y(scale)
+                    .position(icon_scale, icon_scale + i as f64 * 10.0 * scale)
+                    .text(line)
+                    .colour((0, 102, 204, 255))
+                    .shadow(false)
+                    .create(ui_container),
+            );
+        }
     }
 
     fn render_chat(&mut self, renderer: &Arc<Renderer>, ui_container: &mut Container) {
@@ -1171,7 +1622,69 @@ impl Hud {
     }
 }
 
+/// How often the debug overlay picks up new network statistics
+const DEBUG_REFRESH_MS: u128 = 1000;
+/// How many of the busiest packet types the debug overlay lists
+const DEBUG_TOP_PACKETS: usize = 5;
+
+fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 {
+        format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KiB", bytes / 1024.0)
+    } else {
+        format!("{:.0} B", bytes)
+    }
+}
+
 pub const CHAT_WIDTH: f64 = 490.0 / 2.0;
 const HUD_PRIORITY: isize = -2;
 pub const START_TICKS: usize = 10 * 20;
 pub const FADE_OUT_START_TICKS: usize = 20;
+
+/// Splits a text into one component per line, keeping the formatting
+fn split_lines(text: &Component) -> Vec<Component> {
+    let mut lines = vec![Component {
+        list: vec![],
+        source: None,
+    }];
+    for part in &text.list {
+        for (i, piece) in part.get_text().split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Component {
+                    list: vec![],
+                    source: None,
+                });
+            }
+            lines
+                .last_mut()
+                .unwrap()
+                .list
+                .push(format::ComponentType::Text {
+                    text: piece.to_owned(),
+                    modifier: part.get_modifier().clone(),
+                });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_lines() {
+        let header = Component::from_str(
+            r#"{"text":"Welcome\n","extra":[{"text":"to the\nserver","color":"red"}]}"#,
+        );
+        let lines = split_lines(&header);
+        let texts: Vec<String> = lines.iter().map(Component::to_string).collect();
+        assert_eq!(texts, ["Welcome", "to the", "server"]);
+        let server = &lines[2].list[0];
+        assert_eq!(server.get_modifier().color, format::Color::Red);
+
+        let footer = Component::from_str(r#"{"text":"one line"}"#);
+        assert_eq!(split_lines(&footer).len(), 1);
+    }
+}
@@ -103,6 +103,8 @@ pub struct LightData {
     // Light rendering
     pub light_level: f32,
     pub sky_offset: f32,
+    // Distance at which chunks fade fully into the sky, 0 for no fog
+    pub fog_end: f32,
 }
 
 #[derive(Copy, Clone)]
@@ -156,6 +158,8 @@ init_shader! {
             required texture => "textures",
             required light_level => "lightLevel",
             required sky_offset => "skyOffset",
+            required fog_end => "fogEnd",
+            required fog_colour => "fogColour",
         },
     }
 }
@@ -178,6 +182,8 @@ init_shader! {
             required texture => "textures",
             required light_level => "lightLevel",
             required sky_offset => "skyOffset",
+            required fog_end => "fogEnd",
+            required fog_colour => "fogColour",
         },
     }
 }
@@ -267,6 +273,7 @@ impl Renderer {
             light_data: Mutex::new(LightData {
                 light_level: 0.8,
                 sky_offset: 1.0,
+                fog_end: 0.0,
             }),
             element_buffer_data: Mutex::new(ElementBufferData {
                 element_buffer: gl::Buffer::new(),
@@ -375,12 +382,12 @@ impl Renderer {
             gl::enable(gl::MULTISAMPLE);
 
             let time_offset = self.light_data.lock().sky_offset * 0.9;
-            gl::clear_color(
+            let sky_colour = (
                 (122.0 / 255.0) * time_offset,
                 (165.0 / 255.0) * time_offset,
                 (247.0 / 255.0) * time_offset,
-                1.0,
             );
+            gl::clear_color(sky_colour.0, sky_colour.1, sky_colour.2, 1.0);
             gl::clear(gl::ClearFlags::Color | gl::ClearFlags::Depth);
             // Chunk rendering
             self.chunk_render_data
@@ -414,6 +421,16 @@ impl Renderer {
                 .chunk_shader
                 .sky_offset
                 .set_float(self.light_data.lock().sky_offset);
+            self.chunk_render_data
+                .lock()
+                .chunk_shader
+                .fog_end
+                .set_float(self.light_data.lock().fog_end);
+            self.chunk_render_data
+                .lock()
+                .chunk_shader
+                .fog_colour
+                .set_float3(sky_colour.0, sky_colour.1, sky_colour.2);
 
             let tmp_world = world.as_ref().unwrap().clone();
 
@@ -494,6 +511,16 @@ impl Renderer {
                     .chunk_shader_alpha
                     .sky_offset
                     .set_float(light_data.sky_offset);
+                self.chunk_render_data
+                    .lock()
+                    .chunk_shader_alpha
+                    .fog_end
+                    .set_float(light_data.fog_end);
+                self.chunk_render_data
+                    .lock()
+                    .chunk_shader_alpha
+                    .fog_colour
+                    .set_float3(sky_colour.0, sky_colour.1, sky_colour.2);
 
                 // Copy the depth buffer
                 let chunk_data = self.chunk_render_data.lock();
@@ -208,6 +208,25 @@ impl Manager {
         ModelHandle(key, renderer, None)
     }
 
+    /// Replaces the vertices of a model, which all belong to its first part
+    pub fn update_model(&mut self, key: &ModelHandle, verts: Vec<Vertex>) {
+        let collection = &mut self.collections[(key.0 .0).0];
+        let model = match collection.models.get_mut(&key.0) {
+            Some(model) => model,
+            None => return,
+        };
+        model.verts = verts;
+        Self::rebuild_model(model);
+        if self.max_index < model.count as usize {
+            let (data, ty) = super::generate_element_buffer(model.count as usize);
+            self.index_buffer.bind(gl::ELEMENT_ARRAY_BUFFER);
+            self.index_buffer
+                .set_data(gl::ELEMENT_ARRAY_BUFFER, &data, gl::DYNAMIC_DRAW);
+            self.max_index = model.count as usize;
+            self.index_type = ty;
+        }
+    }
+
     fn remove_model(&mut self, key: &ModelHandle) {
         let collection = &mut self.collections[(key.0 .0).0];
         collection.models.remove(&key.0);
@@ -1,10 +1,13 @@
 uniform sampler2DArray textures;
+uniform float fogEnd;
+uniform vec3 fogColour;
 
 in vec3 vColor;
 in vec4 vTextureInfo;
 in vec2 vTextureOffset;
 in float vAtlas;
 in vec3 vLighting;
+in float vDistance;
 
 #ifdef ES
 layout(location = 2) out vec4 fragColor;
@@ -29,6 +32,10 @@ void main() {
     #endif
     col *= vec4(vColor, 1.0);
     col.rgb *= vLighting;
+    if (fogEnd > 0.0) {
+        float fog = clamp((vDistance - fogEnd * 0.05) / (fogEnd * 0.95), 0.0, 1.0);
+        col.rgb = mix(col.rgb, fogColour, fog);
+    }
 
     #ifndef alpha
     fragColor = col;
@@ -159,6 +159,17 @@ impl Manager {
         ret
     }
 
+    /// Like `open_all`, but for files outside of any namespace such as the
+    /// `pack.mcmeta` of the assets, which lists the languages.
+    pub fn open_root_all(&self, name: &str) -> Vec<Box<dyn io::Read>> {
+        let path = format!("assets/{}", name);
+        self.packs
+            .iter()
+            .rev()
+            .filter_map(|pack| pack.open(&path))
+            .collect()
+    }
+
     pub fn tick(&mut self, mui: &mut ManagerUI, ui_container: &mut ui::Container, delta: f64) {
         let delta = delta.min(5.0);
 
@@ -14,7 +14,6 @@
 
 use std::sync::Arc;
 
-use crate::protocol::packet;
 use crate::render::hud::{Hud, START_TICKS};
 use crate::render::{hud, Renderer};
 use crate::screen::{Screen, ScreenSystem, ScreenType};
@@ -34,6 +33,8 @@ const MAX_MESSAGE_LENGTH_SINCE_1_11: usize = 256;
 
 pub struct ChatContext {
     messages: Arc<RwLock<Vec<(usize, Component)>>>,
+    /// Server provided preview of the message being typed (1.19)
+    preview: RwLock<Option<Component>>,
     dirty: AtomicBool,
 }
 
@@ -41,6 +42,7 @@ impl ChatContext {
     pub fn new() -> Self {
         ChatContext {
             messages: Arc::new(Default::default()),
+            preview: RwLock::new(None),
             dirty: Default::default(),
         }
     }
@@ -53,6 +55,11 @@ impl ChatContext {
         self.dirty.store(true, Ordering::Release);
     }
 
+    pub fn set_preview(&self, preview: Option<Component>) {
+        *self.preview.write() = preview;
+        self.dirty.store(true, Ordering::Release);
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.dirty.load(Ordering::Acquire)
     }
@@ -269,12 +276,13 @@ impl super::Screen for Chat {
         }
         if key.0 == Key::Named(NamedKey::Enter) && !repeat {
             if !self.written.is_empty() {
-                game.server.load().as_ref().unwrap().write_packet(
-                    packet::play::serverbound::ChatMessage {
-                        message: self.written.clone(),
-                    },
-                );
+                game.server
+                    .load()
+                    .as_ref()
+                    .unwrap()
+                    .send_chat(self.written.clone());
             }
+            self.context.set_preview(None);
             game.screen_sys.pop_screen();
             return;
         }
@@ -294,7 +302,7 @@ impl super::Screen for Chat {
                     }
                     self.written.push(c);
                 }
-                self.dirty_written = true;
+                self.on_written_changed(game);
             }
             return;
         }
@@ -302,7 +310,7 @@ impl super::Screen for Chat {
             // Handle backspace
             if !self.written.is_empty() {
                 self.written.pop();
-                self.dirty_written = true;
+                self.on_written_changed(game);
             }
             return;
         }
@@ -326,7 +334,7 @@ impl super::Screen for Chat {
                     return;
                 }
                 self.written.push_str(str);
-                self.dirty_written = true;
+                self.on_written_changed(game);
             }
         }
     }
@@ -345,6 +353,15 @@ impl super::Screen for Chat {
 }
 
 impl Chat {
+    fn on_written_changed(&mut self, game: &Game) {
+        self.dirty_written = true;
+        game.server
+            .load()
+            .as_ref()
+            .unwrap()
+            .request_chat_preview(&self.written);
+    }
+
     fn render_chat(&mut self, renderer: Arc<Renderer>, ui_container: &mut Container) {
         let scale = Hud::icon_scale(&renderer);
         let history_size = self.context.messages.read().len();
@@ -388,6 +405,17 @@ impl Chat {
                 .create(ui_container),
         );
 
+        if let Some(preview) = self.context.preview.read().clone() {
+            let text = ui::FormattedBuilder::new()
+                .draw_index(0)
+                .alignment(VAttach::Bottom, HAttach::Left)
+                .position(2.0 * scale, (5.0 * scale + 0.4 * scale) * 1.5 + 2.0 * scale)
+                .text(preview)
+                .max_width(hud::CHAT_WIDTH * scale)
+                .create(ui_container);
+            self.rendered_messages.push(text);
+        }
+
         let mut component_lines = 0;
         for i in 0..cmp::min(10, history_size) {
             let message = self.context.messages.read()[history_size - 1 - i].clone();
@@ -15,11 +15,15 @@
 use crate::render;
 use crate::screen::{Screen, ScreenSystem};
 use crate::ui;
+use crossbeam_channel::Receiver;
+use leafish_protocol::translate::translate;
 use std::sync::Arc;
 
 pub struct Connecting {
     elements: Option<UIElements>,
     target: String,
+    status: Receiver<String>,
+    last_status: String,
 }
 
 impl Clone for Connecting {
@@ -27,6 +31,8 @@ impl Clone for Connecting {
         Connecting {
             elements: None,
             target: self.target.clone(),
+            status: self.status.clone(),
+            last_status: self.last_status.clone(),
         }
     }
 }
@@ -35,14 +41,18 @@ struct UIElements {
     logo: ui::logo::Logo,
     _connect_msg: ui::TextRef,
     _msg: ui::TextRef,
+    status: ui::TextRef,
     _disclaimer: ui::TextRef,
 }
 
 impl Connecting {
-    pub fn new(target: &str) -> Connecting {
+    /// `status` delivers progress messages, like which address answered
+    pub fn new(target: &str, status: Receiver<String>) -> Connecting {
         Connecting {
             elements: None,
             target: target.to_owned(),
+            status,
+            last_status: String::new(),
         }
     }
 }
@@ -57,7 +67,7 @@ impl super::Screen for Connecting {
         let logo = ui::logo::Logo::new(renderer.resources.clone(), ui_container);
 
         let connect_msg = ui::TextBuilder::new()
-            .text("Connecting to")
+            .text(translate("leafish.connecting"))
             .position(0.0, -16.0)
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .create(ui_container);
@@ -69,6 +79,13 @@ impl super::Screen for Connecting {
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .create(ui_container);
 
+        let status = ui::TextBuilder::new()
+            .text(self.last_status.clone())
+            .position(0.0, 48.0)
+            .colour((170, 170, 170, 255))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
         // Disclaimer
         let disclaimer = ui::TextBuilder::new()
             .text("Not affiliated with Mojang/Minecraft")
@@ -81,6 +98,7 @@ impl super::Screen for Connecting {
             logo,
             _disclaimer: disclaimer,
             _msg: msg,
+            status,
             _connect_msg: connect_msg,
         });
     }
@@ -103,6 +121,10 @@ impl super::Screen for Connecting {
     ) {
         let elements = self.elements.as_mut().unwrap();
         elements.logo.tick(renderer);
+        if let Some(status) = self.status.try_iter().last() {
+            elements.status.borrow_mut().text = status.clone();
+            self.last_status = status;
+        }
     }
 
     fn clone_screen(&self) -> Box<dyn Screen> {
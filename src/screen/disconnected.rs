@@ -0,0 +1,185 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::format::Component;
+use crate::render;
+use crate::screen::{Screen, ScreenSystem};
+use crate::ui;
+use leafish_protocol::translate::{translate, translate_with};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Shown after losing the connection to a server, offering to join it again
+pub struct Disconnected {
+    elements: Option<UIElements>,
+    reason: Component,
+    reconnect_at: Option<Instant>,
+}
+
+impl Clone for Disconnected {
+    fn clone(&self) -> Self {
+        Disconnected {
+            elements: None,
+            reason: self.reason.clone(),
+            reconnect_at: self.reconnect_at,
+        }
+    }
+}
+
+struct UIElements {
+    logo: ui::logo::Logo,
+    _title: ui::TextRef,
+    _reason: ui::FormattedRef,
+    countdown: ui::TextRef,
+    _reconnect: ui::ButtonRef,
+    _back: ui::ButtonRef,
+    _disclaimer: ui::TextRef,
+}
+
+impl Disconnected {
+    /// `reconnect_at` is when the game is going to reconnect on its own,
+    /// if automatic reconnecting is enabled.
+    pub fn new(reason: Component, reconnect_at: Option<Instant>) -> Disconnected {
+        Disconnected {
+            elements: None,
+            reason,
+            reconnect_at,
+        }
+    }
+
+    fn countdown_text(&self) -> String {
+        match self.reconnect_at {
+            Some(at) => {
+                let left = at.saturating_duration_since(Instant::now());
+                translate_with("leafish.reconnecting", &[&(left.as_secs() + 1).to_string()])
+            }
+            None => String::new(),
+        }
+    }
+}
+
+impl super::Screen for Disconnected {
+    fn on_active(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        renderer: Arc<render::Renderer>,
+        ui_container: &mut ui::Container,
+    ) {
+        let logo = ui::logo::Logo::new(renderer.resources.clone(), ui_container);
+
+        let title = ui::TextBuilder::new()
+            .text(translate("leafish.disconnected"))
+            .position(0.0, -40.0)
+            .colour((255, 0, 0, 255))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
+        let reason = ui::FormattedBuilder::new()
+            .text(self.reason.clone())
+            .position(0.0, -16.0)
+            .max_width(600.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
+        let countdown = ui::TextBuilder::new()
+            .text(self.countdown_text())
+            .position(0.0, 40.0)
+            .colour((255, 255, 85, 255))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
+        let reconnect = ui::ButtonBuilder::new()
+            .position(0.0, 80.0)
+            .size(400.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut reconnect = reconnect.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(translate("leafish.reconnect"))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *reconnect);
+            reconnect.add_text(txt);
+            reconnect.add_click_func(|_, game| {
+                game.reconnect();
+                true
+            });
+        }
+
+        let back = ui::ButtonBuilder::new()
+            .position(0.0, 130.0)
+            .size(400.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut back = back.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(translate("gui.toMenu"))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *back);
+            back.add_text(txt);
+            back.add_click_func(|_, game| {
+                game.cancel_reconnect();
+                game.screen_sys
+                    .clone()
+                    .replace_screen(Box::new(super::ServerList::new(None)));
+                true
+            });
+        }
+
+        // Disclaimer
+        let disclaimer = ui::TextBuilder::new()
+            .text("Not affiliated with Mojang/Minecraft")
+            .position(5.0, 5.0)
+            .colour((255, 200, 200, 255))
+            .alignment(ui::VAttach::Bottom, ui::HAttach::Right)
+            .create(ui_container);
+
+        self.elements = Some(UIElements {
+            logo,
+            _title: title,
+            _reason: reason,
+            countdown,
+            _reconnect: reconnect,
+            _back: back,
+            _disclaimer: disclaimer,
+        });
+    }
+    fn on_deactive(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        _renderer: Arc<render::Renderer>,
+        _ui_container: &mut ui::Container,
+    ) {
+        // Clean up
+        self.elements = None
+    }
+
+    fn tick(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        renderer: Arc<render::Renderer>,
+        _ui_container: &mut ui::Container,
+        _delta: f64,
+    ) {
+        let countdown = self.countdown_text();
+        let elements = self.elements.as_mut().unwrap();
+        elements.logo.tick(renderer);
+        elements.countdown.borrow_mut().text = countdown;
+    }
+
+    fn clone_screen(&self) -> Box<dyn Screen> {
+        Box::new(self.clone())
+    }
+}
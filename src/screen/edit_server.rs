@@ -12,19 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::{Cell, RefCell};
 use std::collections::BTreeMap;
 use std::fs;
+use std::rc::Rc;
 
+use crate::protocol::versions::PROTOCOL_NAMES;
 use crate::ui;
-use crate::{paths, render};
+use crate::{paths, protocol, render};
 
 use crate::screen::{Screen, ScreenSystem};
+use leafish_protocol::translate::translate;
 use serde_json::Value;
 use std::sync::Arc;
 
 pub struct EditServerEntry {
     elements: Option<UIElements>,
-    entry_info: Option<(usize, String, String)>,
+    entry_info: Option<(usize, String, String, Option<String>, Option<String>)>,
 }
 
 impl Clone for EditServerEntry {
@@ -41,19 +45,29 @@ struct UIElements {
 
     _name: ui::TextBoxRef,
     _address: ui::TextBoxRef,
+    _proxy: ui::TextBoxRef,
+    _version: ui::ButtonRef,
     _done: ui::ButtonRef,
     _cancel: ui::ButtonRef,
 }
 
 impl EditServerEntry {
-    pub fn new(entry_info: Option<(usize, String, String)>) -> EditServerEntry {
+    pub fn new(
+        entry_info: Option<(usize, String, String, Option<String>, Option<String>)>,
+    ) -> EditServerEntry {
         EditServerEntry {
             elements: None,
             entry_info,
         }
     }
 
-    fn save_servers(index: Option<usize>, name: &str, address: &str) {
+    fn save_servers(
+        index: Option<usize>,
+        name: &str,
+        address: &str,
+        proxy: &str,
+        version: Option<&str>,
+    ) {
         let mut servers_info = match fs::File::open(paths::get_data_dir().join("servers.json")) {
             Ok(val) => serde_json::from_reader(val).unwrap(),
             Err(_) => {
@@ -63,13 +77,6 @@ impl EditServerEntry {
             }
         };
 
-        let new_entry = {
-            let mut entry = BTreeMap::default();
-            entry.insert("name".to_owned(), Value::String(name.to_owned()));
-            entry.insert("address".to_owned(), Value::String(address.to_owned()));
-            Value::Object(entry.into_iter().collect())
-        };
-
         {
             let servers = servers_info
                 .as_object_mut()
@@ -78,16 +85,42 @@ impl EditServerEntry {
                 .unwrap()
                 .as_array_mut()
                 .unwrap();
-            if let Some(index) = index {
-                *servers.get_mut(index).unwrap() = new_entry;
+            // Update existing entries in place to keep fields we don't edit here
+            let entry = if let Some(index) = index {
+                servers.get_mut(index).unwrap()
             } else {
-                servers.push(new_entry);
+                servers.push(Value::Object(Default::default()));
+                servers.last_mut().unwrap()
+            };
+            let entry = entry.as_object_mut().unwrap();
+            entry.insert("name".to_owned(), Value::String(name.to_owned()));
+            entry.insert("address".to_owned(), Value::String(address.to_owned()));
+            if proxy.is_empty() {
+                entry.remove("proxy");
+            } else {
+                entry.insert("proxy".to_owned(), Value::String(proxy.to_owned()));
+            }
+            match version {
+                Some(version) => {
+                    entry.insert("version".to_owned(), Value::String(version.to_owned()));
+                }
+                None => {
+                    entry.remove("version");
+                }
             }
         }
 
         let mut out = fs::File::create(paths::get_data_dir().join("servers.json")).unwrap();
         serde_json::to_writer_pretty(&mut out, &servers_info).unwrap();
     }
+
+    fn version_label(index: Option<usize>, unknown: Option<&str>) -> String {
+        match (index, unknown) {
+            (Some(index), _) => format!("Version: {}", PROTOCOL_NAMES[index]),
+            (None, Some(unknown)) => format!("Version: {} (unsupported)", unknown),
+            (None, None) => "Version: Auto-detect".to_owned(),
+        }
+    }
 }
 
 impl super::Screen for EditServerEntry {
@@ -125,38 +158,117 @@ impl super::Screen for EditServerEntry {
             .position(0.0, -18.0)
             .attach(&mut *server_address.borrow_mut());
 
+        // Proxy
+        let server_proxy = ui::TextBoxBuilder::new()
+            .input(
+                self.entry_info
+                    .as_ref()
+                    .and_then(|v| v.3.as_deref())
+                    .unwrap_or(""),
+            )
+            .position(0.0, 100.0)
+            .size(400.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        ui::TextBox::make_focusable(&server_proxy, ui_container);
+        ui::TextBuilder::new()
+            .text("Proxy (optional, \"direct\" to bypass the default):")
+            .position(0.0, -18.0)
+            .attach(&mut *server_proxy.borrow_mut());
+
+        // Protocol version, cycling through the supported versions with
+        // autodetection in front
+        let pinned_version = self.entry_info.as_ref().and_then(|v| v.4.clone());
+        let version_index = Rc::new(Cell::new(
+            pinned_version
+                .as_deref()
+                .and_then(protocol::versions::try_protocol_name_to_protocol_version)
+                .and_then(|version| {
+                    protocol::SUPPORTED_PROTOCOLS
+                        .iter()
+                        .position(|supported| *supported == version)
+                }),
+        ));
+        // A version pinned by hand in servers.json which we can't speak, kept
+        // until another one is picked so saving doesn't drop it silently
+        let unknown_version = Rc::new(RefCell::new(
+            pinned_version.filter(|_| version_index.get().is_none()),
+        ));
+        let server_version = ui::ButtonBuilder::new()
+            .position(0.0, 160.0)
+            .size(400.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut server_version = server_version.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(Self::version_label(
+                    version_index.get(),
+                    unknown_version.borrow().as_deref(),
+                ))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *server_version);
+            let txt_version = txt.clone();
+            server_version.add_text(txt);
+            let version_index = version_index.clone();
+            let unknown_version = unknown_version.clone();
+            server_version.add_click_func(move |_, _| {
+                let next = match version_index.get() {
+                    None => Some(0),
+                    Some(index) if index + 1 < PROTOCOL_NAMES.len() => Some(index + 1),
+                    Some(_) => None,
+                };
+                version_index.set(next);
+                unknown_version.borrow_mut().take();
+                txt_version.borrow_mut().text = Self::version_label(next, None);
+                true
+            });
+        }
+
         let save_server_error = ui::TextBuilder::new()
             .text("")
-            .position(0.0, 150.0)
+            .position(0.0, 270.0)
             .colour((255, 50, 50, 255))
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .create(ui_container);
 
         // Done
         let done = ui::ButtonBuilder::new()
-            .position(110.0, 100.0)
+            .position(110.0, 220.0)
             .size(200.0, 40.0)
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .create(ui_container);
         {
             let mut done = done.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done);
             done.add_text(txt);
             let index = self.entry_info.as_ref().map(|v| v.0);
             let server_name = server_name.clone();
             let server_address = server_address.clone();
+            let server_proxy = server_proxy.clone();
             done.add_click_func(move |_, game| {
                 if server_address.borrow().input.is_empty() {
                     save_server_error.borrow_mut().text = "Please enter a Server Address".into();
                     return false;
                 }
+                if let Err(err) = protocol::proxy::Proxy::parse(&server_proxy.borrow().input) {
+                    save_server_error.borrow_mut().text = err.to_string();
+                    return false;
+                }
+                if let Some(unknown) = unknown_version.borrow().as_ref() {
+                    save_server_error.borrow_mut().text =
+                        format!("Version {} isn't supported, please pick another", unknown);
+                    return false;
+                }
                 Self::save_servers(
                     index,
                     &server_name.borrow().input,
                     &server_address.borrow().input,
+                    &server_proxy.borrow().input,
+                    version_index.get().map(|index| PROTOCOL_NAMES[index]),
                 );
                 game.screen_sys
                     .clone()
@@ -167,14 +279,14 @@ impl super::Screen for EditServerEntry {
 
         // Cancel
         let cancel = ui::ButtonBuilder::new()
-            .position(-110.0, 100.0)
+            .position(-110.0, 220.0)
             .size(200.0, 40.0)
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .create(ui_container);
         {
             let mut cancel = cancel.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Cancel")
+                .text(translate("gui.cancel"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *cancel);
             cancel.add_text(txt);
@@ -190,6 +302,8 @@ impl super::Screen for EditServerEntry {
             logo,
             _name: server_name,
             _address: server_address,
+            _proxy: server_proxy,
+            _version: server_version,
             _done: done,
             _cancel: cancel,
         });
@@ -19,6 +19,7 @@ pub use self::login::*;
 
 pub mod confirm_box;
 pub mod connecting;
+pub mod disconnected;
 pub mod edit_server;
 
 pub mod background;
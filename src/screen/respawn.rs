@@ -5,6 +5,7 @@ use crate::ui;
 use crate::ui::{Container, ImageRef};
 use leafish_protocol::protocol::packet::play::serverbound::ClientStatus;
 use leafish_protocol::protocol::VarInt;
+use leafish_protocol::translate::translate;
 use shared::Version;
 use std::sync::Arc;
 
@@ -58,7 +59,7 @@ impl super::Screen for Respawn {
             .colour((104, 0, 0, 100))
             .create(ui_container);
         let text = ui::TextBuilder::new()
-            .text("You died!")
+            .text(translate("deathScreen.title"))
             .position(0.0, -(icon_scale * 10.0 * 3.0))
             .colour((255, 255, 255, 255))
             .scale_y(icon_scale)
@@ -81,7 +82,7 @@ impl super::Screen for Respawn {
         {
             let mut respawn_button = respawn_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Respawn")
+                .text(translate("deathScreen.respawn"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *respawn_button);
             respawn_button.add_text(txt);
@@ -111,7 +112,7 @@ impl super::Screen for Respawn {
         {
             let mut main_menu_button = main_menu_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Title screen")
+                .text(translate("deathScreen.titleScreen"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *main_menu_button);
             main_menu_button.add_text(txt);
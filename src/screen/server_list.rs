@@ -25,20 +25,24 @@ use crate::protocol;
 use crate::render;
 use crate::ui;
 
-use crate::render::hud::{Hud, HudContext};
 use crate::render::Renderer;
 use crate::screen::{Screen, ScreenSystem};
 use crate::ui::Container;
+use crate::ServerTarget;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use crossbeam_channel::unbounded;
 use crossbeam_channel::{Receiver, TryRecvError};
-use instant::Duration;
-use parking_lot::RwLock;
+use instant::{Duration, Instant};
+use leafish_protocol::translate::{translate, translate_with};
+use log::warn;
 use rand::Rng;
 use serde_json::Value;
 use std::collections::BTreeMap;
 
+/// How often the worlds found on the local network are pinged again
+const LAN_PING_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct ServerList {
     elements: Option<UIElements>,
     disconnect_reason: Option<Component>,
@@ -66,6 +70,17 @@ struct UIElements {
     _disclaimer: ui::TextRef,
 
     _disconnected: Option<ui::ImageRef>,
+
+    lan: Option<protocol::lan::LanDiscovery>,
+    lan_header: LanHeader,
+}
+
+/// Heading of the worlds found on the local network, listed below the
+/// saved servers
+struct LanHeader {
+    back: ui::ImageRef,
+    offset: f64,
+    y: f64,
 }
 
 struct Server {
@@ -73,6 +88,12 @@ struct Server {
     offset: f64,
     y: f64,
 
+    address: String,
+    proxy: Option<String>,
+    protocol_version: Option<i32>,
+    /// Found on the local network rather than saved
+    lan: bool,
+
     motd: ui::FormattedRef,
     ping: ui::ImageRef,
     players: ui::TextRef,
@@ -82,6 +103,7 @@ struct Server {
     icon_texture: Option<String>,
 
     done_ping: bool,
+    pinged_at: Instant,
     recv: Receiver<PingInfo>,
 }
 
@@ -97,13 +119,34 @@ struct PingInfo {
     favicon: Option<image::DynamicImage>,
 }
 
+fn row_y(offset: f64) -> f64 {
+    if offset < 0.0 {
+        offset * 200.0
+    } else {
+        offset * 100.0
+    }
+}
+
+fn animate_row(back: &ui::ImageRef, y: f64, delta: f64) {
+    let mut back = back.borrow_mut();
+    let dy = y - back.y;
+    if dy * dy > 1.0 {
+        let y = back.y;
+        back.y = y + delta * dy * 0.1;
+    } else {
+        back.y = y;
+    }
+}
+
 impl Server {
     fn update_position(&mut self) {
-        if self.offset < 0.0 {
-            self.y = self.offset * 200.0;
-        } else {
-            self.y = self.offset * 100.0;
-        }
+        self.y = row_y(self.offset);
+    }
+}
+
+impl LanHeader {
+    fn update_position(&mut self) {
+        self.y = row_y(self.offset);
     }
 }
 
@@ -134,102 +177,128 @@ impl ServerList {
         }
         elements.servers.clear();
 
-        let file = match fs::File::open(paths::get_data_dir().join("servers.json")) {
-            Ok(val) => val,
-            Err(_) => return,
-        };
-        let servers_info: serde_json::Value = serde_json::from_reader(file).unwrap();
-        let servers = servers_info.get("servers").unwrap().as_array().unwrap();
         let mut offset = 0.0;
-
-        for (index, svr) in servers.iter().enumerate() {
-            let name = svr.get("name").unwrap().as_str().unwrap().to_owned();
-            let address = svr.get("address").unwrap().as_str().unwrap().to_owned();
-
-            // Everything is attached to this
-            let back = ui::ImageBuilder::new()
-                .texture("leafish:solid")
-                .position(0.0, offset * 100.0)
-                .size(700.0, 100.0)
-                .colour((0, 0, 0, 100))
-                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
-                .create(ui_container);
-
-            let (send, recv) = unbounded();
-            // Make whole entry interactable
-            {
-                let mut backr = back.borrow_mut();
-                let address = address.clone();
-                backr.add_hover_func(move |this, over, _| {
-                    this.colour.3 = if over { 200 } else { 100 };
-                    false
-                });
-                backr.add_click_func(move |_, game| {
-                    game.screen_sys
-                        .clone()
-                        .replace_screen(Box::new(super::connecting::Connecting::new(&address)));
-                    let hud_context = Arc::new(RwLock::new(HudContext::new()));
-                    let result = game.connect_to(&address, hud_context.clone());
-                    game.screen_sys.clone().pop_screen();
-                    if let Err(error) = result {
-                        game.screen_sys
-                            .clone()
-                            .add_screen(Box::new(ServerList::new(Some(Component::new(
-                                ComponentType::new(&error.to_string(), None),
-                            )))));
-                    } else {
-                        game.screen_sys
-                            .clone()
-                            .add_screen(Box::new(Hud::new(hud_context)));
-                    }
-                    true
-                });
+        if let Ok(file) = fs::File::open(paths::get_data_dir().join("servers.json")) {
+            let servers_info: serde_json::Value = serde_json::from_reader(file).unwrap();
+            let servers = servers_info.get("servers").unwrap().as_array().unwrap();
+
+            for (index, svr) in servers.iter().enumerate() {
+                let name = svr.get("name").unwrap().as_str().unwrap().to_owned();
+                let address = svr.get("address").unwrap().as_str().unwrap().to_owned();
+                let proxy = svr.get("proxy").and_then(Value::as_str).map(str::to_owned);
+                let version = svr
+                    .get("version")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned);
+                let protocol_version = version
+                    .as_deref()
+                    .and_then(protocol::versions::try_protocol_name_to_protocol_version);
+
+                let server = Self::create_entry(
+                    ui_container,
+                    name,
+                    address,
+                    proxy,
+                    protocol_version,
+                    offset,
+                    Some((index, version)),
+                );
+                elements.servers.push(server);
+                offset += 1.0;
             }
+        }
 
-            // Server name
-            ui::TextBuilder::new()
-                .text(name.clone())
-                .position(100.0, 5.0)
-                .attach(&mut *back.borrow_mut());
-
-            // Server icon
-            let icon = ui::ImageBuilder::new()
-                .texture("misc/unknown_server")
-                .position(5.0, 5.0)
-                .size(90.0, 90.0)
-                .attach(&mut *back.borrow_mut());
+        // LAN worlds are added back as they are announced
+        elements.lan_header.offset = offset;
+        elements.lan_header.update_position();
+    }
 
-            // Ping indicator
-            let ping = ui::ImageBuilder::new()
-                .texture("gui/icons")
-                .position(5.0, 5.0)
-                .size(20.0, 16.0)
-                .texture_coords((0.0, 56.0, 10.0, 8.0))
-                .alignment(ui::VAttach::Top, ui::HAttach::Right)
-                .attach(&mut *back.borrow_mut());
+    /// Creates the entry of a server and starts pinging it. `saved` holds
+    /// the index and version of entries from servers.json, which can be
+    /// edited and deleted.
+    fn create_entry(
+        ui_container: &mut ui::Container,
+        name: String,
+        address: String,
+        proxy: Option<String>,
+        protocol_version: Option<i32>,
+        offset: f64,
+        saved: Option<(usize, Option<String>)>,
+    ) -> Server {
+        // Everything is attached to this
+        let back = ui::ImageBuilder::new()
+            .texture("leafish:solid")
+            .position(0.0, offset * 100.0)
+            .size(700.0, 100.0)
+            .colour((0, 0, 0, 100))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
 
-            // Player count
-            let players = ui::TextBuilder::new()
-                .text("???")
-                .position(30.0, 5.0)
-                .alignment(ui::VAttach::Top, ui::HAttach::Right)
-                .attach(&mut *back.borrow_mut());
+        // Make whole entry interactable
+        {
+            let mut backr = back.borrow_mut();
+            let address = address.clone();
+            let proxy = proxy.clone();
+            backr.add_hover_func(move |this, over, _| {
+                this.colour.3 = if over { 200 } else { 100 };
+                false
+            });
+            backr.add_click_func(move |_, game| {
+                game.join_server(ServerTarget {
+                    address: address.clone(),
+                    proxy: proxy.clone(),
+                    protocol_version,
+                });
+                true
+            });
+        }
 
-            // Server's message of the day
-            let motd = ui::FormattedBuilder::new()
-                .text(Component::new(ComponentType::new("Connecting...", None)))
-                .position(100.0, 23.0)
-                .max_width(700.0 - (90.0 + 10.0 + 5.0))
-                .attach(&mut *back.borrow_mut());
+        // Server name
+        ui::TextBuilder::new()
+            .text(name.clone())
+            .position(100.0, 5.0)
+            .attach(&mut *back.borrow_mut());
 
-            // Version information
-            let version = ui::FormattedBuilder::new()
-                .text(Component::new(ComponentType::new("", None)))
-                .position(100.0, 5.0)
-                .max_width(700.0 - (90.0 + 10.0 + 5.0))
-                .alignment(ui::VAttach::Bottom, ui::HAttach::Left)
-                .attach(&mut *back.borrow_mut());
+        // Server icon
+        let icon = ui::ImageBuilder::new()
+            .texture("misc/unknown_server")
+            .position(5.0, 5.0)
+            .size(90.0, 90.0)
+            .attach(&mut *back.borrow_mut());
 
+        // Ping indicator
+        let ping = ui::ImageBuilder::new()
+            .texture("gui/icons")
+            .position(5.0, 5.0)
+            .size(20.0, 16.0)
+            .texture_coords((0.0, 56.0, 10.0, 8.0))
+            .alignment(ui::VAttach::Top, ui::HAttach::Right)
+            .attach(&mut *back.borrow_mut());
+
+        // Player count
+        let players = ui::TextBuilder::new()
+            .text("???")
+            .position(30.0, 5.0)
+            .alignment(ui::VAttach::Top, ui::HAttach::Right)
+            .attach(&mut *back.borrow_mut());
+
+        // Server's message of the day
+        let motd = ui::FormattedBuilder::new()
+            .text(Component::new(ComponentType::new("Connecting...", None)))
+            .position(100.0, 23.0)
+            .max_width(700.0 - (90.0 + 10.0 + 5.0))
+            .attach(&mut *back.borrow_mut());
+
+        // Version information
+        let version = ui::FormattedBuilder::new()
+            .text(Component::new(ComponentType::new("", None)))
+            .position(100.0, 5.0)
+            .max_width(700.0 - (90.0 + 10.0 + 5.0))
+            .alignment(ui::VAttach::Bottom, ui::HAttach::Left)
+            .attach(&mut *back.borrow_mut());
+
+        let lan = saved.is_none();
+        if let Some((index, sversion)) = saved {
             // Delete entry button
             let delete_entry = ui::ButtonBuilder::new()
                 .position(0.0, 0.0)
@@ -276,85 +345,161 @@ impl ServerList {
                     .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                     .attach(&mut *btn);
                 btn.add_text(txt);
-                let sname = name.clone();
+                let sname = name;
                 let saddr = address.clone();
+                let sproxy = proxy.clone();
                 btn.add_click_func(move |_, game| {
                     game.screen_sys.clone().replace_screen(Box::new(
                         super::edit_server::EditServerEntry::new(Some((
                             index,
                             sname.clone(),
                             saddr.clone(),
+                            sproxy.clone(),
+                            sversion.clone(),
                         ))),
                     ));
                     true
                 })
             }
+        }
 
-            let mut server = Server {
-                back,
-                offset,
-                y: 0.0,
-                done_ping: false,
-                recv,
-
-                motd,
-                ping,
-                players,
-                version,
-
-                icon,
-                icon_texture: None,
-            };
-            server.update_position();
-            elements.servers.push(server);
-            offset += 1.0;
-
-            // Don't block the main thread whilst pinging the server
-            thread::spawn(move || {
-                match protocol::Conn::new(&address, protocol::SUPPORTED_PROTOCOLS[0])
-                    .and_then(|conn| conn.do_status())
-                {
-                    Ok(res) => {
-                        let desc = res.0.description;
-                        let favicon = if let Some(icon) = res.0.favicon {
-                            let data_base64 = &icon["data:image/png;base64,".len()..];
-                            let data_base64: String =
-                                data_base64.chars().filter(|c| !c.is_whitespace()).collect();
-                            let data = STANDARD.decode(data_base64).unwrap();
-                            Some(image::load_from_memory(&data).unwrap())
-                        } else {
-                            None
-                        };
-                        drop(send.send(PingInfo {
-                            motd: desc,
-                            ping: res.1,
-                            exists: true,
-                            online: res.0.players.online,
-                            max: res.0.players.max,
-                            protocol_version: res.0.version.protocol,
-                            protocol_name: res.0.version.name,
-                            forge_mods: res.0.forge_mods,
-                            favicon,
-                        }));
-                    }
-                    Err(err) => {
-                        let e = format!("{}", err);
-                        let msg = ComponentType::new(&e, Some(format::Color::Red));
-                        let _ = send.send(PingInfo {
-                            motd: Component::new(msg),
-                            ping: Duration::new(99999, 0),
-                            exists: false,
-                            online: 0,
-                            max: 0,
-                            protocol_version: 0,
-                            protocol_name: "".to_owned(),
-                            forge_mods: vec![],
-                            favicon: None,
-                        });
+        let recv = Self::ping(address.clone(), proxy.clone(), protocol_version);
+        let mut server = Server {
+            back,
+            offset,
+            y: 0.0,
+
+            address,
+            proxy,
+            protocol_version,
+            lan,
+
+            done_ping: false,
+            pinged_at: Instant::now(),
+            recv,
+
+            motd,
+            ping,
+            players,
+            version,
+
+            icon,
+            icon_texture: None,
+        };
+        server.update_position();
+        server
+    }
+
+    /// Pings a server without blocking the main thread
+    fn ping(
+        address: String,
+        proxy: Option<String>,
+        protocol_version: Option<i32>,
+    ) -> Receiver<PingInfo> {
+        let (send, recv) = unbounded();
+        thread::spawn(move || {
+            match protocol::proxy::resolve(proxy.as_deref()).and_then(|proxy| {
+                protocol::Conn::ping(
+                    &address,
+                    protocol_version.unwrap_or(protocol::SUPPORTED_PROTOCOLS[0]),
+                    proxy.as_ref(),
+                )
+            }) {
+                Ok(res) => {
+                    let desc = res.0.description;
+                    let favicon = if let Some(icon) = res.0.favicon {
+                        let data_base64 = &icon["data:image/png;base64,".len()..];
+                        let data_base64: String =
+                            data_base64.chars().filter(|c| !c.is_whitespace()).collect();
+                        let data = STANDARD.decode(data_base64).unwrap();
+                        Some(image::load_from_memory(&data).unwrap())
+                    } else {
+                        None
+                    };
+                    drop(send.send(PingInfo {
+                        motd: desc,
+                        ping: res.1,
+                        exists: true,
+                        online: res.0.players.online,
+                        max: res.0.players.max,
+                        protocol_version: res.0.version.protocol,
+                        protocol_name: res.0.version.name,
+                        forge_mods: res.0.forge_mods,
+                        favicon,
+                    }));
+                }
+                Err(err) => {
+                    let e = format!("{}", err);
+                    let msg = ComponentType::new(&e, Some(format::Color::Red));
+                    let _ = send.send(PingInfo {
+                        motd: Component::new(msg),
+                        ping: Duration::new(99999, 0),
+                        exists: false,
+                        online: 0,
+                        max: 0,
+                        protocol_version: 0,
+                        protocol_name: "".to_owned(),
+                        forge_mods: vec![],
+                        favicon: None,
+                    });
+                }
+            }
+        });
+        recv
+    }
+
+    /// Adds the LAN worlds which were just announced and removes the ones
+    /// which stopped announcing themselves.
+    fn update_lan_servers(&mut self, renderer: &Renderer, ui_container: &mut ui::Container) {
+        let elements = self.elements.as_mut().unwrap();
+        let found = match elements.lan.as_ref() {
+            Some(lan) => lan.servers(),
+            None => return,
+        };
+
+        let count = elements.servers.len();
+        {
+            let mut tex = renderer.get_textures_ref().write();
+            elements.servers.retain(|server| {
+                let keep = !server.lan || found.iter().any(|lan| lan.address == server.address);
+                if !keep {
+                    if let Some(ref icon) = server.icon_texture {
+                        tex.remove_dynamic(icon);
                     }
                 }
+                keep
             });
         }
+        let mut changed = elements.servers.len() != count;
+        for world in found {
+            let known = elements
+                .servers
+                .iter()
+                .any(|server| server.lan && server.address == world.address);
+            if !known {
+                let server = Self::create_entry(
+                    ui_container,
+                    world.motd,
+                    world.address,
+                    // Always connect directly within the local network
+                    Some("direct".to_owned()),
+                    None,
+                    0.0,
+                    None,
+                );
+                elements.servers.push(server);
+                changed = true;
+            }
+        }
+
+        if changed {
+            let mut offset = elements.lan_header.offset;
+            for server in elements.servers.iter_mut().filter(|server| server.lan) {
+                offset += 1.0;
+                server.offset = offset;
+                server.update_position();
+            }
+        }
     }
 
     fn delete_server(index: usize) {
@@ -395,7 +540,7 @@ impl ServerList {
         {
             let mut refresh = refresh.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Refresh")
+                .text(translate("selectServer.refresh"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *refresh);
             refresh.add_text(txt);
@@ -501,6 +646,35 @@ impl ServerList {
             None
         };
 
+        // Worlds opened to LAN, only as long as this screen is shown
+        let (lan, lan_status) = match protocol::lan::LanDiscovery::start() {
+            Ok(lan) => (Some(lan), translate("lanServer.scanning")),
+            Err(err) => {
+                warn!("Failed to start LAN discovery: {}", err);
+                (
+                    None,
+                    translate_with("leafish.lanUnavailable", &[&err.to_string()]),
+                )
+            }
+        };
+        let lan_back = ui::ImageBuilder::new()
+            .texture("leafish:solid")
+            .size(700.0, 100.0)
+            .colour((0, 0, 0, 50))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        ui::TextBuilder::new()
+            .text(translate("leafish.lanWorlds"))
+            .position(0.0, 30.0)
+            .alignment(ui::VAttach::Top, ui::HAttach::Center)
+            .attach(&mut *lan_back.borrow_mut());
+        ui::TextBuilder::new()
+            .text(lan_status)
+            .position(0.0, 55.0)
+            .colour((170, 170, 170, 255))
+            .alignment(ui::VAttach::Top, ui::HAttach::Center)
+            .attach(&mut *lan_back.borrow_mut());
+
         self.elements = Some(UIElements {
             logo,
             servers: vec![],
@@ -511,6 +685,13 @@ impl ServerList {
             _disclaimer: disclaimer,
 
             _disconnected: disconnected,
+
+            lan,
+            lan_header: LanHeader {
+                back: lan_back,
+                offset: 0.0,
+                y: 0.0,
+            },
         });
     }
 }
@@ -555,22 +736,15 @@ impl super::Screen for ServerList {
         if *self.needs_reload.borrow() {
             self.reload_server_list(renderer.clone(), ui_container);
         }
+        self.update_lan_servers(&renderer, ui_container);
         let elements = self.elements.as_mut().unwrap();
 
         elements.logo.tick(renderer.clone());
+        animate_row(&elements.lan_header.back, elements.lan_header.y, delta);
 
         for s in &mut elements.servers {
             // Animate the entries
-            {
-                let mut back = s.back.borrow_mut();
-                let dy = s.y - back.y;
-                if dy * dy > 1.0 {
-                    let y = back.y;
-                    back.y = y + delta * dy * 0.1;
-                } else {
-                    back.y = s.y;
-                }
-            }
+            animate_row(&s.back, s.y, delta);
             #[allow(clippy::if_same_then_else)]
             if s.y < elements._add_btn.borrow().y {
                 // TODO: Make button invisible!
@@ -578,6 +752,13 @@ impl super::Screen for ServerList {
                 // TODO: Make button visible.
             }
 
+            // LAN worlds come and go, so keep their status up to date
+            if s.lan && s.done_ping && s.pinged_at.elapsed() >= LAN_PING_INTERVAL {
+                s.done_ping = false;
+                s.pinged_at = Instant::now();
+                s.recv = Self::ping(s.address.clone(), s.proxy.clone(), s.protocol_version);
+            }
+
             // Keep checking to see if the server has finished being
             // pinged
             if !s.done_ping {
@@ -634,6 +815,9 @@ impl super::Screen for ServerList {
                                 .take(30)
                                 .collect();
                             let tex = renderer.get_textures_ref();
+                            if let Some(ref icon) = s.icon_texture {
+                                tex.write().remove_dynamic(icon);
+                            }
                             s.icon_texture = Some(name.clone());
                             let icon_tex = tex.write().put_dynamic(&name, favicon);
                             s.icon.borrow_mut().texture = icon_tex.name;
@@ -656,25 +840,31 @@ impl super::Screen for ServerList {
 
     fn on_scroll(&mut self, _: f64, y: f64) {
         let elements = self.elements.as_mut().unwrap();
-        if elements.servers.is_empty() {
-            return;
-        }
+        // The LAN heading comes after the saved servers, before the LAN worlds
+        let first = elements
+            .servers
+            .iter()
+            .find(|s| !s.lan)
+            .map_or(elements.lan_header.offset, |s| s.offset);
+        let last = elements
+            .servers
+            .iter()
+            .map(|s| s.offset)
+            .fold(elements.lan_header.offset, f64::max);
         let mut diff = y / 1.0;
-        {
-            let last = elements.servers.last().unwrap();
-            if last.offset + diff <= 2.0 {
-                diff = 2.0 - last.offset;
-            }
-            let first = elements.servers.first().unwrap();
-            if first.offset + diff >= 0.0 {
-                diff = -first.offset;
-            }
+        if last + diff <= 2.0 {
+            diff = 2.0 - last;
+        }
+        if first + diff >= 0.0 {
+            diff = -first;
         }
 
         for s in &mut elements.servers {
             s.offset += diff;
             s.update_position();
         }
+        elements.lan_header.offset += diff;
+        elements.lan_header.update_position();
     }
 
     fn on_resize(
@@ -1,11 +1,15 @@
+use crate::lang;
 use crate::render;
 use crate::settings::SettingStore;
+use crate::sound;
 use crate::ui;
 
 use crate::screen::{Screen, ScreenSystem};
 use crate::BoolSetting;
 use crate::FloatSetting;
 use crate::IntSetting;
+use crate::StringSetting;
+use leafish_protocol::translate::{translate, translate_with};
 use std::sync::Arc;
 
 pub struct UIElements {
@@ -65,7 +69,7 @@ impl super::Screen for SettingsMenu {
         {
             let mut audio_settings = audio_settings.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Audio settings...")
+                .text(translate("options.sounds"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *audio_settings);
             audio_settings.add_text(txt);
@@ -85,7 +89,7 @@ impl super::Screen for SettingsMenu {
         {
             let mut video_settings = video_settings.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Video settings...")
+                .text(translate("options.video"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *video_settings);
             video_settings.add_text(txt);
@@ -105,7 +109,7 @@ impl super::Screen for SettingsMenu {
         {
             let mut controls_settings = controls_settings.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Controls...")
+                .text(translate("options.controls"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *controls_settings);
             controls_settings.add_text(txt);
@@ -125,10 +129,15 @@ impl super::Screen for SettingsMenu {
         {
             let mut lang_settings = lang_settings.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Language...")
+                .text(translate("options.language"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *lang_settings);
             lang_settings.add_text(txt);
+            lang_settings.add_click_func(|_, game| {
+                game.screen_sys
+                    .add_screen(Box::new(LanguageMenu::new(game.settings.clone())));
+                true
+            });
         }
         buttons.push(lang_settings);
 
@@ -140,7 +149,7 @@ impl super::Screen for SettingsMenu {
         {
             let mut skin_settings = skin_settings.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Skin Customization...")
+                .text(translate("options.skinCustomisation"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *skin_settings);
             skin_settings.add_text(txt);
@@ -162,7 +171,7 @@ impl super::Screen for SettingsMenu {
         {
             let mut done_button = done_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done_button);
             done_button.add_text(txt);
@@ -182,7 +191,7 @@ impl super::Screen for SettingsMenu {
             {
                 let mut disconnect_button = disconnect_button.borrow_mut();
                 let txt = ui::TextBuilder::new()
-                    .text("Disconnect")
+                    .text(translate("menu.disconnect"))
                     .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                     .attach(&mut *disconnect_button);
                 disconnect_button.add_text(txt);
@@ -372,7 +381,7 @@ impl super::Screen for VideoSettingsMenu {
         {
             let mut done_button = done_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done_button);
             done_button.add_text(txt);
@@ -433,28 +442,35 @@ impl super::Screen for VideoSettingsMenu {
 }
 
 pub struct AudioSettingsMenu {
-    _settings: Arc<SettingStore>,
+    settings: Arc<SettingStore>,
     elements: Option<UIElements>,
 }
 
 impl Clone for AudioSettingsMenu {
     fn clone(&self) -> Self {
         AudioSettingsMenu {
-            _settings: self._settings.clone(),
+            settings: self.settings.clone(),
             elements: None,
         }
     }
 }
 
 impl AudioSettingsMenu {
-    pub fn new(_settings: Arc<SettingStore>) -> AudioSettingsMenu {
+    pub fn new(settings: Arc<SettingStore>) -> AudioSettingsMenu {
         AudioSettingsMenu {
-            _settings,
+            settings,
             elements: None,
         }
     }
 }
 
+fn volume_label(category: sound::Category, volume: i32) -> String {
+    translate_with(
+        "options.percent_value",
+        &[&translate(category.translation_key()), &volume.to_string()],
+    )
+}
+
 impl super::Screen for AudioSettingsMenu {
     fn on_active(
         &mut self,
@@ -470,8 +486,50 @@ impl super::Screen for AudioSettingsMenu {
             .create(ui_container);
 
         let mut buttons = vec![];
+        let mut sliders = vec![];
 
-        // TODO
+        // Master volume spans both columns, the categories are below it
+        for (i, category) in sound::Category::ALL.iter().copied().enumerate() {
+            let (x, y) = match i {
+                0 => (0.0, -150.0),
+                _ => (
+                    if i % 2 == 1 { -160.0 } else { 160.0 },
+                    -100.0 + ((i - 1) / 2) as f64 * 50.0,
+                ),
+            };
+            let volume = self.settings.get_int(category.volume_setting());
+            let slider = ui::SliderBuilder::new()
+                .position(x, y)
+                .size(300.0, 40.0)
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .create(ui_container);
+            {
+                let mut slider = slider.borrow_mut();
+                let txt = ui::TextBuilder::new()
+                    .text(volume_label(category, volume))
+                    .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                    .attach(&mut *slider);
+                slider.add_text(txt);
+                slider.button.as_mut().unwrap().borrow_mut().x = volume as f64 * 3.0 - 150.0;
+                slider.add_click_func(move |this, game| {
+                    let screen_width =
+                        game.screen_sys.screens.read().last().unwrap().last_width as f64;
+                    let slider_btn = this.button.as_mut().expect("Slider had no button");
+                    slider_btn.borrow_mut().x =
+                        (game.get_last_mouse_x() - screen_width / 2.0 - this.x)
+                            .clamp(-150.0, 150.0);
+                    let volume = ((slider_btn.borrow().x + 150.0) / 3.0).round() as i32;
+                    game.settings.set_int(category.volume_setting(), volume);
+                    this.text
+                        .as_mut()
+                        .expect("Slider had no text")
+                        .borrow_mut()
+                        .text = volume_label(category, volume);
+                    true
+                });
+            }
+            sliders.push(slider);
+        }
 
         let done_button = ui::ButtonBuilder::new()
             .position(0.0, 50.0)
@@ -481,7 +539,7 @@ impl super::Screen for AudioSettingsMenu {
         {
             let mut done_button = done_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done_button);
             done_button.add_text(txt);
@@ -495,7 +553,7 @@ impl super::Screen for AudioSettingsMenu {
         self.elements = Some(UIElements {
             background,
             _buttons: buttons,
-            _sliders: vec![],
+            _sliders: sliders,
         });
     }
     fn on_deactive(
@@ -682,7 +740,7 @@ impl super::Screen for SkinSettingsMenu {
         {
             let mut done_button = done_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done_button);
             done_button.add_text(txt);
@@ -791,7 +849,7 @@ impl super::Screen for ControlsMenu {
         {
             let mut done_button = done_button.borrow_mut();
             let txt = ui::TextBuilder::new()
-                .text("Done")
+                .text(translate("gui.done"))
                 .alignment(ui::VAttach::Middle, ui::HAttach::Center)
                 .attach(&mut *done_button);
             done_button.add_text(txt);
@@ -890,3 +948,212 @@ impl super::Screen for ControlsMenu {
         Box::new(self.clone())
     }
 }
+
+/// How many languages are listed at once, scrolling moves through the rest
+const LANGUAGE_ROWS: usize = 7;
+
+struct LanguageElements {
+    background: ui::ImageRef,
+    _title: ui::TextRef,
+    _warning: ui::TextRef,
+    _done: ui::ButtonRef,
+    rows: Vec<ui::ButtonRef>,
+}
+
+pub struct LanguageMenu {
+    settings: Arc<SettingStore>,
+    languages: Vec<lang::Language>,
+    first_row: usize,
+    rows_dirty: bool,
+    elements: Option<LanguageElements>,
+}
+
+impl Clone for LanguageMenu {
+    fn clone(&self) -> Self {
+        Self {
+            settings: self.settings.clone(),
+            languages: self.languages.clone(),
+            first_row: self.first_row,
+            rows_dirty: true,
+            elements: None,
+        }
+    }
+}
+
+impl LanguageMenu {
+    pub fn new(settings: Arc<SettingStore>) -> Self {
+        Self {
+            settings,
+            languages: vec![],
+            first_row: 0,
+            rows_dirty: true,
+            elements: None,
+        }
+    }
+
+    fn last_first_row(&self) -> usize {
+        self.languages.len().saturating_sub(LANGUAGE_ROWS)
+    }
+
+    fn create_rows(&self, ui_container: &mut ui::Container) -> Vec<ui::ButtonRef> {
+        let current = self.settings.get_string(StringSetting::Language);
+        self.languages
+            .iter()
+            .skip(self.first_row)
+            .take(LANGUAGE_ROWS)
+            .enumerate()
+            .map(|(i, language)| {
+                let row = ui::ButtonBuilder::new()
+                    .position(0.0, -170.0 + i as f64 * 42.0)
+                    .size(400.0, 38.0)
+                    .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                    .create(ui_container);
+                {
+                    let mut row = row.borrow_mut();
+                    let txt = ui::TextBuilder::new()
+                        .text(format!("{} ({})", language.name, language.region))
+                        .colour(if language.code == current {
+                            (255, 255, 85, 255)
+                        } else {
+                            (255, 255, 255, 255)
+                        })
+                        .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                        .attach(&mut *row);
+                    row.add_text(txt);
+                    let code = language.code.clone();
+                    row.add_click_func(move |_, game| {
+                        game.set_language(&code);
+                        game.screen_sys.pop_screen();
+                        true
+                    });
+                }
+                row
+            })
+            .collect()
+    }
+}
+
+impl super::Screen for LanguageMenu {
+    fn on_active(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        renderer: Arc<render::Renderer>,
+        ui_container: &mut ui::Container,
+    ) {
+        self.languages = lang::available(&renderer.resources.read());
+        // Start with the selected language in view
+        let current = self.settings.get_string(StringSetting::Language);
+        if let Some(index) = self.languages.iter().position(|l| l.code == current) {
+            self.first_row = index
+                .saturating_sub(LANGUAGE_ROWS / 2)
+                .min(self.last_first_row());
+        }
+
+        let background = ui::ImageBuilder::new()
+            .texture("leafish:solid")
+            .position(0.0, 0.0)
+            .size(854.0, 480.0)
+            .colour((0, 0, 0, 100))
+            .create(ui_container);
+
+        let title = ui::TextBuilder::new()
+            .text(translate("options.language"))
+            .position(0.0, -215.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
+        let warning = ui::TextBuilder::new()
+            .text(translate_with("options.languageWarning", &[]))
+            .position(0.0, 120.0)
+            .colour((170, 170, 170, 255))
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+
+        let done_button = ui::ButtonBuilder::new()
+            .position(0.0, 50.0)
+            .size(300.0, 40.0)
+            .alignment(ui::VAttach::Bottom, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut done_button = done_button.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(translate("gui.done"))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *done_button);
+            done_button.add_text(txt);
+            done_button.add_click_func(|_, game| {
+                game.screen_sys.pop_screen();
+                true
+            });
+        }
+
+        self.rows_dirty = false;
+        self.elements = Some(LanguageElements {
+            background,
+            _title: title,
+            _warning: warning,
+            _done: done_button,
+            rows: self.create_rows(ui_container),
+        });
+    }
+
+    fn on_deactive(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        _renderer: Arc<render::Renderer>,
+        _ui_container: &mut ui::Container,
+    ) {
+        self.elements = None;
+    }
+
+    // Called every frame the screen is active
+    fn tick(
+        &mut self,
+        _screen_sys: &ScreenSystem,
+        renderer: Arc<render::Renderer>,
+        ui_container: &mut ui::Container,
+        _delta: f64,
+    ) {
+        if self.rows_dirty {
+            self.rows_dirty = false;
+            let rows = self.create_rows(ui_container);
+            self.elements.as_mut().unwrap().rows = rows;
+        }
+        let elements = self.elements.as_mut().unwrap();
+        {
+            let mode = ui_container.mode;
+            let mut background = elements.background.borrow_mut();
+            background.width = match mode {
+                ui::Mode::Unscaled(scale) => 854.0 / scale,
+                ui::Mode::Scaled => renderer.screen_data.read().width as f64,
+            };
+            background.height = match mode {
+                ui::Mode::Unscaled(scale) => 480.0 / scale,
+                ui::Mode::Scaled => renderer.screen_data.read().height as f64,
+            };
+        }
+    }
+
+    // Events
+    fn on_scroll(&mut self, _x: f64, y: f64) {
+        let first_row = if y > 0.0 {
+            self.first_row.saturating_sub(1)
+        } else if y < 0.0 {
+            (self.first_row + 1).min(self.last_first_row())
+        } else {
+            self.first_row
+        };
+        if first_row != self.first_row {
+            self.first_row = first_row;
+            self.rows_dirty = true;
+        }
+    }
+
+    fn is_closable(&self) -> bool {
+        true
+    }
+
+    fn clone_screen(&self) -> Box<dyn Screen> {
+        Box::new(self.clone())
+    }
+}
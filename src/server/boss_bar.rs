@@ -0,0 +1,201 @@
+//! Boss bars as the server sends them, by uuid.
+
+use crate::format::Component;
+use crate::protocol::UUID;
+
+/// Before 1.10 the music flag also asked for fog
+const FOG_FLAG_VERSION: i32 = 210;
+
+/// Where the fog of a boss ends, as in vanilla at the default render distance
+pub const FOG_END: f32 = 96.0;
+/// How bright the sky still is while a boss darkens it
+pub const DARKENED_SKY: f32 = 0.6;
+
+const DARKEN_SKY: u8 = 0x1;
+const PLAY_MUSIC: u8 = 0x2;
+const CREATE_FOG: u8 = 0x4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BossBarColour {
+    Pink,
+    Blue,
+    Red,
+    Green,
+    Yellow,
+    Purple,
+    White,
+}
+
+impl BossBarColour {
+    fn from_id(id: i32) -> BossBarColour {
+        match id {
+            1 => BossBarColour::Blue,
+            2 => BossBarColour::Red,
+            3 => BossBarColour::Green,
+            4 => BossBarColour::Yellow,
+            5 => BossBarColour::Purple,
+            6 => BossBarColour::White,
+            _ => BossBarColour::Pink,
+        }
+    }
+
+    /// The row of the colour in the bars texture
+    pub fn row(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BossBar {
+    pub title: Component,
+    /// How full the bar is, from 0 to 1
+    pub health: f32,
+    pub colour: BossBarColour,
+    /// How many segments the bar is divided into, 1 for none
+    pub notches: u32,
+    pub darken_sky: bool,
+    pub create_fog: bool,
+}
+
+#[derive(Default)]
+pub struct BossBars {
+    fog_flag: u8,
+    /// In the order they were added, which is the order they're drawn in
+    bars: Vec<(UUID, BossBar)>,
+    /// Bumped on every change, for redrawing
+    version: usize,
+}
+
+impl BossBars {
+    pub fn new(protocol_version: i32) -> BossBars {
+        BossBars {
+            fog_flag: if protocol_version >= FOG_FLAG_VERSION {
+                CREATE_FOG
+            } else {
+                PLAY_MUSIC
+            },
+            ..BossBars::default()
+        }
+    }
+
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    pub fn bars(&self) -> impl Iterator<Item = &BossBar> {
+        self.bars.iter().map(|(_, bar)| bar)
+    }
+
+    pub fn darken_sky(&self) -> bool {
+        self.bars().any(|bar| bar.darken_sky)
+    }
+
+    pub fn create_fog(&self) -> bool {
+        self.bars().any(|bar| bar.create_fog)
+    }
+
+    fn get_mut(&mut self, uuid: &UUID) -> Option<&mut BossBar> {
+        self.version += 1;
+        self.bars
+            .iter_mut()
+            .find(|(id, _)| id == uuid)
+            .map(|(_, bar)| bar)
+    }
+
+    pub fn add(
+        &mut self,
+        uuid: UUID,
+        title: Component,
+        health: f32,
+        colour: i32,
+        style: i32,
+        flags: u8,
+    ) {
+        let mut bar = BossBar {
+            title,
+            health: health.clamp(0.0, 1.0),
+            colour: BossBarColour::Pink,
+            notches: 1,
+            darken_sky: false,
+            create_fog: false,
+        };
+        set_style(&mut bar, colour, style);
+        set_flags(&mut bar, flags, self.fog_flag);
+        self.remove(&uuid);
+        self.bars.push((uuid, bar));
+    }
+
+    pub fn remove(&mut self, uuid: &UUID) {
+        self.bars.retain(|(id, _)| id != uuid);
+        self.version += 1;
+    }
+
+    pub fn set_health(&mut self, uuid: &UUID, health: f32) {
+        if let Some(bar) = self.get_mut(uuid) {
+            bar.health = health.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn set_title(&mut self, uuid: &UUID, title: Component) {
+        if let Some(bar) = self.get_mut(uuid) {
+            bar.title = title;
+        }
+    }
+
+    pub fn set_style(&mut self, uuid: &UUID, colour: i32, style: i32) {
+        if let Some(bar) = self.get_mut(uuid) {
+            set_style(bar, colour, style);
+        }
+    }
+
+    pub fn set_flags(&mut self, uuid: &UUID, flags: u8) {
+        let fog_flag = self.fog_flag;
+        if let Some(bar) = self.get_mut(uuid) {
+            set_flags(bar, flags, fog_flag);
+        }
+    }
+}
+
+fn set_style(bar: &mut BossBar, colour: i32, style: i32) {
+    bar.colour = BossBarColour::from_id(colour);
+    bar.notches = match style {
+        1 => 6,
+        2 => 10,
+        3 => 12,
+        4 => 20,
+        _ => 1,
+    };
+}
+
+fn set_flags(bar: &mut BossBar, flags: u8, fog_flag: u8) {
+    bar.darken_sky = flags & DARKEN_SKY != 0;
+    bar.create_fog = flags & fog_flag != 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fog_flag_moved_in_1_10() {
+        let uuid = UUID::default();
+        let mut old = BossBars::new(109);
+        old.add(uuid.clone(), Component::default(), 0.5, 2, 1, PLAY_MUSIC);
+        assert!(old.create_fog());
+        assert!(!old.darken_sky());
+
+        let mut new = BossBars::new(210);
+        new.add(uuid.clone(), Component::default(), 1.5, 2, 1, PLAY_MUSIC);
+        assert!(!new.create_fog());
+        new.set_flags(&uuid, DARKEN_SKY | CREATE_FOG);
+        assert!(new.create_fog() && new.darken_sky());
+        assert_eq!(BossBars::new(340).fog_flag, CREATE_FOG);
+
+        let bar = new.bars().next().unwrap();
+        assert_eq!(bar.health, 1.0);
+        assert_eq!(bar.colour, BossBarColour::Red);
+        assert_eq!(bar.notches, 6);
+        new.remove(&uuid);
+        assert!(new.bars().next().is_none());
+    }
+}
@@ -14,7 +14,7 @@
 
 use crate::ecs::{Manager, SystemExecStage};
 use crate::entity;
-use crate::entity::player::{create_local, MovementDelta, PlayerModel, PlayerMovement};
+use crate::entity::player::{create_local, MovementDelta, PlayerModel, PlayerMovement, PlayerName};
 use crate::entity::{EntityType, GameInfo, Gravity, MouseButtons, TargetPosition, TargetRotation};
 use crate::format;
 use crate::inventory::material::versions::to_material;
@@ -31,6 +31,7 @@ use crate::screen::respawn::Respawn;
 use crate::screen::ScreenSystem;
 use crate::settings::Actionkey;
 use crate::shared::Position;
+use crate::sound;
 use crate::types::hash::FNVHash;
 use crate::types::GameMode;
 use crate::world::{self, World};
@@ -51,10 +52,11 @@ use dashmap::DashMap;
 use instant::{Duration, Instant};
 use leafish_protocol::format::Component;
 use leafish_protocol::item::Stack;
-use leafish_protocol::protocol::login::Account;
+use leafish_protocol::protocol::login::{Account, AccountType};
 use leafish_protocol::protocol::mapped_packet::MappablePacket;
 use leafish_protocol::protocol::mapped_packet::MappedPacket;
 use leafish_protocol::protocol::packet::{send_client_status, send_drop_item, ClientStatus, Hand};
+use leafish_protocol::protocol::profile_key::ProfileKey;
 use leafish_protocol::protocol::Conn;
 use log::{debug, error, info, warn};
 use parking_lot::Mutex;
@@ -66,17 +68,27 @@ use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
 use std::io::Cursor;
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use self::sun::SunModel;
 
+pub mod boss_bar;
 pub mod plugin_messages;
+pub mod scoreboard;
 mod sun;
 pub mod target;
 
+/// How long the server may stay silent before we give up on it, the
+/// vanilla server sends a keep alive every 15 seconds.
+const SERVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Before 1.14 the blocks of an explosion were relative to its position
+/// truncated toward zero, since then to its position rounded down
+const EXPLOSION_FLOOR_VERSION: i32 = 477;
+
 #[derive(Default)]
 pub struct DisconnectData {
     pub disconnect_reason: Option<format::Component>,
@@ -105,11 +117,16 @@ impl Default for WorldData {
 
 pub struct Server {
     uuid: protocol::UUID,
+    profile_key: Option<ProfileKey>,
     pub conn: Arc<RwLock<Option<protocol::Conn>>>,
     pub(crate) disconnect_gracefully: AtomicBool,
     pub protocol_version: i32,
     pub mapped_protocol_version: Version,
     forge_mods: Vec<forge::ForgeMod>,
+    channels: RwLock<plugin_messages::ChannelRegistry>,
+    // Played by the game's sound manager on the next tick
+    sound_commands: Mutex<Vec<sound::Command>>,
+    sound_registry: sound::registry::SoundRegistry,
     pub disconnect_data: Arc<RwLock<DisconnectData>>,
 
     pub world: Arc<world::World>,
@@ -122,10 +139,19 @@ pub struct Server {
     pub player: ArcSwapOption<(i32, Entity)>,
     entity_map: Arc<RwLock<HashMap<i32, Entity, BuildHasherDefault<FNVHash>>>>,
     players: Arc<RwLock<HashMap<protocol::UUID, PlayerInfo, BuildHasherDefault<FNVHash>>>>,
+    pub scoreboard: RwLock<scoreboard::Scoreboard>,
+    pub boss_bars: RwLock<boss_bar::BossBars>,
 
     tick_timer: AtomicF64,
     entity_tick_timer: AtomicF64,
     pub received_chat_at: ArcSwapOption<Instant>,
+    // Registries sent on join, needed to resolve dimension and chat types
+    dimension_codec: ArcSwapOption<crate::nbt::NamedTag>,
+    chat_preview_enabled: AtomicBool,
+    chat_preview_query: AtomicI32,
+    pub network_stats: Arc<std::sync::Mutex<protocol::stats::NetworkStats>>,
+    // Used to notice a server which stopped talking to us
+    last_packet_at: Mutex<Instant>,
 
     target_info: Arc<RwLock<target::Info>>,
     pub render_list_computer: Sender<bool>,
@@ -153,20 +179,68 @@ pub struct PlayerInfo {
     gamemode: GameMode,
 }
 
+/// A line of the player list shown while holding tab
+pub struct PlayerListEntry {
+    pub name: format::Component,
+    pub ping: i32,
+    pub gamemode: GameMode,
+    pub skin_url: Option<String>,
+}
+
 impl Server {
     pub fn connect(
         resources: Arc<RwLock<resources::Manager>>,
         account: &Account,
         address: &str,
+        proxy: Option<&protocol::proxy::Proxy>,
         protocol_version: i32,
         forge_mods: Vec<forge::ForgeMod>,
         fml_network_version: Option<i64>,
         renderer: Arc<Renderer>,
         hud_context: Arc<RwLock<HudContext>>,
         screen_sys: Arc<ScreenSystem>,
+        status: &Sender<String>,
     ) -> Result<Arc<Server>, protocol::Error> {
-        let mut conn = protocol::Conn::new(address, protocol_version)?;
+        let mut conn = protocol::Conn::new_with_proxy(address, protocol_version, proxy)?;
+        let _ = status.send(match conn.remote_addr {
+            Some(addr) => format!("Connected to {}, logging in", addr),
+            None => "Connected, logging in".to_owned(),
+        });
+        if let Some(path) = protocol::capture::next_capture_path() {
+            info!("Recording packet capture to {}", path.display());
+            if let Err(err) = conn.start_capture(&path) {
+                warn!(
+                    "Failed to create packet capture {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+        }
+        Server::connect_over(
+            conn,
+            resources,
+            account,
+            forge_mods,
+            fml_network_version,
+            renderer,
+            hud_context,
+            screen_sys,
+        )
+    }
 
+    /// Logs in over an already established connection, regardless of
+    /// which transport it runs on.
+    pub fn connect_over(
+        mut conn: protocol::Conn,
+        resources: Arc<RwLock<resources::Manager>>,
+        account: &Account,
+        forge_mods: Vec<forge::ForgeMod>,
+        fml_network_version: Option<i64>,
+        renderer: Arc<Renderer>,
+        hud_context: Arc<RwLock<HudContext>>,
+        screen_sys: Arc<ScreenSystem>,
+    ) -> Result<Arc<Server>, protocol::Error> {
+        let protocol_version = conn.protocol_version;
         let tag = match fml_network_version {
             Some(1) => "\0FML\0",
             Some(2) => "\0FML2\0",
@@ -183,9 +257,43 @@ impl Server {
             next: protocol::VarInt(2),
         })?;
         conn.state = protocol::State::Login;
-        conn.write_packet(protocol::packet::login::serverbound::LoginStart {
-            username: account.name.clone(),
-        })?;
+
+        // Chat is signed with the profile key since 1.19, offline accounts
+        // don't have one and fall back to unsigned chat.
+        let profile_key = if protocol_version >= 759 && account.account_type != AccountType::None {
+            account.profile_key().unwrap_or_else(|err| {
+                warn!(
+                    "Failed to fetch profile key, chat will be unsigned: {}",
+                    err
+                );
+                None
+            })
+        } else {
+            None
+        };
+        if protocol_version >= 759 {
+            conn.write_packet(protocol::packet::login::serverbound::LoginStart_Sig {
+                username: account.name.clone(),
+                has_sig_data: profile_key.is_some(),
+                expires_at: profile_key.as_ref().map_or(0, |key| key.expires_at),
+                public_key: protocol::LenPrefixedBytes::new(
+                    profile_key
+                        .as_ref()
+                        .map(|key| key.public_key.clone())
+                        .unwrap_or_default(),
+                ),
+                key_signature: protocol::LenPrefixedBytes::new(
+                    profile_key
+                        .as_ref()
+                        .map(|key| key.key_signature.clone())
+                        .unwrap_or_default(),
+                ),
+            })?;
+        } else {
+            conn.write_packet(protocol::packet::login::serverbound::LoginStart {
+                username: account.name.clone(),
+            })?;
+        }
 
         use std::rc::Rc;
         let (server_id, public_key, verify_token);
@@ -216,6 +324,7 @@ impl Server {
                         protocol_version,
                         forge_mods,
                         uuid,
+                        profile_key,
                         resources,
                         renderer,
                         hud_context,
@@ -232,6 +341,25 @@ impl Server {
                         protocol_version,
                         forge_mods,
                         val.uuid,
+                        profile_key,
+                        resources,
+                        renderer,
+                        hud_context,
+                        screen_sys,
+                    );
+
+                    return Ok(server);
+                }
+                protocol::packet::Packet::LoginSuccess_Properties(val) => {
+                    warn!("Server is running in offline mode");
+                    debug!("Login: {} {:?}", val.username, val.uuid);
+                    conn.state = protocol::State::Play;
+                    let server = Server::connect0(
+                        conn,
+                        protocol_version,
+                        forge_mods,
+                        val.uuid,
+                        profile_key,
                         resources,
                         renderer,
                         hud_context,
@@ -255,7 +383,28 @@ impl Server {
 
         account.join_server(&server_id, &shared, &public_key)?;
 
-        if protocol_version >= 47 {
+        if protocol_version >= 759 {
+            // With a profile key the verify token is signed instead of encrypted
+            let salt: i64 = rand::thread_rng().gen();
+            conn.write_packet(
+                protocol::packet::login::serverbound::EncryptionResponse_Sig {
+                    shared_secret: protocol::LenPrefixedBytes::new(shared_e),
+                    has_verify_token: profile_key.is_none(),
+                    verify_token: protocol::LenPrefixedBytes::new(if profile_key.is_none() {
+                        token_e
+                    } else {
+                        vec![]
+                    }),
+                    salt,
+                    message_signature: protocol::LenPrefixedBytes::new(
+                        profile_key
+                            .as_ref()
+                            .map(|key| key.sign_verify_token(&verify_token, salt))
+                            .unwrap_or_default(),
+                    ),
+                },
+            )?;
+        } else if protocol_version >= 47 {
             conn.write_packet(protocol::packet::login::serverbound::EncryptionResponse {
                 shared_secret: protocol::LenPrefixedBytes::new(shared_e),
                 verify_token: protocol::LenPrefixedBytes::new(token_e),
@@ -290,6 +439,12 @@ impl Server {
                     conn.state = protocol::State::Play;
                     break;
                 }
+                protocol::packet::Packet::LoginSuccess_Properties(val) => {
+                    debug!("Login: {} {:?}", val.username, val.uuid);
+                    uuid = val.uuid;
+                    conn.state = protocol::State::Play;
+                    break;
+                }
                 protocol::packet::Packet::LoginDisconnect(val) => {
                     return Err(protocol::Error::Disconnect(val.reason))
                 }
@@ -368,6 +523,7 @@ impl Server {
             protocol_version,
             forge_mods,
             uuid,
+            profile_key,
             resources,
             renderer,
             hud_context,
@@ -377,11 +533,45 @@ impl Server {
         Ok(server)
     }
 
+    /// Replays a previously recorded packet capture as if it were a live server
+    pub fn replay(
+        resources: Arc<RwLock<resources::Manager>>,
+        path: &str,
+        renderer: Arc<Renderer>,
+        hud_context: Arc<RwLock<HudContext>>,
+        screen_sys: Arc<ScreenSystem>,
+    ) -> Result<Arc<Server>, protocol::Error> {
+        let conn = protocol::Conn::replay(path)?;
+        let protocol_version = conn.protocol_version;
+        if !protocol::versions::is_supported_protocol(protocol_version) {
+            return Err(protocol::Error::Err(format!(
+                "The capture's version isn't supported!\n(protocol version: {})",
+                protocol_version
+            )));
+        }
+        info!(
+            "Replaying packet capture {} (protocol version {})",
+            path, protocol_version
+        );
+        Ok(Server::connect0(
+            conn,
+            protocol_version,
+            vec![],
+            protocol::UUID::default(),
+            None,
+            resources,
+            renderer,
+            hud_context,
+            screen_sys,
+        ))
+    }
+
     fn connect0(
-        conn: Conn,
+        mut conn: Conn,
         protocol_version: i32,
         forge_mods: Vec<forge::ForgeMod>,
         uuid: protocol::UUID,
+        profile_key: Option<ProfileKey>,
         resources: Arc<RwLock<resources::Manager>>,
         renderer: Arc<Renderer>,
         hud_context: Arc<RwLock<HudContext>>,
@@ -390,6 +580,12 @@ impl Server {
         let server_callback = Arc::new(Mutex::new(None));
         let inner_server = server_callback.clone();
         let mut inner_server = inner_server.lock();
+        if let Err(err) = conn.start_writer() {
+            warn!(
+                "Failed to start the writer thread, sending directly: {}",
+                err
+            );
+        }
         Self::spawn_reader(conn.clone(), server_callback.clone());
         let light_updater = Self::spawn_light_updater(server_callback.clone());
         let render_list_computer =
@@ -399,6 +595,7 @@ impl Server {
             protocol_version,
             forge_mods,
             uuid,
+            profile_key,
             resources,
             conn,
             light_updater,
@@ -419,12 +616,26 @@ impl Server {
     fn spawn_reader(mut read: protocol::Conn, server: Arc<Mutex<Option<Arc<Server>>>>) {
         thread::spawn(move || {
             let threads = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+            let stats = read.stats();
             loop {
                 let server = server.lock().as_ref().unwrap().clone();
                 let pck = read.read_packet();
                 match pck {
                     Ok(pck) => {
+                        let name = pck.name();
+                        let handle_start = Instant::now();
+                        *server.last_packet_at.lock() = handle_start;
                         let pck = pck.map();
+                        // Here only the hand-over would be measured, so work
+                        // done on the pool adds its own time once finished
+                        let spawn = |handle: Box<dyn FnOnce() + Send>| {
+                            let stats = stats.clone();
+                            threads.spawn(move || {
+                                let start = Instant::now();
+                                handle();
+                                stats.lock().unwrap().record_handled(name, start.elapsed());
+                            });
+                        };
 
                         match pck {
                             MappedPacket::KeepAliveClientbound(keep_alive) => {
@@ -434,6 +645,10 @@ impl Server {
                                 )
                                 .map_err(|_| server.disconnect_closed(None));
                             }
+                            MappedPacket::Ping(ping) => {
+                                read.write_packet(packet::play::serverbound::Pong { id: ping.id })
+                                    .unwrap();
+                            }
                             MappedPacket::ChunkData_NoEntities(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
                                 server.on_chunk_data_no_entities(chunk_data, sky_light);
@@ -459,35 +674,46 @@ impl Server {
                             MappedPacket::UpdateBlockEntity(block_update) => {
                                 server.on_block_entity_update(block_update);
                             }
+                            MappedPacket::ChunkData_Biomes3D_Bitmasks(chunk_data) => {
+                                let sky_light = server.world.dimension.load().has_sky_light();
+                                spawn(Box::new(move || {
+                                    server.on_chunk_data_biomes3d_bitmasks(chunk_data, sky_light);
+                                }));
+                            }
+                            MappedPacket::ChunkData_AndLight(chunk_data) => {
+                                spawn(Box::new(move || {
+                                    server.on_chunk_data_and_light(chunk_data);
+                                }));
+                            }
                             MappedPacket::ChunkData_Biomes3D(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
-                                threads.spawn(move || {
+                                spawn(Box::new(move || {
                                     server.on_chunk_data_biomes3d(chunk_data, sky_light);
-                                });
+                                }));
                             }
                             MappedPacket::ChunkData_Biomes3D_i32(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
-                                threads.spawn(move || {
+                                spawn(Box::new(move || {
                                     server.on_chunk_data_biomes3d_varint(chunk_data, sky_light);
-                                });
+                                }));
                             }
                             MappedPacket::ChunkData_Biomes3D_bool(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
-                                threads.spawn(move || {
+                                spawn(Box::new(move || {
                                     server.on_chunk_data_biomes3d_bool(chunk_data, sky_light);
-                                });
+                                }));
                             }
                             MappedPacket::ChunkData(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
-                                threads.spawn(move || {
+                                spawn(Box::new(move || {
                                     server.on_chunk_data(chunk_data, sky_light);
-                                });
+                                }));
                             }
                             MappedPacket::ChunkData_HeightMap(chunk_data) => {
                                 let sky_light = server.world.dimension.load().has_sky_light();
-                                threads.spawn(move || {
+                                spawn(Box::new(move || {
                                     server.on_chunk_data_heightmap(chunk_data, sky_light);
-                                });
+                                }));
                             }
                             MappedPacket::UpdateSign(update_sign) => {
                                 server.on_sign_update(update_sign);
@@ -531,10 +757,18 @@ impl Server {
                                     dimension_name,
                                     dimension,
                                     world_name,
+                                    dimension_codec,
                                     ..
                                 } = join;
 
                                 server.on_game_join(gamemode, entity_id);
+                                server.dimension_codec.store(dimension_codec.map(Arc::new));
+
+                                let height = dimension
+                                    .as_ref()
+                                    .and_then(world::WorldHeight::from_tag)
+                                    .unwrap_or_default();
+                                server.world.set_height(height);
 
                                 let dimension = dimension_id
                                     .map(world::Dimension::from_index)
@@ -686,6 +920,48 @@ impl Server {
                             MappedPacket::PluginMessageClientbound(plugin_message) => {
                                 server.on_plugin_message_clientbound(plugin_message);
                             }
+                            MappedPacket::NamedSoundEffect(sound_effect) => {
+                                server.on_named_sound_effect(sound_effect);
+                            }
+                            MappedPacket::StopSound(stop_sound) => {
+                                server.on_stop_sound(stop_sound);
+                            }
+                            MappedPacket::Particle(particle) => {
+                                server.on_particle(particle);
+                            }
+                            MappedPacket::Effect(effect) => {
+                                server.on_effect(effect);
+                            }
+                            MappedPacket::Explosion(explosion) => {
+                                server.on_explosion(explosion);
+                            }
+                            MappedPacket::ScoreboardObjective(objective) => {
+                                server.on_scoreboard_objective(objective);
+                            }
+                            MappedPacket::UpdateScore(score) => {
+                                server.on_update_score(score);
+                            }
+                            MappedPacket::ScoreboardDisplay(display) => {
+                                server.on_scoreboard_display(display);
+                            }
+                            MappedPacket::Teams(teams) => {
+                                server.on_teams(teams);
+                            }
+                            MappedPacket::BossBar(boss_bar) => {
+                                server.on_boss_bar(boss_bar);
+                            }
+                            MappedPacket::PlayerListHeaderFooter(header_footer) => {
+                                server.hud_context.write().set_player_list_header_footer(
+                                    header_footer.header,
+                                    header_footer.footer,
+                                );
+                            }
+                            MappedPacket::SoundEffect(sound_effect) => {
+                                server.on_sound_effect(sound_effect);
+                            }
+                            MappedPacket::EntitySoundEffect(sound_effect) => {
+                                server.on_entity_sound_effect(sound_effect);
+                            }
                             MappedPacket::SetExperience(set_exp) => {
                                 server
                                     .hud_context
@@ -701,9 +977,15 @@ impl Server {
                                 }
                             }
                             MappedPacket::WindowItems(items) => {
+                                if let Some(state_id) = items.state_id {
+                                    server.on_window_state_id(items.id as i32, state_id);
+                                }
                                 for item in items.items.into_iter().enumerate() {
                                     server.on_set_slot(items.id as i16, item.0 as i16, item.1);
                                 }
+                                if items.state_id.is_some() {
+                                    server.on_set_slot(-1, -1, items.carried_item);
+                                }
                             }
                             MappedPacket::WindowProperty(data) => {
                                 let win_id: i32 = data.id as i32;
@@ -719,6 +1001,9 @@ impl Server {
                                 }
                             }
                             MappedPacket::WindowSetSlot(set_slot) => {
+                                if let Some(state_id) = set_slot.state_id {
+                                    server.on_window_state_id(set_slot.id as i32, state_id);
+                                }
                                 server.on_set_slot(
                                     set_slot.id as i16,
                                     set_slot.slot,
@@ -813,6 +1098,30 @@ impl Server {
                                         .insert(block_break.entity_id, entity);
                                 }
                             }
+                            MappedPacket::PlayerChatMessage(chat_message) => {
+                                server.on_player_chat(chat_message);
+                            }
+                            MappedPacket::ChatPreview(preview) => {
+                                // Drop answers to queries which were superseded already
+                                if preview.query_id
+                                    == server.chat_preview_query.load(Ordering::Acquire)
+                                {
+                                    server.chat_ctx.set_preview(preview.message);
+                                }
+                            }
+                            MappedPacket::ServerData(server_data) => {
+                                server
+                                    .chat_preview_enabled
+                                    .store(server_data.previews_chat, Ordering::Release);
+                            }
+                            MappedPacket::SetDisplayChatPreview(display) => {
+                                server
+                                    .chat_preview_enabled
+                                    .store(display.enabled, Ordering::Release);
+                                if !display.enabled {
+                                    server.chat_ctx.set_preview(None);
+                                }
+                            }
                             MappedPacket::Title(title) => {
                                 if let Some(bar) = title.action_bar_text {
                                     server
@@ -825,6 +1134,10 @@ impl Server {
                                 // debug!("other packet!");
                             }
                         }
+                        stats
+                            .lock()
+                            .unwrap()
+                            .record_handled(name, handle_start.elapsed());
                     }
                     Err(err) => {
                         if server.disconnect_data.read().disconnect_reason.is_none() {
@@ -835,6 +1148,12 @@ impl Server {
                                 )),
                             );
                         }
+                        if read.is_replay() {
+                            // There is nothing left to read once a capture ends
+                            let reason = server.disconnect_data.write().disconnect_reason.take();
+                            server.disconnect_closed(reason);
+                            break;
+                        }
                     }
                 }
             }
@@ -896,6 +1215,7 @@ impl Server {
         protocol_version: i32,
         forge_mods: Vec<forge::ForgeMod>,
         uuid: protocol::UUID,
+        profile_key: Option<ProfileKey>,
         resources: Arc<RwLock<resources::Manager>>,
         conn: Arc<RwLock<Option<protocol::Conn>>>,
         light_updater: Sender<LightUpdate>,
@@ -930,6 +1250,9 @@ impl Server {
         entities.world.insert_resource(DeltaResource(0.0));
         entities.world.insert_resource(WorldData::default());
         entities.world.insert_resource(RenderCtxResource::default());
+        entities
+            .world
+            .insert_resource(crate::particle::sprite::ParticleModel::default());
         entity::add_systems(
             &mut entities.schedule.write(),
             &mut entities.render_schedule.write(),
@@ -938,14 +1261,35 @@ impl Server {
 
         hud_context.write().slots = Some(inventory_context.read().base_slots.clone());
 
+        let network_stats = conn
+            .read()
+            .as_ref()
+            .map_or_else(Default::default, |conn| conn.stats());
         let version = resources.read().version();
+        let sound_registry = if protocol_version >= 107 {
+            sound::registry::SoundRegistry::load(&resources.read(), protocol_version)
+        } else {
+            // Sounds are always sent by name before 1.9
+            Default::default()
+        };
+        let mut channels = plugin_messages::ChannelRegistry::new();
+        // Not every Forge server lists its mods in the ping, so the handshake
+        // is always listened for
+        channels.register_handler(
+            "FML|HS",
+            Arc::new(|server: &Server, data: &[u8]| server.on_fmlhs_message(data)),
+        );
         Self {
             uuid,
+            profile_key,
             conn,
             disconnect_gracefully: Default::default(),
             protocol_version,
             mapped_protocol_version,
             forge_mods,
+            channels: RwLock::new(channels),
+            sound_commands: Mutex::new(vec![]),
+            sound_registry,
             disconnect_data: Arc::new(RwLock::new(DisconnectData::default())),
 
             world,
@@ -960,10 +1304,17 @@ impl Server {
             players: Arc::new(RwLock::new(HashMap::with_hasher(
                 BuildHasherDefault::default(),
             ))),
+            scoreboard: RwLock::new(scoreboard::Scoreboard::new(protocol_version)),
+            boss_bars: RwLock::new(boss_bar::BossBars::new(protocol_version)),
 
             tick_timer: AtomicF64::new(0.0),
             entity_tick_timer: AtomicF64::new(0.0),
             received_chat_at: ArcSwapOption::new(None),
+            dimension_codec: ArcSwapOption::new(None),
+            chat_preview_enabled: AtomicBool::new(false),
+            chat_preview_query: AtomicI32::new(0),
+            network_stats,
+            last_packet_at: Mutex::new(Instant::now()),
 
             target_info: Arc::new(RwLock::new(target::Info::new())),
             render_list_computer,
@@ -1025,6 +1376,32 @@ impl Server {
     }
 
     pub fn tick(&self, delta: f64, game: &Game) {
+        let write_error = self.conn.read().as_ref().and_then(Conn::write_error);
+        if let Some(err) = write_error {
+            self.disconnect(Some(Component::new(format::ComponentType::new(
+                &format!("Failed to send a packet: {}", err),
+                None,
+            ))));
+            return;
+        }
+        let sound_commands = std::mem::take(&mut *self.sound_commands.lock());
+        if !sound_commands.is_empty() {
+            let mut sound = game.sound.lock();
+            for command in sound_commands {
+                sound.run(&self.resources, command);
+            }
+        }
+        let is_replay = self.conn.read().as_ref().map_or(false, Conn::is_replay);
+        if !is_replay && self.last_packet_at.lock().elapsed() > SERVER_TIMEOUT {
+            self.disconnect(Some(Component::new(format::ComponentType::new(
+                &format!(
+                    "Timed out: the server hasn't sent anything for {} seconds",
+                    SERVER_TIMEOUT.as_secs()
+                ),
+                None,
+            ))));
+            return;
+        }
         {
             let mut entities = self.entities.write();
             // FIXME: is there another way to do this?
@@ -1179,7 +1556,17 @@ impl Server {
     }*/
 
     fn update_time(&self, renderer: &Arc<render::Renderer>) {
-        renderer.light_data.lock().sky_offset = self.calculate_sky_offset();
+        let boss_bars = self.boss_bars.read();
+        let mut light_data = renderer.light_data.lock();
+        light_data.sky_offset = self.calculate_sky_offset();
+        if boss_bars.darken_sky() {
+            light_data.sky_offset *= boss_bar::DARKENED_SKY;
+        }
+        light_data.fog_end = if boss_bars.create_fog() {
+            boss_bar::FOG_END
+        } else {
+            0.0
+        };
     }
 
     fn calculate_sky_offset(&self) -> f32 {
@@ -1286,6 +1673,10 @@ impl Server {
                 mouse_buttons.right = false;
             }
         }
+        // Everything sent during this tick goes out together
+        if let Some(conn) = self.conn.read().as_ref() {
+            conn.end_batch();
+        }
     }
 
     pub fn key_press(&self, down: bool, key: Actionkey, focused: bool, ctrl_pressed: bool) -> bool {
@@ -1303,6 +1694,9 @@ impl Server {
                     movement.pressed_keys.insert(key, down);
                 }
             }
+            if key == Actionkey::PlayerList && state_changed {
+                self.hud_context.write().show_player_list(down);
+            }
             if !down {
                 return false;
             }
@@ -1334,6 +1728,11 @@ impl Server {
                         self.hud_context.write().debug = !curr;
                     }
                 }
+                Actionkey::DumpNetworkStats => {
+                    if state_changed {
+                        self.dump_network_stats();
+                    }
+                }
                 Actionkey::ToggleChat => {
                     self.screen_sys
                         .add_screen(Box::new(Chat::new(self.chat_ctx.clone())));
@@ -1537,8 +1936,13 @@ impl Server {
                 return;
             }
         }
+        let text = match conn.as_ref().and_then(Conn::write_error) {
+            Some(err) => format!("Failed to send a packet: {}", err),
+            None => "Already disconnected!".to_string(),
+        };
+        drop(conn);
         self.disconnect(Some(Component::new(format::ComponentType::Text {
-            text: "Already disconnected!".to_string(),
+            text,
             modifier: Default::default(),
         })));
     }
@@ -1554,98 +1958,347 @@ impl Server {
             );
         }
 
-        match &*msg.channel {
-            "REGISTER" => {}   // TODO
-            "UNREGISTER" => {} // TODO
-            "FML|HS" => {
-                let msg =
-                    crate::protocol::Serializable::read_from(&mut std::io::Cursor::new(msg.data))
-                        .unwrap();
-                // debug!("FML|HS msg={:?}", msg);
-
-                use forge::FmlHs::*;
-                use forge::Phase::*;
-                match msg {
-                    ServerHello {
-                        fml_protocol_version,
-                        override_dimension,
-                    } => {
-                        debug!(
-                            "Received FML|HS ServerHello {} {:?}",
-                            fml_protocol_version, override_dimension
-                        );
+        match plugin_messages::normalize_channel(&msg.channel) {
+            "minecraft:register" => self.channels.write().on_register(&msg.data),
+            "minecraft:unregister" => self.channels.write().on_unregister(&msg.data),
+            channel => {
+                // Not called under the lock, handlers may register channels
+                let handler = self.channels.read().handler(channel);
+                match handler {
+                    Some(handler) => handler(self, &msg.data),
+                    None => debug!("No handler for plugin channel {}", msg.channel),
+                }
+            }
+        }
+    }
 
-                        self.write_plugin_message("REGISTER", b"FML|HS\0FML\0FML|MP\0FML\0FORGE");
-                        self.write_fmlhs_plugin_message(&ClientHello {
-                            fml_protocol_version,
-                        });
-                        // Send stashed mods list received from ping packet, client matching server
-                        let mods = crate::protocol::LenPrefixed::<
-                            crate::protocol::VarInt,
-                            forge::ForgeMod,
-                        >::new(self.forge_mods.clone());
-                        self.write_fmlhs_plugin_message(&ModList { mods });
-                    }
-                    ModList { mods } => {
-                        debug!("Received FML|HS ModList: {:?}", mods);
+    fn on_named_sound_effect(
+        &self,
+        sound_effect: mapped_packet::play::clientbound::NamedSoundEffect,
+    ) {
+        // Positions are fixed point with 3 fractional bits
+        let position = Vector3::new(
+            sound_effect.x as f64 / 8.0,
+            sound_effect.y as f64 / 8.0,
+            sound_effect.z as f64 / 8.0,
+        );
+        self.sound_commands.lock().push(sound::Command::Play {
+            event: sound_effect.name,
+            category: sound_effect
+                .category
+                .map_or(sound::Category::Master, sound::Category::from_id),
+            position: Some(position),
+            volume: sound_effect.volume,
+            pitch: sound_effect.pitch,
+        });
+    }
 
-                        self.write_fmlhs_plugin_message(&HandshakeAck {
-                            phase: WaitingServerData,
-                        });
-                    }
-                    ModIdData {
-                        mappings,
-                        block_substitutions: _,
-                        item_substitutions: _,
-                    } => {
-                        debug!("Received FML|HS ModIdData");
-                        let mut mod_ids = self.world.modded_block_ids.load().as_ref().clone();
-                        for m in mappings.data {
-                            let (namespace, name) = m.name.split_at(1);
-                            if namespace == protocol::forge::BLOCK_NAMESPACE {
-                                mod_ids.insert(m.id.0 as usize, name.to_string());
-                            }
-                        }
-                        self.world.modded_block_ids.store(Arc::new(mod_ids));
-                        self.write_fmlhs_plugin_message(&HandshakeAck {
-                            phase: WaitingServerComplete,
-                        });
+    fn on_sound_effect(&self, sound_effect: mapped_packet::play::clientbound::SoundEffect) {
+        let event = match self.sound_registry.name(sound_effect.name) {
+            Some(event) => event.to_owned(),
+            None => {
+                debug!("Unknown sound id {}", sound_effect.name);
+                return;
+            }
+        };
+        let position = Vector3::new(
+            sound_effect.x as f64 / 8.0,
+            sound_effect.y as f64 / 8.0,
+            sound_effect.z as f64 / 8.0,
+        );
+        self.sound_commands.lock().push(sound::Command::Play {
+            event,
+            category: sound::Category::from_id(sound_effect.category),
+            position: Some(position),
+            volume: sound_effect.volume,
+            pitch: sound_effect.pitch,
+        });
+    }
+
+    fn on_entity_sound_effect(
+        &self,
+        sound_effect: mapped_packet::play::clientbound::EntitySoundEffect,
+    ) {
+        let event = match self.sound_registry.name(sound_effect.sound_id) {
+            Some(event) => event.to_owned(),
+            None => {
+                debug!("Unknown sound id {}", sound_effect.sound_id);
+                return;
+            }
+        };
+        let entity = match self.entity_map.read().get(&sound_effect.entity_id) {
+            Some(entity) => *entity,
+            None => return,
+        };
+        let position = self
+            .entities
+            .read()
+            .world
+            .get_entity(entity)
+            .and_then(|entity| entity.get::<crate::entity::Position>())
+            .map(|position| position.position);
+        if let Some(position) = position {
+            self.sound_commands.lock().push(sound::Command::Play {
+                event,
+                category: sound::Category::from_id(sound_effect.sound_category),
+                position: Some(position),
+                volume: sound_effect.volume,
+                pitch: sound_effect.pitch,
+            });
+        }
+    }
+
+    fn spawn_particles(&self, particles: Vec<crate::particle::sprite::Particle>) {
+        if !particles.is_empty() {
+            self.entities.write().world.spawn_batch(particles);
+        }
+    }
+
+    /// The block of the id in block particles and effects, which packs the
+    /// metadata into the high bits before 1.13
+    fn particle_block(&self, id: i32) -> world::block::Block {
+        let id = if self.protocol_version < 393 {
+            ((id & 0xfff) << 4) | ((id >> 12) & 0xf)
+        } else {
+            id
+        };
+        self.world
+            .id_map
+            .by_vanilla_id(id as usize, &self.world.modded_block_ids.load())
+    }
+
+    fn on_particle(&self, particle: mapped_packet::play::clientbound::Particle) {
+        let legacy_name = particle.particle_name.as_deref();
+        let name = match (legacy_name, particle.particle_id) {
+            (Some(name), _) => crate::particle::kinds::legacy_particle_name(name),
+            (None, Some(id)) => crate::particle::kinds::particle_name(self.protocol_version, id),
+            (None, None) => None,
+        };
+        let name = match name {
+            Some(name) => name,
+            None => return,
+        };
+        let block = if !matches!(name, "block" | "block_marker") {
+            None
+        } else if let Some(name) = legacy_name {
+            // Like blockcrack_1_0, for the id and metadata
+            let mut data = name
+                .split('_')
+                .skip(1)
+                .map(|v| v.parse::<i32>().unwrap_or(0));
+            data.next()
+                .map(|id| self.particle_block(id | (data.next().unwrap_or(0) << 12)))
+        } else {
+            particle
+                .block_state
+                .or(particle.data1)
+                .map(|id| self.particle_block(id))
+        };
+        let colour = match (particle.red, particle.green, particle.blue) {
+            (Some(red), Some(green), Some(blue)) if name.starts_with("dust") => {
+                Some([red, green, blue].map(|c| (c.clamp(0.0, 1.0) * 255.0) as u8))
+            }
+            _ => None,
+        };
+        let emitter = crate::particle::Emitter {
+            name,
+            position: Vector3::new(particle.x, particle.y, particle.z),
+            offset: Vector3::new(
+                particle.offset_x as f64,
+                particle.offset_y as f64,
+                particle.offset_z as f64,
+            ),
+            speed: particle.speed as f64,
+            count: particle.count,
+            block,
+            colour,
+        };
+        self.spawn_particles(emitter.particles(&mut rand::thread_rng()));
+    }
+
+    fn on_effect(&self, effect: mapped_packet::play::clientbound::Effect) {
+        let rng = &mut rand::thread_rng();
+        let pos = effect.location;
+        let centre = Vector3::new(pos.x as f64 + 0.5, pos.y as f64, pos.z as f64 + 0.5);
+        let particles = match effect.effect_id {
+            2000 => crate::particle::smoke(pos, rng),
+            2001 => {
+                let block = self.particle_block(effect.data);
+                crate::particle::block_debris(block, pos, rng)
+            }
+            2002 | 2007 => {
+                let colour = [
+                    (effect.data >> 16) as u8,
+                    (effect.data >> 8) as u8,
+                    effect.data as u8,
+                ];
+                crate::particle::potion_splash(centre, colour, effect.effect_id == 2007, rng)
+            }
+            2004 => crate::particle::spawner_flames(pos, rng),
+            _ => return,
+        };
+        self.spawn_particles(particles);
+    }
+
+    fn on_explosion(&self, explosion: mapped_packet::play::clientbound::Explosion) {
+        let (x, y, z) = (explosion.x as f64, explosion.y as f64, explosion.z as f64);
+        let origin = if self.protocol_version >= EXPLOSION_FLOOR_VERSION {
+            Position::new(x.floor() as i32, y.floor() as i32, z.floor() as i32)
+        } else {
+            Position::new(x as i32, y as i32, z as i32)
+        };
+        for record in &explosion.records {
+            let pos = Position::new(
+                origin.x + record.x as i32,
+                origin.y + record.y as i32,
+                origin.z + record.z as i32,
+            );
+            self.world.set_block(pos, world::block::Block::Air {});
+        }
+        self.spawn_particles(crate::particle::explosion(
+            Vector3::new(x, y, z),
+            explosion.radius as f64,
+            &mut rand::thread_rng(),
+        ));
+    }
+
+    fn on_stop_sound(&self, stop_sound: mapped_packet::play::clientbound::StopSound) {
+        let category = if stop_sound.flags & 0x1 != 0 {
+            stop_sound.source.map(sound::Category::from_id)
+        } else {
+            None
+        };
+        let event = if stop_sound.flags & 0x2 != 0 {
+            stop_sound.sound
+        } else {
+            None
+        };
+        self.sound_commands
+            .lock()
+            .push(sound::Command::Stop { category, event });
+    }
+
+    /// Makes messages on the channel go to the handler. Channels registered
+    /// after joining are advertised to the server right away.
+    pub fn register_channel_handler(
+        &self,
+        channel: &str,
+        handler: plugin_messages::ChannelHandler,
+    ) {
+        self.channels.write().register_handler(channel, handler);
+        if self.player.load().is_some() {
+            let advertised =
+                plugin_messages::ChannelRegistry::advertised_name(channel, self.protocol_version);
+            if let Some(channel) = advertised {
+                self.write_plugin_message(
+                    plugin_messages::channel_for_version(
+                        "minecraft:register",
+                        self.protocol_version,
+                    ),
+                    channel.as_bytes(),
+                );
+            }
+        }
+    }
+
+    /// Sends a message on the channel, named the way the server expects.
+    /// Returns false without sending if the server doesn't listen on it.
+    pub fn write_channel_message(&self, channel: &str, data: &[u8]) -> bool {
+        if !self.channels.read().server_listens(channel) {
+            return false;
+        }
+        self.write_plugin_message(
+            plugin_messages::channel_for_version(channel, self.protocol_version),
+            data,
+        );
+        true
+    }
+
+    fn on_fmlhs_message(&self, data: &[u8]) {
+        let msg =
+            crate::protocol::Serializable::read_from(&mut std::io::Cursor::new(data)).unwrap();
+        // debug!("FML|HS msg={:?}", msg);
+
+        use forge::FmlHs::*;
+        use forge::Phase::*;
+        match msg {
+            ServerHello {
+                fml_protocol_version,
+                override_dimension,
+            } => {
+                debug!(
+                    "Received FML|HS ServerHello {} {:?}",
+                    fml_protocol_version, override_dimension
+                );
+
+                self.write_plugin_message("REGISTER", b"FML|HS\0FML\0FML|MP\0FML\0FORGE");
+                self.write_fmlhs_plugin_message(&ClientHello {
+                    fml_protocol_version,
+                });
+                // Send stashed mods list received from ping packet, client matching server
+                let mods =
+                    crate::protocol::LenPrefixed::<crate::protocol::VarInt, forge::ForgeMod>::new(
+                        self.forge_mods.clone(),
+                    );
+                self.write_fmlhs_plugin_message(&ModList { mods });
+            }
+            ModList { mods } => {
+                debug!("Received FML|HS ModList: {:?}", mods);
+
+                self.write_fmlhs_plugin_message(&HandshakeAck {
+                    phase: WaitingServerData,
+                });
+            }
+            ModIdData {
+                mappings,
+                block_substitutions: _,
+                item_substitutions: _,
+            } => {
+                debug!("Received FML|HS ModIdData");
+                let mut mod_ids = self.world.modded_block_ids.load().as_ref().clone();
+                for m in mappings.data {
+                    let (namespace, name) = m.name.split_at(1);
+                    if namespace == protocol::forge::BLOCK_NAMESPACE {
+                        mod_ids.insert(m.id.0 as usize, name.to_string());
                     }
-                    RegistryData {
-                        has_more,
-                        name,
-                        ids,
-                        substitutions: _,
-                        dummies: _,
-                    } => {
-                        debug!("Received FML|HS RegistryData for {}", name);
-                        if name == "minecraft:blocks" {
-                            let mut mod_ids = self.world.modded_block_ids.load().as_ref().clone();
-                            for m in ids.data {
-                                mod_ids.insert(m.id.0 as usize, m.name);
-                            }
-                            self.world.modded_block_ids.store(Arc::new(mod_ids));
-                        }
-                        if !has_more {
-                            self.write_fmlhs_plugin_message(&HandshakeAck {
-                                phase: WaitingServerComplete,
-                            });
-                        }
+                }
+                self.world.modded_block_ids.store(Arc::new(mod_ids));
+                self.write_fmlhs_plugin_message(&HandshakeAck {
+                    phase: WaitingServerComplete,
+                });
+            }
+            RegistryData {
+                has_more,
+                name,
+                ids,
+                substitutions: _,
+                dummies: _,
+            } => {
+                debug!("Received FML|HS RegistryData for {}", name);
+                if name == "minecraft:blocks" {
+                    let mut mod_ids = self.world.modded_block_ids.load().as_ref().clone();
+                    for m in ids.data {
+                        mod_ids.insert(m.id.0 as usize, m.name);
                     }
-                    HandshakeAck { phase } => match phase {
-                        WaitingCAck => {
-                            self.write_fmlhs_plugin_message(&HandshakeAck {
-                                phase: PendingComplete,
-                            });
-                        }
-                        Complete => {
-                            debug!("FML|HS handshake complete!");
-                        }
-                        _ => unimplemented!(),
-                    },
-                    _ => (),
+                    self.world.modded_block_ids.store(Arc::new(mod_ids));
+                }
+                if !has_more {
+                    self.write_fmlhs_plugin_message(&HandshakeAck {
+                        phase: WaitingServerComplete,
+                    });
                 }
             }
+            HandshakeAck { phase } => match phase {
+                WaitingCAck => {
+                    self.write_fmlhs_plugin_message(&HandshakeAck {
+                        phase: PendingComplete,
+                    });
+                }
+                Complete => {
+                    debug!("FML|HS handshake complete!");
+                }
+                _ => unimplemented!(),
+            },
             _ => (),
         }
     }
@@ -1735,6 +2388,15 @@ impl Server {
             .on_confirm_transaction(id, action_number, accepted);
     }
 
+    fn on_window_state_id(&self, id: i32, state_id: i32) {
+        if id < 0 {
+            return;
+        }
+        self.inventory_context
+            .write()
+            .set_state_id(id, state_id as i16);
+    }
+
     #[allow(unused_must_use)]
     fn on_game_join(&self, gamemode: u8, entity_id: i32) {
         let gamemode = GameMode::from_int((gamemode & 0x7) as i32);
@@ -1780,6 +2442,14 @@ impl Server {
             brand: "leafish".into(),
         };
         brand.write_to(self.conn.write().as_mut().unwrap());
+        // and which plugin channels we listen on
+        let register = self.channels.read().register_payload(self.protocol_version);
+        if let Some(register) = register {
+            self.write_plugin_message(
+                plugin_messages::channel_for_version("minecraft:register", self.protocol_version),
+                &register,
+            );
+        }
         /*packet::send_position_look(
             self.conn.write().as_mut().unwrap(),
             &Vector3::new(0.0, 0.0, 0.0),
@@ -1843,6 +2513,22 @@ impl Server {
         }
         self.entity_map.write().insert(entity_id, local_player);
 
+        // Since 1.19 only the name of the dimension type is sent on respawn
+        let dimension_tag = dimension_tag.or_else(|| {
+            let codec = self.dimension_codec.load();
+            codec.as_ref().and_then(|codec| {
+                dimension_name
+                    .as_ref()
+                    .and_then(|name| mapped_packet::dimension_type_from_codec(codec, name))
+            })
+        });
+
+        let height = dimension_tag
+            .as_ref()
+            .and_then(world::WorldHeight::from_tag)
+            .unwrap_or_default();
+        self.world.set_height(height);
+
         let dimension = dimension
             .map(world::Dimension::from_index)
             .or_else(|| dimension_name.map(|d| world::Dimension::from_name(&d)))
@@ -1994,6 +2680,113 @@ impl Server {
         }
     }
 
+    fn on_boss_bar(&self, boss_bar: mapped_packet::play::clientbound::BossBar) {
+        let mut boss_bars = self.boss_bars.write();
+        let uuid = &boss_bar.uuid;
+        match boss_bar.action {
+            0 => boss_bars.add(
+                boss_bar.uuid.clone(),
+                boss_bar.title,
+                boss_bar.health,
+                boss_bar.color,
+                boss_bar.style,
+                boss_bar.flags,
+            ),
+            1 => boss_bars.remove(uuid),
+            2 => boss_bars.set_health(uuid, boss_bar.health),
+            3 => boss_bars.set_title(uuid, boss_bar.title),
+            4 => boss_bars.set_style(uuid, boss_bar.color, boss_bar.style),
+            5 => boss_bars.set_flags(uuid, boss_bar.flags),
+            action => warn!("Unknown boss bar action {}", action),
+        }
+    }
+
+    fn on_scoreboard_objective(
+        &self,
+        objective: mapped_packet::play::clientbound::ScoreboardObjective,
+    ) {
+        // 1.7 sends the mode where later versions send the type
+        let mode = objective.mode.or(objective.ty).unwrap_or(0);
+        let mut scoreboard = self.scoreboard.write();
+        match mode {
+            1 => scoreboard.remove_objective(&objective.name),
+            _ => scoreboard.set_objective(objective.name, &objective.value),
+        }
+    }
+
+    fn on_update_score(&self, score: mapped_packet::play::clientbound::UpdateScore) {
+        let mut scoreboard = self.scoreboard.write();
+        if score.action == 1 {
+            let objective = Some(score.object_name.as_str()).filter(|name| !name.is_empty());
+            scoreboard.remove_score(&score.name, objective);
+        } else {
+            scoreboard.set_score(score.name, score.object_name, score.value.unwrap_or(0));
+        }
+    }
+
+    fn on_scoreboard_display(&self, display: mapped_packet::play::clientbound::ScoreboardDisplay) {
+        self.scoreboard
+            .write()
+            .set_display(display.position, display.name);
+    }
+
+    fn on_teams(&self, teams: mapped_packet::play::clientbound::Teams) {
+        let members = teams.players.unwrap_or_default();
+        let info = scoreboard::TeamInfo {
+            prefix: teams.prefix.unwrap_or_default(),
+            suffix: teams.suffix.unwrap_or_default(),
+            name_tag_visibility: teams.name_tag_visibility,
+            colour: teams
+                .formatting
+                .or(teams.color.map(i32::from))
+                .unwrap_or(-1),
+        };
+        let update = match teams.mode {
+            0 => scoreboard::TeamUpdate::Create(info, members),
+            1 => scoreboard::TeamUpdate::Remove,
+            2 => scoreboard::TeamUpdate::Update(info),
+            3 => scoreboard::TeamUpdate::AddMembers(members),
+            4 => scoreboard::TeamUpdate::RemoveMembers(members),
+            mode => {
+                warn!("Unknown team mode {} for {}", mode, teams.name);
+                return;
+            }
+        };
+        let changed = self.scoreboard.write().update_team(teams.name, update);
+        self.hud_context.write().update_player_list();
+
+        // Visibility depends on our own team too, so every tag may change with it
+        let own_name = self.own_name();
+        let everyone = own_name.map_or(false, |name| changed.contains(&name));
+        let mut entities = self.entities.write();
+        let mut query = entities.world.query::<(&PlayerName, &mut PlayerModel)>();
+        for (name, mut model) in query.iter_mut(&mut entities.world) {
+            if everyone || changed.contains(&name.0) {
+                let (display_name, visible) = self.name_tag(&name.0);
+                model.set_name_tag(display_name, visible);
+            }
+        }
+    }
+
+    /// The name of the player we play as
+    pub fn own_name(&self) -> Option<String> {
+        self.players
+            .read()
+            .get(&self.uuid)
+            .map(|info| info.name.clone())
+    }
+
+    /// The name above another player's head, and whether we see it
+    fn name_tag(&self, name: &str) -> (Component, bool) {
+        let own_name = self.own_name().unwrap_or_default();
+        let scoreboard = self.scoreboard.read();
+        let plain = Component::new(format::ComponentType::new(name, None));
+        (
+            scoreboard.decorate(name, &plain),
+            scoreboard.name_tag_visible(name, &own_name),
+        )
+    }
+
     fn on_player_spawn(
         &self,
         entity_id: i32,
@@ -2008,18 +2801,18 @@ impl Server {
         if let Some(entity) = self.entity_map.write().remove(&entity_id) {
             self.entities.write().world.despawn(entity);
         }
+        let name = self
+            .players
+            .read()
+            .get(&uuid)
+            .map(|v| v.name.clone())
+            .unwrap();
+        let (display_name, has_name_tag) = self.name_tag(&name);
         let world_entity = entity::player::create_remote(
             &mut self.entities.write(),
-            self.players
-                .read()
-                .get(&uuid)
-                .map(|v| {
-                    v.display_name
-                        .clone()
-                        .unwrap_or_else(|| Component::from_str(v.name.as_str()))
-                })
-                .unwrap()
-                .clone(),
+            name,
+            display_name,
+            has_name_tag,
         );
         let mut entities = self.entities.write();
         let mut entity = entities.world.entity_mut(world_entity);
@@ -2313,6 +3106,30 @@ impl Server {
                 }
             }
         }
+        self.hud_context.write().update_player_list();
+    }
+
+    /// The players in the order the player list shows them, spectators last
+    pub fn player_list(&self) -> Vec<PlayerListEntry> {
+        let players = self.players.read();
+        let scoreboard = self.scoreboard.read();
+        let mut players: Vec<&PlayerInfo> = players.values().collect();
+        sort_player_list(&mut players, |name| scoreboard.team_name_of(name));
+        players
+            .into_iter()
+            .map(|info| {
+                let name = info.display_name.clone().unwrap_or_else(|| {
+                    let plain = Component::new(format::ComponentType::new(&info.name, None));
+                    scoreboard.decorate(&info.name, &plain)
+                });
+                PlayerListEntry {
+                    name,
+                    ping: info.ping,
+                    gamemode: info.gamemode,
+                    skin_url: info.skin_url.clone(),
+                }
+            })
+            .collect()
     }
 
     fn on_servermessage(&self, message: mapped_packet::play::clientbound::ServerMessage) {
@@ -2336,6 +3153,117 @@ impl Server {
         }
     }
 
+    fn on_player_chat(&self, message: mapped_packet::play::clientbound::PlayerChatMessage) {
+        let codec = self.dimension_codec.load();
+        let chat_type = codec.as_ref().and_then(|codec| {
+            codec
+                .1
+                .get("minecraft:chat_type")?
+                .get("value")?
+                .as_list()?
+                .iter()
+                .find(|entry| {
+                    entry.get("id").and_then(|id| id.as_int()) == Some(message.message_type)
+                })?
+                .get("element")
+        });
+        let (text, position) = match chat_type {
+            Some(chat_type) => {
+                let (decoration, position) = match chat_type.get("chat") {
+                    Some(chat) => (chat.get("decoration"), 0),
+                    // Chat types without a chat section are only displayed in the action bar
+                    None => (
+                        chat_type
+                            .get("overlay")
+                            .and_then(|overlay| overlay.get("decoration")),
+                        2,
+                    ),
+                };
+                let text = match decoration {
+                    Some(decoration) => {
+                        let parameters = decoration
+                            .get("parameters")
+                            .and_then(|parameters| parameters.as_list())
+                            .unwrap_or_default()
+                            .iter()
+                            .filter_map(|parameter| parameter.as_str())
+                            .collect::<Vec<_>>();
+                        decorate_chat(
+                            decoration
+                                .get("translation_key")
+                                .and_then(|key| key.as_str())
+                                .unwrap_or("chat.type.text"),
+                            &parameters,
+                            decoration.get("style"),
+                            &message,
+                        )
+                    }
+                    // Undecorated chat types show just the message
+                    None => message.content.clone(),
+                };
+                (text, position)
+            }
+            None => (
+                decorate_chat("chat.type.text", &["sender", "content"], None, &message),
+                0,
+            ),
+        };
+
+        self.on_servermessage(mapped_packet::play::clientbound::ServerMessage {
+            message: text,
+            position: Some(position),
+            sender: Some(message.sender),
+        });
+    }
+
+    /// Writes the traffic counters of the connection to the log, and with
+    /// that to the console.
+    pub fn dump_network_stats(&self) {
+        let dump = self.network_stats.lock().unwrap().dump();
+        for line in dump.lines() {
+            info!("{}", line);
+        }
+    }
+
+    /// Sends a chat message or command, signing it if the account has a
+    /// profile key and the server speaks 1.19.
+    pub fn send_chat(&self, message: String) {
+        let salt = rand::thread_rng().gen();
+        let mut conn = self.conn.write();
+        if let Some(conn) = conn.as_mut() {
+            let result = packet::send_chat_message(
+                conn,
+                message,
+                &self.uuid,
+                self.profile_key.as_ref(),
+                salt,
+            );
+            if result.is_ok() {
+                return;
+            }
+        }
+        drop(conn);
+        self.disconnect_closed(None);
+    }
+
+    /// Asks the server for a styled preview of the message being typed,
+    /// only done if the server announced that it previews chat.
+    pub fn request_chat_preview(&self, message: &str) {
+        if !self.chat_preview_enabled.load(Ordering::Acquire) {
+            return;
+        }
+        // Bumping the query id also invalidates answers still in flight
+        let query_id = self.chat_preview_query.fetch_add(1, Ordering::AcqRel) + 1;
+        if message.is_empty() || message.starts_with('/') {
+            self.chat_ctx.set_preview(None);
+            return;
+        }
+        self.write_packet(packet::play::serverbound::ChatPreviewServerbound {
+            query_id,
+            message: message.to_owned(),
+        });
+    }
+
     fn load_block_entities(&self, block_entities: Vec<Option<crate::nbt::NamedTag>>) {
         for block_entity in block_entities.into_iter().flatten() {
             let x = block_entity.1.get("x").unwrap().as_int().unwrap();
@@ -2384,6 +3312,55 @@ impl Server {
         self.load_block_entities(chunk_data.block_entities);
     }
 
+    fn on_chunk_data_biomes3d_bitmasks(
+        &self,
+        chunk_data: mapped_packet::play::clientbound::ChunkData_Biomes3D_Bitmasks,
+        sky_light: bool,
+    ) {
+        let bitmask = chunk_data.bitmask.low_bits();
+        if bitmask > u16::MAX as u64 || chunk_data.bitmask.0.len() > 1 {
+            warn!(
+                "Chunk {},{} has sections outside of the supported world height",
+                chunk_data.chunk_x, chunk_data.chunk_z
+            );
+        }
+        self.world
+            .load_chunk115(
+                chunk_data.chunk_x,
+                chunk_data.chunk_z,
+                true,
+                sky_light,
+                bitmask as u16,
+                chunk_data.data,
+            )
+            .unwrap();
+        self.load_block_entities(chunk_data.block_entities);
+    }
+
+    fn on_chunk_data_and_light(
+        &self,
+        chunk_data: mapped_packet::play::clientbound::ChunkData_AndLight,
+    ) {
+        self.world.lighting_cache.write().insert(
+            CPos(chunk_data.chunk_x, chunk_data.chunk_z),
+            LightData {
+                arrays: Cursor::new(chunk_data.light_arrays),
+                block_light_mask: chunk_data.block_light_mask,
+                sky_light_mask: chunk_data.sky_light_mask,
+            },
+        );
+        self.world
+            .load_chunk118(chunk_data.chunk_x, chunk_data.chunk_z, chunk_data.data)
+            .unwrap();
+        for block_entity in chunk_data.block_entities {
+            // Block entities without data or an action are created from the
+            // block states alone
+            if block_entity.nbt.is_some() && block_entity.action != 0 {
+                self.on_block_entity_update(block_entity);
+            }
+        }
+    }
+
     fn on_chunk_data_biomes3d_bool(
         &self,
         chunk_data: mapped_packet::play::clientbound::ChunkData_Biomes3D_bool,
@@ -2685,3 +3662,91 @@ fn add_systems(sched: &mut Schedule) {
         .add_systems(tick_world.in_set(SystemExecStage::Normal))
         .add_systems(tick_time.in_set(SystemExecStage::Normal));
 }
+
+/// Builds the message of a 1.19 chat type: its translation with the parts of
+/// the message named by `parameters` as arguments, styled like the chat
+/// type's decoration.
+fn decorate_chat(
+    translation_key: &str,
+    parameters: &[&str],
+    style: Option<&crate::nbt::Tag>,
+    message: &mapped_packet::play::clientbound::PlayerChatMessage,
+) -> format::Component {
+    let with = parameters
+        .iter()
+        .map(|parameter| match *parameter {
+            "sender" => message.sender_display_name.to_value(),
+            "content" => message.content.to_value(),
+            "team_name" => message
+                .team_name
+                .as_ref()
+                .map_or_else(|| serde_json::json!(""), |team| team.to_value()),
+            _ => serde_json::json!(""),
+        })
+        .collect::<Vec<_>>();
+    let mut value = serde_json::json!({
+        "translate": translation_key,
+        "with": with,
+    });
+    for (key, tag) in style
+        .and_then(|style| style.as_compound())
+        .into_iter()
+        .flatten()
+    {
+        // Colours and fonts are strings, the formatting flags bytes
+        value[key.as_str()] = match (tag.as_str(), tag.as_byte()) {
+            (Some(text), _) => serde_json::json!(text),
+            (_, Some(flag)) => serde_json::json!(flag != 0),
+            _ => continue,
+        };
+    }
+    format::Component::from_json(&value).unwrap_or_default()
+}
+
+/// Sorts the players the way the player list shows them: spectators last,
+/// then by team and by name
+fn sort_player_list<'a>(players: &mut [&PlayerInfo], team_of: impl Fn(&str) -> Option<&'a str>) {
+    players.sort_by(|a, b| {
+        let team = |info: &PlayerInfo| team_of(&info.name).unwrap_or("");
+        (a.gamemode == GameMode::Spectator)
+            .cmp(&(b.gamemode == GameMode::Spectator))
+            .then_with(|| team(a).cmp(team(b)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(name: &str, gamemode: GameMode) -> PlayerInfo {
+        PlayerInfo {
+            name: name.to_owned(),
+            uuid: protocol::UUID::default(),
+            skin_url: None,
+            display_name: None,
+            ping: 0,
+            gamemode,
+        }
+    }
+
+    #[test]
+    fn player_list_order() {
+        let players = [
+            player("dave", GameMode::Spectator),
+            player("carol", GameMode::Survival),
+            player("bob", GameMode::Creative),
+            player("alice", GameMode::Spectator),
+            player("erin", GameMode::Survival),
+            player("frank", GameMode::Adventure),
+        ];
+        let mut list: Vec<&PlayerInfo> = players.iter().collect();
+        sort_player_list(&mut list, |name| match name {
+            "carol" | "dave" => Some("blue"),
+            "frank" => Some("red"),
+            _ => None,
+        });
+        let names: Vec<&str> = list.iter().map(|info| info.name.as_str()).collect();
+        assert_eq!(names, ["bob", "erin", "carol", "frank", "alice", "dave"]);
+    }
+}
@@ -2,6 +2,142 @@ use crate::protocol::packet::play::serverbound::PluginMessageServerbound;
 use crate::protocol::packet::play::serverbound::PluginMessageServerbound_i16;
 use crate::protocol::{Serializable, VarShort};
 use leafish_protocol::protocol::Conn;
+use log::{debug, info};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::Server;
+
+/// Plugin channels were renamed to namespaced identifiers in 1.13
+const NAMESPACED_CHANNELS_VERSION: i32 = 393;
+
+/// Channels which had a different name before 1.13, as (legacy, namespaced)
+const LEGACY_CHANNELS: [(&str, &str); 5] = [
+    ("MC|Brand", "minecraft:brand"),
+    ("REGISTER", "minecraft:register"),
+    ("UNREGISTER", "minecraft:unregister"),
+    ("BungeeCord", "bungeecord:main"),
+    ("WECUI", "worldedit:cui"),
+];
+
+/// The name the registry knows a channel by, whichever naming scheme the
+/// server uses. Legacy channels without a namespaced name are kept as is.
+pub fn normalize_channel(channel: &str) -> &str {
+    LEGACY_CHANNELS
+        .iter()
+        .find(|(legacy, _)| *legacy == channel)
+        .map_or(channel, |(_, namespaced)| namespaced)
+}
+
+/// The name of a channel on the wire for the protocol version
+pub fn channel_for_version(channel: &str, protocol_version: i32) -> &str {
+    if protocol_version >= NAMESPACED_CHANNELS_VERSION {
+        return normalize_channel(channel);
+    }
+    LEGACY_CHANNELS
+        .iter()
+        .find(|(_, namespaced)| *namespaced == channel)
+        .map_or(channel, |(legacy, _)| legacy)
+}
+
+/// Called with the payload of every message received on a channel
+pub type ChannelHandler = Arc<dyn Fn(&Server, &[u8]) + Send + Sync>;
+
+/// Keeps track of the channels the server listens on and of the handlers
+/// for the channels we listen on.
+#[derive(Default)]
+pub struct ChannelRegistry {
+    server_channels: HashSet<String>,
+    handlers: HashMap<String, ChannelHandler>,
+}
+
+impl ChannelRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self::default();
+        registry.register_handler(
+            "minecraft:brand",
+            Arc::new(|_, data| {
+                let mut data = data;
+                if let Ok(brand) = <String as Serializable>::read_from(&mut data) {
+                    info!("Server brand: {}", brand);
+                }
+            }),
+        );
+        registry
+    }
+
+    /// Makes messages on the channel go to the handler, replacing the
+    /// previous one. Either naming scheme may be used for the channel.
+    pub fn register_handler(&mut self, channel: &str, handler: ChannelHandler) {
+        self.handlers
+            .insert(normalize_channel(channel).to_owned(), handler);
+    }
+
+    pub fn unregister_handler(&mut self, channel: &str) {
+        self.handlers.remove(normalize_channel(channel));
+    }
+
+    pub fn handler(&self, channel: &str) -> Option<ChannelHandler> {
+        self.handlers.get(normalize_channel(channel)).cloned()
+    }
+
+    /// Whether the server registered the channel, so messages sent on it
+    /// aren't dropped
+    pub fn server_listens(&self, channel: &str) -> bool {
+        self.server_channels.contains(normalize_channel(channel))
+    }
+
+    /// Handles the payload of a `REGISTER` message
+    pub fn on_register(&mut self, data: &[u8]) {
+        for channel in split_channels(data) {
+            debug!("Server registered plugin channel {}", channel);
+            self.server_channels
+                .insert(normalize_channel(channel).to_owned());
+        }
+    }
+
+    /// Handles the payload of an `UNREGISTER` message
+    pub fn on_unregister(&mut self, data: &[u8]) {
+        for channel in split_channels(data) {
+            self.server_channels.remove(normalize_channel(channel));
+        }
+    }
+
+    /// The name a channel we listen on is advertised by in `REGISTER`
+    /// messages. Vanilla channels are always understood, so they aren't
+    /// advertised, and neither are legacy channels the version can't name.
+    pub fn advertised_name(channel: &str, protocol_version: i32) -> Option<&str> {
+        if normalize_channel(channel).starts_with("minecraft:") {
+            return None;
+        }
+        let name = channel_for_version(channel, protocol_version);
+        if protocol_version >= NAMESPACED_CHANNELS_VERSION && !name.contains(':') {
+            return None;
+        }
+        Some(name)
+    }
+
+    /// The payload of the `REGISTER` message advertising our channels
+    pub fn register_payload(&self, protocol_version: i32) -> Option<Vec<u8>> {
+        let mut channels: Vec<_> = self
+            .handlers
+            .keys()
+            .filter_map(|channel| Self::advertised_name(channel, protocol_version))
+            .collect();
+        if channels.is_empty() {
+            return None;
+        }
+        channels.sort_unstable();
+        Some(channels.join("\0").into_bytes())
+    }
+}
+
+/// Channel lists are separated by NUL bytes
+fn split_channels(data: &[u8]) -> impl Iterator<Item = &str> {
+    data.split(|b| *b == 0)
+        .filter_map(|channel| std::str::from_utf8(channel).ok())
+        .filter(|channel| !channel.is_empty())
+}
 
 pub struct Brand {
     pub brand: String,
@@ -14,13 +150,8 @@ impl Brand {
         let mut data = vec![];
         Serializable::write_to(&self.brand, &mut data).unwrap();
         if protocol_version >= 47 {
-            let channel_name = if protocol_version >= 404 {
-                "minecraft:brand"
-            } else {
-                "MC|Brand"
-            };
             let packet = PluginMessageServerbound {
-                channel: channel_name.into(),
+                channel: channel_for_version("minecraft:brand", protocol_version).into(),
                 data,
             };
             conn.write_packet(packet).unwrap();
@@ -33,3 +164,67 @@ impl Brand {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_names_per_version() {
+        assert_eq!(normalize_channel("MC|Brand"), "minecraft:brand");
+        assert_eq!(normalize_channel("minecraft:brand"), "minecraft:brand");
+        assert_eq!(normalize_channel("FML|HS"), "FML|HS");
+
+        assert_eq!(channel_for_version("minecraft:brand", 340), "MC|Brand");
+        assert_eq!(channel_for_version("MC|Brand", 340), "MC|Brand");
+        assert_eq!(channel_for_version("BungeeCord", 393), "bungeecord:main");
+        assert_eq!(channel_for_version("worldedit:cui", 393), "worldedit:cui");
+        assert_eq!(channel_for_version("FML|HS", 340), "FML|HS");
+    }
+
+    #[test]
+    fn advertised_names() {
+        let name = ChannelRegistry::advertised_name;
+        assert_eq!(name("minecraft:brand", 759), None);
+        assert_eq!(name("MC|Brand", 340), None);
+        assert_eq!(name("FML|HS", 340), Some("FML|HS"));
+        assert_eq!(name("FML|HS", 393), None);
+        assert_eq!(name("WECUI", 393), Some("worldedit:cui"));
+        assert_eq!(name("worldedit:cui", 340), Some("WECUI"));
+    }
+
+    #[test]
+    fn split_channel_lists() {
+        let channels: Vec<_> = split_channels(b"a:b\0\0WECUI\0\xff\0c:d\0").collect();
+        assert_eq!(channels, ["a:b", "WECUI", "c:d"]);
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = ChannelRegistry::new();
+        assert!(registry.handler("MC|Brand").is_some());
+        assert_eq!(registry.register_payload(759), None);
+
+        registry.register_handler("WECUI", Arc::new(|_, _| {}));
+        registry.register_handler("FML|HS", Arc::new(|_, _| {}));
+        assert!(registry.handler("worldedit:cui").is_some());
+        assert_eq!(
+            registry.register_payload(340),
+            Some(b"FML|HS\0WECUI".to_vec())
+        );
+        assert_eq!(
+            registry.register_payload(393),
+            Some(b"worldedit:cui".to_vec())
+        );
+        registry.unregister_handler("worldedit:cui");
+        assert!(registry.handler("WECUI").is_none());
+        assert_eq!(registry.register_payload(393), None);
+
+        registry.on_register(b"WECUI\0bungeecord:main");
+        assert!(registry.server_listens("worldedit:cui"));
+        assert!(registry.server_listens("BungeeCord"));
+        registry.on_unregister(b"worldedit:cui");
+        assert!(!registry.server_listens("WECUI"));
+        assert!(registry.server_listens("bungeecord:main"));
+    }
+}
@@ -0,0 +1,407 @@
+//! Objectives, scores and teams as the server sends them.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::format::{Color, Component, ComponentType, Modifier};
+
+/// Texts are json components from 1.13, legacy strings before
+const JSON_TEXT_VERSION: i32 = 393;
+
+/// The sidebar shows at most this many scores
+pub const SIDEBAR_LINES: usize = 15;
+
+/// Display slots, from 1.8 the team sidebars follow for every colour
+const SIDEBAR_SLOT: u8 = 1;
+const TEAM_SIDEBAR_SLOTS: u8 = 3;
+
+/// Colours by their legacy formatting code
+const COLOURS: [Color; 16] = [
+    Color::Black,
+    Color::DarkBlue,
+    Color::DarkGreen,
+    Color::DarkAqua,
+    Color::DarkRed,
+    Color::DarkPurple,
+    Color::Gold,
+    Color::Gray,
+    Color::DarkGray,
+    Color::Blue,
+    Color::Green,
+    Color::Aqua,
+    Color::Red,
+    Color::LightPurple,
+    Color::Yellow,
+    Color::White,
+];
+
+#[derive(Clone, Debug)]
+pub struct Objective {
+    pub name: String,
+    pub display_name: Component,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameTagVisibility {
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+impl NameTagVisibility {
+    fn from_name(name: &str) -> NameTagVisibility {
+        match name {
+            "never" => NameTagVisibility::Never,
+            "hideForOtherTeams" => NameTagVisibility::HideForOtherTeams,
+            "hideForOwnTeam" => NameTagVisibility::HideForOwnTeam,
+            _ => NameTagVisibility::Always,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Team {
+    pub prefix: Component,
+    pub suffix: Component,
+    pub colour: Option<Color>,
+    pub name_tag_visibility: NameTagVisibility,
+    pub members: HashSet<String>,
+}
+
+/// What a `Teams` packet changes
+pub enum TeamUpdate {
+    Create(TeamInfo, Vec<String>),
+    Remove,
+    Update(TeamInfo),
+    AddMembers(Vec<String>),
+    RemoveMembers(Vec<String>),
+}
+
+pub struct TeamInfo {
+    pub prefix: String,
+    pub suffix: String,
+    pub name_tag_visibility: Option<String>,
+    /// The formatting code, -1 or out of range for none
+    pub colour: i32,
+}
+
+/// A scoreboard to show on the right of the screen
+pub struct Sidebar {
+    pub title: Component,
+    /// The entries with the highest scores, highest first
+    pub lines: Vec<(Component, i32)>,
+}
+
+#[derive(Default)]
+pub struct Scoreboard {
+    json_text: bool,
+    objectives: HashMap<String, Objective>,
+    /// Scores of every objective by entry
+    scores: HashMap<String, HashMap<String, i32>>,
+    display: HashMap<u8, String>,
+    teams: HashMap<String, Team>,
+    /// The team of every entry in one
+    entry_teams: HashMap<String, String>,
+    /// Bumped on every change, for redrawing
+    version: usize,
+}
+
+impl Scoreboard {
+    pub fn new(protocol_version: i32) -> Scoreboard {
+        Scoreboard {
+            json_text: protocol_version >= JSON_TEXT_VERSION,
+            ..Scoreboard::default()
+        }
+    }
+
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    fn text(&self, text: &str) -> Component {
+        if self.json_text {
+            Component::from_str(text)
+        } else {
+            Component::from_legacy_str(text, &Modifier::default())
+        }
+    }
+
+    pub fn set_objective(&mut self, name: String, display_name: &str) {
+        let objective = Objective {
+            name: name.clone(),
+            display_name: self.text(display_name),
+        };
+        self.objectives.insert(name, objective);
+        self.version += 1;
+    }
+
+    pub fn remove_objective(&mut self, name: &str) {
+        self.objectives.remove(name);
+        self.scores.remove(name);
+        self.display.retain(|_, objective| objective != name);
+        self.version += 1;
+    }
+
+    pub fn set_score(&mut self, entry: String, objective: String, value: i32) {
+        self.scores
+            .entry(objective)
+            .or_default()
+            .insert(entry, value);
+        self.version += 1;
+    }
+
+    /// Removes the score of an entry in the objective, or in all of them
+    /// when there's none
+    pub fn remove_score(&mut self, entry: &str, objective: Option<&str>) {
+        match objective {
+            Some(objective) => {
+                if let Some(scores) = self.scores.get_mut(objective) {
+                    scores.remove(entry);
+                }
+            }
+            None => {
+                for scores in self.scores.values_mut() {
+                    scores.remove(entry);
+                }
+            }
+        }
+        self.version += 1;
+    }
+
+    /// Shows the objective in the slot, an empty name clears it
+    pub fn set_display(&mut self, slot: u8, objective: String) {
+        if objective.is_empty() {
+            self.display.remove(&slot);
+        } else {
+            self.display.insert(slot, objective);
+        }
+        self.version += 1;
+    }
+
+    fn displayed(&self, slot: u8) -> Option<&Objective> {
+        self.objectives.get(self.display.get(&slot)?)
+    }
+
+    /// Returns the entries whose name tag or list entry changed
+    pub fn update_team(&mut self, name: String, update: TeamUpdate) -> Vec<String> {
+        self.version += 1;
+        match update {
+            TeamUpdate::Create(info, members) => {
+                let team = Team {
+                    prefix: Component::default(),
+                    suffix: Component::default(),
+                    colour: None,
+                    name_tag_visibility: NameTagVisibility::Always,
+                    members: HashSet::new(),
+                };
+                self.teams.insert(name.clone(), team);
+                self.set_team_info(&name, info);
+                self.add_members(&name, &members);
+                members
+            }
+            TeamUpdate::Remove => match self.teams.remove(&name) {
+                Some(team) => {
+                    for member in &team.members {
+                        self.entry_teams.remove(member);
+                    }
+                    team.members.into_iter().collect()
+                }
+                None => vec![],
+            },
+            TeamUpdate::Update(info) => {
+                self.set_team_info(&name, info);
+                self.teams
+                    .get(&name)
+                    .map_or_else(Vec::new, |team| team.members.iter().cloned().collect())
+            }
+            TeamUpdate::AddMembers(members) => {
+                self.add_members(&name, &members);
+                members
+            }
+            TeamUpdate::RemoveMembers(members) => {
+                if let Some(team) = self.teams.get_mut(&name) {
+                    for member in &members {
+                        team.members.remove(member);
+                        if self.entry_teams.get(member) == Some(&name) {
+                            self.entry_teams.remove(member);
+                        }
+                    }
+                }
+                members
+            }
+        }
+    }
+
+    fn set_team_info(&mut self, name: &str, info: TeamInfo) {
+        let prefix = self.text(&info.prefix);
+        let suffix = self.text(&info.suffix);
+        if let Some(team) = self.teams.get_mut(name) {
+            team.prefix = prefix;
+            team.suffix = suffix;
+            team.colour = usize::try_from(info.colour)
+                .ok()
+                .and_then(|colour| COLOURS.get(colour))
+                .copied();
+            if let Some(visibility) = info.name_tag_visibility {
+                team.name_tag_visibility = NameTagVisibility::from_name(&visibility);
+            }
+        }
+    }
+
+    fn add_members(&mut self, name: &str, members: &[String]) {
+        for member in members {
+            // An entry is in one team at most
+            if let Some(old) = self.entry_teams.insert(member.clone(), name.to_owned()) {
+                if let Some(team) = self.teams.get_mut(&old) {
+                    team.members.remove(member);
+                }
+            }
+            if let Some(team) = self.teams.get_mut(name) {
+                team.members.insert(member.clone());
+            }
+        }
+    }
+
+    pub fn team_of(&self, entry: &str) -> Option<&Team> {
+        self.teams.get(self.entry_teams.get(entry)?)
+    }
+
+    pub fn team_name_of(&self, entry: &str) -> Option<&str> {
+        self.entry_teams.get(entry).map(String::as_str)
+    }
+
+    /// The name of an entry with its team's prefix, colour and suffix
+    pub fn decorate(&self, entry: &str, name: &Component) -> Component {
+        let team = match self.team_of(entry) {
+            Some(team) => team,
+            None => return name.clone(),
+        };
+        // Legacy prefixes usually end in a formatting code meant for the name
+        let carried = if self.json_text {
+            None
+        } else {
+            team.prefix
+                .list
+                .last()
+                .map(|prefix| prefix.get_modifier().color)
+        };
+        let colour = team
+            .colour
+            .or(carried)
+            .filter(|colour| *colour != Color::None);
+        let mut list = team.prefix.list.clone();
+        list.extend(name.list.iter().map(|component| {
+            let mut modifier = component.get_modifier().clone();
+            if let Some(colour) = colour {
+                modifier.color = modifier.color.use_or_def(colour);
+            }
+            ComponentType::Text {
+                text: component.get_text().to_owned(),
+                modifier,
+            }
+        }));
+        list.extend(team.suffix.list.iter().cloned());
+        Component { list, source: None }
+    }
+
+    /// Whether `viewer` sees the name tag of `entry`
+    pub fn name_tag_visible(&self, entry: &str, viewer: &str) -> bool {
+        let team = match self.team_of(entry) {
+            Some(team) => team,
+            None => return true,
+        };
+        let same_team = team.members.contains(viewer);
+        match team.name_tag_visibility {
+            NameTagVisibility::Always => true,
+            NameTagVisibility::Never => false,
+            NameTagVisibility::HideForOtherTeams => same_team,
+            NameTagVisibility::HideForOwnTeam => !same_team,
+        }
+    }
+
+    /// The sidebar `viewer` sees, which prefers the one of their team's colour
+    pub fn sidebar(&self, viewer: &str) -> Option<Sidebar> {
+        let team_colour = self
+            .team_of(viewer)
+            .and_then(|team| team.colour)
+            .and_then(|colour| COLOURS.iter().position(|c| *c == colour));
+        let objective = team_colour
+            .and_then(|colour| self.displayed(TEAM_SIDEBAR_SLOTS + colour as u8))
+            .or_else(|| self.displayed(SIDEBAR_SLOT))?;
+
+        let mut scores: Vec<(&String, i32)> = self
+            .scores
+            .get(&objective.name)
+            .into_iter()
+            .flatten()
+            // Entries starting with # are hidden on purpose
+            .filter(|(entry, _)| !entry.starts_with('#'))
+            .map(|(entry, score)| (entry, *score))
+            .collect();
+        scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        scores.truncate(SIDEBAR_LINES);
+        Some(Sidebar {
+            title: objective.display_name.clone(),
+            lines: scores
+                .into_iter()
+                .map(|(entry, score)| {
+                    let name = Component::new(ComponentType::new(entry, None));
+                    (self.decorate(entry, &name), score)
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(prefix: &str, colour: i32) -> TeamInfo {
+        TeamInfo {
+            prefix: prefix.to_owned(),
+            suffix: String::new(),
+            name_tag_visibility: Some("hideForOtherTeams".to_owned()),
+            colour,
+        }
+    }
+
+    #[test]
+    fn sidebar_sorts_and_decorates() {
+        let mut scoreboard = Scoreboard::new(340);
+        scoreboard.set_objective("kills".to_owned(), "§eKills");
+        scoreboard.set_display(1, "kills".to_owned());
+        scoreboard.set_score("alice".to_owned(), "kills".to_owned(), 3);
+        scoreboard.set_score("bob".to_owned(), "kills".to_owned(), 7);
+        scoreboard.set_score("#hidden".to_owned(), "kills".to_owned(), 9);
+        scoreboard.update_team(
+            "red".to_owned(),
+            TeamUpdate::Create(info("§c[R] ", 12), vec!["alice".to_owned()]),
+        );
+
+        let sidebar = scoreboard.sidebar("bob").unwrap();
+        assert_eq!(sidebar.title.to_string(), "Kills");
+        let lines: Vec<_> = sidebar
+            .lines
+            .iter()
+            .map(|(name, score)| (name.to_string(), *score))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![("bob".to_owned(), 7), ("[R] alice".to_owned(), 3)]
+        );
+        assert!(scoreboard.sidebar("bob").unwrap().lines[1]
+            .0
+            .list
+            .iter()
+            .filter(|part| !part.get_text().is_empty())
+            .all(|part| part.get_modifier().color == Color::Red));
+
+        assert!(!scoreboard.name_tag_visible("alice", "bob"));
+        scoreboard.update_team("red".to_owned(), TeamUpdate::Remove);
+        assert!(scoreboard.name_tag_visible("alice", "bob"));
+
+        scoreboard.remove_objective("kills");
+        assert!(scoreboard.sidebar("bob").is_none());
+    }
+}
@@ -36,6 +36,15 @@ pub enum IntSetting {
     MaxFps,
     FOV,
     MasterVolume,
+    MusicVolume,
+    RecordVolume,
+    WeatherVolume,
+    BlockVolume,
+    HostileVolume,
+    NeutralVolume,
+    PlayerVolume,
+    AmbientVolume,
+    VoiceVolume,
     DefaultProtocolVersion,
 }
 
@@ -49,6 +58,7 @@ pub enum BoolSetting {
     RightPantsVisible,
     LeftPantsVisible,
     HatVisible,
+    AutoReconnect,
 }
 
 #[derive(PartialEq, PartialOrd, Hash, Eq, Ord, Clone, Copy)]
@@ -60,8 +70,10 @@ pub enum FloatSetting {
 pub enum StringSetting {
     AuthClientToken, // TODO: get rid of this as this is a dead, unused setting
     BackgroundImage,
+    Language,
     LogLevelFile,
     LogLevelTerm,
+    Proxy,
 }
 
 #[rustfmt::skip]
@@ -92,6 +92,14 @@ pub fn create_keybinds() -> Vec<(Key, Keybind)> {
                 action: Actionkey::ToggleDebug,
             },
         ),
+        (
+            Key::Named(NamedKey::F4),
+            Keybind {
+                name: "keybind_dump_network_stats",
+                description: "Keybinding for writing the network statistics to the console",
+                action: Actionkey::DumpNetworkStats,
+            },
+        ),
         (
             Key::Character(SmolStr::new_inline("t")),
             Keybind {
@@ -100,6 +108,14 @@ pub fn create_keybinds() -> Vec<(Key, Keybind)> {
                 action: Actionkey::ToggleChat,
             },
         ),
+        (
+            Key::Named(NamedKey::Tab),
+            Keybind {
+                name: "keybind_player_list",
+                description: "Keybinding for showing the player list while held",
+                action: Actionkey::PlayerList,
+            },
+        ),
         (
             Key::Character(SmolStr::new_inline("1")),
             Keybind {
@@ -55,6 +55,19 @@ impl KeybindStore {
         None
     }
 
+    /// The name of the key bound to a vanilla key binding like `key.jump`,
+    /// for the keybind components of chat messages
+    pub fn key_name(&self, binding: &str) -> Option<String> {
+        let action = Actionkey::from_vanilla(binding)?;
+        let cache = self.key_cache.load();
+        let (key, _) = cache.iter().find(|(_, v)| v.action == action)?;
+        match key {
+            Key::Character(text) => Some(text.to_uppercase()),
+            Key::Named(named) => Some(format!("{:?}", named)),
+            _ => None,
+        }
+    }
+
     pub fn set(&self, key: Key, action: Actionkey) {
         // ensure that we ignore all modifiers when saving the key
         // as the key should always trigger the action no matter the modifiers
@@ -184,7 +197,9 @@ pub enum Actionkey {
     DropItem,
     ToggleHud,
     ToggleDebug,
+    DumpNetworkStats,
     ToggleChat,
+    PlayerList,
     Hotbar1,
     Hotbar2,
     Hotbar3,
@@ -211,7 +226,9 @@ impl FromStr for Actionkey {
             "keybind_drop_item" => Ok(Actionkey::DropItem),
             "keybind_toggle_hud" => Ok(Actionkey::ToggleHud),
             "keybind_toggle_debug_info" => Ok(Actionkey::ToggleDebug),
+            "keybind_dump_network_stats" => Ok(Actionkey::DumpNetworkStats),
             "keybind_toggle_chat" => Ok(Actionkey::ToggleChat),
+            "keybind_player_list" => Ok(Actionkey::PlayerList),
             "keybind_hotbar_1" => Ok(Actionkey::Hotbar1),
             "keybind_hotbar_2" => Ok(Actionkey::Hotbar2),
             "keybind_hotbar_3" => Ok(Actionkey::Hotbar3),
@@ -227,7 +244,7 @@ impl FromStr for Actionkey {
 }
 
 impl Actionkey {
-    const VALUES: [Actionkey; 21] = [
+    const VALUES: [Actionkey; 23] = [
         Actionkey::Forward,
         Actionkey::Backward,
         Actionkey::Left,
@@ -239,7 +256,9 @@ impl Actionkey {
         Actionkey::DropItem,
         Actionkey::ToggleHud,
         Actionkey::ToggleDebug,
+        Actionkey::DumpNetworkStats,
         Actionkey::ToggleChat,
+        Actionkey::PlayerList,
         Actionkey::Hotbar1,
         Actionkey::Hotbar2,
         Actionkey::Hotbar3,
@@ -251,6 +270,33 @@ impl Actionkey {
         Actionkey::Hotbar9,
     ];
 
+    /// The action of a vanilla key binding name
+    pub fn from_vanilla(binding: &str) -> Option<Actionkey> {
+        match binding {
+            "key.forward" => Some(Actionkey::Forward),
+            "key.back" => Some(Actionkey::Backward),
+            "key.left" => Some(Actionkey::Left),
+            "key.right" => Some(Actionkey::Right),
+            "key.inventory" => Some(Actionkey::OpenInv),
+            "key.sneak" => Some(Actionkey::Sneak),
+            "key.sprint" => Some(Actionkey::Sprint),
+            "key.jump" => Some(Actionkey::Jump),
+            "key.drop" => Some(Actionkey::DropItem),
+            "key.chat" => Some(Actionkey::ToggleChat),
+            "key.playerlist" => Some(Actionkey::PlayerList),
+            "key.hotbar.1" => Some(Actionkey::Hotbar1),
+            "key.hotbar.2" => Some(Actionkey::Hotbar2),
+            "key.hotbar.3" => Some(Actionkey::Hotbar3),
+            "key.hotbar.4" => Some(Actionkey::Hotbar4),
+            "key.hotbar.5" => Some(Actionkey::Hotbar5),
+            "key.hotbar.6" => Some(Actionkey::Hotbar6),
+            "key.hotbar.7" => Some(Actionkey::Hotbar7),
+            "key.hotbar.8" => Some(Actionkey::Hotbar8),
+            "key.hotbar.9" => Some(Actionkey::Hotbar9),
+            _ => None,
+        }
+    }
+
     pub fn values() -> &'static [Actionkey] {
         &Self::VALUES
     }
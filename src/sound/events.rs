@@ -0,0 +1,156 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use log::warn;
+use rand::Rng;
+use serde_json::Value;
+
+use crate::resources;
+
+/// Events may refer to other events, this stops reference loops
+const MAX_EVENT_DEPTH: usize = 8;
+
+/// One of the sounds an event picks from
+#[derive(Clone, Debug)]
+pub struct Sound {
+    /// `namespace:path` of the file below `sounds/`, or of an event
+    pub name: String,
+    pub volume: f32,
+    pub pitch: f32,
+    pub weight: u32,
+    pub is_event: bool,
+}
+
+impl Sound {
+    fn parse(value: &Value) -> Option<Sound> {
+        if let Some(name) = value.as_str() {
+            return Some(Sound {
+                name: with_namespace(name),
+                volume: 1.0,
+                pitch: 1.0,
+                weight: 1,
+                is_event: false,
+            });
+        }
+        let float = |name| value.get(name).and_then(Value::as_f64).unwrap_or(1.0) as f32;
+        Some(Sound {
+            name: with_namespace(value.get("name")?.as_str()?),
+            volume: float("volume"),
+            pitch: float("pitch"),
+            weight: value.get("weight").and_then(Value::as_u64).unwrap_or(1) as u32,
+            is_event: value.get("type").and_then(Value::as_str) == Some("event"),
+        })
+    }
+
+    /// The path of the sound's file in the resource packs
+    pub fn file(&self) -> (&str, String) {
+        let (namespace, path) = self.name.split_once(':').unwrap();
+        (namespace, format!("sounds/{}.ogg", path))
+    }
+}
+
+/// The sound events declared by the `sounds.json` of every resource pack
+#[derive(Default)]
+pub struct SoundEvents {
+    events: HashMap<String, Vec<Sound>>,
+}
+
+impl SoundEvents {
+    pub fn load(res: &resources::Manager) -> SoundEvents {
+        let mut events = SoundEvents::default();
+        // Packs are returned highest priority first, later ones add to or
+        // replace the events of earlier ones
+        for file in res.open_all("minecraft", "sounds.json").into_iter().rev() {
+            match serde_json::from_reader::<_, Value>(file) {
+                Ok(json) => events.add_pack(&json),
+                Err(err) => warn!("Invalid sounds.json: {}", err),
+            }
+        }
+        events
+    }
+
+    fn add_pack(&mut self, json: &Value) {
+        let json = match json.as_object() {
+            Some(json) => json,
+            None => return,
+        };
+        for (event, info) in json {
+            let sounds = info
+                .get("sounds")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Sound::parse);
+            let entry = self.events.entry(with_namespace(event)).or_default();
+            if info.get("replace").and_then(Value::as_bool) == Some(true) {
+                entry.clear();
+            }
+            entry.extend(sounds);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Picks one of the sounds of the event by weight, following references
+    /// to other events. Volume and pitch of the references are multiplied.
+    pub fn pick<R: Rng>(&self, event: &str, rng: &mut R) -> Option<Sound> {
+        let mut event = with_namespace(event);
+        let mut volume = 1.0;
+        let mut pitch = 1.0;
+        for _ in 0..MAX_EVENT_DEPTH {
+            let sounds = self.events.get(&event)?;
+            let total: u32 = sounds.iter().map(|sound| sound.weight).sum();
+            if total == 0 {
+                return None;
+            }
+            let mut pick = rng.gen_range(0..total);
+            let sound = sounds.iter().find(|sound| {
+                if pick < sound.weight {
+                    return true;
+                }
+                pick -= sound.weight;
+                false
+            })?;
+            volume *= sound.volume;
+            pitch *= sound.pitch;
+            if !sound.is_event {
+                return Some(Sound {
+                    volume,
+                    pitch,
+                    ..sound.clone()
+                });
+            }
+            event = sound.name.clone();
+        }
+        warn!("Sound event {} refers to too many other events", event);
+        None
+    }
+}
+
+/// Names without a namespace are in `minecraft`
+pub fn with_namespace(name: &str) -> String {
+    if name.contains(':') {
+        name.to_owned()
+    } else {
+        format!("minecraft:{}", name)
+    }
+}
@@ -0,0 +1,604 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Plays the sounds the server asks for. Events are looked up in the
+//! `sounds.json` of the resource packs, their OGG files are decoded once and
+//! mixed in software relative to the camera, then handed to an `Output`.
+//! Looking up and decoding happens on a loader thread, sounds start playing
+//! once they are ready.
+
+pub mod events;
+pub mod output;
+pub mod registry;
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+use std::thread;
+
+use cgmath::{InnerSpace, Vector3};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use lewton::inside_ogg::OggStreamReader;
+use log::{debug, info, warn};
+use parking_lot::RwLock;
+
+use self::events::SoundEvents;
+use self::output::Output;
+use crate::resources;
+use crate::settings::{IntSetting, SettingStore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Category {
+    Master,
+    Music,
+    Record,
+    Weather,
+    Block,
+    Hostile,
+    Neutral,
+    Player,
+    Ambient,
+    Voice,
+}
+
+impl Category {
+    /// In the order of their ids in sound packets
+    pub const ALL: [Category; 10] = [
+        Category::Master,
+        Category::Music,
+        Category::Record,
+        Category::Weather,
+        Category::Block,
+        Category::Hostile,
+        Category::Neutral,
+        Category::Player,
+        Category::Ambient,
+        Category::Voice,
+    ];
+
+    pub fn from_id(id: i32) -> Category {
+        Category::ALL
+            .get(id as usize)
+            .copied()
+            .unwrap_or(Category::Master)
+    }
+
+    pub fn volume_setting(self) -> IntSetting {
+        match self {
+            Category::Master => IntSetting::MasterVolume,
+            Category::Music => IntSetting::MusicVolume,
+            Category::Record => IntSetting::RecordVolume,
+            Category::Weather => IntSetting::WeatherVolume,
+            Category::Block => IntSetting::BlockVolume,
+            Category::Hostile => IntSetting::HostileVolume,
+            Category::Neutral => IntSetting::NeutralVolume,
+            Category::Player => IntSetting::PlayerVolume,
+            Category::Ambient => IntSetting::AmbientVolume,
+            Category::Voice => IntSetting::VoiceVolume,
+        }
+    }
+
+    pub fn translation_key(self) -> &'static str {
+        match self {
+            Category::Master => "soundCategory.master",
+            Category::Music => "soundCategory.music",
+            Category::Record => "soundCategory.record",
+            Category::Weather => "soundCategory.weather",
+            Category::Block => "soundCategory.block",
+            Category::Hostile => "soundCategory.hostile",
+            Category::Neutral => "soundCategory.neutral",
+            Category::Player => "soundCategory.player",
+            Category::Ambient => "soundCategory.ambient",
+            Category::Voice => "soundCategory.voice",
+        }
+    }
+}
+
+/// What the server asks of the sound manager
+#[derive(Debug)]
+pub enum Command {
+    Play {
+        event: String,
+        category: Category,
+        /// Sounds without a position play at the listener
+        position: Option<Vector3<f64>>,
+        volume: f32,
+        pitch: f32,
+    },
+    /// Stops the sounds matching both filters, `None` matches everything
+    Stop {
+        category: Option<Category>,
+        event: Option<String>,
+    },
+}
+
+/// A decoded sound file
+struct Clip {
+    /// Interleaved by channel
+    samples: Vec<f32>,
+    channels: usize,
+    sample_rate: u32,
+}
+
+impl Clip {
+    fn decode(data: Vec<u8>) -> Result<Clip, lewton::VorbisError> {
+        let mut reader = OggStreamReader::new(Cursor::new(data))?;
+        let channels = reader.ident_hdr.audio_channels as usize;
+        let sample_rate = reader.ident_hdr.audio_sample_rate;
+        let mut samples = vec![];
+        while let Some(packet) = reader.read_dec_packet_itl()? {
+            samples.extend(packet.into_iter().map(|s| s as f32 / 32768.0));
+        }
+        Ok(Clip {
+            samples,
+            channels: channels.max(1),
+            sample_rate,
+        })
+    }
+
+    /// The left and right sample at a fractional frame, interpolated
+    fn frame_at(&self, position: f64) -> Option<(f32, f32)> {
+        let frames = self.samples.len() / self.channels;
+        let index = position as usize;
+        if index + 1 >= frames {
+            return None;
+        }
+        let frac = (position - index as f64) as f32;
+        let sample = |frame: usize, channel: usize| {
+            self.samples[frame * self.channels + channel.min(self.channels - 1)]
+        };
+        let lerp = |channel| {
+            let a = sample(index, channel);
+            a + (sample(index + 1, channel) - a) * frac
+        };
+        Some((lerp(0), lerp(1)))
+    }
+}
+
+struct Source {
+    clip: Arc<Clip>,
+    event: String,
+    category: Category,
+    position: Option<Vector3<f64>>,
+    volume: f32,
+    pitch: f32,
+    /// In frames of the clip
+    cursor: f64,
+}
+
+impl Source {
+    /// Whether a stop command with the filters stops the source
+    fn stopped_by(&self, category: Option<Category>, event: Option<&str>) -> bool {
+        let category_matches = category.is_none() || category == Some(self.category);
+        let event_matches = event.is_none() || event == Some(&*self.event);
+        category_matches && event_matches
+    }
+
+    /// The gain of the left and right channel
+    fn gains(&self, listener: &Listener, volumes: &[f32; 10]) -> (f32, f32) {
+        let mut volume = self.volume.min(1.0) * volumes[Category::Master as usize];
+        if self.category != Category::Master {
+            volume *= volumes[self.category as usize];
+        }
+        let position = match self.position {
+            Some(position) => position,
+            None => return (volume, volume),
+        };
+        // Like vanilla, sounds fade out linearly and louder ones carry further
+        let offset = position - listener.position;
+        let distance = offset.magnitude();
+        let range = 16.0 * (self.volume as f64).max(1.0);
+        volume *= (1.0 - distance / range).max(0.0) as f32;
+        // Equal power panning by how far to the side the sound is
+        let side = if distance > 0.001 {
+            offset.dot(listener.right()) / distance
+        } else {
+            0.0
+        };
+        let angle = (side + 1.0) * PI / 4.0;
+        (volume * angle.cos() as f32, volume * angle.sin() as f32)
+    }
+
+    /// Adds the source to the samples, returns whether it is still playing
+    fn mix(&mut self, out: &mut [f32], output_rate: u32, gains: (f32, f32)) -> bool {
+        let positional = self.position.is_some();
+        let step = self.clip.sample_rate as f64 / output_rate as f64 * self.pitch as f64;
+        for frame in out.chunks_exact_mut(2) {
+            let (mut left, mut right) = match self.clip.frame_at(self.cursor) {
+                Some(samples) => samples,
+                None => return false,
+            };
+            if positional {
+                // Panning replaces whatever stereo the file had
+                left = (left + right) / 2.0;
+                right = left;
+            }
+            frame[0] += left * gains.0;
+            frame[1] += right * gains.1;
+            self.cursor += step;
+        }
+        true
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Listener {
+    position: Vector3<f64>,
+    yaw: f64,
+}
+
+impl Listener {
+    fn right(&self) -> Vector3<f64> {
+        Vector3::new(-self.yaw.cos(), 0.0, self.yaw.sin())
+    }
+}
+
+/// A sound to look up and decode on the loader thread
+struct LoadRequest {
+    res: Arc<RwLock<resources::Manager>>,
+    /// Numbers the requests, to tell which stop commands came after it
+    sequence: u64,
+    event: String,
+    category: Category,
+    position: Option<Vector3<f64>>,
+    volume: f32,
+    pitch: f32,
+}
+
+/// The events and decoded clips of the current resources, owned by the
+/// loader thread
+#[derive(Default)]
+struct Loader {
+    events: SoundEvents,
+    resource_version: Option<usize>,
+    /// `None` for files which are missing or failed to decode
+    clips: HashMap<String, Option<Arc<Clip>>>,
+}
+
+impl Loader {
+    fn run(requests: Receiver<LoadRequest>, loaded: Sender<(u64, Option<Source>)>) {
+        let mut loader = Loader::default();
+        for request in requests {
+            let sequence = request.sequence;
+            let source = loader.load(request);
+            // Sources which failed to load still count as done
+            if loaded.send((sequence, source)).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// The source playing the request, `None` if it doesn't play anything
+    fn load(&mut self, request: LoadRequest) -> Option<Source> {
+        let res = request.res.read();
+        if self.resource_version != Some(res.version()) {
+            self.resource_version = Some(res.version());
+            self.events = SoundEvents::load(&res);
+            self.clips.clear();
+            info!("Loaded {} sound events", self.events.len());
+        }
+        let event = events::with_namespace(&request.event);
+        let sound = match self.events.pick(&event, &mut rand::thread_rng()) {
+            Some(sound) => sound,
+            None => {
+                debug!("Unknown sound event {}", event);
+                return None;
+            }
+        };
+        let clip = match self.clips.get(&sound.name) {
+            Some(clip) => clip.clone(),
+            None => {
+                let data = read_clip(&res, &sound);
+                // Resources can change while decoding
+                drop(res);
+                let clip = data.and_then(|data| decode_clip(&sound, data));
+                self.clips.insert(sound.name.clone(), clip.clone());
+                clip
+            }
+        }?;
+        Some(Source {
+            clip,
+            event,
+            category: request.category,
+            position: request.position,
+            volume: request.volume * sound.volume,
+            pitch: (request.pitch * sound.pitch).clamp(0.5, 2.0),
+            cursor: 0.0,
+        })
+    }
+}
+
+pub struct Manager {
+    output: Box<dyn Output>,
+    requests: Sender<LoadRequest>,
+    loaded: Receiver<(u64, Option<Source>)>,
+    /// The sequence number of the next request
+    next_sequence: u64,
+    /// Requests the loader hasn't answered yet
+    in_flight: usize,
+    /// Stop commands given while sounds were loading, with the sequence
+    /// number of the first request they don't apply to
+    stops: Vec<(u64, Option<Category>, Option<String>)>,
+    sources: Vec<Source>,
+    listener: Listener,
+    volumes: [f32; 10],
+    buffer: Vec<f32>,
+}
+
+impl Manager {
+    pub fn new(output: Box<dyn Output>) -> Manager {
+        let (requests, receiver) = unbounded();
+        let (sender, loaded) = unbounded();
+        thread::Builder::new()
+            .name("sound-loader".to_owned())
+            .spawn(move || Loader::run(receiver, sender))
+            .unwrap();
+        Manager {
+            output,
+            requests,
+            loaded,
+            next_sequence: 0,
+            in_flight: 0,
+            stops: vec![],
+            sources: vec![],
+            listener: Listener {
+                position: Vector3::new(0.0, 0.0, 0.0),
+                yaw: 0.0,
+            },
+            volumes: [1.0; 10],
+            buffer: vec![],
+        }
+    }
+
+    /// Plays on the default output device, falling back to silence
+    pub fn with_default_output() -> Manager {
+        #[cfg(feature = "audio")]
+        match output::DeviceOutput::open() {
+            Ok(output) => return Manager::new(Box::new(output)),
+            Err(err) => warn!("Failed to open sound output, sound is disabled: {}", err),
+        }
+        Manager::new(Box::new(output::NullOutput::new(44100)))
+    }
+
+    pub fn run(&mut self, res: &Arc<RwLock<resources::Manager>>, command: Command) {
+        match command {
+            Command::Play {
+                event,
+                category,
+                position,
+                volume,
+                pitch,
+            } => self.play(res, event, category, position, volume, pitch),
+            Command::Stop { category, event } => self.stop(category, event.as_deref()),
+        }
+    }
+
+    /// Starts the sound once the loader thread has looked it up and decoded it
+    pub fn play(
+        &mut self,
+        res: &Arc<RwLock<resources::Manager>>,
+        event: String,
+        category: Category,
+        position: Option<Vector3<f64>>,
+        volume: f32,
+        pitch: f32,
+    ) {
+        let request = LoadRequest {
+            res: res.clone(),
+            sequence: self.next_sequence,
+            event,
+            category,
+            position,
+            volume,
+            pitch,
+        };
+        if self.requests.send(request).is_ok() {
+            self.next_sequence += 1;
+            self.in_flight += 1;
+        }
+    }
+
+    pub fn stop(&mut self, category: Option<Category>, event: Option<&str>) {
+        let event = event.map(events::with_namespace);
+        self.sources
+            .retain(|source| !source.stopped_by(category, event.as_deref()));
+        if self.in_flight > 0 {
+            self.stops.push((self.next_sequence, category, event));
+        }
+    }
+
+    pub fn stop_all(&mut self) {
+        self.stop(None, None);
+    }
+
+    /// Starts the sounds the loader thread finished, unless they were
+    /// stopped while loading
+    fn receive(&mut self, sequence: u64, source: Option<Source>) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        if let Some(source) = source {
+            let stopped = self.stops.iter().any(|(before, category, event)| {
+                sequence < *before && source.stopped_by(*category, event.as_deref())
+            });
+            if !stopped {
+                self.sources.push(source);
+            }
+        }
+        if self.in_flight == 0 {
+            self.stops.clear();
+        }
+    }
+
+    /// Places the listener, `yaw` is the camera's
+    pub fn set_listener(&mut self, position: Vector3<f64>, yaw: f64) {
+        self.listener = Listener { position, yaw };
+    }
+
+    pub fn update_volumes(&mut self, settings: &SettingStore) {
+        for category in Category::ALL {
+            let volume = settings.get_int(category.volume_setting()).clamp(0, 100);
+            self.volumes[category as usize] = volume as f32 / 100.0;
+        }
+    }
+
+    /// Mixes as much as the output wants after `delta` seconds
+    pub fn tick(&mut self, delta: f64) {
+        while let Ok((sequence, source)) = self.loaded.try_recv() {
+            self.receive(sequence, source);
+        }
+        let frames = self.output.frames_wanted(delta);
+        if frames == 0 {
+            return;
+        }
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        buffer.resize(frames * 2, 0.0);
+        self.mix(&mut buffer);
+        self.output.write(&buffer);
+        self.buffer = buffer;
+    }
+
+    fn mix(&mut self, out: &mut [f32]) {
+        let listener = self.listener;
+        let volumes = self.volumes;
+        let output_rate = self.output.sample_rate();
+        self.sources.retain_mut(|source| {
+            let gains = source.gains(&listener, &volumes);
+            source.mix(out, output_rate, gains)
+        });
+        for sample in out {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+fn read_clip(res: &resources::Manager, sound: &events::Sound) -> Option<Vec<u8>> {
+    let (namespace, path) = sound.file();
+    let mut data = vec![];
+    let mut file = match res.open(namespace, &path) {
+        Some(file) => file,
+        None => {
+            warn!("Missing sound {}:{}", namespace, path);
+            return None;
+        }
+    };
+    if let Err(err) = file.read_to_end(&mut data) {
+        warn!("Failed to read sound {}:{}: {}", namespace, path, err);
+        return None;
+    }
+    Some(data)
+}
+
+fn decode_clip(sound: &events::Sound, data: Vec<u8>) -> Option<Arc<Clip>> {
+    match Clip::decode(data) {
+        Ok(clip) => Some(Arc::new(clip)),
+        Err(err) => {
+            warn!("Failed to decode sound {}: {:?}", sound.name, err);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::output::NullOutput;
+    use super::*;
+
+    fn tone(frames: usize) -> Arc<Clip> {
+        Arc::new(Clip {
+            samples: (0..frames).map(|i| (i as f32 * 0.1).sin()).collect(),
+            channels: 1,
+            sample_rate: 44100,
+        })
+    }
+
+    fn source(position: Option<Vector3<f64>>) -> Source {
+        Source {
+            clip: tone(1000),
+            event: "minecraft:test".into(),
+            category: Category::Block,
+            position,
+            volume: 1.0,
+            pitch: 1.0,
+            cursor: 0.0,
+        }
+    }
+
+    fn loudness(samples: &[f32]) -> (f32, f32) {
+        samples.chunks_exact(2).fold((0.0, 0.0), |(l, r), frame| {
+            (l + frame[0].abs(), r + frame[1].abs())
+        })
+    }
+
+    #[test]
+    fn positional_mixing() {
+        let mut manager = Manager::new(Box::new(NullOutput::new(44100)));
+        // Facing +z, so +x is to the left
+        manager.set_listener(Vector3::new(0.0, 0.0, 0.0), 0.0);
+        manager
+            .sources
+            .push(source(Some(Vector3::new(-4.0, 0.0, 0.0))));
+        let mut out = vec![0.0; 200];
+        manager.mix(&mut out);
+        let (left, right) = loudness(&out);
+        assert!(right > left * 4.0);
+
+        // Out of range
+        manager.stop_all();
+        manager
+            .sources
+            .push(source(Some(Vector3::new(0.0, 0.0, 20.0))));
+        let mut out = vec![0.0; 200];
+        manager.mix(&mut out);
+        assert_eq!(loudness(&out), (0.0, 0.0));
+
+        // Category volume and finishing
+        manager.stop_all();
+        manager.volumes[Category::Block as usize] = 0.5;
+        manager.sources.push(source(None));
+        let mut half = vec![0.0; 200];
+        manager.mix(&mut half);
+        manager.volumes[Category::Block as usize] = 1.0;
+        manager.stop_all();
+        manager.sources.push(source(None));
+        let mut full = vec![0.0; 200];
+        manager.mix(&mut full);
+        assert!((loudness(&full).0 - loudness(&half).0 * 2.0).abs() < 0.01);
+        let mut rest = vec![0.0; 2000];
+        manager.mix(&mut rest);
+        assert!(manager.sources.is_empty());
+    }
+
+    #[test]
+    fn stops_apply_to_loading_sounds() {
+        let mut manager = Manager::new(Box::new(NullOutput::new(44100)));
+        // Two sounds were requested, then the block sounds were stopped
+        manager.next_sequence = 2;
+        manager.in_flight = 2;
+        manager.stop(Some(Category::Block), None);
+        manager.next_sequence = 3;
+        manager.in_flight = 3;
+
+        manager.receive(0, Some(source(None)));
+        assert!(manager.sources.is_empty());
+        let mut music = source(None);
+        music.category = Category::Music;
+        manager.receive(1, Some(music));
+        assert_eq!(manager.sources.len(), 1);
+        // Requested after the stop
+        manager.receive(2, Some(source(None)));
+        assert_eq!(manager.sources.len(), 2);
+        assert!(manager.stops.is_empty());
+    }
+}
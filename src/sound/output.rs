@@ -0,0 +1,282 @@
+// Copyright 2016 Matthew Collins
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Where mixed sound ends up. Samples are interleaved stereo `f32`s.
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+pub trait Output: Send {
+    fn sample_rate(&self) -> u32;
+    /// How many frames should be mixed now that `delta` seconds passed
+    fn frames_wanted(&mut self, delta: f64) -> usize;
+    fn write(&mut self, samples: &[f32]);
+}
+
+/// Turns elapsed time into frames for outputs which consume them as fast
+/// as they are mixed
+struct Clock {
+    sample_rate: u32,
+    pending: f64,
+}
+
+impl Clock {
+    fn frames(&mut self, delta: f64) -> usize {
+        self.pending += delta * self.sample_rate as f64;
+        let frames = self.pending.floor();
+        self.pending -= frames;
+        frames as usize
+    }
+}
+
+/// Discards everything, sounds still play out in time
+pub struct NullOutput {
+    clock: Clock,
+}
+
+impl NullOutput {
+    pub fn new(sample_rate: u32) -> NullOutput {
+        NullOutput {
+            clock: Clock {
+                sample_rate,
+                pending: 0.0,
+            },
+        }
+    }
+}
+
+impl Output for NullOutput {
+    fn sample_rate(&self) -> u32 {
+        self.clock.sample_rate
+    }
+
+    fn frames_wanted(&mut self, delta: f64) -> usize {
+        self.clock.frames(delta)
+    }
+
+    fn write(&mut self, _samples: &[f32]) {}
+}
+
+/// Records into a 16-bit PCM WAV file
+pub struct WavOutput<W: Write + Seek + Send> {
+    inner: W,
+    clock: Clock,
+    data_len: u32,
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Seek + Send> WavOutput<W> {
+    const HEADER_LEN: u32 = 44;
+
+    pub fn new(mut inner: W, sample_rate: u32) -> io::Result<WavOutput<W>> {
+        Self::write_header(&mut inner, sample_rate, 0)?;
+        Ok(WavOutput {
+            inner,
+            clock: Clock {
+                sample_rate,
+                pending: 0.0,
+            },
+            data_len: 0,
+            error: None,
+        })
+    }
+
+    fn write_header(w: &mut W, sample_rate: u32, data_len: u32) -> io::Result<()> {
+        const CHANNELS: u16 = 2;
+        const BITS: u16 = 16;
+        let block_align = CHANNELS * BITS / 8;
+        w.write_all(b"RIFF")?;
+        w.write_u32::<LittleEndian>(Self::HEADER_LEN - 8 + data_len)?;
+        w.write_all(b"WAVEfmt ")?;
+        w.write_u32::<LittleEndian>(16)?;
+        w.write_u16::<LittleEndian>(1)?; // PCM
+        w.write_u16::<LittleEndian>(CHANNELS)?;
+        w.write_u32::<LittleEndian>(sample_rate)?;
+        w.write_u32::<LittleEndian>(sample_rate * block_align as u32)?;
+        w.write_u16::<LittleEndian>(block_align)?;
+        w.write_u16::<LittleEndian>(BITS)?;
+        w.write_all(b"data")?;
+        w.write_u32::<LittleEndian>(data_len)
+    }
+
+    /// Fills in the lengths of the header and returns the writer
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.inner.seek(SeekFrom::Start(0))?;
+        Self::write_header(&mut self.inner, self.clock.sample_rate, self.data_len)?;
+        self.inner.seek(SeekFrom::End(0))?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write + Seek + Send> Output for WavOutput<W> {
+    fn sample_rate(&self) -> u32 {
+        self.clock.sample_rate
+    }
+
+    fn frames_wanted(&mut self, delta: f64) -> usize {
+        self.clock.frames(delta)
+    }
+
+    fn write(&mut self, samples: &[f32]) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut data = Vec::with_capacity(samples.len() * 2);
+        for sample in samples {
+            let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            data.write_i16::<LittleEndian>(sample).unwrap();
+        }
+        match self.inner.write_all(&data) {
+            Ok(()) => self.data_len += data.len() as u32,
+            Err(err) => self.error = Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+pub use self::device::DeviceOutput;
+
+#[cfg(feature = "audio")]
+mod device {
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+    use std::thread;
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, SampleFormat, SizedSample};
+    use crossbeam_channel::{bounded, Sender};
+    use log::{error, info};
+    use parking_lot::Mutex;
+
+    use super::Output;
+
+    /// How far ahead of playback mixing runs, in seconds. Frames come in
+    /// bursts, so this has to cover a slow frame.
+    const LATENCY: f64 = 0.1;
+
+    /// Plays on the default output device of the system
+    pub struct DeviceOutput {
+        queue: Arc<Mutex<VecDeque<f32>>>,
+        sample_rate: u32,
+        // Dropping this stops the thread owning the stream, the stream
+        // itself can't move between threads on every platform.
+        _stop: Sender<()>,
+    }
+
+    impl DeviceOutput {
+        pub fn open() -> Result<DeviceOutput, String> {
+            let queue = Arc::new(Mutex::new(VecDeque::new()));
+            let (stop, stopped) = bounded(0);
+            let (opened, result) = bounded(1);
+            let stream_queue = queue.clone();
+            thread::Builder::new()
+                .name("sound output".into())
+                .spawn(move || {
+                    let stream = match open_stream(stream_queue) {
+                        Ok((stream, sample_rate)) => {
+                            let _ = opened.send(Ok(sample_rate));
+                            stream
+                        }
+                        Err(err) => {
+                            let _ = opened.send(Err(err));
+                            return;
+                        }
+                    };
+                    let _ = stopped.recv();
+                    drop(stream);
+                })
+                .map_err(|err| err.to_string())?;
+            let sample_rate = result.recv().map_err(|err| err.to_string())??;
+            Ok(DeviceOutput {
+                queue,
+                sample_rate,
+                _stop: stop,
+            })
+        }
+    }
+
+    fn open_stream(queue: Arc<Mutex<VecDeque<f32>>>) -> Result<(cpal::Stream, u32), String> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or("No sound output device")?;
+        let config = device
+            .default_output_config()
+            .map_err(|err| err.to_string())?;
+        info!(
+            "Playing sound on {} ({} Hz, {} channels)",
+            device.name().unwrap_or_default(),
+            config.sample_rate().0,
+            config.channels()
+        );
+        let sample_rate = config.sample_rate().0;
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => build_stream::<f32>(&device, &config.into(), queue),
+            SampleFormat::I16 => build_stream::<i16>(&device, &config.into(), queue),
+            SampleFormat::U16 => build_stream::<u16>(&device, &config.into(), queue),
+            format => return Err(format!("Unsupported sample format {}", format)),
+        }?;
+        stream.play().map_err(|err| err.to_string())?;
+        Ok((stream, sample_rate))
+    }
+
+    fn build_stream<T: SizedSample + FromSample<f32>>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        queue: Arc<Mutex<VecDeque<f32>>>,
+    ) -> Result<cpal::Stream, String> {
+        let channels = config.channels as usize;
+        device
+            .build_output_stream(
+                config,
+                move |data: &mut [T], _| {
+                    let mut queue = queue.lock();
+                    for frame in data.chunks_mut(channels) {
+                        // Runs dry when frames take too long, play silence
+                        let left = queue.pop_front().unwrap_or(0.0);
+                        let right = queue.pop_front().unwrap_or(0.0);
+                        for (i, sample) in frame.iter_mut().enumerate() {
+                            *sample = T::from_sample(match (i, channels) {
+                                (_, 1) => (left + right) / 2.0,
+                                (0, _) => left,
+                                (1, _) => right,
+                                _ => 0.0,
+                            });
+                        }
+                    }
+                },
+                |err| error!("Sound output failed: {}", err),
+                None,
+            )
+            .map_err(|err| err.to_string())
+    }
+
+    impl Output for DeviceOutput {
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn frames_wanted(&mut self, _delta: f64) -> usize {
+            let target = (self.sample_rate as f64 * LATENCY) as usize;
+            target.saturating_sub(self.queue.lock().len() / 2)
+        }
+
+        fn write(&mut self, samples: &[f32]) {
+            self.queue.lock().extend(samples);
+        }
+    }
+}
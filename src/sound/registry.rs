@@ -0,0 +1,92 @@
+//! Since 1.9 most sounds are sent by their id in the sound event registry
+//! instead of by name. The registry of every version is listed in
+//! `sounds/ids/<protocol>.json` of the internal resource pack, generated from
+//! minecraft-data by `generate_sounds`.
+
+use std::convert::TryFrom;
+use std::io::Read;
+
+use log::{info, warn};
+
+use crate::resources;
+
+/// The names of the sound events of a version, by id
+#[derive(Default)]
+pub struct SoundRegistry {
+    names: Vec<String>,
+}
+
+impl SoundRegistry {
+    pub fn load(res: &resources::Manager, protocol_version: i32) -> SoundRegistry {
+        let path = format!("sounds/ids/{}.json", protocol_version);
+        let file = match res.open("leafish", &path) {
+            Some(file) => file,
+            None => {
+                warn!(
+                    "No sound ids for protocol {}, only sounds sent by name will play",
+                    protocol_version
+                );
+                return SoundRegistry::default();
+            }
+        };
+        match SoundRegistry::read(file) {
+            Ok(registry) => {
+                info!("Loaded {} sound ids", registry.names.len());
+                registry
+            }
+            Err(err) => {
+                warn!("Invalid sound ids {}: {}", path, err);
+                SoundRegistry::default()
+            }
+        }
+    }
+
+    fn read<R: Read>(file: R) -> serde_json::Result<SoundRegistry> {
+        Ok(SoundRegistry {
+            names: serde_json::from_reader(file)?,
+        })
+    }
+
+    /// The event of the id, ids minecraft-data skips are empty
+    pub fn name(&self, id: i32) -> Option<&str> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| self.names.get(id))
+            .map(String::as_str)
+            .filter(|name| !name.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::SUPPORTED_PROTOCOLS;
+
+    #[test]
+    fn names_by_id() {
+        let registry = SoundRegistry {
+            names: vec!["ambient.cave".into(), "".into(), "block.anvil.break".into()],
+        };
+        assert_eq!(registry.name(0), Some("ambient.cave"));
+        assert_eq!(registry.name(1), None);
+        assert_eq!(registry.name(2), Some("block.anvil.break"));
+        assert_eq!(registry.name(3), None);
+        assert_eq!(registry.name(-1), None);
+    }
+
+    #[test]
+    fn ids_of_supported_versions() {
+        for protocol_version in SUPPORTED_PROTOCOLS.iter().filter(|pv| **pv >= 107) {
+            let path = format!("assets/leafish/sounds/ids/{}.json", protocol_version);
+            let file = leafish_resources::get_file(&path)
+                .unwrap_or_else(|| panic!("{} is missing, run generate_sounds", path));
+            let registry = SoundRegistry::read(file).unwrap();
+            let levelup = registry
+                .names
+                .iter()
+                .position(|name| name == "entity.player.levelup")
+                .unwrap_or_else(|| panic!("{} has no entity.player.levelup", path));
+            assert_eq!(registry.name(levelup as i32), Some("entity.player.levelup"));
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
 
+use leafish_protocol::nbt::NamedTag;
 use leafish_protocol::types::hash::FNVHash;
 use leafish_shared::position::Position;
 
@@ -15,46 +16,109 @@ mod chunk_section;
 #[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 pub struct CPos(pub i32, pub i32);
 
+/// WorldHeight is the vertical extent of a dimension. Before 1.17 every
+/// dimension spans y 0 to 255, afterwards the dimension type decides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldHeight {
+    pub min_y: i32,
+    pub height: i32,
+}
+
+impl Default for WorldHeight {
+    fn default() -> Self {
+        WorldHeight {
+            min_y: 0,
+            height: 256,
+        }
+    }
+}
+
+impl WorldHeight {
+    /// Reads the height from a dimension type tag, older tags don't carry
+    /// one and return None.
+    pub fn from_tag(tag: &NamedTag) -> Option<Self> {
+        let min_y = tag.1.get("min_y")?.as_int()?;
+        let height = tag.1.get("height")?.as_int()?;
+        Some(WorldHeight { min_y, height })
+    }
+
+    pub fn min_section(&self) -> i32 {
+        self.min_y >> 4
+    }
+
+    pub fn section_count(&self) -> usize {
+        (self.height >> 4) as usize
+    }
+}
+
 #[derive(Clone)]
 pub struct Chunk {
     pub(crate) position: CPos,
 
-    pub(crate) sections: [Option<ChunkSection>; 16],
-    pub(crate) sections_rendered_on: [u32; 16],
+    /// The section y of the first entry in `sections`.
+    pub(crate) min_section: i32,
+    pub(crate) sections: Vec<Option<ChunkSection>>,
+    pub(crate) sections_rendered_on: Vec<u32>,
     pub(crate) biomes: [u8; 16 * 16],
 
-    pub(crate) heightmap: [u8; 16 * 16],
+    pub(crate) heightmap: [i32; 16 * 16],
     pub(crate) heightmap_dirty: bool,
 
     pub(crate) block_entities: HashMap<Position, Entity, BuildHasherDefault<FNVHash>>,
 }
 
 impl Chunk {
-    pub(crate) fn new(pos: CPos) -> Chunk {
+    pub(crate) fn new(pos: CPos, height: WorldHeight) -> Chunk {
         Chunk {
             position: pos,
-            sections: [
-                None, None, None, None, None, None, None, None, None, None, None, None, None, None,
-                None, None,
-            ],
-            sections_rendered_on: [0; 16],
+            min_section: height.min_section(),
+            sections: vec![None; height.section_count()],
+            sections_rendered_on: vec![0; height.section_count()],
             biomes: [0; 16 * 16],
-            heightmap: [0; 16 * 16],
+            heightmap: [height.min_y; 16 * 16],
             heightmap_dirty: true,
             block_entities: HashMap::with_hasher(BuildHasherDefault::default()),
         }
     }
 
+    /// Returns the index into `sections` of the section at the passed
+    /// section y, if it is inside of the world.
+    pub(crate) fn section_index(&self, y: i32) -> Option<usize> {
+        let idx = y - self.min_section;
+        if idx >= 0 && (idx as usize) < self.sections.len() {
+            Some(idx as usize)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn section(&self, y: i32) -> Option<&ChunkSection> {
+        self.section_index(y)
+            .and_then(|idx| self.sections[idx].as_ref())
+    }
+
+    pub(crate) fn section_mut(&mut self, y: i32) -> Option<&mut ChunkSection> {
+        self.section_index(y)
+            .and_then(move |idx| self.sections[idx].as_mut())
+    }
+
+    pub(crate) fn min_y(&self) -> i32 {
+        self.min_section << 4
+    }
+
+    pub(crate) fn max_y(&self) -> i32 {
+        self.min_y() + (self.sections.len() << 4) as i32
+    }
+
     pub(crate) fn calculate_heightmap(&mut self) {
         for x in 0..16 {
             for z in 0..16 {
                 let idx = ((z << 4) | x) as usize;
-                for yy in 0..256 {
-                    let sy = 255 - yy;
+                for sy in (self.min_y()..self.max_y()).rev() {
                     if let block::Air { .. } = self.get_block(x, sy, z) {
                         continue;
                     }
-                    self.heightmap[idx] = sy as u8;
+                    self.heightmap[idx] = sy;
                     break;
                 }
             }
@@ -63,17 +127,16 @@ impl Chunk {
     }
 
     pub(crate) fn set_block(&mut self, x: i32, y: i32, z: i32, b: block::Block) -> bool {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return false;
-        }
-        let s_idx = s_idx as usize;
+        let s_idx = match self.section_index(y >> 4) {
+            Some(s_idx) => s_idx,
+            None => return false,
+        };
         if self.sections[s_idx].is_none() {
             if let block::Air {} = b {
                 return false;
             }
             let fill_sky = self.sections.iter().skip(s_idx).all(|v| v.is_none());
-            self.sections[s_idx] = Some(ChunkSection::new(s_idx as u8, fill_sky));
+            self.sections[s_idx] = Some(ChunkSection::new(y >> 4, fill_sky));
         }
         {
             let section = self.sections[s_idx].as_mut().unwrap();
@@ -82,19 +145,18 @@ impl Chunk {
             }
         }
         let idx = ((z << 4) | x) as usize;
-        match self.heightmap[idx].cmp(&(y as u8)) {
+        match self.heightmap[idx].cmp(&y) {
             Ordering::Less => {
-                self.heightmap[idx] = y as u8;
+                self.heightmap[idx] = y;
                 self.heightmap_dirty = true;
             }
             Ordering::Equal => {
                 // Find a new lowest
-                for yy in 0..y {
-                    let sy = y - yy - 1;
+                for sy in (self.min_y()..y).rev() {
                     if let block::Air { .. } = self.get_block(x, sy, z) {
                         continue;
                     }
-                    self.heightmap[idx] = sy as u8;
+                    self.heightmap[idx] = sy;
                     break;
                 }
                 self.heightmap_dirty = true;
@@ -105,39 +167,30 @@ impl Chunk {
     }
 
     pub(crate) fn get_block(&self, x: i32, y: i32, z: i32) -> block::Block {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return block::Air {};
-        }
-        match self.sections[s_idx as usize].as_ref() {
+        match self.section(y >> 4) {
             Some(sec) => sec.get_block(x, y & 0xF, z),
             None => block::Air {},
         }
     }
 
     pub(crate) fn get_block_light(&self, x: i32, y: i32, z: i32) -> u8 {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return 0;
-        }
-        match self.sections[s_idx as usize].as_ref() {
+        match self.section(y >> 4) {
             Some(sec) => sec.get_block_light(x, y & 0xF, z),
             None => 0,
         }
     }
 
     pub(crate) fn set_block_light(&mut self, x: i32, y: i32, z: i32, light: u8) {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return;
-        }
-        let s_idx = s_idx as usize;
+        let s_idx = match self.section_index(y >> 4) {
+            Some(s_idx) => s_idx,
+            None => return,
+        };
         if self.sections[s_idx].is_none() {
             if light == 0 {
                 return;
             }
             let fill_sky = self.sections.iter().skip(s_idx).all(|v| v.is_none());
-            self.sections[s_idx] = Some(ChunkSection::new(s_idx as u8, fill_sky));
+            self.sections[s_idx] = Some(ChunkSection::new(y >> 4, fill_sky));
         }
         if let Some(sec) = self.sections[s_idx].as_mut() {
             sec.set_block_light(x, y & 0xF, z, light)
@@ -145,28 +198,23 @@ impl Chunk {
     }
 
     pub(crate) fn get_sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return 15;
-        }
-        match self.sections[s_idx as usize].as_ref() {
+        match self.section(y >> 4) {
             Some(sec) => sec.get_sky_light(x, y & 0xF, z),
             None => 15,
         }
     }
 
     pub(crate) fn set_sky_light(&mut self, x: i32, y: i32, z: i32, light: u8) {
-        let s_idx = y >> 4;
-        if !(0..=15).contains(&s_idx) {
-            return;
-        }
-        let s_idx = s_idx as usize;
+        let s_idx = match self.section_index(y >> 4) {
+            Some(s_idx) => s_idx,
+            None => return,
+        };
         if self.sections[s_idx].is_none() {
             if light == 15 {
                 return;
             }
             let fill_sky = self.sections.iter().skip(s_idx).all(|v| v.is_none());
-            self.sections[s_idx] = Some(ChunkSection::new(s_idx as u8, fill_sky));
+            self.sections[s_idx] = Some(ChunkSection::new(y >> 4, fill_sky));
         }
         if let Some(sec) = self.sections[s_idx].as_mut() {
             sec.set_sky_light(x, y & 0xF, z, light)
@@ -180,16 +228,15 @@ impl Chunk {
     }
 
     pub fn capture_snapshot(&self) -> ChunkSnapshot {
-        let mut snapshot_sections = [
-            None, None, None, None, None, None, None, None, None, None, None, None, None, None,
-            None, None,
-        ];
-        for section in self.sections.iter().enumerate() {
-            if section.1.is_some() {
-                snapshot_sections[section.0] =
-                    Some(section.1.as_ref().unwrap().capture_snapshot(self.biomes));
-            }
-        }
+        let snapshot_sections = self
+            .sections
+            .iter()
+            .map(|section| {
+                section
+                    .as_ref()
+                    .map(|section| section.capture_snapshot(self.biomes))
+            })
+            .collect();
         ChunkSnapshot {
             position: self.position,
             sections: snapshot_sections,
@@ -201,7 +248,7 @@ impl Chunk {
 
 pub struct ChunkSnapshot {
     pub position: CPos,
-    pub sections: [Option<ChunkSectionSnapshot>; 16],
+    pub sections: Vec<Option<ChunkSectionSnapshot>>,
     pub biomes: [u8; 16 * 16],
-    pub heightmap: [u8; 16 * 16],
+    pub heightmap: [i32; 16 * 16],
 }
@@ -79,6 +79,7 @@ pub struct World {
     pub id_map: Arc<block::VanillaIDMap>,
 
     pub dimension: ArcSwap<Dimension>,
+    pub height: ArcSwap<WorldHeight>,
 }
 
 impl World {
@@ -94,6 +95,7 @@ impl World {
             render_list: Arc::new(Default::default()),
             block_entity_actions: unbounded(),
             dimension: ArcSwap::new(Arc::new(Default::default())),
+            height: ArcSwap::new(Arc::new(Default::default())),
         }
     }
 
@@ -117,7 +119,9 @@ impl World {
     fn set_block_raw(&self, pos: Position, b: block::Block) -> bool {
         let cpos = CPos(pos.x >> 4, pos.z >> 4);
         let mut chunks = self.chunks.write();
-        let chunk = chunks.entry(cpos).or_insert_with(|| Chunk::new(cpos));
+        let chunk = chunks
+            .entry(cpos)
+            .or_insert_with(|| Chunk::new(cpos, **self.height.load()));
         if chunk.set_block(pos.x & 0xF, pos.y, pos.z & 0xF, b) {
             if chunk.block_entities.contains_key(&pos) {
                 self.block_entity_actions
@@ -206,7 +210,9 @@ impl World {
     pub(crate) fn set_block_light(&self, pos: Position, light: u8) {
         let cpos = CPos(pos.x >> 4, pos.z >> 4);
         let mut chunks = self.chunks.write();
-        let chunk = chunks.entry(cpos).or_insert_with(|| Chunk::new(cpos));
+        let chunk = chunks
+            .entry(cpos)
+            .or_insert_with(|| Chunk::new(cpos, **self.height.load()));
         chunk.set_block_light(pos.x & 0xF, pos.y, pos.z & 0xF, light);
     }
 
@@ -220,7 +226,9 @@ impl World {
     pub(crate) fn set_sky_light(&self, pos: Position, light: u8) {
         let cpos = CPos(pos.x >> 4, pos.z >> 4);
         let mut chunks = self.chunks.write();
-        let chunk = chunks.entry(cpos).or_insert_with(|| Chunk::new(cpos));
+        let chunk = chunks
+            .entry(cpos)
+            .or_insert_with(|| Chunk::new(cpos, **self.height.load()));
         chunk.set_sky_light(pos.x & 0xF, pos.y, pos.z & 0xF, light);
     }
 
@@ -285,8 +293,9 @@ impl World {
     #[allow(dead_code)]
     pub(crate) fn do_light_update(&self, update: LightUpdate) {
         use std::cmp;
-        if update.pos.y < 0
-            || update.pos.y > 255
+        let height = self.height.load();
+        if update.pos.y < height.min_y
+            || update.pos.y >= height.min_y + height.height
             || !self.is_chunk_loaded(update.pos.x >> 4, update.pos.z >> 4)
         {
             return;
@@ -348,7 +357,7 @@ impl World {
                     for zz in 0..16 {
                         data[(((c.position.0 << 4) as usize + xx) & 0x1FF)
                             + ((((c.position.1 << 4) as usize + zz) & 0x1FF) << 9)] =
-                            c.heightmap[(zz << 4) | xx];
+                            c.heightmap[(zz << 4) | xx].clamp(0, 255) as u8;
                     }
                 }
             }
@@ -526,7 +535,9 @@ impl World {
                     return;
                 }
                 if let Some(chunk) = self.chunks.write().get_mut(&CPos(pos.0, pos.2)) {
-                    chunk.sections_rendered_on[pos.1 as usize] = frame_id;
+                    if let Some(idx) = chunk.section_index(pos.1) {
+                        chunk.sections_rendered_on[idx] = frame_id;
+                    }
                 }
 
                 let min = cgmath::Point3::new(
@@ -585,7 +596,7 @@ impl World {
                 let chunks = self.chunks.read();
                 let chunk = chunks.get(&CPos(v.0, v.2));
                 if let Some(chunk) = chunk {
-                    if let Some(sec) = chunk.sections[v.1 as usize].as_ref() {
+                    if let Some(sec) = chunk.section(v.1) {
                         return Some((*v, sec.render_buffer.clone()));
                     }
                 }
@@ -723,12 +734,10 @@ impl World {
 
     // TODO: Improve the perf of this method as it is the MAIN bottleneck slowing down the program!
     fn get_render_section_mut(&self, x: i32, y: i32, z: i32) -> Option<(Option<CullInfo>, u32)> {
-        if !(0..=15).contains(&y) {
-            return None;
-        }
         if let Some(chunk) = self.chunks.read().get(&CPos(x, z)) {
-            let rendered = &chunk.sections_rendered_on[y as usize];
-            if let Some(sec) = chunk.sections[y as usize].as_ref() {
+            let idx = chunk.section_index(y)?;
+            let rendered = &chunk.sections_rendered_on[idx];
+            if let Some(sec) = chunk.sections[idx].as_ref() {
                 return Some((Some(sec.cull_info), *rendered));
             }
             return Some((None, *rendered));
@@ -742,7 +751,7 @@ impl World {
             for sec in &chunk.sections {
                 if let Some(sec) = sec.as_ref() {
                     if !sec.building && sec.dirty {
-                        out.push((chunk.position.0, sec.y as i32, chunk.position.1));
+                        out.push((chunk.position.0, sec.y, chunk.position.1));
                     }
                 }
             }
@@ -752,7 +761,7 @@ impl World {
 
     fn set_dirty(&self, x: i32, y: i32, z: i32) {
         if let Some(chunk) = self.chunks.write().get_mut(&CPos(x, z)) {
-            if let Some(sec) = chunk.sections.get_mut(y as usize).and_then(|v| v.as_mut()) {
+            if let Some(sec) = chunk.section_mut(y) {
                 sec.dirty = true;
             }
         }
@@ -760,7 +769,7 @@ impl World {
 
     pub fn is_section_dirty(&self, pos: (i32, i32, i32)) -> bool {
         if let Some(chunk) = self.chunks.read().get(&CPos(pos.0, pos.2)) {
-            if let Some(sec) = chunk.sections[pos.1 as usize].as_ref() {
+            if let Some(sec) = chunk.section(pos.1) {
                 return sec.dirty && !sec.building;
             }
         }
@@ -769,7 +778,7 @@ impl World {
 
     pub fn set_building_flag(&self, pos: (i32, i32, i32)) {
         if let Some(chunk) = self.chunks.write().get_mut(&CPos(pos.0, pos.2)) {
-            if let Some(sec) = chunk.sections[pos.1 as usize].as_mut() {
+            if let Some(sec) = chunk.section_mut(pos.1) {
                 sec.building = true;
                 sec.dirty = false;
             }
@@ -778,7 +787,7 @@ impl World {
 
     pub fn reset_building_flag(&self, pos: (i32, i32, i32)) {
         if let Some(chunk) = self.chunks.write().get_mut(&CPos(pos.0, pos.2)) {
-            if let Some(section) = chunk.sections[pos.1 as usize].as_mut() {
+            if let Some(section) = chunk.section_mut(pos.1) {
                 section.building = false;
             }
         }
@@ -806,11 +815,9 @@ impl World {
                 return None;
             }
         };
-        let sec = &chunk.sections[cy as usize];
-        if sec.is_none() {
-            return None;
-        }
-        return Some(sec.as_ref().unwrap().capture_snapshot(chunk.biomes));
+        chunk
+            .section(cy)
+            .map(|sec| sec.capture_snapshot(chunk.biomes))
     }
 
     pub fn unload_chunk(&self, x: i32, z: i32, m: &mut ecs::Manager) {
@@ -839,7 +846,7 @@ impl World {
         {
             let mut chunk = if new {
                 // TODO: Improve lighting with something similar to bixilon's light accessor!
-                Chunk::new(cpos)
+                Chunk::new(cpos, **self.height.load())
             } else {
                 match self.chunks.read().get(&cpos) {
                     Some(chunk) => chunk.clone(),
@@ -849,13 +856,15 @@ impl World {
 
             // Block type array - whole byte per block  // 17
             let mut block_types: [[u8; 4096]; 16] = [[0u8; 4096]; 16]; // 17
-            for (i, block_type) in block_types.iter_mut().enumerate() {
+            let section_count = chunk.sections.len();
+            for (i, block_type) in block_types.iter_mut().enumerate().take(section_count) {
                 if chunk.sections[i].is_none() {
                     let mut fill_sky = chunk.sections.iter().skip(i).all(|v| v.is_none());
                     fill_sky &= (mask & !((1 << i) | ((1 << i) - 1))) == 0;
                     fill_sky &= self.dimension.load().has_sky_light();
                     if !fill_sky || mask & (1 << i) != 0 {
-                        chunk.sections[i] = Some(ChunkSection::new(i as u8, fill_sky));
+                        let y = chunk.min_section + i as i32;
+                        chunk.sections[i] = Some(ChunkSection::new(y, fill_sky));
                     }
                 }
                 if mask & (1 << i) == 0 {
@@ -897,7 +906,7 @@ impl World {
             self.chunks.write().insert(cpos, chunk);
         }
 
-        self.dirty_chunks_by_bitmask(x, z, mask);
+        self.dirty_chunks_by_bitmask(x, z, mask as u64);
         Ok(())
     }
 
@@ -962,7 +971,7 @@ impl World {
                     ((block_index >> 4) & 0xF) as i32,
                 ) + (
                     chunk.position.0 << 4,
-                    (section_id << 4) as i32,
+                    (chunk.min_section + section_id as i32) << 4,
                     chunk.position.1 << 4,
                 );
                 if chunk.block_entities.contains_key(&pos) {
@@ -1006,7 +1015,7 @@ impl World {
                     ((bi >> 4) & 0xF) as i32,
                 ) + (
                     chunk.position.0 << 4,
-                    (section_id << 4) as i32,
+                    (chunk.min_section + section_id as i32) << 4,
                     chunk.position.1 << 4,
                 );
                 if chunk.block_entities.contains_key(&pos) {
@@ -1142,7 +1151,7 @@ impl World {
                         ((bi >> 4) & 0xF) as i32,
                     ) + (
                         chunk.position.0 << 4,
-                        (i << 4) as i32,
+                        (chunk.min_section + i as i32) << 4,
                         chunk.position.1 << 4,
                     );
                     if chunk.block_entities.contains_key(&pos) {
@@ -1192,11 +1201,13 @@ impl World {
         Ok(())
     }
 
-    fn dirty_chunks_by_bitmask(&self, x: i32, z: i32, mask: u16) {
-        for i in 0..16 {
-            if mask & (1 << i) == 0 {
+    fn dirty_chunks_by_bitmask(&self, x: i32, z: i32, mask: u64) {
+        let min_section = self.height.load().min_section();
+        for bit in 0..64 {
+            if mask & (1 << bit) == 0 {
                 continue;
             }
+            let i = min_section + bit;
             for pos in [
                 (-1, 0, 0),
                 (1, 0, 0),
@@ -1313,49 +1324,42 @@ impl World {
         data: &mut Cursor<Vec<u8>>,
     ) {
         if sky_light {
-            for i in 0..17 {
-                if sky_light_mask & (1 << i) == 0 {
-                    continue;
-                }
-                if i == 0 {
-                    let _size = VarInt::read_from(data);
-
-                    data.consume(2048);
-                    continue;
-                }
-                let i = i - 1;
-                if chunk.sections[i as usize].as_ref().is_none() {
-                    chunk.sections[i as usize].replace(ChunkSection::new(i, false));
-                }
-                let section = chunk.sections[i as usize].as_mut().unwrap();
-                let _size = VarInt::read_from(data);
-
-                data.read_exact(&mut section.sky_light.data).unwrap();
-            }
+            self.load_light_arrays(chunk, sky_light_mask, LightType::Sky, data);
         }
-        if sky_light_mask & (1 << 63) != 0 {
-            let _size = VarInt::read_from(data);
-
-            data.consume(2048);
-        }
-        for i in 0..17 {
-            if block_light_mask & (1 << i) == 0 {
-                continue;
-            }
-            if i == 0 {
-                let _size = VarInt::read_from(data);
+        self.load_light_arrays(chunk, block_light_mask, LightType::Block, data);
+    }
 
-                data.consume(2048);
+    /// Reads one light array per set bit of the mask. Bit 0 is the section
+    /// below the world, so bit n belongs to the section n - 1 sections above
+    /// the bottom of the world. Arrays of sections outside of the world are
+    /// skipped.
+    fn load_light_arrays(
+        &self,
+        chunk: &mut Chunk,
+        mask: i64,
+        ty: LightType,
+        data: &mut Cursor<Vec<u8>>,
+    ) {
+        for bit in 0..64 {
+            if mask & (1 << bit) == 0 {
                 continue;
             }
-            let i = i - 1;
-            if chunk.sections[i as usize].as_ref().is_none() {
-                chunk.sections[i as usize].replace(ChunkSection::new(i, false));
-            }
-            let section = chunk.sections[i as usize].as_mut().unwrap();
             let _size = VarInt::read_from(data);
 
-            data.read_exact(&mut section.block_light.data).unwrap();
+            let y = chunk.min_section + bit - 1;
+            let idx = match chunk.section_index(y) {
+                Some(idx) => idx,
+                None => {
+                    data.consume(2048);
+                    continue;
+                }
+            };
+            let section = chunk.sections[idx].get_or_insert_with(|| ChunkSection::new(y, false));
+            let light = match ty {
+                LightType::Block => &mut section.block_light,
+                LightType::Sky => &mut section.sky_light,
+            };
+            data.read_exact(&mut light.data).unwrap();
         }
     }
 
@@ -1407,13 +1411,110 @@ impl World {
         )
     }
 
-    fn flag_section_dirty(&self, x: i32, y: i32, z: i32) {
-        if !(0..=15).contains(&y) {
-            return;
+    pub fn load_chunk118(&self, x: i32, z: i32, data: Vec<u8>) -> Result<(), protocol::Error> {
+        let additional_light_data = self.lighting_cache.clone().write().remove(&CPos(x, z));
+        let cpos = CPos(x, z);
+        let mut data = Cursor::new(data);
+        let mut chunk = Chunk::new(cpos, **self.height.load());
+        let mut mask = 0u64;
+        for i in 0..chunk.sections.len() {
+            let block_count = data.read_i16::<byteorder::BigEndian>()?;
+            let block_ids = Self::read_paletted_container(&mut data, 4096, 4, 8)?;
+            // Biome ids refer to the registry sent with JoinGame, which isn't tracked
+            Self::read_paletted_container(&mut data, 64, 0, 3)?;
+            if block_count == 0 {
+                continue;
+            }
+            mask |= 1u64.checked_shl(i as u32).unwrap_or(0);
+
+            let section_y = chunk.min_section + i as i32;
+            let mut section = ChunkSection::new(section_y, false);
+            for (bi, id) in block_ids.into_iter().enumerate() {
+                section.blocks.set(
+                    bi,
+                    self.id_map.by_vanilla_id(id, &self.modded_block_ids.load()),
+                );
+
+                // Spawn block entities
+                let b = section.blocks.get(bi);
+                if block_entity::BlockEntityType::get_block_entity(b).is_some() {
+                    let pos = Position::new(
+                        (bi & 0xF) as i32,
+                        (bi >> 8) as i32,
+                        ((bi >> 4) & 0xF) as i32,
+                    ) + (chunk.position.0 << 4, section_y << 4, chunk.position.1 << 4);
+                    self.block_entity_actions
+                        .0
+                        .send(BlockEntityAction::Create(pos))
+                        .unwrap();
+                }
+            }
+            section.dirty = true;
+            chunk.sections[i] = Some(section);
+        }
+
+        if let Some(mut additional_light_data) = additional_light_data {
+            self.load_light(
+                &mut chunk,
+                additional_light_data.block_light_mask,
+                true,
+                additional_light_data.sky_light_mask,
+                &mut additional_light_data.arrays,
+            );
         }
+
+        chunk.calculate_heightmap();
+
+        self.chunks.write().insert(cpos, chunk);
+
+        self.dirty_chunks_by_bitmask(x, z, mask);
+        Ok(())
+    }
+
+    /// Reads a paletted container as sent since 1.18 and returns the global
+    /// ids of its entries. Palettes of up to `max_indirect_bits` bits per
+    /// entry are sent with the container, larger ones use the global ids
+    /// directly.
+    fn read_paletted_container(
+        data: &mut Cursor<Vec<u8>>,
+        entries: usize,
+        min_bits: u8,
+        max_indirect_bits: u8,
+    ) -> Result<Vec<usize>, protocol::Error> {
+        use crate::protocol::LenPrefixed;
+        use leafish_protocol::types::bit;
+
+        let bit_size = data.read_u8()?;
+        if bit_size == 0 {
+            // Single valued, the (empty) data array follows the value
+            let id = VarInt::read_from(data)?.0 as usize;
+            LenPrefixed::<VarInt, u64>::read_from(data)?;
+            return Ok(vec![id; entries]);
+        }
+        let palette = if bit_size <= max_indirect_bits {
+            Some(LenPrefixed::<VarInt, VarInt>::read_from(data)?.data)
+        } else {
+            None
+        };
+        let bit_size = bit_size.max(min_bits);
+
+        let bits = LenPrefixed::<VarInt, u64>::read_from(data)?.data;
+        let m = bit::Map::from_raw(bits, bit_size as usize, true);
+        Ok((0..entries)
+            .map(|i| {
+                let id = m.get(i);
+                match palette.as_ref() {
+                    Some(palette) => palette.get(id).map_or(0, |v| v.0 as usize),
+                    None => id,
+                }
+            })
+            .collect())
+    }
+
+    fn flag_section_dirty(&self, x: i32, y: i32, z: i32) {
         let cpos = CPos(x, z);
         if let Some(chunk) = self.chunks.write().get_mut(&cpos) {
-            if let Some(sec) = chunk.sections[y as usize].as_mut() {
+            if let Some(sec) = chunk.section_mut(y) {
                 sec.dirty = true;
             }
         }
@@ -1422,6 +1523,10 @@ impl World {
     pub fn set_dimension(&self, new_dimension: Dimension) {
         self.dimension.store(Arc::new(new_dimension));
     }
+
+    pub fn set_height(&self, height: WorldHeight) {
+        self.height.store(Arc::new(height));
+    }
 }
 
 impl block::WorldAccess for World {
@@ -1731,4 +1836,41 @@ mod tests {
         let data = include_bytes!("testdata/chunk_1.16.4_nether.bin");
         load_chunk(&world, 0, 0, true, false, false, 247, 0, data, 19);
     }
+
+    #[test]
+    fn parse_chunk_1_18_2() {
+        let world = build_world(758);
+        world.set_height(WorldHeight {
+            min_y: -64,
+            height: 384,
+        });
+
+        // The bottom section is filled with stone, all others are empty. Every
+        // container is single valued: bits, value, empty data array.
+        let mut data = vec![];
+        for i in 0..24 {
+            let (block_count, block_id) = if i == 0 { (4096i16, 1) } else { (0, 0) };
+            data.extend_from_slice(&block_count.to_be_bytes());
+            data.extend_from_slice(&[0, block_id, 0]);
+            data.extend_from_slice(&[0, 0, 0]);
+        }
+        world.load_chunk118(0, 0, data).unwrap();
+
+        let chunks = world.chunks.read();
+        let chunk = chunks.get(&CPos(0, 0)).unwrap();
+        assert_eq!(chunk.sections.len(), 24);
+        assert_eq!(chunk.section(-4).unwrap().y, -4);
+        assert!(chunk.section(-3).is_none());
+        assert_eq!(chunk.heightmap[0], -49);
+        drop(chunks);
+
+        assert!(!matches!(
+            world.get_block(Position::new(0, -64, 0)),
+            block::Air {}
+        ));
+        assert!(matches!(
+            world.get_block(Position::new(0, -48, 0)),
+            block::Air {}
+        ));
+    }
 }